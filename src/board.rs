@@ -0,0 +1,209 @@
+//!
+//! Board description files: core, memories and peripherals of a target.
+//!
+
+use crate::errors::*;
+use serde::Deserialize;
+use std::fs;
+use zmu_cortex_m::core::profile::{ArchProfile, CORE_NAMES};
+use zmu_cortex_m::device::DEVICE_MODELS;
+use zmu_cortex_m::memory::map::MemoryMapConfig;
+use zmu_cortex_m::system::simulation::{DeviceConfig, SimulationConfig};
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+///
+/// Board description, read from a TOML file
+///
+pub struct Board {
+    pub name: Option<String>,
+    pub core: Option<String>,
+    pub cpuid: Option<u32>,
+    pub nvic_irqs: Option<usize>,
+    #[serde(default)]
+    pub flash: Vec<Region>,
+    #[serde(default)]
+    pub ram: Vec<Region>,
+    #[serde(default)]
+    pub alias: Vec<Alias>,
+    #[serde(default)]
+    pub device: Vec<Device>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+///
+/// Memory region
+///
+pub struct Region {
+    pub base: u32,
+    pub size: usize,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+///
+/// Address range `base..base+size` that mirrors the memory at `target`
+///
+pub struct Alias {
+    pub base: u32,
+    pub target: u32,
+    pub size: usize,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+///
+/// Peripheral device model
+///
+pub struct Device {
+    pub model: String,
+    pub base: Option<u32>,
+}
+
+fn check_region(kind: &str, base: u32, size: usize) -> Result<()> {
+    if size == 0 {
+        bail!("{} region at 0x{:08x} has zero size", kind, base);
+    }
+    if u64::from(base) + size as u64 > 0x1_0000_0000 {
+        bail!(
+            "{} region at 0x{:08x} extends past the end of the address space",
+            kind,
+            base
+        );
+    }
+    Ok(())
+}
+
+impl Board {
+    ///
+    /// Read and validate board description from given file
+    ///
+    pub fn load(filename: &str) -> Result<Self> {
+        let content = fs::read_to_string(filename)
+            .chain_err(|| format!("unable to read board file {filename}"))?;
+        let board: Self =
+            toml::from_str(&content).chain_err(|| format!("invalid board file {filename}"))?;
+        board.validate()?;
+        Ok(board)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(core) = &self.core {
+            if ArchProfile::from_core_name(core).is_none() {
+                bail!(
+                    "unsupported core '{}', supported cores are: {}",
+                    core,
+                    CORE_NAMES.join(", ")
+                );
+            }
+        }
+        for region in &self.flash {
+            check_region("flash", region.base, region.size)?;
+        }
+        for region in &self.ram {
+            check_region("RAM", region.base, region.size)?;
+        }
+        for alias in &self.alias {
+            check_region("alias", alias.base, alias.size)?;
+            check_region("alias target", alias.target, alias.size)?;
+        }
+        for device in &self.device {
+            if !DEVICE_MODELS.contains(&device.model.as_str()) {
+                bail!(
+                    "unknown device model '{}', supported models are: {}",
+                    device.model,
+                    DEVICE_MODELS.join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Apply the board description to the simulation configuration
+    ///
+    pub fn configure(&self, config: &mut SimulationConfig) {
+        if let Some(name) = &self.name {
+            info!("Configuring board {}", name);
+        }
+        config.profile = self.core.as_deref().and_then(ArchProfile::from_core_name);
+        config.flash = self.flash.iter().map(|r| (r.base, r.size)).collect();
+        config.ram = self.ram.iter().map(|r| (r.base, r.size)).collect();
+        config.aliases = self
+            .alias
+            .iter()
+            .map(|a| MemoryMapConfig::new(a.base, a.target, a.size))
+            .collect();
+        config.cpuid = self.cpuid;
+        config.nvic_irqs = self.nvic_irqs;
+        config.devices = self
+            .device
+            .iter()
+            .map(|d| DeviceConfig {
+                model: d.model.clone(),
+                base_address: d.base,
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"
+        name = "example"
+        core = "cortex-m4f"
+        cpuid = 0x412F_C230
+        nvic_irqs = 68
+
+        [[flash]]
+        base = 0x0800_0000
+        size = 0x2_0000
+
+        [[ram]]
+        base = 0x2000_0000
+        size = 0x5000
+
+        [[alias]]
+        base = 0x0000_0000
+        target = 0x0800_0000
+        size = 0x2_0000
+
+        [[device]]
+        model = "stm32f1xx"
+        base = 0x4000_0000
+    "#;
+
+    #[test]
+    fn test_parse_board() {
+        let board: Board = toml::from_str(BOARD).unwrap();
+        board.validate().unwrap();
+
+        let mut config = SimulationConfig::default();
+        board.configure(&mut config);
+        assert_eq!(config.profile, Some(ArchProfile::CORTEX_M4F));
+        assert_eq!(config.flash, vec![(0x0800_0000, 0x2_0000)]);
+        assert_eq!(config.ram, vec![(0x2000_0000, 0x5000)]);
+        assert_eq!(config.aliases.len(), 1);
+        assert_eq!(config.cpuid, Some(0x412F_C230));
+        assert_eq!(config.nvic_irqs, Some(68));
+        assert_eq!(config.devices[0].model, "stm32f1xx");
+    }
+
+    #[test]
+    fn test_invalid_board() {
+        let board: Board = toml::from_str("[[device]]\nmodel = \"nrf52\"\n").unwrap();
+        assert!(board.validate().is_err());
+
+        let board: Board =
+            toml::from_str("[[ram]]\nbase = 0xffff_0000\nsize = 0x2_0000\n").unwrap();
+        assert!(board.validate().is_err());
+
+        let board: Board = toml::from_str("core = \"cortex-a9\"\n").unwrap();
+        assert!(board.validate().is_err());
+
+        assert!(toml::from_str::<Board>("flash_size = 10\n").is_err());
+    }
+}
@@ -0,0 +1,349 @@
+//!
+//! Loading of executable images: ELF, Intel HEX, Motorola S-record, UF2 and raw binary.
+//!
+
+use crate::errors::*;
+use goblin::elf::program_header::pt_to_str;
+use goblin::elf::Elf;
+pub use zmu_cortex_m::memory::image::Segment;
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Supported image file formats
+///
+pub enum ImageFormat {
+    Elf,
+    IntelHex,
+    SRecord,
+    Uf2,
+    Binary,
+}
+
+const UF2_MAGIC_START0: u32 = 0x0A32_4655;
+const UF2_MAGIC_START1: u32 = 0x9E5D_5157;
+const UF2_MAGIC_END: u32 = 0x0AB1_6F30;
+const UF2_BLOCK_SIZE: usize = 512;
+const UF2_FLAG_NOT_MAIN_FLASH: u32 = 0x0000_0001;
+
+///
+/// Detect the format of the image from its content
+///
+pub fn detect_format(buffer: &[u8]) -> ImageFormat {
+    if buffer.starts_with(b"\x7fELF") {
+        return ImageFormat::Elf;
+    }
+
+    if buffer.len() >= UF2_BLOCK_SIZE
+        && read_u32(buffer, 0) == UF2_MAGIC_START0
+        && read_u32(buffer, 4) == UF2_MAGIC_START1
+    {
+        return ImageFormat::Uf2;
+    }
+
+    let first_line = buffer
+        .split(|&b| b == b'\n')
+        .map(<[u8]>::trim_ascii)
+        .find(|line| !line.is_empty());
+
+    match first_line {
+        Some(line) if is_text_record(line, b':') => ImageFormat::IntelHex,
+        Some(line)
+            if line.len() >= 2
+                && line[0] == b'S'
+                && line[1].is_ascii_digit()
+                && is_text_record(line, b'S') =>
+        {
+            ImageFormat::SRecord
+        }
+        _ => ImageFormat::Binary,
+    }
+}
+
+fn is_text_record(line: &[u8], start: u8) -> bool {
+    line.len() > 2 && line[0] == start && line[1..].iter().all(u8::is_ascii_hexdigit)
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        buffer[offset],
+        buffer[offset + 1],
+        buffer[offset + 2],
+        buffer[offset + 3],
+    ])
+}
+
+///
+/// Append data to list of segments, extending the last segment if the data is contiguous
+///
+fn push_data(segments: &mut Vec<Segment>, address: u32, data: &[u8]) {
+    if data.is_empty() {
+        return;
+    }
+    if let Some(last) = segments.last_mut() {
+        if last.address.wrapping_add(last.data.len() as u32) == address {
+            last.data.extend_from_slice(data);
+            return;
+        }
+    }
+    segments.push(Segment {
+        address,
+        data: data.to_vec(),
+    });
+}
+
+///
+/// Collect the loadable (`PT_LOAD`) segments of an ELF file
+///
+pub fn elf_segments(elf: &Elf, buffer: &[u8]) -> Vec<Segment> {
+    let mut segments = Vec::new();
+
+    debug!("Determining ELF code sections");
+    for ph in &elf.program_headers {
+        if ph.p_type == goblin::elf::program_header::PT_LOAD && ph.p_filesz > 0 {
+            debug!(
+                "PT_LOAD section at 0x{:08x} - 0x{:08x} (size = {} bytes)",
+                ph.p_paddr,
+                ph.p_paddr + ph.p_filesz,
+                ph.p_filesz
+            );
+            let src_addr = ph.p_offset as usize;
+            let src_end_addr = (ph.p_offset + ph.p_filesz) as usize;
+            segments.push(Segment {
+                address: ph.p_paddr as u32,
+                data: buffer[src_addr..src_end_addr].to_vec(),
+            });
+        } else {
+            debug!(
+                "ignoring section : {} (size = {} bytes)",
+                pt_to_str(ph.p_type),
+                ph.p_filesz
+            );
+        }
+    }
+    segments
+}
+
+fn hex_bytes(line: &[u8], lineno: usize) -> Result<Vec<u8>> {
+    if !line.len().is_multiple_of(2) {
+        bail!("line {}: odd number of hex digits", lineno);
+    }
+    line.chunks(2)
+        .map(|pair| {
+            let s =
+                std::str::from_utf8(pair).chain_err(|| format!("line {lineno}: invalid hex"))?;
+            u8::from_str_radix(s, 16).chain_err(|| format!("line {lineno}: invalid hex"))
+        })
+        .collect()
+}
+
+///
+/// Parse Intel HEX formatted image
+///
+pub fn parse_ihex(buffer: &[u8]) -> Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+    let mut base_address: u32 = 0;
+
+    for (index, line) in buffer.split(|&b| b == b'\n').enumerate() {
+        let lineno = index + 1;
+        let line = line.trim_ascii();
+        if line.is_empty() {
+            continue;
+        }
+        if line[0] != b':' {
+            bail!("line {}: missing record start code", lineno);
+        }
+        let record = hex_bytes(&line[1..], lineno)?;
+        if record.len() < 5 || record.len() != usize::from(record[0]) + 5 {
+            bail!("line {}: invalid record length", lineno);
+        }
+        if record.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)) != 0 {
+            bail!("line {}: checksum mismatch", lineno);
+        }
+
+        let offset = u32::from(u16::from_be_bytes([record[1], record[2]]));
+        let data = &record[4..record.len() - 1];
+
+        match record[3] {
+            // data
+            0x00 => push_data(&mut segments, base_address.wrapping_add(offset), data),
+            // end of file
+            0x01 => break,
+            // extended segment address
+            0x02 if data.len() == 2 => {
+                base_address = u32::from(u16::from_be_bytes([data[0], data[1]])) << 4;
+            }
+            // extended linear address
+            0x04 if data.len() == 2 => {
+                base_address = u32::from(u16::from_be_bytes([data[0], data[1]])) << 16;
+            }
+            // start segment / start linear address, entry point comes from the vector table
+            0x03 | 0x05 => {}
+            record_type => bail!(
+                "line {}: unsupported record type {:02x}",
+                lineno,
+                record_type
+            ),
+        }
+    }
+    Ok(segments)
+}
+
+///
+/// Parse Motorola S-record formatted image
+///
+pub fn parse_srec(buffer: &[u8]) -> Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+
+    for (index, line) in buffer.split(|&b| b == b'\n').enumerate() {
+        let lineno = index + 1;
+        let line = line.trim_ascii();
+        if line.is_empty() {
+            continue;
+        }
+        if line.len() < 4 || line[0] != b'S' {
+            bail!("line {}: missing record start code", lineno);
+        }
+        let record = hex_bytes(&line[2..], lineno)?;
+        if record.is_empty() || record.len() != usize::from(record[0]) + 1 {
+            bail!("line {}: invalid record length", lineno);
+        }
+        if record.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)) != 0xff {
+            bail!("line {}: checksum mismatch", lineno);
+        }
+
+        let address_len = match line[1] {
+            b'1' => 2,
+            b'2' => 3,
+            b'3' => 4,
+            // header, record counts and termination records carry no data
+            b'0' | b'5' | b'6' | b'7' | b'8' | b'9' => continue,
+            _ => bail!(
+                "line {}: unsupported record type S{}",
+                lineno,
+                line[1] as char
+            ),
+        };
+        if record.len() < address_len + 2 {
+            bail!("line {}: invalid record length", lineno);
+        }
+        let address = record[1..=address_len]
+            .iter()
+            .fold(0u32, |acc, b| (acc << 8) | u32::from(*b));
+        push_data(
+            &mut segments,
+            address,
+            &record[address_len + 1..record.len() - 1],
+        );
+    }
+    Ok(segments)
+}
+
+///
+/// Parse UF2 formatted image
+///
+pub fn parse_uf2(buffer: &[u8]) -> Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+
+    if !buffer.len().is_multiple_of(UF2_BLOCK_SIZE) {
+        bail!(
+            "UF2 file size is not a multiple of {} bytes",
+            UF2_BLOCK_SIZE
+        );
+    }
+
+    for (blockno, block) in buffer.chunks(UF2_BLOCK_SIZE).enumerate() {
+        if read_u32(block, 0) != UF2_MAGIC_START0
+            || read_u32(block, 4) != UF2_MAGIC_START1
+            || read_u32(block, UF2_BLOCK_SIZE - 4) != UF2_MAGIC_END
+        {
+            bail!("UF2 block {}: invalid magic", blockno);
+        }
+        let flags = read_u32(block, 8);
+        let target_addr = read_u32(block, 12);
+        let payload_size = read_u32(block, 16) as usize;
+
+        if payload_size > 476 {
+            bail!(
+                "UF2 block {}: invalid payload size {}",
+                blockno,
+                payload_size
+            );
+        }
+        if flags & UF2_FLAG_NOT_MAIN_FLASH != 0 {
+            debug!("ignoring UF2 block {} not meant for main flash", blockno);
+            continue;
+        }
+        push_data(&mut segments, target_addr, &block[32..32 + payload_size]);
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format(b"\x7fELF\x01\x01"), ImageFormat::Elf);
+        assert_eq!(
+            detect_format(b":0400000001020304F2\n:00000001FF\n"),
+            ImageFormat::IntelHex
+        );
+        assert_eq!(
+            detect_format(b"S00600004844521B\nS107000001020304EE\n"),
+            ImageFormat::SRecord
+        );
+        assert_eq!(
+            detect_format(&[0x00, 0x10, 0x00, 0x20]),
+            ImageFormat::Binary
+        );
+    }
+
+    #[test]
+    fn test_parse_ihex() {
+        let hex = b":020000040800F2\n:0400000001020304F2\n:0400040005060708DE\n:00000001FF\n";
+        let segments = parse_ihex(hex).unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].address, 0x0800_0000);
+        assert_eq!(segments[0].data, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_parse_ihex_checksum() {
+        assert!(parse_ihex(b":0400000001020304F3\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_srec() {
+        let srec = b"S00600004844521B\nS107000001020304EE\nS30908000010AABBCCDDD0\nS9030000FC\n";
+        let segments = parse_srec(srec).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].address, 0);
+        assert_eq!(segments[0].data, vec![1, 2, 3, 4]);
+        assert_eq!(segments[1].address, 0x0800_0010);
+        assert_eq!(segments[1].data, vec![0xaa, 0xbb, 0xcc, 0xdd]);
+    }
+
+    #[test]
+    fn test_parse_uf2() {
+        let mut image = Vec::new();
+        for (i, address) in [0x1000_0000u32, 0x1000_0100].iter().enumerate() {
+            let mut block = vec![0u8; UF2_BLOCK_SIZE];
+            block[0..4].copy_from_slice(&UF2_MAGIC_START0.to_le_bytes());
+            block[4..8].copy_from_slice(&UF2_MAGIC_START1.to_le_bytes());
+            block[12..16].copy_from_slice(&address.to_le_bytes());
+            block[16..20].copy_from_slice(&256u32.to_le_bytes());
+            block[20..24].copy_from_slice(&(i as u32).to_le_bytes());
+            block[24..28].copy_from_slice(&2u32.to_le_bytes());
+            block[32..288].fill(i as u8 + 1);
+            block[UF2_BLOCK_SIZE - 4..].copy_from_slice(&UF2_MAGIC_END.to_le_bytes());
+            image.extend_from_slice(&block);
+        }
+        assert_eq!(detect_format(&image), ImageFormat::Uf2);
+        let segments = parse_uf2(&image).unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].address, 0x1000_0000);
+        assert_eq!(segments[0].data.len(), 512);
+        assert_eq!(segments[0].data[256], 2);
+    }
+}
@@ -18,29 +18,57 @@ use clap::Arg;
 use clap::ArgAction;
 use clap::ArgMatches;
 use clap::Command;
-use goblin::elf::program_header::pt_to_str;
 use goblin::Object;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
-use std::time::Instant;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
+mod board;
+mod loader;
 mod semihost;
+mod stats;
 mod trace;
 
-use crate::semihost::get_semihost_func;
+use crate::board::Board;
+use crate::loader::{
+    detect_format, elf_segments, parse_ihex, parse_srec, parse_uf2, ImageFormat, Segment,
+};
+use crate::semihost::{elf_heap_info, get_semihost_func, SemihostOptions};
+use crate::stats::write_stats_json;
 use crate::trace::format_trace_entry;
 
-use std::cmp;
 use std::collections::HashMap;
 use tabwriter::TabWriter;
-use zmu_cortex_m::memory::map::MemoryMapConfig;
+use zmu_cortex_m::core::exception::LockupReason;
+use zmu_cortex_m::core::fault::Fault;
+use zmu_cortex_m::core::instruction::Instruction;
+use zmu_cortex_m::core::profile::{ArchProfile, CORE_NAMES};
 use zmu_cortex_m::Processor;
 
 use zmu_cortex_m::system::simulation::simulate_trace;
-use zmu_cortex_m::system::simulation::{simulate, SimulationError};
+use zmu_cortex_m::system::simulation::{
+    simulate, Limit, ProcessorState, SimulationConfig, SimulationError,
+};
 use zmu_cortex_m::gdb::server::GdbServer;
 
+///
+/// Exit code of a run stopped by `--max-instructions`, `--max-cycles` or `--timeout`
+///
+const LIMIT_EXIT_CODE: u32 = 124;
+
+///
+/// Exit code of a run stopped by `--fault-trap`
+///
+const FAULT_TRAP_EXIT_CODE: u32 = 125;
+
+///
+/// Exit code of a run stopped by the processor entering the Lockup state
+///
+const LOCKUP_EXIT_CODE: u32 = 126;
+
+#[allow(unexpected_cfgs)]
 mod errors {
     // Create the Error, ErrorKind, ResultExt, and Result types
     error_chain! {}
@@ -55,114 +83,100 @@ impl From<SimulationError> for errors::Error {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_bin(
     buffer: &[u8],
     trace: bool,
     option_trace_start: Option<u64>,
     itm_file: Option<Box<dyn io::Write + 'static>>,
     gdb: bool,
+    load_address: Option<u32>,
+    mut semihost_options: SemihostOptions,
+    mut config: SimulationConfig,
+    stats_json: Option<&str>,
 ) -> Result<u32> {
-    let res = Object::parse(buffer).unwrap();
+    let format = detect_format(buffer);
+    debug!("Detected {:?} file.", format);
+
+    if load_address.is_some() && format != ImageFormat::Binary {
+        warn!("--load-address is ignored for {:?} files.", format);
+    }
 
-    let elf = match res {
-        Object::Elf(elf) => elf,
-        _ => {
-            bail!("Unsupported file format.");
+    let elf = if format == ImageFormat::Elf {
+        match Object::parse(buffer).chain_err(|| "failed to parse ELF file")? {
+            Object::Elf(elf) => Some(elf),
+            _ => {
+                bail!("Unsupported file format.");
+            }
         }
+    } else {
+        None
     };
 
-    debug!("Detected ELF file.");
-
-    // auto detection of required flash size:
-    // loop 1: determine lower bound and upper bound
-
-    let mut min_address = 0xffff_ffff;
-    let mut max_address = 0;
-    debug!("Determining ELF code sections");
-    for ph in &elf.program_headers {
-        if ph.p_type == goblin::elf::program_header::PT_LOAD && ph.p_filesz > 0 {
-            let dst_addr = ph.p_paddr as usize;
-            let dst_end_addr = (ph.p_paddr + ph.p_filesz) as usize;
+    let segments = match format {
+        ImageFormat::Elf => elf_segments(elf.as_ref().unwrap(), buffer),
+        ImageFormat::IntelHex => parse_ihex(buffer).chain_err(|| "invalid Intel HEX file")?,
+        ImageFormat::SRecord => parse_srec(buffer).chain_err(|| "invalid S-record file")?,
+        ImageFormat::Uf2 => parse_uf2(buffer).chain_err(|| "invalid UF2 file")?,
+        ImageFormat::Binary => vec![Segment {
+            address: load_address.unwrap_or(0),
+            data: buffer.to_vec(),
+        }],
+    };
 
-            debug!(
-                "PT_LOAD section at 0x{:08x} - 0x{:08x} (size = {} bytes)",
-                dst_addr, dst_end_addr, ph.p_filesz
-            );
-            min_address = cmp::min(dst_addr, min_address);
-            max_address = cmp::max(dst_end_addr, max_address);
-        } else {
-            debug!(
-                "ignoring section : {} (size = {} bytes)",
-                pt_to_str(ph.p_type),
-                ph.p_filesz
-            );
-        }
+    if segments.is_empty() {
+        bail!("No loadable data in file.");
     }
 
-    let flash_start_address = min_address as u32;
-    let flash_size = max_address - min_address;
-    info!(
-        "Auto configuring flash: address space is 0x{:x}..0x{:x}, size= {} bytes",
-        flash_start_address, max_address, flash_size
-    );
-    let mut flash_mem = vec![0; flash_size];
-
-    // loop 2: load data by offset
-    for ph in &elf.program_headers {
-        if ph.p_type == goblin::elf::program_header::PT_LOAD && ph.p_filesz > 0 {
-            let dst_addr = (ph.p_paddr - u64::from(flash_start_address)) as usize;
-            let dst_end_addr =
-                ((ph.p_paddr + ph.p_filesz) - u64::from(flash_start_address)) as usize;
-
-            let src_addr = ph.p_offset as usize;
-            let src_end_addr = (ph.p_offset + ph.p_filesz) as usize;
+    for segment in &segments {
+        info!(
+            "Loading segment at 0x{:08x} - 0x{:08x} (size = {} bytes)",
+            segment.address,
+            segment.end_address(),
+            segment.data.len()
+        );
+    }
 
-            flash_mem[dst_addr..dst_end_addr].copy_from_slice(&buffer[src_addr..src_end_addr]);
+    if !config.flash.is_empty() {
+        for segment in &segments {
+            let inside = config.flash.iter().chain(&config.ram).any(|&(base, size)| {
+                segment.address >= base && segment.end_address() <= u64::from(base) + size as u64
+            });
+            if !inside {
+                warn!(
+                    "Segment at 0x{:08x} - 0x{:08x} is outside of the configured memories",
+                    segment.address,
+                    segment.end_address()
+                );
+            }
         }
     }
 
+    config.image = segments;
+
     let trace_start = option_trace_start.unwrap_or(0);
-    let semihost_func = Box::new(get_semihost_func(Instant::now()));
+    if let Some(elf) = &elf {
+        semihost_options.heap_info = elf_heap_info(elf);
+    }
+    let semihost_func = Box::new(get_semihost_func(Instant::now(), semihost_options));
 
     if gdb {
-        let gdb = GdbServer::new(
-            &flash_mem,
-            semihost_func,
-            if flash_start_address != 0 {
-                Some(MemoryMapConfig::new(flash_start_address, 0, flash_size))
-            } else {
-                None
-            },
-            flash_size,
-        );
+        let gdb = GdbServer::new(&config, semihost_func);
 
         let exit_code = gdb?.start().expect("GDB server failed");
         return Ok(exit_code);
     }
 
-    let statistics = if trace {
+    // only ELF files carry symbols
+    let symboltable = elf.as_ref().map(symbol_table).unwrap_or_default();
+
+    let start = Instant::now();
+    let result = if trace {
         debug!("Configuring tracing.");
 
-        let mut symboltable = HashMap::new();
         let mut trace_stdout = TabWriter::new(io::stdout()).minwidth(16).padding(1);
 
-        for sym in &elf.syms {
-            if sym.st_type() != goblin::elf::sym::STT_FILE {
-                if let Some(maybe_name) = elf.strtab.get_at(sym.st_name) {
-                    let name = maybe_name;
-                    let mut count = 0;
-                    let mut pos = sym.st_value as u32;
-                    while count <= sym.st_size {
-                        // Align addresses to 2 byte alignment
-                        symboltable.insert(pos & 0xffff_fffe, name);
-                        pos += 2;
-                        count += 2;
-                    }
-                }
-            }
-        }
-
-        let tracefunc = |processor: &Processor| {
+        let tracefunc = |processor: &mut Processor| {
             if processor.instruction_count >= trace_start {
                 let trace_entry = format_trace_entry(processor, &symboltable);
                 writeln!(&mut trace_stdout, "{}", trace_entry).unwrap();
@@ -171,31 +185,30 @@ fn run_bin(
         };
         debug!("Starting simulation with trace.");
 
-        simulate_trace(
-            &flash_mem,
-            tracefunc,
-            semihost_func,
-            itm_file,
-            if flash_start_address != 0 {
-                Some(MemoryMapConfig::new(flash_start_address, 0, flash_size))
-            } else {
-                None
-            },
-            flash_size,
-        )?
+        simulate_trace(&config, tracefunc, semihost_func, itm_file)
     } else {
         debug!("Starting simulation.");
-        simulate(
-            &flash_mem,
-            semihost_func,
-            itm_file,
-            if flash_start_address != 0 {
-                Some(MemoryMapConfig::new(flash_start_address, 0, flash_size))
-            } else {
-                None
-            },
-            flash_size,
-        )?
+        simulate(&config, semihost_func, itm_file)
+    };
+
+    let statistics = match result {
+        Err(SimulationError::LimitReached { limit, state }) => {
+            report_limit(limit, &state, &symboltable);
+            state.statistics(start.elapsed(), LIMIT_EXIT_CODE)
+        }
+        Err(SimulationError::FaultTrap {
+            fault,
+            instruction,
+            state,
+        }) => {
+            report_fault(fault, instruction, &state, &symboltable);
+            state.statistics(start.elapsed(), FAULT_TRAP_EXIT_CODE)
+        }
+        Err(SimulationError::Lockup { pc, reason, state }) => {
+            report_lockup(pc, reason, &state, &symboltable);
+            state.statistics(start.elapsed(), LOCKUP_EXIT_CODE)
+        }
+        Ok(statistics) => statistics,
     };
 
     let duration_in_secs = statistics.duration.as_secs() as f64
@@ -214,9 +227,212 @@ fn run_bin(
         cycles_per_sec,
         cycles_per_sec / 1_000_000.0,
     );
+    if let Some(filename) = stats_json {
+        write_stats_json(filename, &statistics)?;
+    }
     Ok(statistics.exit_code)
 }
 
+fn parse_address(value: &str) -> std::result::Result<u32, String> {
+    let value = value.replace('_', "");
+    let parsed = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => value.parse::<u32>(),
+    };
+    parsed.map_err(|e| format!("invalid address '{value}': {e}"))
+}
+
+fn parse_size(value: &str) -> std::result::Result<usize, String> {
+    let (number, multiplier) = match value.strip_suffix(['k', 'K']) {
+        Some(number) => (number, 1024),
+        None => match value.strip_suffix('M') {
+            Some(number) => (number, 1024 * 1024),
+            None => (value, 1),
+        },
+    };
+    let size = parse_address(number)? as usize;
+    size.checked_mul(multiplier)
+        .filter(|&size| size > 0)
+        .ok_or_else(|| format!("invalid size '{value}'"))
+}
+
+///
+/// Combine the `--ram-base` and `--ram-size` options to list of RAM banks.
+/// A single `--ram-size` without a base resizes the default RAM at 0x2000_0000.
+///
+fn ram_banks(run_matches: &ArgMatches) -> Result<Vec<(u32, usize)>> {
+    let bases: Vec<u32> = run_matches
+        .get_many::<u32>("ram-base")
+        .unwrap_or_default()
+        .copied()
+        .collect();
+    let sizes: Vec<usize> = run_matches
+        .get_many::<usize>("ram-size")
+        .unwrap_or_default()
+        .copied()
+        .collect();
+
+    let banks: Vec<(u32, usize)> = if bases.is_empty() && sizes.len() == 1 {
+        vec![(0x2000_0000, sizes[0])]
+    } else if bases.len() == sizes.len() {
+        bases.into_iter().zip(sizes).collect()
+    } else {
+        bail!("each --ram-base needs a matching --ram-size");
+    };
+
+    for (i, &(base, size)) in banks.iter().enumerate() {
+        let end = u64::from(base) + size as u64;
+        if end > 0x1_0000_0000 {
+            bail!(
+                "RAM bank at 0x{:08x} extends past the end of the address space",
+                base
+            );
+        }
+        for &(other_base, other_size) in &banks[..i] {
+            if u64::from(other_base) < end
+                && u64::from(base) < u64::from(other_base) + other_size as u64
+            {
+                bail!(
+                    "RAM banks at 0x{:08x} and 0x{:08x} overlap",
+                    other_base,
+                    base
+                );
+            }
+        }
+    }
+    Ok(banks)
+}
+
+fn parse_timeout(value: &str) -> std::result::Result<Duration, String> {
+    let (number, unit) = match value.strip_suffix("ms") {
+        Some(number) => (number, 0.001),
+        None => match value.strip_suffix('s') {
+            Some(number) => (number, 1.0),
+            None => match value.strip_suffix('m') {
+                Some(number) => (number, 60.0),
+                None => (value, 1.0),
+            },
+        },
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .and_then(|number| Duration::try_from_secs_f64(number * unit).ok())
+        .ok_or_else(|| format!("invalid timeout '{value}'"))
+}
+
+///
+/// Map of halfword aligned addresses to the ELF symbols covering them
+///
+fn symbol_table<'a>(elf: &'a goblin::elf::Elf) -> HashMap<u32, &'a str> {
+    let mut symboltable = HashMap::new();
+    for sym in &elf.syms {
+        if sym.st_type() != goblin::elf::sym::STT_FILE {
+            if let Some(maybe_name) = elf.strtab.get_at(sym.st_name) {
+                let name = maybe_name;
+                let mut count = 0;
+                let mut pos = sym.st_value as u32;
+                while count <= sym.st_size {
+                    // Align addresses to 2 byte alignment
+                    symboltable.insert(pos & 0xffff_fffe, name);
+                    pos += 2;
+                    count += 2;
+                }
+            }
+        }
+    }
+    symboltable
+}
+
+///
+/// Address with the symbol covering it, if any
+///
+fn location(pc: u32, symboltable: &HashMap<u32, &str>) -> String {
+    match symboltable.get(&(pc & 0xffff_fffe)) {
+        Some(symbol) => format!("0x{pc:08x} ({symbol})"),
+        None => format!("0x{pc:08x}"),
+    }
+}
+
+fn print_registers(state: &ProcessorState) {
+    const NAMES: [&str; 16] = [
+        "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11", "r12", "sp",
+        "lr", "pc",
+    ];
+    for (names, values) in NAMES.chunks(4).zip(state.registers.chunks(4)) {
+        let line: Vec<String> = names
+            .iter()
+            .zip(values)
+            .map(|(name, value)| format!("{name:>3}: {value:08x}"))
+            .collect();
+        eprintln!("{}", line.join("  "));
+    }
+    eprintln!(
+        "psr: {:08x}  msp: {:08x}  psp: {:08x}",
+        state.psr, state.msp, state.psp
+    );
+}
+
+fn report_limit(limit: Limit, state: &ProcessorState, symboltable: &HashMap<u32, &str>) {
+    let reason = match limit {
+        Limit::Instructions => "instruction limit",
+        Limit::Cycles => "cycle limit",
+        Limit::Timeout => "timeout",
+    };
+    eprintln!(
+        "Simulation stopped on {} at {} after {} instructions, {} cycles",
+        reason,
+        location(state.pc(), symboltable),
+        state.instruction_count,
+        state.cycle_count
+    );
+    print_registers(state);
+}
+
+fn report_fault(
+    fault: Fault,
+    instruction: Option<Instruction>,
+    state: &ProcessorState,
+    symboltable: &HashMap<u32, &str>,
+) {
+    eprintln!(
+        "Fault trap: {:?} at {} after {} instructions, {} cycles",
+        fault,
+        location(state.pc(), symboltable),
+        state.instruction_count,
+        state.cycle_count
+    );
+    if let Some(instruction) = instruction {
+        eprintln!("instruction: {}", instruction);
+    }
+    print_registers(state);
+}
+
+fn report_lockup(
+    pc: u32,
+    reason: LockupReason,
+    state: &ProcessorState,
+    symboltable: &HashMap<u32, &str>,
+) {
+    let reason = match reason {
+        LockupReason::Fault(fault) => format!("{fault:?} at HardFault or NMI priority"),
+        LockupReason::ExceptionEntry { exception, fault } => {
+            format!("{fault:?} on entry to {exception:?}")
+        }
+    };
+    eprintln!(
+        "Lockup: {} at {} after {} instructions, {} cycles",
+        reason,
+        location(pc, symboltable),
+        state.instruction_count,
+        state.cycle_count
+    );
+    print_registers(state);
+}
+
 fn open_itm_file(filename: &str) -> Option<Box<dyn io::Write + 'static>> {
     let result = File::create(filename);
 
@@ -240,6 +456,35 @@ fn run(args: &ArgMatches) -> Result<u32> {
                 None => None,
             };
 
+            let mut config = SimulationConfig::default();
+            if let Some(board_file) = run_matches.get_one::<String>("board") {
+                Board::load(board_file)?.configure(&mut config);
+            }
+            if let Some(cpu) = run_matches.get_one::<String>("cpu") {
+                config.profile = ArchProfile::from_core_name(cpu);
+            }
+            config.fault_trap = run_matches.get_flag("fault-trap");
+            config.max_instructions = run_matches.get_one::<u64>("max-instructions").copied();
+            config.max_cycles = run_matches.get_one::<u64>("max-cycles").copied();
+            config.timeout = run_matches.get_one::<Duration>("timeout").copied();
+            let ram = ram_banks(run_matches)?;
+            if !ram.is_empty() {
+                config.ram = ram;
+            }
+
+            // command line seen by the firmware: executable name followed by the ARGS
+            let cmdline = std::iter::once(filename)
+                .chain(run_matches.get_many::<String>("ARGS").into_iter().flatten())
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let semihost_options = SemihostOptions {
+                cmdline,
+                root: run_matches.get_one::<PathBuf>("semihost-root").cloned(),
+                allow_system: run_matches.get_flag("semihost-system"),
+                ..SemihostOptions::default()
+            };
+
             let buffer = {
                 let mut v = Vec::new();
                 let mut f = File::open(filename).chain_err(|| "unable to open file")?;
@@ -253,6 +498,12 @@ fn run(args: &ArgMatches) -> Result<u32> {
                 trace_start,
                 itm_output,
                 run_matches.get_flag("gdb"),
+                run_matches.get_one::<u32>("load-address").copied(),
+                semihost_options,
+                config,
+                run_matches
+                    .get_one::<String>("stats-json")
+                    .map(String::as_str),
             )?
         }
         Some((_, _)) => unreachable!(),
@@ -296,10 +547,94 @@ fn main() {
                         .help("Name of file to which itm trace data is written to. ")
                         .num_args(1),
                 )
+                .arg(
+                    Arg::new("load-address")
+                        .long("load-address")
+                        .help("Address at which a raw binary image is loaded (default 0)")
+                        .action(ArgAction::Set)
+                        .value_parser(parse_address),
+                )
+                .arg(
+                    Arg::new("board")
+                        .long("board")
+                        .help("Board description file (TOML) declaring core, memories and devices")
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("cpu")
+                        .long("cpu")
+                        .help("Simulated core, overrides the core of the board file")
+                        .action(ArgAction::Set)
+                        .value_parser(CORE_NAMES),
+                )
+                .arg(
+                    Arg::new("ram-base")
+                        .long("ram-base")
+                        .help("Start address of a RAM bank, repeat for several banks")
+                        .action(ArgAction::Append)
+                        .value_parser(parse_address),
+                )
+                .arg(
+                    Arg::new("ram-size")
+                        .long("ram-size")
+                        .help("Size of a RAM bank (e.g. 0x8000, 64K, 1M), repeat for several banks")
+                        .action(ArgAction::Append)
+                        .value_parser(parse_size),
+                )
+                .arg(
+                    Arg::new("semihost-root")
+                        .long("semihost-root")
+                        .help(
+                            "Directory in which the firmware may access host files via semihosting",
+                        )
+                        .action(ArgAction::Set)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("semihost-system")
+                        .long("semihost-system")
+                        .action(ArgAction::SetTrue)
+                        .help("Allow the firmware to run host commands via SYS_SYSTEM semihosting"),
+                )
+                .arg(
+                    Arg::new("max-instructions")
+                        .long("max-instructions")
+                        .help("Stop the simulation after given number of instructions")
+                        .action(ArgAction::Set)
+                        .value_parser(value_parser!(u64)),
+                )
+                .arg(
+                    Arg::new("max-cycles")
+                        .long("max-cycles")
+                        .help("Stop the simulation after given number of clock cycles")
+                        .action(ArgAction::Set)
+                        .value_parser(value_parser!(u64)),
+                )
+                .arg(
+                    Arg::new("timeout")
+                        .long("timeout")
+                        .help("Stop the simulation after given wallclock time (e.g. 30, 1.5s, 500ms, 2m)")
+                        .action(ArgAction::Set)
+                        .value_parser(parse_timeout),
+                )
+                .arg(
+                    Arg::new("fault-trap")
+                        .long("fault-trap")
+                        .action(ArgAction::SetTrue)
+                        .help("Stop on the first fault and report the faulting instruction"),
+                )
+                .arg(
+                    Arg::new("stats-json")
+                        .long("stats-json")
+                        .help("Write run statistics to given file in JSON format")
+                        .num_args(1),
+                )
                 .arg(
                     Arg::new("EXECUTABLE")
                         .index(1)
-                        .help("Set executable to load")
+                        .help(
+                            "Set executable to load (ELF, Intel HEX, S-record, UF2 or raw binary)",
+                        )
                         .required(true),
                 )
                 .arg(
@@ -347,3 +682,42 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_json_on_limit() {
+        // vector table with the reset handler at 0x8: "b ."
+        let data = [
+            0x00, 0x10, 0x00, 0x20, 0x09, 0x00, 0x00, 0x00, 0xfe, 0xe7, 0x00, 0x00,
+        ];
+        let filename = std::env::temp_dir().join(format!("zmu-stats-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&filename);
+        let config = SimulationConfig {
+            max_instructions: Some(100),
+            ..SimulationConfig::default()
+        };
+
+        let exit_code = run_bin(
+            &data,
+            false,
+            None,
+            None,
+            false,
+            None,
+            SemihostOptions::default(),
+            config,
+            filename.to_str(),
+        )
+        .unwrap();
+        assert_eq!(exit_code, LIMIT_EXIT_CODE);
+
+        let json: serde_json::Value =
+            serde_json::from_reader(File::open(&filename).expect("statistics not written"))
+                .unwrap();
+        assert_eq!(json["instructions"], 100);
+        std::fs::remove_file(&filename).unwrap();
+    }
+}
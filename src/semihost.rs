@@ -1,14 +1,30 @@
 use std::cmp::min;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
 use std::io;
 use std::io::prelude::*;
-use std::time::Instant;
+use std::io::SeekFrom;
+use std::path::{Component, Path, PathBuf};
+use std::process;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
+use goblin::elf::Elf;
 use zmu_cortex_m::semihosting::{SemihostingCommand, SemihostingResponse, SysExceptionReason};
 
 const TT_HANDLE_STDIN: u32 = 1;
 const TT_HANDLE_STDOUT: u32 = 2;
 const TT_HANDLE_STDERR: u32 = 3;
 const SEMIHOST_FEATURES_HANDLE: u32 = 4;
+const FIRST_FILE_HANDLE: u32 = 5;
+
+// SYS_ELAPSED ticks are microseconds
+const TICK_FREQUENCY: u32 = 1_000_000;
+
+// errno reported when the host error has no OS error code (EIO)
+const DEFAULT_ERRNO: u32 = 5;
+
+// errno reported for an unsupported operation (ENOSYS)
+const UNSUPPORTED_ERRNO: u32 = 38;
 
 /*
  byte 0: SHFB_MAGIC_0 0x53
@@ -19,8 +35,106 @@ const SEMIHOST_FEATURES_HANDLE: u32 = 4;
 */
 static FEATURE_DATA: [u8; 5] = [0x53, 0x48, 0x46, 0x42, 3];
 
-pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> SemihostingResponse {
+///
+/// Host side settings of the semihosting calls
+///
+#[derive(Default)]
+pub struct SemihostOptions {
+    /// command line returned by SYS_GET_CMDLINE
+    pub cmdline: String,
+    /// directory to which host file access is confined, no file access if not set
+    pub root: Option<PathBuf>,
+    /// allow SYS_SYSTEM to run commands on the host
+    pub allow_system: bool,
+    /// heap base, heap limit, stack base and stack limit returned by SYS_HEAPINFO
+    pub heap_info: [u32; 4],
+}
+
+///
+/// Heap and stack parameters from the symbols defined by the CMSIS linker scripts.
+/// Zero tells the C library to use its own defaults.
+///
+pub fn elf_heap_info(elf: &Elf) -> [u32; 4] {
+    let lookup = |name: &str| {
+        elf.syms
+            .iter()
+            .find(|sym| elf.strtab.get_at(sym.st_name) == Some(name))
+            .map_or(0, |sym| sym.st_value as u32)
+    };
+    [
+        lookup("__end__"),
+        lookup("__HeapLimit"),
+        lookup("__StackTop"),
+        lookup("__StackLimit"),
+    ]
+}
+
+///
+/// Resolve a file name of the firmware inside the sandbox directory.
+/// Absolute names and names referring to parent directories are rejected.
+///
+fn sandbox_path(root: Option<&Path>, name: &str) -> Option<PathBuf> {
+    let root = root?;
+    let path = Path::new(name);
+    if name.is_empty()
+        || !path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return None;
+    }
+    Some(root.join(path))
+}
+
+///
+/// Open a host file with ISO C `fopen` mode: 0..=11 for "r", "rb", "r+", "r+b",
+/// "w", "wb", "w+", "w+b", "a", "ab", "a+", "a+b"
+///
+fn open_file(path: &Path, mode: u32) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    let update = mode & 2 != 0;
+    match mode >> 2 {
+        0 => options.read(true).write(update),
+        1 => options.write(true).read(update).create(true).truncate(true),
+        _ => options.append(true).read(update).create(true),
+    };
+    options.open(path)
+}
+
+fn errno_of(error: &io::Error) -> u32 {
+    error
+        .raw_os_error()
+        .map_or(DEFAULT_ERRNO, |code| code as u32)
+}
+
+///
+/// Run a command with the host shell, in the sandbox directory if set
+///
+fn run_system(command: &str, root: Option<&Path>) -> io::Result<process::ExitStatus> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+    if let Some(root) = root {
+        shell.current_dir(root);
+    }
+    shell.status()
+}
+
+pub fn get_semihost_func(
+    start: Instant,
+    options: SemihostOptions,
+) -> impl FnMut(&SemihostingCommand) -> SemihostingResponse {
     let mut semihost_features_position: u32 = 0;
+    let mut files: HashMap<u32, File> = HashMap::new();
+    let mut next_handle = FIRST_FILE_HANDLE;
+    let mut errno: u32 = 0;
 
     move |semihost_cmd: &SemihostingCommand| -> SemihostingResponse {
         match semihost_cmd {
@@ -45,7 +159,22 @@ pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> S
                     SemihostingResponse::SysOpen {
                         result: Ok(SEMIHOST_FEATURES_HANDLE),
                     }
+                } else if let Some(path) = sandbox_path(options.root.as_deref(), name) {
+                    match open_file(&path, *mode) {
+                        Ok(file) => {
+                            let handle = next_handle;
+                            next_handle += 1;
+                            files.insert(handle, file);
+                            SemihostingResponse::SysOpen { result: Ok(handle) }
+                        }
+                        Err(e) => {
+                            debug!("semihosting: cannot open {}: {}", path.display(), e);
+                            errno = errno_of(&e);
+                            SemihostingResponse::SysOpen { result: Err(-1) }
+                        }
+                    }
                 } else {
+                    warn!("semihosting: access to file '{}' denied", name);
                     SemihostingResponse::SysOpen { result: Err(-1) }
                 }
             }
@@ -54,6 +183,9 @@ pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> S
                 if *handle == SEMIHOST_FEATURES_HANDLE {
                     semihost_features_position = 0;
                 }
+                if *handle >= FIRST_FILE_HANDLE && files.remove(handle).is_none() {
+                    return SemihostingResponse::SysClose { success: false };
+                }
 
                 SemihostingResponse::SysClose { success: true }
             }
@@ -64,6 +196,16 @@ pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> S
                     SemihostingResponse::SysFlen { result: Ok(0) }
                 } else if *handle == SEMIHOST_FEATURES_HANDLE {
                     SemihostingResponse::SysFlen { result: Ok(5) }
+                } else if let Some(file) = files.get(handle) {
+                    match file.metadata() {
+                        Ok(metadata) => SemihostingResponse::SysFlen {
+                            result: Ok(metadata.len() as u32),
+                        },
+                        Err(e) => {
+                            errno = errno_of(&e);
+                            SemihostingResponse::SysFlen { result: Err(-1) }
+                        }
+                    }
                 } else {
                     SemihostingResponse::SysFlen { result: Err(-1) }
                 }
@@ -76,7 +218,7 @@ pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> S
                     || *handle == TT_HANDLE_STDERR
                 {
                     SemihostingResponse::SysIstty { result: Ok(1) }
-                } else if *handle == SEMIHOST_FEATURES_HANDLE {
+                } else if *handle == SEMIHOST_FEATURES_HANDLE || files.contains_key(handle) {
                     SemihostingResponse::SysIstty { result: Ok(0) }
                 } else {
                     SemihostingResponse::SysIstty { result: Err(-1) }
@@ -95,8 +237,37 @@ pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> S
                     print!("{}", String::from_utf8_lossy(text));
                     io::stdout().flush().expect("Could not flush stdout");
                     SemihostingResponse::SysWrite { result: Ok(0) }
+                } else if *handle == TT_HANDLE_STDERR {
+                    eprint!("{}", String::from_utf8_lossy(data));
+                    SemihostingResponse::SysWrite { result: Ok(0) }
+                } else if let Some(file) = files.get_mut(handle) {
+                    match file.write_all(data) {
+                        Ok(()) => SemihostingResponse::SysWrite { result: Ok(0) },
+                        Err(e) => {
+                            errno = errno_of(&e);
+                            SemihostingResponse::SysWrite {
+                                result: Err(data.len() as i32),
+                            }
+                        }
+                    }
                 } else {
-                    SemihostingResponse::SysWrite { result: Err(-1) }
+                    SemihostingResponse::SysWrite {
+                        result: Err(data.len() as i32),
+                    }
+                }
+            }
+            SemihostingCommand::SysWrite0 { ref data } => {
+                print!("{}", String::from_utf8_lossy(data));
+                io::stdout().flush().expect("Could not flush stdout");
+                SemihostingResponse::SysWrite { result: Ok(0) }
+            }
+            SemihostingCommand::SysReadC => {
+                let mut byte = [0u8; 1];
+                match io::stdin().read(&mut byte) {
+                    Ok(1) => SemihostingResponse::SysReadC {
+                        result: Ok(byte[0]),
+                    },
+                    _ => SemihostingResponse::SysReadC { result: Err(-1) },
                 }
             }
             SemihostingCommand::SysRead {
@@ -128,6 +299,25 @@ pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> S
                     SemihostingResponse::SysRead {
                         result: Ok((*memoryptr, data, diff)),
                     }
+                } else if *handle == TT_HANDLE_STDIN || files.contains_key(handle) {
+                    let mut data = vec![0u8; *len as usize];
+                    let result = if *handle == TT_HANDLE_STDIN {
+                        io::stdin().read(&mut data)
+                    } else {
+                        files.get_mut(handle).unwrap().read(&mut data)
+                    };
+                    match result {
+                        Ok(count) => {
+                            data.truncate(count);
+                            SemihostingResponse::SysRead {
+                                result: Ok((*memoryptr, data, *len - count as u32)),
+                            }
+                        }
+                        Err(e) => {
+                            errno = errno_of(&e);
+                            SemihostingResponse::SysRead { result: Err(-1) }
+                        }
+                    }
                 } else {
                     SemihostingResponse::SysRead { result: Err(-1) }
                 }
@@ -142,11 +332,102 @@ pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> S
                     } else {
                         SemihostingResponse::SysSeek { success: false }
                     }
+                } else if let Some(file) = files.get_mut(handle) {
+                    match file.seek(SeekFrom::Start(u64::from(*position))) {
+                        Ok(_) => SemihostingResponse::SysSeek { success: true },
+                        Err(e) => {
+                            errno = errno_of(&e);
+                            SemihostingResponse::SysSeek { success: false }
+                        }
+                    }
                 } else {
                     SemihostingResponse::SysSeek { success: false }
                 }
             }
-            SemihostingCommand::SysClock { .. } => {
+            SemihostingCommand::SysIsError { status } => SemihostingResponse::SysIsError {
+                result: *status < 0,
+            },
+            SemihostingCommand::SysTmpnam { memoryptr, id, len } => {
+                let name = format!("zmu-tmp{:03}.tmp", id & 0xff);
+                if name.len() < *len as usize {
+                    SemihostingResponse::SysTmpnam {
+                        result: Ok((*memoryptr, name.into_bytes())),
+                    }
+                } else {
+                    SemihostingResponse::SysTmpnam { result: Err(-1) }
+                }
+            }
+            SemihostingCommand::SysRemove { name } => {
+                let result = match sandbox_path(options.root.as_deref(), name) {
+                    Some(path) => fs::remove_file(path),
+                    None => Err(io::Error::from(io::ErrorKind::PermissionDenied)),
+                };
+                if let Err(e) = &result {
+                    warn!("semihosting: cannot remove '{}': {}", name, e);
+                    errno = errno_of(e);
+                }
+                SemihostingResponse::SysRemove {
+                    success: result.is_ok(),
+                }
+            }
+            SemihostingCommand::SysRename { from, to } => {
+                let root = options.root.as_deref();
+                let result = match (sandbox_path(root, from), sandbox_path(root, to)) {
+                    (Some(from_path), Some(to_path)) => fs::rename(from_path, to_path),
+                    _ => Err(io::Error::from(io::ErrorKind::PermissionDenied)),
+                };
+                if let Err(e) = &result {
+                    warn!("semihosting: cannot rename '{}' to '{}': {}", from, to, e);
+                    errno = errno_of(e);
+                }
+                SemihostingResponse::SysRename {
+                    success: result.is_ok(),
+                }
+            }
+            SemihostingCommand::SysTime => SemihostingResponse::SysTime {
+                result: Ok(SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs() as u32)),
+            },
+            SemihostingCommand::SysSystem { command } => {
+                if !options.allow_system {
+                    warn!("semihosting: running host command '{}' denied", command);
+                    return SemihostingResponse::SysSystem { result: Err(-1) };
+                }
+                match run_system(command, options.root.as_deref()) {
+                    Ok(status) => SemihostingResponse::SysSystem {
+                        result: Ok(status.code().unwrap_or(-1) as u32),
+                    },
+                    Err(e) => {
+                        errno = errno_of(&e);
+                        SemihostingResponse::SysSystem { result: Err(-1) }
+                    }
+                }
+            }
+            SemihostingCommand::SysHeapinfo { memoryptr } => SemihostingResponse::SysHeapinfo {
+                result: (*memoryptr, options.heap_info),
+            },
+            SemihostingCommand::SysElapsed { memoryptr } => SemihostingResponse::SysElapsed {
+                result: Ok((*memoryptr, start.elapsed().as_micros() as u64)),
+            },
+            SemihostingCommand::SysTickFreq => SemihostingResponse::SysTickFreq {
+                result: Ok(TICK_FREQUENCY),
+            },
+            SemihostingCommand::SysGetCmdline {
+                params_ptr,
+                memoryptr,
+                len,
+            } => {
+                // buffer must also hold the null terminator
+                if options.cmdline.len() < *len as usize {
+                    SemihostingResponse::SysGetCmdline {
+                        result: Ok((*params_ptr, *memoryptr, options.cmdline.as_bytes().to_vec())),
+                    }
+                } else {
+                    SemihostingResponse::SysGetCmdline { result: Err(-1) }
+                }
+            }
+            SemihostingCommand::SysClock => {
                 // println!("sysclock");
                 let elapsed = start.elapsed();
                 let in_cs =
@@ -188,11 +469,125 @@ pub fn get_semihost_func(start: Instant) -> impl FnMut(&SemihostingCommand) -> S
                     exit_code
                 }
             }
-            SemihostingCommand::SysErrno { .. } => {
+            SemihostingCommand::SysErrno => {
                 // println!("syserrno");
 
-                SemihostingResponse::SysErrno { result: 0 }
+                SemihostingResponse::SysErrno { result: errno }
+            }
+            SemihostingCommand::SysUnsupported { operation } => {
+                warn!("unsupported semihosting operation {operation:#x}");
+                errno = UNSUPPORTED_ERRNO;
+                SemihostingResponse::SysUnsupported
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_path() {
+        let root = Path::new("/sandbox");
+        assert_eq!(
+            sandbox_path(Some(root), "data/in.bin"),
+            Some(PathBuf::from("/sandbox/data/in.bin"))
+        );
+        assert_eq!(sandbox_path(Some(root), "../etc/passwd"), None);
+        assert_eq!(sandbox_path(Some(root), "/etc/passwd"), None);
+        assert_eq!(sandbox_path(Some(root), ""), None);
+        assert_eq!(sandbox_path(None, "in.bin"), None);
+    }
+
+    #[test]
+    fn test_file_io() {
+        let root = std::env::temp_dir().join(format!("zmu-semihost-{}", process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let mut semihost = get_semihost_func(
+            Instant::now(),
+            SemihostOptions {
+                root: Some(root.clone()),
+                ..SemihostOptions::default()
+            },
+        );
+
+        // write a file, mode "wb"
+        let SemihostingResponse::SysOpen { result: Ok(handle) } =
+            semihost(&SemihostingCommand::SysOpen {
+                name: "out.bin".to_string(),
+                mode: 5,
+            })
+        else {
+            panic!("open failed");
+        };
+        assert_eq!(
+            semihost(&SemihostingCommand::SysWrite {
+                handle,
+                data: b"hello".to_vec(),
+            }),
+            SemihostingResponse::SysWrite { result: Ok(0) }
+        );
+        assert_eq!(
+            semihost(&SemihostingCommand::SysFlen { handle }),
+            SemihostingResponse::SysFlen { result: Ok(5) }
+        );
+        semihost(&SemihostingCommand::SysClose { handle });
+
+        // read it back, mode "rb"
+        let SemihostingResponse::SysOpen { result: Ok(handle) } =
+            semihost(&SemihostingCommand::SysOpen {
+                name: "out.bin".to_string(),
+                mode: 1,
+            })
+        else {
+            panic!("open failed");
+        };
+        semihost(&SemihostingCommand::SysSeek {
+            handle,
+            position: 1,
+        });
+        assert_eq!(
+            semihost(&SemihostingCommand::SysRead {
+                handle,
+                memoryptr: 0x2000_0000,
+                len: 8,
+            }),
+            SemihostingResponse::SysRead {
+                result: Ok((0x2000_0000, b"ello".to_vec(), 4)),
+            }
+        );
+        semihost(&SemihostingCommand::SysClose { handle });
+
+        assert_eq!(
+            semihost(&SemihostingCommand::SysRemove {
+                name: "out.bin".to_string(),
+            }),
+            SemihostingResponse::SysRemove { success: true }
+        );
+        assert_eq!(
+            semihost(&SemihostingCommand::SysOpen {
+                name: "../out.bin".to_string(),
+                mode: 5,
+            }),
+            SemihostingResponse::SysOpen { result: Err(-1) }
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_unsupported_operation() {
+        let mut semihost = get_semihost_func(Instant::now(), SemihostOptions::default());
+
+        assert_eq!(
+            semihost(&SemihostingCommand::SysUnsupported { operation: 0x40 }),
+            SemihostingResponse::SysUnsupported
+        );
+        assert_eq!(
+            semihost(&SemihostingCommand::SysErrno),
+            SemihostingResponse::SysErrno {
+                result: UNSUPPORTED_ERRNO
+            }
+        );
+    }
+}
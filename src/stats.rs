@@ -0,0 +1,88 @@
+//!
+//! Run statistics in JSON format, for tracking the performance of firmware.
+//!
+
+use crate::errors::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use zmu_cortex_m::core::exception::Exception;
+use zmu_cortex_m::system::simulation::SimulationStatistics;
+
+#[derive(Serialize, Debug)]
+///
+/// Statistics report written by `--stats-json`
+///
+pub struct StatsReport {
+    pub instructions: u64,
+    pub cycles: u64,
+    pub sleep_cycles: u64,
+    pub exceptions: BTreeMap<String, u64>,
+    pub semihosting_calls: u64,
+    pub exit_code: u32,
+    pub wall_time_secs: f64,
+}
+
+///
+/// Name of an exception in the report, eg. "SysTick" or "IRQ3"
+///
+fn exception_name(number: usize) -> String {
+    match Exception::from(number) {
+        Exception::Interrupt { n } => format!("IRQ{n}"),
+        exception => format!("{exception:?}"),
+    }
+}
+
+impl From<&SimulationStatistics> for StatsReport {
+    fn from(statistics: &SimulationStatistics) -> Self {
+        Self {
+            instructions: statistics.instruction_count,
+            cycles: statistics.cycle_count,
+            sleep_cycles: statistics.sleep_cycle_count,
+            exceptions: statistics
+                .exceptions
+                .iter()
+                .map(|(&number, &count)| (exception_name(number), count))
+                .collect(),
+            semihosting_calls: statistics.semihost_call_count,
+            exit_code: statistics.exit_code,
+            wall_time_secs: statistics.duration.as_secs_f64(),
+        }
+    }
+}
+
+///
+/// Write the statistics of the run to given file
+///
+pub fn write_stats_json(filename: &str, statistics: &SimulationStatistics) -> Result<()> {
+    let file = File::create(filename)
+        .chain_err(|| format!("unable to create statistics file {filename}"))?;
+    serde_json::to_writer_pretty(file, &StatsReport::from(statistics))
+        .chain_err(|| format!("failed to write statistics file {filename}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_stats_report() {
+        let statistics = SimulationStatistics {
+            instruction_count: 1000,
+            cycle_count: 1500,
+            sleep_cycle_count: 200,
+            exceptions: BTreeMap::from([(15, 3), (18, 1)]),
+            semihost_call_count: 4,
+            duration: Duration::from_millis(250),
+            exit_code: 0,
+        };
+        let json = serde_json::to_value(StatsReport::from(&statistics)).unwrap();
+        assert_eq!(json["instructions"], 1000);
+        assert_eq!(json["sleep_cycles"], 200);
+        assert_eq!(json["exceptions"]["SysTick"], 3);
+        assert_eq!(json["exceptions"]["IRQ2"], 1);
+        assert_eq!(json["semihosting_calls"], 4);
+        assert_eq!(json["wall_time_secs"], 0.25);
+    }
+}
@@ -8,16 +8,16 @@ use zmu_cortex_m::core::thumb::ThumbCode;
 use zmu_cortex_m::decoder::Decoder;
 use zmu_cortex_m::Processor;
 
-pub fn format_trace_entry(processor: &Processor, symboltable: &HashMap<u32, &str>) -> String {
+pub fn format_trace_entry(processor: &mut Processor, symboltable: &HashMap<u32, &str>) -> String {
     let pc = processor.last_pc;
 
-    let thumb = processor.fetch(pc).unwrap();
+    let thumb = processor.fetch_non_fail(pc);
     let instruction = processor.decode(thumb);
 
     let opcode_str = match thumb {
         ThumbCode::Thumb32 { opcode } => format!("{:08X}", opcode).with_exact_width(8),
         ThumbCode::Thumb16 { opcode } => format!("{:04X}", opcode).with_exact_width(8),
-        ThumbCode::Undefined => format!("undef").with_exact_width(8),
+        ThumbCode::Undefined => "undef".to_string().with_exact_width(8),
     };
 
     let instruction_str = format!("{}", instruction).with_exact_width(32);
@@ -27,10 +27,10 @@ fi
 cd tests/cmsis
 
 
-make -s RUNNER="../../target/release/zmu-armv6m run" TARGET=cm0 XCFLAGS="-mcpu=cortex-m0" run
-make -s RUNNER="../../target/release/zmu-armv6m run" TARGET=cm0p XCFLAGS="-mcpu=cortex-m0plus" run
-make -s RUNNER="../../target/release/zmu-armv7m run" TARGET=cm3 XCFLAGS="-mcpu=cortex-m3"  run
-make -s RUNNER="../../target/release/zmu-armv7m run" TARGET=cm4 XCFLAGS="-mcpu=cortex-m4" run
-make -s RUNNER="../../target/release/zmu-armv7em run" TARGET=cm4f XCFLAGS="-mcpu=cortex-m4 -mfloat-abi=hard -mfpu=fpv4-sp-d16" run
-make -s RUNNER="../../target/release/zmu-armv7em run" TARGET=cm7-d16 XCFLAGS="-mcpu=cortex-m7 -mfloat-abi=hard -mfpu=fpv5-d16" run
-make -s RUNNER="../../target/release/zmu-armv7em run" TARGET=cm7-sp-d16 XCFLAGS="-mcpu=cortex-m7 -mfloat-abi=hard -mfpu=fpv5-sp-d16" run
+make -s RUNNER="../../target/release/zmu run --cpu cortex-m0" TARGET=cm0 XCFLAGS="-mcpu=cortex-m0" run
+make -s RUNNER="../../target/release/zmu run --cpu cortex-m0+" TARGET=cm0p XCFLAGS="-mcpu=cortex-m0plus" run
+make -s RUNNER="../../target/release/zmu run --cpu cortex-m3" TARGET=cm3 XCFLAGS="-mcpu=cortex-m3"  run
+make -s RUNNER="../../target/release/zmu run --cpu cortex-m4" TARGET=cm4 XCFLAGS="-mcpu=cortex-m4" run
+make -s RUNNER="../../target/release/zmu run --cpu cortex-m4f" TARGET=cm4f XCFLAGS="-mcpu=cortex-m4 -mfloat-abi=hard -mfpu=fpv4-sp-d16" run
+make -s RUNNER="../../target/release/zmu run --cpu cortex-m7" TARGET=cm7-d16 XCFLAGS="-mcpu=cortex-m7 -mfloat-abi=hard -mfpu=fpv5-d16" run
+make -s RUNNER="../../target/release/zmu run --cpu cortex-m7" TARGET=cm7-sp-d16 XCFLAGS="-mcpu=cortex-m7 -mfloat-abi=hard -mfpu=fpv5-sp-d16" run
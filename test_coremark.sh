@@ -38,7 +38,7 @@ do
    echo -e "\e[1mCOREMARK GCC: $i"
    echo -e "\e[1m========================================\e[0m"
    make -s PORT_DIR=zmu clean
-   make -s PORT_DIR=zmu XCFLAGS="-mcpu=$i"
+   make -s PORT_DIR=zmu XCFLAGS="-mcpu=$i" RUN="../../../target/release/zmu run --cpu $i"
    cp run1.log "run1_$i.log"
    cp run2.log "run2_$i.log"
    cat run2.log
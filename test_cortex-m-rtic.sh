@@ -29,6 +29,6 @@ cd ../..
 
 for i in "${arr[@]}"
 do
-   echo "./target/release/zmu-armv7m run tests/cortex-m-rtic/target/thumbv7m-none-eabi/debug/examples/$i"
-   timeout 1s ./target/release/zmu-armv7m run tests/cortex-m-rtic/target/thumbv7m-none-eabi/debug/examples/$i || true
+   echo "./target/release/zmu run --cpu cortex-m3 tests/cortex-m-rtic/target/thumbv7m-none-eabi/debug/examples/$i"
+   timeout 1s ./target/release/zmu run --cpu cortex-m3 tests/cortex-m-rtic/target/thumbv7m-none-eabi/debug/examples/$i || true
 done
\ No newline at end of file
@@ -16,22 +16,31 @@ then
 fi
 
 
-function arch_supports_cores()
+function core_cpu()
 {
    case "$1" in
-   "armv6m") 
-      cores=("cm0" "cm0p")
+   "cm0")
+      cpu="cortex-m0"
       ;;
-   "armv7m") 
-      cores=("cm0" "cm0p" "cm3")
+   "cm0p")
+      cpu="cortex-m0+"
       ;;
-   "armv7em") 
-      cores=("cm0" "cm0p" "cm3" "cm4" "cm4f" "cm7-d16" "cm7-sp-d16")
+   "cm3")
+      cpu="cortex-m3"
+      ;;
+   "cm4")
+      cpu="cortex-m4"
+      ;;
+   "cm4f")
+      cpu="cortex-m4f"
+      ;;
+   "cm7-d16" | "cm7-sp-d16")
+      cpu="cortex-m7"
       ;;
    esac
 }
 
-declare -a archs=("armv6m" "armv7m" "armv7em")
+declare -a cores=("cm0" "cm0p" "cm3" "cm4" "cm4f" "cm7-d16" "cm7-sp-d16")
 declare -a gcc_tests=("hello_world" "instruction-test-bench" "pi")
 
 for i in "${gcc_tests[@]}"
@@ -40,22 +49,19 @@ do
    make -s clean
    make
    cd ../..
-   for a in "${archs[@]}"
+   echo -e "\e[1m========================================"
+   echo -e "\e[1mGCC TEST: $i"
+   echo -e "\e[1m========================================\e[0m"
+   for c in "${cores[@]}"
    do
-      echo -e "\e[1m========================================"
-      echo -e "\e[1mGCC TEST: $i / $a"
-      echo -e "\e[1m========================================\e[0m"
-      arch_supports_cores $a
-      for c in "${cores[@]}"
-      do
-         echo "./target/release/zmu-$a run tests/$i/$i-$c.elf"
-         # read return code and abort on failure:
-         ./target/release/zmu-$a run tests/$i/$i-$c.elf
-         if [[ $? -ne 0 ]]; then
-            echo "Test failed"
-            exit $?
-         fi
-         echo ""
-      done
+      core_cpu $c
+      echo "./target/release/zmu run --cpu $cpu tests/$i/$i-$c.elf"
+      # read return code and abort on failure:
+      ./target/release/zmu run --cpu $cpu tests/$i/$i-$c.elf
+      if [[ $? -ne 0 ]]; then
+         echo "Test failed"
+         exit $?
+      fi
+      echo ""
    done
 done
@@ -37,7 +37,7 @@ PORT_SRCS = $(PORT_DIR)/core_portme.c $(STARTUP_PATH)/startup_ARMCM4.S
 
 #For native compilation and execution
 LOAD = echo Loading done
-RUN = ../../../target/release/zmu-armv7m run 
+RUN = ../../../target/release/zmu run
 
 OEXT = .o
 EXE = .elf
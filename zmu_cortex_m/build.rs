@@ -112,10 +112,75 @@ fn main() -> Result<(), Box<dyn Error>> {
         ("1111110....0....................", "STC2_t2"),
         ("111110111110............0000....", "UMLAL_t1"),
         ("111110111100............0000....", "SMLAL_t1"),
+        ("111110111100............10......", "SMLALxy_t1"),
+        ("111110111100............110.....", "SMLALD_t1"),
+        ("111110111101............110.....", "SMLSLD_t1"),
         ("111110111011....1111....1111....", "UDIV_t1"),
         ("111110101000....1111....0100....", "UADD8_t1"),
         ("111110101010....1111....1000....", "SEL_t1"),
         ("111110111010............0000....", "UMULL_t1"),
+        ("111110110010............000.....", "SMLAD_t1"),
+        ("111110110010....1111....000.....", "SMUAD_t1"),
+        ("111110110011............000.....", "SMLAW_t1"),
+        ("111110110011....1111....000.....", "SMULW_t1"),
+        ("111110110100............000.....", "SMLSD_t1"),
+        ("111110110100....1111....000.....", "SMUSD_t1"),
+        ("111110110101............000.....", "SMMLA_t1"),
+        ("111110110101....1111....000.....", "SMMUL_t1"),
+        ("111110110110............000.....", "SMMLS_t1"),
+        ("111110110111............0000....", "USADA8_t1"),
+        ("111110110111....1111....0000....", "USAD8_t1"),
+        ("111110111110............0110....", "UMAAL_t1"),
+        ("111110101000....1111....1000....", "QADD_t1"),
+        ("111110101000....1111....1001....", "QDADD_t1"),
+        ("111110101000....1111....1010....", "QSUB_t1"),
+        ("111110101000....1111....1011....", "QDSUB_t1"),
+        ("11111010001011111111....10......", "SXTB16_t1"),
+        ("111110100010....1111....10......", "SXTAB16_t1"),
+        ("111110100100....1111....10......", "SXTAB_t1"),
+        ("111110100000....1111....10......", "SXTAH_t1"),
+        ("111110100001....1111....10......", "UXTAH_t1"),
+        ("11111010001111111111....10......", "UXTB16_t1"),
+        ("111110100011....1111....10......", "UXTAB16_t1"),
+        ("111010101100....0.........00....", "PKHBT_t1"),
+        ("111010101100....0.........10....", "PKHTB_t1"),
+        ("111100110010....0000....0000....", "SSAT16_t1"),
+        ("111100111010....0000....0000....", "USAT16_t1"),
+        ("111110101001....1111....0000....", "SADD16_t1"),
+        ("111110101001....1111....0001....", "QADD16_t1"),
+        ("111110101001....1111....0010....", "SHADD16_t1"),
+        ("111110101001....1111....0100....", "UADD16_t1"),
+        ("111110101001....1111....0101....", "UQADD16_t1"),
+        ("111110101001....1111....0110....", "UHADD16_t1"),
+        ("111110101010....1111....0000....", "SASX_t1"),
+        ("111110101010....1111....0001....", "QASX_t1"),
+        ("111110101010....1111....0010....", "SHASX_t1"),
+        ("111110101010....1111....0100....", "UASX_t1"),
+        ("111110101010....1111....0101....", "UQASX_t1"),
+        ("111110101010....1111....0110....", "UHASX_t1"),
+        ("111110101110....1111....0000....", "SSAX_t1"),
+        ("111110101110....1111....0001....", "QSAX_t1"),
+        ("111110101110....1111....0010....", "SHSAX_t1"),
+        ("111110101110....1111....0100....", "USAX_t1"),
+        ("111110101110....1111....0101....", "UQSAX_t1"),
+        ("111110101110....1111....0110....", "UHSAX_t1"),
+        ("111110101101....1111....0000....", "SSUB16_t1"),
+        ("111110101101....1111....0001....", "QSUB16_t1"),
+        ("111110101101....1111....0010....", "SHSUB16_t1"),
+        ("111110101101....1111....0100....", "USUB16_t1"),
+        ("111110101101....1111....0101....", "UQSUB16_t1"),
+        ("111110101101....1111....0110....", "UHSUB16_t1"),
+        ("111110101000....1111....0000....", "SADD8_t1"),
+        ("111110101000....1111....0001....", "QADD8_t1"),
+        ("111110101000....1111....0010....", "SHADD8_t1"),
+        ("111110101000....1111....0101....", "UQADD8_t1"),
+        ("111110101000....1111....0110....", "UHADD8_t1"),
+        ("111110101100....1111....0000....", "SSUB8_t1"),
+        ("111110101100....1111....0001....", "QSUB8_t1"),
+        ("111110101100....1111....0010....", "SHSUB8_t1"),
+        ("111110101100....1111....0100....", "USUB8_t1"),
+        ("111110101100....1111....0101....", "UQSUB8_t1"),
+        ("111110101100....1111....0110....", "UHSUB8_t1"),
         ("111110111001....1111....1111....", "SDIV_t1"),
         ("111110110001....1111....00......", "SMUL_t1"),
         ("111110111000............0000....", "SMULL_t1"),
@@ -261,6 +326,20 @@ fn main() -> Result<(), Box<dyn Error>> {
         ("1110100100.0....0.0.............", "STMDB_t1"),
         ("111010001101....111100000001....", "TBH_t1"),
         ("111010001101....111100000000....", "TBB_t1"),
+        ("11101001011111111110100101111111", "SG_t1"),
+        ("111010000100....1111......000000", "TT_t1"),
+        ("111010001101........111110101111", "LDA_t1"),
+        ("111010001101........111110001111", "LDAB_t1"),
+        ("111010001101........111110011111", "LDAH_t1"),
+        ("111010001101........111111101111", "LDAEX_t1"),
+        ("111010001101........111111001111", "LDAEXB_t1"),
+        ("111010001101........111111011111", "LDAEXH_t1"),
+        ("111010001100........111110101111", "STL_t1"),
+        ("111010001100........111110001111", "STLB_t1"),
+        ("111010001100........111110011111", "STLH_t1"),
+        ("111010001100........11111110....", "STLEX_t1"),
+        ("111010001100........11111100....", "STLEXB_t1"),
+        ("111010001100........11111101....", "STLEXH_t1"),
         ("111010001101........111101011111", "LDREXH_t1"),
         ("111010001101........111101001111", "LDREXB_t1"),
         ("111010001100........11110101....", "STREXH_t1"),
@@ -280,12 +359,37 @@ fn main() -> Result<(), Box<dyn Error>> {
         ("111011101.110101....101..1.0....", "VCMP_t2"),
         ("111011101.111.......101..1.0....", "VCVT_t1"),
         //("111011101.111.1.....101..1.0....": "VCVT_fx_t1"),
-        //("111011101.110111....101.11.0....": "VCVT_ds_t1"),
+        ("111011101.110111....101.11.0....", "VCVT_ds_t1"),
         //("111011101.11001.....101..1.0....": "VCVTB"),
-        //("111011101.00........101..0.0....": "VDIV"),
-        //("111011101.10........101....0....": "VFMAS"),
-        //("111011101.01........101....0....": "VFNMAS"),
+        ("111011101.00........101..0.0....", "VDIV_t1"),
+        ("111011101.10........101..0.0....", "VFMA_t1"),
+        ("111011101.10........101..1.0....", "VFMS_t1"),
+        ("111011101.01........101..1.0....", "VFNMA_t1"),
+        ("111011101.01........101..0.0....", "VFNMS_t1"),
+        ("111011100.00........101..0.0....", "VMLA_t1"),
+        ("111011100.00........101..1.0....", "VMLS_t1"),
+        ("111011100.01........101..1.0....", "VNMLA_t1"),
+        ("111011100.01........101..0.0....", "VNMLS_t1"),
+        ("111011100.10........101..0.0....", "VMUL_t1"),
+        ("111011100.10........101..1.0....", "VNMUL_t1"),
+        ("111011101.110001....101.01.0....", "VNEG_t1"),
+        ("111011101.110001....101.11.0....", "VSQRT_t1"),
+        ("111011101.110110....101.01.0....", "VRINTR_t1"),
+        ("111011101.110110....101.11.0....", "VRINTZ_t1"),
+        ("111011101.110111....101.01.0....", "VRINTX_t1"),
+        ("111111101.111000....101.01.0....", "VRINTA_t1"),
+        ("111111101.111001....101.01.0....", "VRINTN_t1"),
+        ("111111101.111010....101.01.0....", "VRINTP_t1"),
+        ("111111101.111011....101.01.0....", "VRINTM_t1"),
+        ("111111101.111100....101..1.0....", "VCVTA_t1"),
+        ("111111101.111101....101..1.0....", "VCVTN_t1"),
+        ("111111101.111110....101..1.0....", "VCVTP_t1"),
+        ("111111101.111111....101..1.0....", "VCVTM_t1"),
+        ("111111101.00........101..0.0....", "VMAXNM_t1"),
+        ("111111101.00........101..1.0....", "VMINNM_t1"),
+        ("111111100...........101..0.0....", "VSEL_t1"),
         ("1110111011110001....101000010000", "VMRS"),
+        ("1110111011100001....101000010000", "VMSR"),
         ("1110110....0........1011.......0", "VSTM_t1"),
         ("1110110....0........1010........", "VSTM_t2"),
         ("11101101..01........1011........", "VLDR_t1"),
@@ -340,6 +444,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         ("01000110........", "MOV_reg_t1"),
         ("010001110....000", "BX_t1"),
         ("010001111....000", "BLX_t1"),
+        ("010001110....100", "BXNS_t1"),
+        ("010001111....100", "BLXNS_t1"),
         ("01001...........", "LDR_lit_t1"),
         ("0101000.........", "STR_reg_t1"),
         ("0101001.........", "STRH_reg_t1"),
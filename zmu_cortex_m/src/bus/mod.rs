@@ -7,8 +7,11 @@ use crate::Processor;
 use crate::core::fault::Fault;
 use crate::memory::map::MapMemory;
 use crate::peripheral::dwt::Dwt;
+use crate::peripheral::fpu::FloatingPointUnit;
 use crate::peripheral::itm::InstrumentationTraceMacrocell;
+use crate::peripheral::mpu::{MemoryAccess, MemoryProtectionUnit};
 use crate::peripheral::nvic::NVIC;
+use crate::peripheral::sau::SecurityAttributionUnit;
 use crate::peripheral::scb::SystemControlBlock;
 use crate::peripheral::systick::SysTick;
 
@@ -22,11 +25,11 @@ pub trait Bus {
 
     /// Reads a 16 bit value via the bus from the given address.
     ///
-    fn read16(&self, addr: u32) -> Result<u16, Fault>;
+    fn read16(&mut self, addr: u32) -> Result<u16, Fault>;
 
     /// Reads a 8 bit value via the bus from the given address.
     ///
-    fn read8(&self, addr: u32) -> Result<u8, Fault>;
+    fn read8(&mut self, addr: u32) -> Result<u8, Fault>;
 
     /// Writes a 32 bit value to the bus targeting the given address.
     ///
@@ -45,71 +48,100 @@ pub trait Bus {
     fn in_range(&self, addr: u32) -> bool;
 }
 
-impl Bus for Processor {
-    fn read8(&self, bus_addr: u32) -> Result<u8, Fault> {
+///
+/// Accesses without the MPU checks, for vector table reads and the debugger
+///
+impl Processor {
+    ///
+    /// Bring DWT CYCCNT and `SysTick` up to date with the instructions executed
+    /// so far in the current block, before an access to their registers
+    ///
+    #[inline(always)]
+    fn sync_cycle_counters(&mut self, addr: u32) {
+        if self.block_cycles > 0
+            && matches!(addr, 0xE000_1004..=0xE000_1007 | 0xE000_E010..=0xE000_E01B)
+        {
+            self.tick_block_cycles();
+        }
+    }
+
+    pub(crate) fn bus_read8(&mut self, bus_addr: u32) -> Result<u8, Fault> {
         let addr = self.map_address(bus_addr);
+        self.sync_cycle_counters(addr);
 
         let result = match addr {
+            0xE000_1004..=0xE000_1007 | 0xE000_E010..=0xE000_E01B => {
+                (self.bus_read32(addr & !3)? >> ((addr & 3) * 8)) as u8
+            }
             0xE000_E400..=0xE000_E5EC => {
                 self.nvic_read_ipr_u8(((addr - 0xE000_E400) >> 2) as usize)
             }
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-            0xE000_ED18..=0xE000_ED1B => self.read_shpr1_u8((addr - 0xE000_ED18) as usize),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
+            0xE000_ED18..=0xE000_ED1B if self.profile.is_mainline() => self.read_shpr1_u8((addr - 0xE000_ED18) as usize),
             0xE000_ED1C..=0xE000_ED1F => self.read_shpr2_u8((addr - 0xE000_ED1C) as usize),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             0xE000_ED20..=0xE000_ED23 => self.read_shpr3_u8((addr - 0xE000_ED20) as usize),
+            0xE000_ED28..=0xE000_ED2B if self.profile.is_mainline() => {
+                (self.cfsr >> ((addr - 0xE000_ED28) * 8)) as u8
+            }
 
             _ => {
-                if self.sram.in_range(addr) {
-                    return self.sram.read8(addr);
+                if let Some(bank) = self.sram.iter_mut().find(|b| b.in_range(addr)) {
+                    return bank.read8(addr);
                 } else if self.code.in_range(addr) {
                     return self.code.read8(addr);
-                } else if self.device.in_range(addr) {
-                    return self.device.read8(addr);
+                } else if let Some(region) = self.flash_regions.iter_mut().find(|r| r.in_range(addr)) {
+                    return region.read8(addr);
+                } else if let Some(device) = self.devices.iter_mut().find(|d| d.in_range(addr)) {
+                    return device.read8(addr);
                 } else {
-                    return Err(Fault::DAccViol);
+                    return Err(Fault::Preciserr);
                 }
             }
         };
         Ok(result)
     }
 
-    fn read16(&self, bus_addr: u32) -> Result<u16, Fault> {
+    pub(crate) fn bus_read16(&mut self, bus_addr: u32) -> Result<u16, Fault> {
         let addr = self.map_address(bus_addr);
+        self.sync_cycle_counters(addr);
         match addr {
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-            0xE000_ED18..=0xE000_ED1B => {
+            0xE000_1004..=0xE000_1007 | 0xE000_E010..=0xE000_E01B => {
+                Ok((self.bus_read32(addr & !3)? >> ((addr & 2) * 8)) as u16)
+            }
+            0xE000_ED18..=0xE000_ED1B if self.profile.is_mainline() => {
                 Ok(self.read_shpr1_u16(((addr - 0xE000_ED18) >> 1) as usize))
             }
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             0xE000_ED1C..=0xE000_ED1F => {
                 Ok(self.read_shpr2_u16(((addr - 0xE000_ED1C) >> 1) as usize))
             }
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             0xE000_ED20..=0xE000_ED23 => {
                 Ok(self.read_shpr3_u16(((addr - 0xE000_ED20) >> 1) as usize))
             }
             0xE000_E400..=0xE000_E5EC => {
                 Ok(self.nvic_read_ipr_u16(((addr - 0xE000_E400) >> 1) as usize))
             }
+            0xE000_ED28..=0xE000_ED2B if self.profile.is_mainline() => {
+                Ok((self.cfsr >> ((addr - 0xE000_ED28) * 8)) as u16)
+            }
 
             _ => {
-                if self.sram.in_range(addr) {
-                    self.sram.read16(addr)
+                if let Some(bank) = self.sram.iter_mut().find(|b| b.in_range(addr)) {
+                    bank.read16(addr)
                 } else if self.code.in_range(addr) {
                     self.code.read16(addr)
-                } else if self.device.in_range(addr) {
-                    self.device.read16(addr)
+                } else if let Some(region) = self.flash_regions.iter_mut().find(|r| r.in_range(addr)) {
+                    region.read16(addr)
+                } else if let Some(device) = self.devices.iter_mut().find(|d| d.in_range(addr)) {
+                    device.read16(addr)
                 } else {
-                    Err(Fault::DAccViol)
+                    Err(Fault::Preciserr)
                 }
             }
         }
     }
 
-    fn read32(&mut self, bus_addr: u32) -> Result<u32, Fault> {
+    pub(crate) fn bus_read32(&mut self, bus_addr: u32) -> Result<u32, Fault> {
         let addr = self.map_address(bus_addr);
+        self.sync_cycle_counters(addr);
 
         let result = match addr {
             0xE000_0000 => self.read_stim0(),
@@ -128,6 +160,14 @@ impl Bus for Processor {
             0xE000_E280..=0xE000_E2BC => self.nvic_read_icpr(((addr - 0xE000_E280) >> 5) as usize),
             0xE000_E300..=0xE000_E33C => self.nvic_read_iabr(((addr - 0xE000_E300) >> 5) as usize),
             0xE000_E400..=0xE000_E5EC => self.nvic_read_ipr(((addr - 0xE000_E400) >> 2) as usize),
+            // NVIC_ITNS, Secure only
+            0xE000_E380..=0xE000_E3BC if self.profile.has_security() => {
+                if self.secure {
+                    self.nvic_interrupt_target_ns[((addr - 0xE000_E380) >> 2) as usize]
+                } else {
+                    0
+                }
+            }
 
             0xE000_ED00 => self.cpuid,
             0xE000_ED04 => self.read_icsr(),
@@ -135,13 +175,10 @@ impl Bus for Processor {
             0xE000_ED0C => self.aircr,
             0xE000_ED10 => self.read_scr(),
             0xE000_ED14 => self.ccr,
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-            0xE000_ED18 => self.read_shpr1(),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
+            0xE000_ED18 if self.profile.is_mainline() => self.read_shpr1(),
             0xE000_ED1C => self.read_shpr2(),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             0xE000_ED20 => self.read_shpr3(),
-            0xE000_ED24 => self.shcsr,
+            0xE000_ED24 => self.read_shcsr(),
             0xE000_ED28 => self.cfsr,
             0xE000_ED2C => self.hfsr,
             0xE000_ED30 => self.dfsr,
@@ -151,6 +188,40 @@ impl Bus for Processor {
 
             0xE000_ED88 => self.cpacr,
 
+            0xE000_ED90 if self.profile.has_mpu() => self.read_mpu_type(),
+            0xE000_ED94 if self.profile.has_mpu() => self.mpu_ctrl,
+            0xE000_ED98 if self.profile.has_mpu() => self.mpu_rnr,
+            // RBAR and RASR, followed by their three aliases
+            0xE000_ED9C..=0xE000_EDB8 if self.profile.has_mpu() => {
+                if (addr - 0xE000_ED9C) & 4 == 0 {
+                    self.read_mpu_rbar()
+                } else {
+                    self.read_mpu_rasr()
+                }
+            }
+            // PMSAv8 MPU, not modelled: no regions, RAZ/WI
+            0xE000_ED90..=0xE000_EDC4 if self.profile.is_armv8m() => 0,
+
+            // SAU and the SecureFault status, Secure only
+            0xE000_EDD0..=0xE000_EDE8 if self.profile.has_security() => {
+                if self.secure {
+                    match addr {
+                        0xE000_EDD0 => self.sau_ctrl,
+                        0xE000_EDD4 => self.read_sau_type(),
+                        0xE000_EDD8 => self.sau_rnr,
+                        0xE000_EDDC => self.read_sau_rbar(),
+                        0xE000_EDE0 => self.read_sau_rlar(),
+                        0xE000_EDE4 => self.sfsr,
+                        0xE000_EDE8 => self.sfar,
+                        _ => 0,
+                    }
+                } else {
+                    0
+                }
+            }
+            // Non-secure alias of VTOR
+            0xE002_ED08 if self.profile.has_security() && self.secure => self.banked.vtor,
+
             0xE000_EF34 => self.fpccr,
             0xE000_EF38 => self.fpcar,
             0xE000_EF3C => self.fpdscr,
@@ -164,21 +235,24 @@ impl Bus for Processor {
             // DWT
             0xE000_1000 => self.dwt_ctrl,
             _ => {
-                if self.sram.in_range(addr) {
-                    self.sram.read32(addr)?
+                if let Some(bank) = self.sram.iter_mut().find(|b| b.in_range(addr)) {
+                    bank.read32(addr)?
                 } else if self.code.in_range(addr) {
                     self.code.read32(addr)?
-                } else if self.device.in_range(addr) {
-                    self.device.read32(addr)?
+                } else if let Some(region) = self.flash_regions.iter_mut().find(|r| r.in_range(addr)) {
+                    region.read32(addr)?
+                } else if let Some(device) = self.devices.iter_mut().find(|d| d.in_range(addr)) {
+                    device.read32(addr)?
                 } else {
-                    return Err(Fault::DAccViol);
+                    return Err(Fault::Preciserr);
                 }
             }
         };
         Ok(result)
     }
 
-    fn write32(&mut self, addr: u32, value: u32) -> Result<(), Fault> {
+    fn bus_write32(&mut self, addr: u32, value: u32) -> Result<(), Fault> {
+        self.sync_cycle_counters(addr);
         match addr {
             0xE000_0000..=0xE000_007C => {
                 self.write_stim_u32(((addr - 0xE000_0000) >> 2) as u8, value);
@@ -191,13 +265,48 @@ impl Bus for Processor {
 
             0xE000_ED04 => self.write_icsr(value),
             0xE000_ED08 => self.write_vtor(value),
+            0xE000_ED0C => self.write_aircr(value),
             0xE000_ED10 => self.write_scr(value),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-            0xE000_ED18 => self.write_shpr1(value),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
+            0xE000_ED14 if self.profile.is_mainline() => self.write_ccr(value),
+            0xE000_ED18 if self.profile.is_mainline() => self.write_shpr1(value),
             0xE000_ED1C => self.write_shpr2(value),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             0xE000_ED20 => self.write_shpr3(value),
+            0xE000_ED24 if self.profile.is_mainline() => self.write_shcsr(value),
+            0xE000_ED28 if self.profile.is_mainline() => self.write_cfsr(value),
+            0xE000_ED2C if self.profile.is_mainline() => self.write_hfsr(value),
+            0xE000_ED34 if self.profile.is_mainline() => self.mmfar = value,
+            0xE000_ED38 if self.profile.is_mainline() => self.bfar = value,
+            0xE000_ED88 if self.profile.has_fpu() => self.write_cpacr(value),
+
+            0xE000_ED94 if self.profile.has_mpu() => self.write_mpu_ctrl(value),
+            0xE000_ED98 if self.profile.has_mpu() => self.write_mpu_rnr(value),
+            0xE000_ED9C..=0xE000_EDB8 if self.profile.has_mpu() => {
+                if (addr - 0xE000_ED9C) & 4 == 0 {
+                    self.write_mpu_rbar(value);
+                } else {
+                    self.write_mpu_rasr(value);
+                }
+            }
+            0xE000_ED90..=0xE000_EDC4 if self.profile.is_armv8m() => (),
+
+            0xE000_EDD0..=0xE000_EDE8 if self.profile.has_security() => {
+                if self.secure {
+                    match addr {
+                        0xE000_EDD0 => self.write_sau_ctrl(value),
+                        0xE000_EDD8 => self.write_sau_rnr(value),
+                        0xE000_EDDC => self.write_sau_rbar(value),
+                        0xE000_EDE0 => self.write_sau_rlar(value),
+                        0xE000_EDE4 => self.write_sfsr(value),
+                        0xE000_EDE8 => self.sfar = value,
+                        _ => (),
+                    }
+                }
+            }
+            0xE002_ED08 if self.profile.has_security() && self.secure => self.banked.vtor = value,
+
+            0xE000_EF34 if self.profile.has_fpu() => self.write_fpccr(value),
+            0xE000_EF38 if self.profile.has_fpu() => self.write_fpcar(value),
+            0xE000_EF3C if self.profile.has_fpu() => self.write_fpdscr(value),
 
             0xE000_EDFC => self.write_demcr(value),
 
@@ -216,63 +325,75 @@ impl Bus for Processor {
             0xE000_E280..=0xE000_E2BC => {
                 self.nvic_write_icpr(((addr - 0xE000_E280) >> 5) as usize, value);
             }
+            0xE000_E380..=0xE000_E3BC if self.profile.has_security() => {
+                if self.secure {
+                    self.nvic_interrupt_target_ns[((addr - 0xE000_E380) >> 2) as usize] = value;
+                }
+            }
             0xE000_E400..=0xE000_E5EC => {
                 self.nvic_write_ipr(((addr - 0xE000_E400) >> 2) as usize, value);
             }
 
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-            0xE000_EF00 => self.write_stir(value),
+            0xE000_EF00 if self.profile.is_mainline() => self.write_stir(value),
             _ => {
-                if self.sram.in_range(addr) {
-                    return self.sram.write32(addr, value);
+                if let Some(bank) = self.sram.iter_mut().find(|b| b.in_range(addr)) {
+                    bank.write32(addr, value)?;
                 } else if self.code.in_range(addr) {
-                    return self.code.write32(addr, value);
-                } else if self.device.in_range(addr) {
-                    return self.device.write32(addr, value);
+                    self.code.write32(addr, value)?;
+                } else if let Some(region) = self.flash_regions.iter_mut().find(|r| r.in_range(addr)) {
+                    region.write32(addr, value)?;
+                } else if let Some(device) = self.devices.iter_mut().find(|d| d.in_range(addr)) {
+                    return device.write32(addr, value);
                 } else {
-                    return Err(Fault::DAccViol);
+                    return Err(Fault::Preciserr);
                 }
+                self.invalidate_code(addr, 4);
             }
         }
         Ok(())
     }
 
-    fn write16(&mut self, addr: u32, value: u16) -> Result<(), Fault> {
+    fn bus_write16(&mut self, addr: u32, value: u16) -> Result<(), Fault> {
+        self.sync_cycle_counters(addr);
         match addr {
             0xE000_0000..=0xE000_007C => {
                 self.write_stim_u16(((addr - 0xE000_0000) >> 2) as u8, value);
             }
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-            0xE000_ED18..=0xE000_ED1B => {
+            0xE000_ED18..=0xE000_ED1B if self.profile.is_mainline() => {
                 self.write_shpr1_u16(((addr - 0xE000_ED18) >> 1) as usize, value);
             }
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             0xE000_ED1C..=0xE000_ED1F => {
                 self.write_shpr2_u16(((addr - 0xE000_ED1C) >> 1) as usize, value);
             }
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             0xE000_ED20..=0xE000_ED23 => {
                 self.write_shpr3_u16(((addr - 0xE000_ED20) >> 1) as usize, value);
             }
             0xE000_E400..=0xE000_E5EC => {
                 self.nvic_write_ipr_u16(((addr - 0xE000_E400) >> 1) as usize, value);
             }
+            0xE000_ED28..=0xE000_ED2B if self.profile.is_mainline() => {
+                self.write_cfsr(u32::from(value) << ((addr - 0xE000_ED28) * 8));
+            }
             _ => {
-                if self.sram.in_range(addr) {
-                    return self.sram.write16(addr, value);
+                if let Some(bank) = self.sram.iter_mut().find(|b| b.in_range(addr)) {
+                    bank.write16(addr, value)?;
                 } else if self.code.in_range(addr) {
-                    return self.code.write16(addr, value);
-                } else if self.device.in_range(addr) {
-                    return self.device.write16(addr, value);
+                    self.code.write16(addr, value)?;
+                } else if let Some(region) = self.flash_regions.iter_mut().find(|r| r.in_range(addr)) {
+                    region.write16(addr, value)?;
+                } else if let Some(device) = self.devices.iter_mut().find(|d| d.in_range(addr)) {
+                    return device.write16(addr, value);
                 } else {
-                    return Err(Fault::DAccViol);
+                    return Err(Fault::Preciserr);
                 }
+                self.invalidate_code(addr, 2);
             }
         }
         Ok(())
     }
 
-    fn write8(&mut self, addr: u32, value: u8) -> Result<(), Fault> {
+    pub(crate) fn bus_write8(&mut self, addr: u32, value: u8) -> Result<(), Fault> {
+        self.sync_cycle_counters(addr);
         match addr {
             0xE000_0000..=0xE000_007C => {
                 self.write_stim_u8(((addr - 0xE000_0000) >> 2) as u8, value);
@@ -280,30 +401,154 @@ impl Bus for Processor {
             0xE000_E400..=0xE000_E5EC => {
                 self.nvic_write_ipr_u8((addr - 0xE000_E400) as usize, value);
             }
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-            0xE000_ED18..=0xE000_ED1B => self.write_shpr1_u8((addr - 0xE000_ED18) as usize, value),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
+            0xE000_ED18..=0xE000_ED1B if self.profile.is_mainline() => self.write_shpr1_u8((addr - 0xE000_ED18) as usize, value),
             0xE000_ED1C..=0xE000_ED1F => self.write_shpr2_u8((addr - 0xE000_ED1C) as usize, value),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             0xE000_ED20..=0xE000_ED23 => self.write_shpr3_u8((addr - 0xE000_ED20) as usize, value),
+            0xE000_ED28..=0xE000_ED2B if self.profile.is_mainline() => {
+                self.write_cfsr(u32::from(value) << ((addr - 0xE000_ED28) * 8));
+            }
 
             _ => {
-                if self.sram.in_range(addr) {
-                    return self.sram.write8(addr, value);
+                if let Some(bank) = self.sram.iter_mut().find(|b| b.in_range(addr)) {
+                    bank.write8(addr, value)?;
                 } else if self.code.in_range(addr) {
-                    return self.code.write8(addr, value);
-                } else if self.device.in_range(addr) {
-                    return self.device.write8(addr, value);
+                    self.code.write8(addr, value)?;
+                } else if let Some(region) = self.flash_regions.iter_mut().find(|r| r.in_range(addr)) {
+                    region.write8(addr, value)?;
+                } else if let Some(device) = self.devices.iter_mut().find(|d| d.in_range(addr)) {
+                    return device.write8(addr, value);
                 } else {
-                    return Err(Fault::DAccViol);
+                    return Err(Fault::Preciserr);
                 }
+                self.invalidate_code(addr, 1);
             }
         }
         Ok(())
     }
+}
+
+///
+/// Accesses are checked by the MPU and the SAU. Failed accesses record the
+/// faulting address for MMFAR, BFAR and SFAR
+///
+impl Bus for Processor {
+    fn read8(&mut self, addr: u32) -> Result<u8, Fault> {
+        self.mpu_check(addr, MemoryAccess::Read)
+            .and_then(|()| self.sau_check(addr))
+            .and_then(|()| self.bus_read8(addr))
+            .inspect_err(|_| self.fault_address.set(addr))
+    }
+
+    fn read16(&mut self, addr: u32) -> Result<u16, Fault> {
+        self.mpu_check(addr, MemoryAccess::Read)
+            .and_then(|()| self.sau_check(addr))
+            .and_then(|()| self.bus_read16(addr))
+            .inspect_err(|_| self.fault_address.set(addr))
+    }
+
+    fn read32(&mut self, addr: u32) -> Result<u32, Fault> {
+        self.mpu_check(addr, MemoryAccess::Read)
+            .and_then(|()| self.sau_check(addr))
+            .and_then(|()| self.bus_read32(addr))
+            .inspect_err(|_| self.fault_address.set(addr))
+    }
+
+    fn write32(&mut self, addr: u32, value: u32) -> Result<(), Fault> {
+        self.mpu_check(addr, MemoryAccess::Write)
+            .and_then(|()| self.sau_check(addr))
+            .and_then(|()| self.bus_write32(addr, value))
+            .inspect_err(|_| self.fault_address.set(addr))
+    }
+
+    fn write16(&mut self, addr: u32, value: u16) -> Result<(), Fault> {
+        self.mpu_check(addr, MemoryAccess::Write)
+            .and_then(|()| self.sau_check(addr))
+            .and_then(|()| self.bus_write16(addr, value))
+            .inspect_err(|_| self.fault_address.set(addr))
+    }
+
+    fn write8(&mut self, addr: u32, value: u8) -> Result<(), Fault> {
+        self.mpu_check(addr, MemoryAccess::Write)
+            .and_then(|()| self.sau_check(addr))
+            .and_then(|()| self.bus_write8(addr, value))
+            .inspect_err(|_| self.fault_address.set(addr))
+    }
 
     #[allow(unused)]
     fn in_range(&self, addr: u32) -> bool {
-        self.code.in_range(addr) || self.sram.in_range(addr) || self.device.in_range(addr)
+        self.code.in_range(addr)
+            || self.flash_regions.iter().any(|r| r.in_range(addr))
+            || self.sram.iter().any(|b| b.in_range(addr))
+            || self.devices.iter().any(|d| d.in_range(addr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::image::Segment;
+    use crate::memory::map::MemoryMapConfig;
+
+    #[test]
+    fn test_ram_banks() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.ram_memory(&[(0x2000_0000, 0x2000), (0x1000_0000, 0x1000)]);
+
+        // Act
+        processor.load_image(&[Segment {
+            address: 0x1000_0000,
+            data: vec![0x11, 0x22, 0x33, 0x44],
+        }]);
+        processor.write32(0x2000_1FFC, 0xAABB_CCDD).unwrap();
+
+        // Assert
+        assert_eq!(processor.read32(0x1000_0000).unwrap(), 0x4433_2211);
+        assert_eq!(processor.read32(0x2000_1FFC).unwrap(), 0xAABB_CCDD);
+        assert_eq!(processor.read8(0x2000_0000).unwrap(), 0xcd);
+        assert_eq!(processor.write32(0x2000_2000, 0), Err(Fault::Preciserr));
+        assert_eq!(processor.read16(0x1000_1000), Err(Fault::Preciserr));
+        assert_eq!(processor.fault_address.get(), 0x1000_1000);
+    }
+
+    #[test]
+    fn test_flash_regions() {
+        // Arrange
+        let mut processor = Processor::new();
+
+        // Act
+        processor.load_image(&[
+            Segment {
+                address: 0x0800_0000,
+                data: vec![1, 2, 3, 4],
+            },
+            Segment {
+                address: 0x9000_0000,
+                data: vec![5, 6, 7, 8],
+            },
+        ]);
+
+        // Assert
+        assert_eq!(processor.read32(0x0800_0000).unwrap(), 0x0403_0201);
+        assert_eq!(processor.read32(0).unwrap(), 0x0403_0201);
+        assert_eq!(processor.read32(0x9000_0000).unwrap(), 0x0807_0605);
+        assert_eq!(processor.write8(0x9000_0000, 0), Err(Fault::Preciserr));
+    }
+
+    #[test]
+    fn test_memory_alias() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.load_image(&[Segment {
+            address: 0x0800_0000,
+            data: vec![1, 2, 3, 4],
+        }]);
+
+        // Act
+        processor.memory_alias(MemoryMapConfig::new(0x0010_0000, 0x0800_0000, 4));
+
+        // Assert
+        assert_eq!(processor.read32(0x0010_0000).unwrap(), 0x0403_0201);
+        assert_eq!(processor.read16(0x0010_0004), Err(Fault::Preciserr));
     }
 }
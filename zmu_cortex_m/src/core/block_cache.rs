@@ -0,0 +1,332 @@
+//!
+//! Cache of translated basic blocks
+//!
+
+use crate::core::instruction::Instruction;
+use crate::core::instruction_cache::CachedInstruction;
+use crate::core::register::Reg;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Maximum number of instructions in a block
+pub const MAX_BLOCK_INSTRUCTIONS: usize = 64;
+
+/// Granularity of the code tracking for the invalidation
+const CODE_PAGE_SHIFT: u32 = 8;
+
+///
+/// Straight-line sequence of decoded instructions, entered only at the first
+/// instruction
+///
+struct Block {
+    /// Entry address of the block
+    pc: u32,
+    /// Decoded instructions of the block
+    instructions: Rc<[CachedInstruction]>,
+    /// Memory range of the instructions, after the address mapping
+    range: (u32, u32),
+    /// Entry addresses and indices of the blocks executed after this one
+    successors: [Option<(u32, usize)>; 2],
+}
+
+///
+/// True if the instruction ends a block: branches, other instructions
+/// writing the PC, exception generating and context synchronizing
+/// instructions and the instructions that change the exception masking.
+///
+pub fn ends_block(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::POP { registers, .. } => registers.contains(&Reg::PC),
+        Instruction::LDM { params, .. } => params.registers.contains(&Reg::PC),
+        Instruction::LDR_imm { params, .. } => params.rt == Reg::PC,
+        Instruction::LDR_reg { params, .. } => params.rt == Reg::PC,
+        Instruction::LDR_lit { params, .. } => params.rt == Reg::PC,
+        Instruction::MOV_reg { params, .. } => params.rd == Reg::PC,
+        Instruction::ADD_reg { params, .. } => params.rd == Reg::PC,
+        Instruction::ADD_sp_reg { params, .. } => params.rd == Reg::PC,
+        _ => matches!(
+            instruction,
+            Instruction::B_t13 { .. }
+                | Instruction::B_t24 { .. }
+                | Instruction::BL { .. }
+                | Instruction::BX { .. }
+                | Instruction::BLX { .. }
+                | Instruction::BXNS { .. }
+                | Instruction::BLXNS { .. }
+                | Instruction::CBZ { .. }
+                | Instruction::CBNZ { .. }
+                | Instruction::TBB { .. }
+                | Instruction::TBH { .. }
+                | Instruction::SVC { .. }
+                | Instruction::BKPT { .. }
+                | Instruction::UDF { .. }
+                | Instruction::CPS { .. }
+                | Instruction::MSR_reg { .. }
+                | Instruction::DSB
+                | Instruction::ISB
+                | Instruction::SG
+                | Instruction::WFI { .. }
+                | Instruction::WFE { .. }
+        ),
+    }
+}
+
+#[derive(Default)]
+///
+/// Translated blocks indexed by their entry address. Blocks are chained to
+/// their successors, so that following a branch needs no lookup. A write to
+/// the memory of a block drops the block and the chain links to it.
+///
+pub struct BlockCache {
+    /// Blocks by index, dropped blocks leave a free slot
+    blocks: Vec<Option<Block>>,
+    /// Free slots of the dropped blocks
+    free: Vec<usize>,
+    entries: HashMap<u32, usize>,
+    /// Blocks having instructions in a code page
+    pages: HashMap<u32, Vec<usize>>,
+    /// Lowest and highest address of the cached code
+    bounds: Option<(u32, u32)>,
+    /// The block executed last, source of the next chain link
+    previous: Option<usize>,
+}
+
+impl BlockCache {
+    ///
+    /// Instructions of the block at the entry address. The block is chained
+    /// to the previously executed block.
+    ///
+    #[inline(always)]
+    pub fn lookup(&mut self, pc: u32) -> Option<Rc<[CachedInstruction]>> {
+        let chained = self.previous.and_then(|previous| {
+            self.block(previous)
+                .successors
+                .iter()
+                .flatten()
+                .find(|&&(entry, _)| entry == pc)
+                .map(|&(_, index)| index)
+        });
+        let index = if let Some(index) = chained {
+            index
+        } else {
+            let index = *self.entries.get(&pc)?;
+            self.link(pc, index);
+            index
+        };
+        self.previous = Some(index);
+        Some(Rc::clone(&self.block(index).instructions))
+    }
+
+    ///
+    /// Add a translated block of instructions at the entry address `pc`,
+    /// located at `address` after the address mapping
+    ///
+    pub fn insert(
+        &mut self,
+        pc: u32,
+        address: u32,
+        instructions: Vec<CachedInstruction>,
+    ) -> Rc<[CachedInstruction]> {
+        let size: usize = instructions.iter().map(|&(_, size)| size).sum();
+        let range = (address, address + size as u32 - 1);
+        let index = self.free.pop().unwrap_or(self.blocks.len());
+
+        for page in (range.0 >> CODE_PAGE_SHIFT)..=(range.1 >> CODE_PAGE_SHIFT) {
+            self.pages.entry(page).or_default().push(index);
+        }
+        self.bounds = Some(match self.bounds {
+            Some((low, high)) => (low.min(range.0), high.max(range.1)),
+            None => range,
+        });
+
+        let instructions: Rc<[CachedInstruction]> = instructions.into();
+        let block = Block {
+            pc,
+            instructions: Rc::clone(&instructions),
+            range,
+            successors: [None; 2],
+        };
+        if index == self.blocks.len() {
+            self.blocks.push(Some(block));
+        } else {
+            self.blocks[index] = Some(block);
+        }
+        self.entries.insert(pc, index);
+        self.link(pc, index);
+        self.previous = Some(index);
+        instructions
+    }
+
+    ///
+    /// Forget the previously executed block, the next block is not chained to it
+    ///
+    pub fn unlink(&mut self) {
+        self.previous = None;
+    }
+
+    ///
+    /// Drop the blocks having instructions in the `size` bytes written to the
+    /// address, and the chain links to them. Returns true if any block was
+    /// dropped.
+    ///
+    #[inline(always)]
+    pub fn invalidate(&mut self, address: u32, size: u32) -> bool {
+        if size == 0 {
+            return false;
+        }
+        let last = address.saturating_add(size - 1);
+        match self.bounds {
+            Some((low, high)) if address <= high && last >= low => (),
+            _ => return false,
+        }
+        let mut dropped: Vec<usize> = (address >> CODE_PAGE_SHIFT..=last >> CODE_PAGE_SHIFT)
+            .filter_map(|page| self.pages.get(&page))
+            .flatten()
+            .copied()
+            .filter(|&index| {
+                let (start, end) = self.block(index).range;
+                address <= end && last >= start
+            })
+            .collect();
+        if dropped.is_empty() {
+            return false;
+        }
+        dropped.sort_unstable();
+        dropped.dedup();
+
+        for &index in &dropped {
+            let block = self.blocks[index].take().unwrap();
+            self.entries.remove(&block.pc);
+            let (start, end) = block.range;
+            for page in (start >> CODE_PAGE_SHIFT)..=(end >> CODE_PAGE_SHIFT) {
+                if let Some(indices) = self.pages.get_mut(&page) {
+                    indices.retain(|&other| other != index);
+                    if indices.is_empty() {
+                        self.pages.remove(&page);
+                    }
+                }
+            }
+            self.free.push(index);
+        }
+        for block in self.blocks.iter_mut().flatten() {
+            for successor in &mut block.successors {
+                if successor.is_some_and(|(_, index)| dropped.contains(&index)) {
+                    *successor = None;
+                }
+            }
+        }
+        if self.previous.is_some_and(|index| dropped.contains(&index)) {
+            self.previous = None;
+        }
+        true
+    }
+
+    fn block(&self, index: usize) -> &Block {
+        self.blocks[index].as_ref().unwrap()
+    }
+
+    /// Chain the block at `pc` to the previously executed block
+    fn link(&mut self, pc: u32, index: usize) {
+        if let Some(previous) = self.previous {
+            let successors = &mut self.blocks[previous].as_mut().unwrap().successors;
+            // a block has at most two static successors, the not taken and the taken branch
+            let slot = successors.iter().position(Option::is_none).unwrap_or(1);
+            successors[slot] = Some((pc, index));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instruction::{Reg2Params, RegImm32AddParams};
+
+    fn block(count: usize) -> Vec<CachedInstruction> {
+        vec![(Instruction::DMB, 4); count]
+    }
+
+    #[test]
+    fn test_lookup_and_chaining() {
+        // Arrange
+        let mut cache = BlockCache::default();
+        cache.insert(0x100, 0x100, block(2));
+        cache.insert(0x200, 0x200, block(1));
+        cache.unlink();
+
+        // Act: a loop of two blocks
+        let first = cache.lookup(0x100).unwrap();
+        let second = cache.lookup(0x200).unwrap();
+        cache.lookup(0x100).unwrap();
+
+        // Assert
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1);
+        assert!(cache.lookup(0x104).is_none());
+        assert_eq!(cache.block(0).successors, [Some((0x200, 1)), None]);
+        assert_eq!(cache.block(1).successors, [Some((0x100, 0)), None]);
+    }
+
+    #[test]
+    fn test_invalidate() {
+        // Arrange: two chained blocks
+        let mut cache = BlockCache::default();
+        cache.insert(0x0800_0100, 0x100, block(2));
+        cache.insert(0x2000_0000, 0x2000_0000, block(4));
+
+        // Act: data next to the code, empty write
+        assert!(!cache.invalidate(0x2000_0010, 4));
+        assert!(!cache.invalidate(0x0000_0108, 4));
+        assert!(!cache.invalidate(0x2000_0000, 0));
+
+        // Assert
+        assert_eq!(cache.block(0).successors, [Some((0x2000_0000, 1)), None]);
+        assert!(cache.lookup(0x2000_0000).is_some());
+
+        // Act: last byte of the code in RAM
+        let dropped = cache.invalidate(0x2000_000f, 1);
+
+        // Assert: only the written block and the link to it are dropped
+        assert!(dropped);
+        assert!(cache.lookup(0x2000_0000).is_none());
+        assert!(cache.blocks[1].is_none());
+        assert_eq!(cache.block(0).successors, [None, None]);
+        assert!(cache.lookup(0x0800_0100).is_some());
+
+        // Act: the slot is reused
+        cache.insert(0x2000_0000, 0x2000_0000, block(1));
+
+        // Assert
+        assert_eq!(cache.blocks.len(), 2);
+        assert_eq!(cache.block(0).successors, [Some((0x2000_0000, 1)), None]);
+    }
+
+    #[test]
+    fn test_ends_block() {
+        let pop = |registers: [Reg; 2]| Instruction::POP {
+            registers: registers.into_iter().collect(),
+            thumb32: false,
+        };
+        let mov = |rd| Instruction::MOV_reg {
+            params: Reg2Params {
+                rd,
+                rm: Reg::LR,
+                setflags: false,
+            },
+            thumb32: false,
+        };
+
+        assert!(ends_block(&pop([Reg::R4, Reg::PC])));
+        assert!(!ends_block(&pop([Reg::R4, Reg::LR])));
+        assert!(ends_block(&mov(Reg::PC)));
+        assert!(!ends_block(&mov(Reg::R0)));
+        assert!(ends_block(&Instruction::LDR_lit {
+            params: RegImm32AddParams {
+                rt: Reg::PC,
+                imm32: 4,
+                add: true,
+            },
+            thumb32: true,
+        }));
+        assert!(!ends_block(&Instruction::DMB));
+    }
+}
@@ -6,12 +6,25 @@
 use crate::bus::Bus;
 use crate::core::bits::Bits;
 use crate::core::fault::Fault;
+use crate::core::monitor::Monitor;
 use crate::core::register::{BaseReg, Ipsr, Reg};
 use crate::core::reset::Reset;
+use crate::core::security::{SecurityExtension, AIRCR_BFHFNMINS};
+use crate::peripheral::fpu::{FPCCR_LSPACT, FPCCR_LSPEN};
 use crate::peripheral::nvic::NVIC;
+use crate::peripheral::sau::SFSR_SFARVALID;
 use crate::Processor;
 use crate::ProcessorMode;
 
+/// Size of the exception frame of R0-R3, R12, LR, PC and xPSR
+const BASIC_FRAME_SIZE: u32 = 0x20;
+/// Size of the exception frame with S0-S15 and FPSCR added
+const EXTENDED_FRAME_SIZE: u32 = 0x68;
+/// Size of the additional state context: integrity signature, reserved word and R4-R11
+const ADDITIONAL_STATE_SIZE: u32 = 0x28;
+/// Integrity signature of the additional state context, bit 0 is set for the basic frame
+const INTEGRITY_SIGNATURE: u32 = 0xFEFA_125A;
+
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd, Copy, Clone)]
 ///
 /// Status information for an exception
@@ -21,6 +34,7 @@ pub struct ExceptionState {
     pending: bool,
     active: bool,
     exception_number: usize,
+    taken_count: u64,
 }
 
 impl ExceptionState {
@@ -33,8 +47,40 @@ impl ExceptionState {
             priority,
             pending: false,
             active: false,
+            taken_count: 0,
         }
     }
+
+    ///
+    /// Number of times the exception has been taken
+    ///
+    pub fn taken_count(&self) -> u64 {
+        self.taken_count
+    }
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Reason for the processor to enter the Lockup state
+///
+pub enum LockupReason {
+    ///
+    /// Fault while executing at `HardFault` or NMI priority, or with FAULTMASK set
+    ///
+    Fault(Fault),
+    ///
+    /// Fault while entering an exception, that could not be escalated to `HardFault`
+    ///
+    ExceptionEntry {
+        ///
+        /// Exception that was being entered
+        ///
+        exception: Exception,
+        ///
+        /// Fault on stacking or on the vector table read
+        ///
+        fault: Fault,
+    },
 }
 
 ///
@@ -69,6 +115,18 @@ pub trait ExceptionHandling {
     ///
     fn exception_entry(&mut self, exception: Exception, return_address: u32) -> Result<(), Fault>;
 
+    ///
+    /// Enter the exception handling a synchronous fault.
+    ///
+    /// On Armv7-M the fault status and address registers are updated and the fault is
+    /// taken by `MemManage`, `BusFault` or `UsageFault`, or escalated to `HardFault` if
+    /// that handler is disabled in SHCSR or cannot preempt the current execution.
+    /// On Armv6-M all faults are taken by `HardFault`.
+    ///
+    /// A fault that cannot be handled puts the processor to the Lockup state.
+    ///
+    fn fault_entry(&mut self, fault: Fault, return_address: u32);
+
     ///
     /// Return from an exception.
     ///
@@ -107,14 +165,24 @@ pub trait ExceptionHandling {
 
 trait ExceptionHandlingHelpers {
     fn exception_taken(&mut self, exception: Exception) -> Result<(), Fault>;
+    fn escalating_entry(&mut self, exception: Exception, return_address: u32);
+    fn lockup(&mut self, reason: LockupReason, pc: u32);
+    fn fault_status(&mut self, fault: Fault) -> Exception;
     fn deactivate(&mut self, returning_exception_number: usize);
     fn invalid_exception_return(
         &mut self,
         returning_exception_number: usize,
         exc_return: u32,
     ) -> Result<(), Fault>;
-    fn return_address(&self, exception_type: Exception, return_address: u32) -> u32;
+    fn return_address(&self, exception_type: Exception, return_address: u32) -> Result<u32, Fault>;
     fn push_stack(&mut self, exception_type: Exception, return_address: u32) -> Result<(), Fault>;
+    fn write_frame(
+        &mut self,
+        frameptr: u32,
+        additional_state: bool,
+        frame: [u32; 8],
+    ) -> Result<(), Fault>;
+    fn update_fpccr(&mut self, frameptr: u32);
     fn pop_stack(&mut self, frameptr: u32, exc_return: u32) -> Result<(), Fault>;
     fn exception_active_bit_count(&self) -> usize;
 }
@@ -140,8 +208,8 @@ pub enum Exception {
     BusFault,
     /// Instruction execution faults for multiple underlying reasons. Example: undefined instructions.
     UsageFault,
-    /// Reserved for future
-    Reserved4,
+    /// Security violation on Armv8-M with the security extension, always taken in Secure state
+    SecureFault,
     /// Reserved for future
     Reserved5,
     /// Reserved for future
@@ -168,33 +236,121 @@ pub enum Exception {
 
 impl ExceptionHandlingHelpers for Processor {
     fn exception_taken(&mut self, exception: Exception) -> Result<(), Fault> {
+        if self.profile.has_security() {
+            let secure = self.exception_targets_secure(exception);
+            self.set_security_state(secure);
+            self.banked_exception_taken(exception, secure);
+        }
         self.control.sp_sel = false;
+        self.control.fpca = false;
         self.mode = ProcessorMode::HandlerMode;
         self.psr.set_isr_number(exception.into());
-        self.exceptions.get_mut(&exception.into()).unwrap().active = true;
+        let state = self.exceptions.get_mut(&exception.into()).unwrap();
+        state.active = true;
+        state.taken_count += 1;
 
         self.execution_priority = self.get_execution_priority();
+        self.clear_exclusive_local();
 
         // SetEventRegister();
         // InstructionSynchronizationBarrier();
         let vtor = self.vtor;
         let offset: u32 = usize::from(exception) as u32 * 4;
-        let start = self.read32(vtor + offset)?;
+        let start = self
+            .bus_read32(vtor + offset)
+            .map_err(|_| Fault::VectorTable)?;
         self.blx_write_pc(start);
         Ok(())
     }
 
+    fn escalating_entry(&mut self, exception: Exception, return_address: u32) {
+        let fault = match self.exception_entry(exception, return_address) {
+            Ok(()) => return,
+            Err(fault) => fault,
+        };
+        if exception == Exception::HardFault || exception == Exception::NMI {
+            self.lockup(
+                LockupReason::ExceptionEntry { exception, fault },
+                return_address,
+            );
+            return;
+        }
+
+        // fault on exception entry is taken by HardFault
+        if self.profile.is_mainline() && self.fault_status(fault) != Exception::HardFault {
+            self.hfsr.set_bit(30, true);
+        }
+        if let Err(fault) = self.exception_entry(Exception::HardFault, return_address) {
+            self.lockup(
+                LockupReason::ExceptionEntry {
+                    exception: Exception::HardFault,
+                    fault,
+                },
+                return_address,
+            );
+        }
+    }
+
+    fn lockup(&mut self, reason: LockupReason, pc: u32) {
+        self.lockup = Some((reason, pc));
+        self.running = false;
+        self.sleeping = false;
+    }
+
+    fn fault_status(&mut self, fault: Fault) -> Exception {
+        let exception = fault.exception();
+        if exception == Exception::HardFault {
+            self.hfsr.set_bit(fault.status_bit(), true);
+            return exception;
+        }
+
+        if exception == Exception::SecureFault {
+            self.sfsr.set_bit(fault.status_bit(), true);
+        } else {
+            self.cfsr.set_bit(fault.status_bit(), true);
+        }
+        match fault {
+            Fault::DAccViol => {
+                // MMARVALID
+                self.mmfar = self.fault_address.get();
+                self.cfsr.set_bit(7, true);
+            }
+            Fault::Preciserr => {
+                // BFARVALID
+                self.bfar = self.fault_address.get();
+                self.cfsr.set_bit(15, true);
+            }
+            Fault::Auviol => {
+                self.sfar = self.fault_address.get();
+                self.sfsr.set_bit(SFSR_SFARVALID, true);
+            }
+            _ => {}
+        }
+
+        let enable_bit = match exception {
+            Exception::MemoryManagementFault => 16,
+            Exception::BusFault => 17,
+            Exception::SecureFault => 19,
+            _ => 18,
+        };
+        let priority = self.exceptions[&usize::from(exception)].priority;
+        if self.shcsr.get_bit(enable_bit) && priority < self.execution_priority {
+            exception
+        } else {
+            // FORCED
+            self.hfsr.set_bit(30, true);
+            Exception::HardFault
+        }
+    }
+
     fn deactivate(&mut self, returning_exception_number: usize) {
         self.exceptions
             .get_mut(&returning_exception_number)
             .unwrap()
             .active = false;
 
-        #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-        {
-            if self.psr.get_isr_number() != 0b10 {
-                self.faultmask = false;
-            }
+        if self.psr.get_isr_number() != 0b10 {
+            self.faultmask = false;
         }
         self.execution_priority = self.get_execution_priority();
     }
@@ -216,7 +372,7 @@ impl ExceptionHandlingHelpers for Processor {
             .filter(|&(_, exp)| exp.active)
             .fold(0, |acc, _| acc + 1)
     }
-    fn return_address(&self, exception_type: Exception, return_address: u32) -> u32 {
+    fn return_address(&self, exception_type: Exception, return_address: u32) -> Result<u32, Fault> {
         match exception_type {
             Exception::NMI
             | Exception::HardFault
@@ -225,31 +381,65 @@ impl ExceptionHandlingHelpers for Processor {
             | Exception::SVCall
             | Exception::DebugMonitor
             | Exception::PendSV
+            | Exception::UsageFault
+            | Exception::SecureFault
             | Exception::SysTick
-            | Exception::Interrupt { .. } => return_address,
-            Exception::UsageFault => return_address - 4,
-            _ => todo!("unsupported exception"),
+            | Exception::Interrupt { .. } => Ok(return_address),
+            _ => Err(Fault::Forced),
         }
     }
     fn push_stack(&mut self, exception_type: Exception, return_address: u32) -> Result<(), Fault> {
-        const FRAME_SIZE: u32 = 0x20;
+        // extended frame with the FP context, FPCA is only set when an FPU is present
+        let fp_frame = self.control.fpca;
+        // Secure registers are stacked and cleared before a Non-secure handler
+        let target_secure = self.exception_targets_secure(exception_type);
+        let additional_state = self.profile.has_security() && self.secure && !target_secure;
+        let mut frame_size = if fp_frame {
+            EXTENDED_FRAME_SIZE
+        } else {
+            BASIC_FRAME_SIZE
+        };
+        if additional_state {
+            frame_size += ADDITIONAL_STATE_SIZE;
+        }
 
-        //TODO FP extensions
         //TODO forcealign
         // forces 8 byte alignment on the stack
         let forcealign = true;
         let spmask = (u32::from(forcealign) << 2) ^ 0xFFFF_FFFF;
 
-        let (frameptr, frameptralign) =
-            if self.control.sp_sel && self.mode == ProcessorMode::ThreadMode {
-                let align = u32::from(self.psp.get_bit(2) & forcealign);
-                self.set_psp((self.psp.wrapping_sub(FRAME_SIZE)) & spmask);
-                (self.psp, align)
+        let process_stack = self.control.sp_sel && self.mode == ProcessorMode::ThreadMode;
+        let (frameptr, frameptralign) = if process_stack {
+            let align = u32::from(self.psp.get_bit(2) & forcealign);
+            self.set_psp((self.psp.wrapping_sub(frame_size)) & spmask);
+            (self.psp, align)
+        } else {
+            let align = u32::from(self.msp.get_bit(2));
+            self.set_msp((self.msp.wrapping_sub(frame_size)) & spmask);
+            (self.msp, align)
+        };
+
+        // Armv8-M stack limit: nothing is stacked below the limit, the stack
+        // pointer stops at the limit and a UsageFault is pended
+        let limit = if process_stack {
+            self.psplim
+        } else {
+            self.msplim
+        };
+        let stack_overflow = self.profile.is_armv8m() && frameptr < limit;
+        if stack_overflow {
+            if process_stack {
+                self.set_psp(limit);
             } else {
-                let align = u32::from(self.msp.get_bit(2));
-                self.set_msp((self.msp.wrapping_sub(FRAME_SIZE)) & spmask);
-                (self.msp, align)
-            };
+                self.set_msp(limit);
+            }
+            self.cfsr.set_bit(Fault::Stkof.status_bit(), true);
+            self.set_exception_pending(if self.profile.is_mainline() && self.shcsr.get_bit(18) {
+                Exception::UsageFault
+            } else {
+                Exception::HardFault
+            });
+        }
 
         let r0 = self.get_r(Reg::R0);
         let r1 = self.get_r(Reg::R1);
@@ -258,33 +448,129 @@ impl ExceptionHandlingHelpers for Processor {
         let r12 = self.get_r(Reg::R12);
         let lr = self.get_r(Reg::LR);
 
-        let ret_addr = self.return_address(exception_type, return_address);
-
-        self.write32(frameptr, r0)?;
-        self.write32(frameptr.wrapping_add(0x4), r1)?;
-        self.write32(frameptr.wrapping_add(0x8), r2)?;
-        self.write32(frameptr.wrapping_add(0xc), r3)?;
-        self.write32(frameptr.wrapping_add(0x10), r12)?;
-        self.write32(frameptr.wrapping_add(0x14), lr)?;
-        self.write32(frameptr.wrapping_add(0x18), ret_addr)?;
+        let ret_addr = self.return_address(exception_type, return_address)?;
         let xpsr =
             (self.psr.value & 0b1111_1111_1111_1111_1111_1101_1111_1111) | frameptralign << 9;
-        self.write32(frameptr.wrapping_add(0x1c), xpsr)?;
 
-        if self.mode == ProcessorMode::HandlerMode {
-            self.lr = 0xFFFF_FFF1;
+        if !stack_overflow {
+            self.write_frame(
+                frameptr,
+                additional_state,
+                [r0, r1, r2, r3, r12, lr, ret_addr, xpsr],
+            )?;
+        }
+
+        // EXC_RETURN bit 4 is zero for the extended frame
+        let mut exc_return = if self.mode == ProcessorMode::HandlerMode {
+            0xFFFF_FFF1
         } else if self.control.sp_sel {
-            self.lr = 0xFFFF_FFFD;
+            0xFFFF_FFFD
         } else {
-            self.lr = 0xFFFF_FFF9;
+            0xFFFF_FFF9
+        };
+        if self.profile.has_security() {
+            // S: frame on the Secure stack, DCRS: no additional state context,
+            // ES: the exception is taken in Secure state
+            exc_return.set_bit(6, self.secure);
+            exc_return.set_bit(5, !additional_state);
+            exc_return.set_bit(0, target_secure);
         }
+        if additional_state {
+            self.r0_12 = [0; 13];
+        }
+        self.lr = if fp_frame {
+            exc_return & !(1 << 4)
+        } else {
+            exc_return
+        };
         Ok(())
     }
 
+    fn write_frame(
+        &mut self,
+        frameptr: u32,
+        additional_state: bool,
+        frame: [u32; 8],
+    ) -> Result<(), Fault> {
+        let fp_frame = self.control.fpca;
+        let frameptr = if additional_state {
+            let signature = INTEGRITY_SIGNATURE | u32::from(!fp_frame);
+            let context = [signature, 0].into_iter().chain(self.r0_12[4..12].to_vec());
+            for (offset, value) in (0..ADDITIONAL_STATE_SIZE).step_by(4).zip(context) {
+                self.write32(frameptr.wrapping_add(offset), value)
+                    .map_err(Fault::stacking)?;
+            }
+            frameptr.wrapping_add(ADDITIONAL_STATE_SIZE)
+        } else {
+            frameptr
+        };
+
+        for (offset, value) in (0..BASIC_FRAME_SIZE).step_by(4).zip(frame) {
+            self.write32(frameptr.wrapping_add(offset), value)
+                .map_err(Fault::stacking)?;
+        }
+
+        if fp_frame {
+            if self.fpccr.get_bit(FPCCR_LSPEN) {
+                // space is reserved, S0-S15 and FPSCR are saved on the first FP instruction
+                self.update_fpccr(frameptr);
+            } else {
+                for (offset, index) in (0x20..0x60).step_by(4).zip(0..16) {
+                    let value = self.fp_regs[index];
+                    self.write32(frameptr.wrapping_add(offset), value)
+                        .map_err(Fault::stacking)?;
+                }
+                let fpscr = self.fpscr;
+                self.write32(frameptr.wrapping_add(0x60), fpscr)
+                    .map_err(Fault::stacking)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn update_fpccr(&mut self, frameptr: u32) {
+        let privileged = self.mode == ProcessorMode::HandlerMode || !self.control.n_priv;
+        let priority = |exception: Exception| self.exceptions[&usize::from(exception)].priority;
+        let mmrdy = self.shcsr.get_bit(16)
+            && priority(Exception::MemoryManagementFault) < self.execution_priority;
+        let bfrdy =
+            self.shcsr.get_bit(17) && priority(Exception::BusFault) < self.execution_priority;
+        let hfrdy = self.execution_priority > -1;
+
+        let thread = self.mode == ProcessorMode::ThreadMode;
+
+        self.fpcar = frameptr.wrapping_add(0x20);
+        self.fpccr.set_bit(FPCCR_LSPACT, true);
+        self.fpccr.set_bit(1, !privileged); // USER
+        self.fpccr.set_bit(3, thread); // THREAD
+        self.fpccr.set_bit(4, hfrdy); // HFRDY
+        self.fpccr.set_bit(5, mmrdy); // MMRDY
+        self.fpccr.set_bit(6, bfrdy); // BFRDY
+        self.fpccr.set_bit(8, false); // MONRDY
+    }
+
     fn pop_stack(&mut self, frameptr: u32, exc_return: u32) -> Result<(), Fault> {
-        //TODO: fp extensions
+        let fp_frame = self.profile.has_fpu() && !exc_return.get_bit(4);
+        let mut frame_size = if fp_frame {
+            EXTENDED_FRAME_SIZE
+        } else {
+            BASIC_FRAME_SIZE
+        };
 
-        const FRAME_SIZE: u32 = 0x20;
+        // additional state context of a Secure thread or handler preempted by a Non-secure handler
+        let frameptr = if self.profile.has_security() && !exc_return.get_bit(5) {
+            let signature = self.read32(frameptr)?;
+            if signature != INTEGRITY_SIGNATURE | u32::from(!fp_frame) {
+                return Err(Fault::Invis);
+            }
+            for (offset, index) in (8..ADDITIONAL_STATE_SIZE).step_by(4).zip(4..12) {
+                self.r0_12[index] = self.read32(frameptr.wrapping_add(offset))?;
+            }
+            frame_size += ADDITIONAL_STATE_SIZE;
+            frameptr.wrapping_add(ADDITIONAL_STATE_SIZE)
+        } else {
+            frameptr
+        };
 
         //let forcealign = ccr.stkalign;
         let forcealign = true;
@@ -306,18 +592,33 @@ impl ExceptionHandlingHelpers for Processor {
 
         self.branch_write_pc(pc);
 
+        if fp_frame {
+            if self.fpccr.get_bit(FPCCR_LSPACT) {
+                // FP context was never saved, the registers still hold it
+                self.fpccr.set_bit(FPCCR_LSPACT, false);
+            } else {
+                for (offset, index) in (0x20..0x60).step_by(4).zip(0..16) {
+                    self.fp_regs[index] = self.read32(frameptr.wrapping_add(offset))?;
+                }
+                self.fpscr = self.read32(frameptr.wrapping_add(0x60))?;
+            }
+        }
+        if self.profile.has_fpu() {
+            self.control.fpca = fp_frame;
+        }
+
         let spmask = u32::from(psr.get_bit(9) && forcealign) << 2;
-        match exc_return.get_bits(0..4) {
-            0b0001 | 0b1001 => {
+        match exc_return.get_bits(2..4) {
+            0b00 | 0b10 => {
                 let msp = self.get_msp();
-                self.set_msp((msp.wrapping_add(FRAME_SIZE)) | spmask);
+                self.set_msp((msp.wrapping_add(frame_size)) | spmask);
             }
-            0b1101 => {
+            0b11 => {
                 let psp = self.get_psp();
-                self.set_psp((psp.wrapping_add(FRAME_SIZE)) | spmask);
+                self.set_psp((psp.wrapping_add(frame_size)) | spmask);
             }
             _ => {
-                todo!("wrong exc return");
+                return Err(Fault::InvPc);
             }
         }
         self.psr.value.set_bits(27..32, psr.get_bits(27..32));
@@ -382,18 +683,36 @@ impl ExceptionHandling for Processor {
                 highestpri -= subgroupvalue;
             }
         }
-        if self.basepri != 0 {
-            boostedpri = i16::from(self.basepri);
-            let subgroupvalue = boostedpri % groupvalue;
-            boostedpri -= subgroupvalue;
-        }
-        if self.primask {
-            boostedpri = 0;
-        }
-        #[cfg(any(feature = "armv7m", feature = "armv7em"))]
+        // masks of both security states boost the priority, Non-secure FAULTMASK
+        // only to 0 unless the Non-secure state owns HardFault
+        let banked = self.profile.has_security().then_some((
+            self.banked.basepri,
+            self.banked.primask,
+            self.banked.faultmask,
+            !self.secure,
+        ));
+        for (basepri, primask, faultmask, secure) in
+            [(self.basepri, self.primask, self.faultmask, self.secure)]
+                .into_iter()
+                .chain(banked)
         {
-            if self.faultmask {
-                boostedpri = -1;
+            if basepri != 0 {
+                let mut pri = i16::from(basepri);
+                let subgroupvalue = pri % groupvalue;
+                pri -= subgroupvalue;
+                boostedpri = boostedpri.min(pri);
+            }
+            if primask {
+                boostedpri = boostedpri.min(0);
+            }
+            if faultmask {
+                let non_secure = self.profile.has_security() && !secure;
+                let faultmask_pri = if non_secure && !self.aircr.get_bit(AIRCR_BFHFNMINS) {
+                    0
+                } else {
+                    -1
+                };
+                boostedpri = boostedpri.min(faultmask_pri);
             }
         }
 
@@ -456,54 +775,74 @@ impl ExceptionHandling for Processor {
         }
     }
 
+    fn fault_entry(&mut self, fault: Fault, return_address: u32) {
+        if self.execution_priority < 0 {
+            // HardFault cannot preempt the current execution
+            self.lockup(LockupReason::Fault(fault), return_address);
+            return;
+        }
+        let exception = if self.profile.is_mainline() {
+            self.fault_status(fault)
+        } else {
+            Exception::HardFault
+        };
+        self.escalating_entry(exception, return_address);
+    }
+
     fn exception_return(&mut self, exc_return: u32) -> Result<(), Fault> {
         assert!(self.mode == ProcessorMode::HandlerMode);
 
         let returning_exception_number = self.psr.get_isr_number();
         let nested_activation = self.exception_active_bit_count();
 
+        if !self.profile.has_fpu() && !exc_return.get_bit(4) {
+            // extended frame without an FPU
+            return self.invalid_exception_return(returning_exception_number, exc_return);
+        }
+
+        // ES names the security state of the handler, a Non-secure handler
+        // cannot claim to be Secure
+        let mut return_to = exc_return.get_bits(0..4);
+        if self.profile.has_security() {
+            if exc_return.get_bit(0) && !self.secure {
+                return Err(Fault::Inver);
+            }
+            return_to.set_bit(0, true);
+        }
+
         if self.exceptions[&returning_exception_number].active {
-            let frameptr;
-            match exc_return.get_bits(0..4) {
-                0b0001 => {
-                    // return to handler
-                    frameptr = self.get_msp();
-                    self.mode = ProcessorMode::HandlerMode;
-                    self.control.sp_sel = false;
-                }
-                0b1001 => {
-                    // returning to thread using main stack
-                    if nested_activation == 0
-                    /*&& !self.ccr.nonbasethreadena*/
-                    {
-                        return self
-                            .invalid_exception_return(returning_exception_number, exc_return);
-                    } else {
-                        frameptr = self.get_msp();
-                        self.mode = ProcessorMode::ThreadMode;
-                        self.control.sp_sel = false;
-                    }
-                }
-                0b1101 => {
-                    // returning to thread using process stack
+            let (mode, sp_sel) = match return_to {
+                // return to handler
+                0b0001 => (ProcessorMode::HandlerMode, false),
+                // returning to thread using main or process stack
+                0b1001 | 0b1101 => {
                     if nested_activation == 0
                     /*&& !self.ccr.nonbasethreadena*/
                     {
                         return self
                             .invalid_exception_return(returning_exception_number, exc_return);
-                    } else {
-                        frameptr = self.get_psp();
-                        self.mode = ProcessorMode::ThreadMode;
-                        self.control.sp_sel = true;
                     }
+                    (ProcessorMode::ThreadMode, return_to == 0b1101)
                 }
                 _ => {
                     return self.invalid_exception_return(returning_exception_number, exc_return);
                 }
-            }
+            };
 
             self.deactivate(returning_exception_number);
-            self.pop_stack(frameptr, exc_return)?;
+            self.clear_exclusive_local();
+            if self.profile.has_security() {
+                self.set_security_state(exc_return.get_bit(6));
+            }
+            self.mode = mode;
+            self.control.sp_sel = sp_sel;
+            let frameptr = if sp_sel {
+                self.get_psp()
+            } else {
+                self.get_msp()
+            };
+            self.pop_stack(frameptr, exc_return)
+                .map_err(Fault::unstacking)?;
             if self.mode == ProcessorMode::HandlerMode && self.psr.get_isr_number() == 0 {
                 //ufsr.invpc = true;
                 self.push_stack(Exception::UsageFault, exc_return)?; // to negate pop_stack
@@ -537,9 +876,7 @@ impl ExceptionHandling for Processor {
             self.sleeping = false;
             self.clear_pending_exception(exception);
             let pc = self.get_pc();
-            // TODO: handle failure to enter exception
-            self.exception_entry(exception, pc)
-                .expect("error handling on exception entry not implemented");
+            self.escalating_entry(exception, pc);
         }
     }
 }
@@ -553,7 +890,7 @@ impl From<Exception> for usize {
             Exception::MemoryManagementFault => 4,
             Exception::BusFault => 5,
             Exception::UsageFault => 6,
-            Exception::Reserved4 => 7,
+            Exception::SecureFault => 7,
             Exception::Reserved5 => 8,
             Exception::Reserved6 => 9,
             Exception::DebugMonitor => 10,
@@ -576,7 +913,7 @@ impl From<usize> for Exception {
             4 => Self::MemoryManagementFault,
             5 => Self::BusFault,
             6 => Self::UsageFault,
-            7 => Self::Reserved4,
+            7 => Self::SecureFault,
             8 => Self::Reserved5,
             9 => Self::Reserved6,
             10 => Self::DebugMonitor,
@@ -594,13 +931,12 @@ impl From<usize> for Exception {
 mod tests {
     use super::*;
     use crate::bus::Bus;
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     use crate::core::exception::Exception;
     use crate::core::exception::ExceptionHandling;
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-    use crate::core::instruction::Instruction;
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
+    use crate::core::instruction::{Instruction, VMRSTarget};
+    use crate::core::profile::ArchProfile;
     use crate::executor::Executor;
+    use crate::peripheral::fpu::{FloatingPointUnit, FPCCR_ASPEN};
 
     #[test]
     fn test_push_stack() {
@@ -716,11 +1052,11 @@ mod tests {
         );
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     #[test]
     fn test_faultmask_priority() {
         // Arrange
         let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M3);
 
         processor.reset().unwrap();
 
@@ -740,6 +1076,212 @@ mod tests {
         assert_eq!(processor.get_pending_exception(), None);
     }
 
+    #[test]
+    fn test_fault_entry() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M3);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+
+        // Act: bus fault with BusFault handler disabled
+        assert_eq!(processor.read32(0x6000_0000), Err(Fault::Preciserr));
+        processor.fault_entry(Fault::Preciserr, 0x100);
+
+        // Assert: escalated to HardFault
+        assert!(processor.exception_active(Exception::HardFault));
+        assert_eq!(processor.hfsr, 1 << 30);
+        assert_eq!(processor.cfsr, (1 << 15) | (1 << 9));
+        assert_eq!(processor.bfar, 0x6000_0000);
+
+        // Act: undefined instruction with UsageFault handler enabled
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+        processor.write32(0xE000_ED24, 1 << 18).unwrap();
+        processor.fault_entry(Fault::UndefInstr, 0x100);
+
+        // Assert
+        assert!(processor.exception_active(Exception::UsageFault));
+        assert_eq!(processor.read32(0xE000_ED24).unwrap(), (1 << 18) | (1 << 3));
+        assert_eq!(processor.read16(0xE000_ED2A).unwrap(), 1);
+        assert_eq!(processor.hfsr, 0);
+        assert_eq!(processor.read32(0x2000_0400 - 8).unwrap(), 0x100);
+
+        // Act: fault in the UsageFault handler escalates
+        processor.fault_entry(Fault::DivByZero, 0x200);
+
+        // Assert
+        assert!(processor.exception_active(Exception::HardFault));
+        assert_eq!(processor.hfsr, 1 << 30);
+
+        // Act: clear the status bits
+        processor.write32(0xE000_ED28, 0xffff_ffff).unwrap();
+        processor.write32(0xE000_ED2C, 1 << 30).unwrap();
+
+        // Assert
+        assert_eq!(processor.cfsr, 0);
+        assert_eq!(processor.hfsr, 0);
+    }
+
+    #[test]
+    fn test_fault_entry_armv6m() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M0);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+
+        // Act
+        processor.fault_entry(Fault::UndefInstr, 0x100);
+
+        // Assert
+        assert!(processor.exception_active(Exception::HardFault));
+        assert_eq!(processor.cfsr, 0);
+        assert_eq!(processor.hfsr, 0);
+    }
+
+    #[test]
+    fn test_exception_entry_lockup() {
+        // Arrange: vector table in unmapped memory
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M3);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+        processor.vtor = 0x6000_0000;
+        let pc = processor.get_pc();
+
+        // Act
+        processor.set_exception_pending(Exception::SysTick);
+        processor.check_exceptions();
+
+        // Assert: vector read error escalated to HardFault, which locked up
+        assert_eq!(processor.hfsr, 1 << 1);
+        assert_eq!(
+            processor.lockup,
+            Some((
+                LockupReason::ExceptionEntry {
+                    exception: Exception::HardFault,
+                    fault: Fault::VectorTable,
+                },
+                pc
+            ))
+        );
+        assert!(!processor.running);
+    }
+
+    #[test]
+    fn test_fp_context_stacking() {
+        // Arrange: active FP context, lazy stacking disabled
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M4F);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+        processor.control.fpca = true;
+        processor.fpccr = 1 << FPCCR_ASPEN;
+        processor.fp_regs[0] = 0x3f80_0000;
+        processor.fpscr = 0x0400_0000;
+
+        // Act
+        processor
+            .exception_entry(Exception::SysTick, 0x100)
+            .unwrap();
+
+        // Assert: extended frame
+        assert_eq!(processor.msp, 0x2000_0400 - 0x68);
+        assert_eq!(processor.lr, 0xFFFF_FFE9);
+        assert_eq!(processor.read32(processor.msp + 0x20).unwrap(), 0x3f80_0000);
+        assert_eq!(processor.read32(processor.msp + 0x60).unwrap(), 0x0400_0000);
+        assert!(!processor.control.fpca);
+
+        // Act: handler clobbers the FP registers and returns
+        processor.fp_regs[0] = 0;
+        processor.fpscr = 0;
+        processor.exception_return(0x0FFF_FFE9).unwrap();
+
+        // Assert
+        assert_eq!(processor.msp, 0x2000_0400);
+        assert_eq!(processor.fp_regs[0], 0x3f80_0000);
+        assert_eq!(processor.fpscr, 0x0400_0000);
+        assert!(processor.control.fpca);
+        assert_eq!(processor.get_pc(), 0x100);
+    }
+
+    #[test]
+    fn test_fp_context_lazy_stacking() {
+        // Arrange: active FP context, lazy stacking enabled by reset
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M4F);
+        processor.reset().unwrap();
+        processor.cpacr = 0x00f0_0000;
+        processor.msp = 0x2000_0400;
+        processor.control.fpca = true;
+        processor.fp_regs[15] = 0x4000_0000;
+
+        // Act
+        processor
+            .exception_entry(Exception::SysTick, 0x100)
+            .unwrap();
+
+        // Assert: space reserved, nothing saved yet
+        let frameptr = processor.msp;
+        assert_eq!(frameptr, 0x2000_0400 - 0x68);
+        assert_eq!(processor.fpcar, frameptr + 0x20);
+        assert!(processor.fpccr.get_bit(FPCCR_LSPACT));
+        assert_ne!(processor.read32(frameptr + 0x5c).unwrap(), 0x4000_0000);
+
+        // Act: first FP instruction of the handler
+        processor.execute_fp_check().unwrap();
+        processor.fp_regs[15] = 0;
+
+        // Assert
+        assert_eq!(processor.read32(frameptr + 0x5c).unwrap(), 0x4000_0000);
+        assert!(!processor.fpccr.get_bit(FPCCR_LSPACT));
+        assert!(processor.control.fpca);
+
+        // Act: return restores the saved state
+        processor.exception_return(0x0FFF_FFE9).unwrap();
+
+        // Assert
+        assert_eq!(processor.fp_regs[15], 0x4000_0000);
+        assert_eq!(processor.msp, 0x2000_0400);
+    }
+
+    #[test]
+    fn test_fp_default_context_in_handler() {
+        // Arrange: caller rounds towards zero, handlers default to flush-to-zero
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M4F);
+        processor.reset().unwrap();
+        processor.cpacr = 0x00f0_0000;
+        processor.msp = 0x2000_0400;
+        processor.fpccr = 1 << FPCCR_ASPEN;
+        processor.write_fpdscr(0x0100_0000);
+        processor.set_r(Reg::R0, 0x00c0_0000);
+        processor.execute(&Instruction::VMSR { rt: Reg::R0 }, 4);
+        assert!(processor.control.fpca);
+
+        // Act
+        processor
+            .exception_entry(Exception::SysTick, 0x100)
+            .unwrap();
+        processor.set_r(Reg::R0, 0);
+        processor.execute(
+            &Instruction::VMRS {
+                rt: VMRSTarget::Register(Reg::R0),
+            },
+            4,
+        );
+
+        // Assert: the handler sees the default FPSCR
+        assert_eq!(processor.get_r(Reg::R0), 0x0100_0000);
+
+        // Act: return restores the caller FPSCR
+        processor.exception_return(0x0FFF_FFE9).unwrap();
+
+        // Assert
+        assert_eq!(processor.fpscr, 0x00c0_0000);
+    }
+
     #[test]
     fn test_exception_entry_clears_nvic() {
         // Arrange
@@ -757,4 +1299,91 @@ mod tests {
         // Assert
         assert_eq!(processor.nvic_read_ispr(0), 0);
     }
+
+    #[test]
+    fn test_non_secure_handler_stacks_additional_state() {
+        // Arrange: Secure thread, interrupt 0 targets Non-secure state
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+        processor.nvic_interrupt_target_ns[0] = 1;
+        processor.r0_12[0] = 0x10;
+        processor.r0_12[4] = 0x44;
+        processor.r0_12[11] = 0xbb;
+
+        // Act
+        processor
+            .exception_entry(Exception::Interrupt { n: 0 }, 0x100)
+            .unwrap();
+
+        // Assert: the Secure registers are stacked under the integrity signature and cleared
+        assert!(!processor.secure);
+        let frameptr = processor.banked.msp;
+        assert_eq!(frameptr, 0x2000_0400 - 0x48);
+        assert_eq!(processor.lr, 0xFFFF_FFD8);
+        assert_eq!(processor.bus_read32(frameptr).unwrap(), 0xFEFA_125B);
+        assert_eq!(processor.bus_read32(frameptr + 0x08).unwrap(), 0x44);
+        assert_eq!(processor.bus_read32(frameptr + 0x24).unwrap(), 0xbb);
+        assert_eq!(processor.bus_read32(frameptr + 0x28).unwrap(), 0x10);
+        assert_eq!(processor.r0_12, [0; 13]);
+
+        // Act
+        processor.exception_return(0xFFFF_FFD8).unwrap();
+
+        // Assert
+        assert!(processor.secure);
+        assert_eq!(processor.msp, 0x2000_0400);
+        assert_eq!(processor.r0_12[0], 0x10);
+        assert_eq!(processor.r0_12[4], 0x44);
+        assert_eq!(processor.r0_12[11], 0xbb);
+        assert_eq!(processor.get_pc(), 0x100);
+    }
+
+    #[test]
+    fn test_additional_state_integrity_check() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+        processor.nvic_interrupt_target_ns[0] = 1;
+        processor
+            .exception_entry(Exception::Interrupt { n: 0 }, 0x100)
+            .unwrap();
+
+        // Assert: Non-secure handler claims to be Secure
+        assert_eq!(processor.exception_return(0xFFFF_FFD9), Err(Fault::Inver));
+
+        // Act: the signature is overwritten
+        processor.set_security_state(true);
+        processor.write32(0x2000_0400 - 0x48, 0).unwrap();
+        processor.set_security_state(false);
+
+        // Assert
+        assert_eq!(processor.exception_return(0xFFFF_FFD8), Err(Fault::Invis));
+    }
+
+    #[test]
+    fn test_exception_entry_stack_limit() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0410;
+        processor.msplim = 0x2000_0400;
+
+        // Act
+        processor
+            .exception_entry(Exception::SysTick, 0x100)
+            .unwrap();
+
+        // Assert: nothing stacked below the limit, the overflow is pended
+        assert_eq!(processor.msp, 0x2000_0400);
+        assert!(processor.cfsr.get_bit(Fault::Stkof.status_bit()));
+        assert_eq!(
+            processor.get_pending_exception(),
+            Some(Exception::HardFault)
+        );
+    }
 }
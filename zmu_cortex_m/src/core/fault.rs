@@ -3,6 +3,8 @@
 //!
 //!
 
+use crate::core::exception::Exception;
+
 #[derive(PartialEq, Debug, Copy, Clone)]
 ///
 /// Fault types
@@ -34,6 +36,10 @@ pub enum Fault {
     ///
     Msunskerr,
     ///
+    /// Memmanage fault on exception return stack operations
+    ///
+    Munstkerr,
+    ///
     /// Memmanage fault on data access
     ///
     DAccViol,
@@ -77,6 +83,32 @@ pub enum Fault {
     ///
     DivByZero,
     ///
+    /// Usage fault, stack pointer moved below its Armv8-M stack limit
+    ///
+    Stkof,
+    ///
+    /// Secure fault, Non-secure code branched to Secure memory other than an SG
+    /// instruction in Non-secure callable memory
+    ///
+    Invep,
+    ///
+    /// Secure fault, integrity signature check of the additional state context failed
+    /// on exception return
+    ///
+    Invis,
+    ///
+    /// Secure fault, exception return to the wrong security state
+    ///
+    Inver,
+    ///
+    /// Secure fault, Non-secure access to Secure memory
+    ///
+    Auviol,
+    ///
+    /// Secure fault, Secure code branched to Non-secure memory without BXNS or BLXNS
+    ///
+    Invtran,
+    ///
     /// Memmanage fault, delayed fp processing
     ///
     MlspErr,
@@ -85,3 +117,106 @@ pub enum Fault {
     ///
     LspErr,
 }
+
+impl Fault {
+    ///
+    /// Exception that handles the fault, when the exception is enabled and
+    /// its priority allows it to preempt the current execution
+    ///
+    pub fn exception(self) -> Exception {
+        match self {
+            Self::VectorTable | Self::Forced | Self::DebugEvt => Exception::HardFault,
+            Self::Mstkerr | Self::Munstkerr | Self::DAccViol | Self::IAccViol | Self::MlspErr => {
+                Exception::MemoryManagementFault
+            }
+            Self::Stkerr
+            | Self::Msunskerr
+            | Self::IBusErr
+            | Self::Preciserr
+            | Self::Impreciseerr
+            | Self::LspErr => Exception::BusFault,
+            Self::Nocp
+            | Self::UndefInstr
+            | Self::Invstate
+            | Self::InvPc
+            | Self::Unaligned
+            | Self::DivByZero
+            | Self::Stkof => Exception::UsageFault,
+            Self::Invep | Self::Invis | Self::Inver | Self::Auviol | Self::Invtran => {
+                Exception::SecureFault
+            }
+        }
+    }
+
+    ///
+    /// Fault reported when a stack push on exception entry fails with this fault
+    ///
+    #[must_use]
+    pub fn stacking(self) -> Self {
+        if self == Self::DAccViol {
+            Self::Mstkerr
+        } else {
+            Self::Stkerr
+        }
+    }
+
+    ///
+    /// Fault reported when a stack pop on exception return fails with this fault
+    ///
+    #[must_use]
+    pub fn unstacking(self) -> Self {
+        match self {
+            Self::DAccViol => Self::Munstkerr,
+            Self::Preciserr => Self::Msunskerr,
+            _ => self,
+        }
+    }
+
+    ///
+    /// Fault reported when the lazy floating point state preservation fails with this fault
+    ///
+    #[must_use]
+    pub fn lazy_preservation(self) -> Self {
+        if self == Self::DAccViol {
+            Self::MlspErr
+        } else {
+            Self::LspErr
+        }
+    }
+
+    ///
+    /// Status bit of the fault in the Configurable Fault Status Register (CFSR),
+    /// in the `HardFault` Status Register (HFSR) for the `HardFault` only faults,
+    /// or in the Secure Fault Status Register (SFSR) for the secure faults
+    ///
+    pub fn status_bit(self) -> usize {
+        match self {
+            Self::IAccViol => 0,
+            Self::DAccViol => 1,
+            Self::Munstkerr => 3,
+            Self::Mstkerr => 4,
+            Self::MlspErr => 5,
+            Self::IBusErr => 8,
+            Self::Preciserr => 9,
+            Self::Impreciseerr => 10,
+            Self::Msunskerr => 11,
+            Self::Stkerr => 12,
+            Self::LspErr => 13,
+            Self::UndefInstr => 16,
+            Self::Invstate => 17,
+            Self::InvPc => 18,
+            Self::Nocp => 19,
+            Self::Stkof => 20,
+            Self::Unaligned => 24,
+            Self::DivByZero => 25,
+            Self::Invep => 0,
+            Self::Invis => 1,
+            Self::Inver => 2,
+            Self::Auviol => 3,
+            Self::Invtran => 4,
+            Self::VectorTable => 1,
+            Self::Forced => 30,
+            Self::DebugEvt => 31,
+        }
+    }
+}
@@ -2,9 +2,9 @@
 //! Fetching instructions for execution
 //!
 //!
-use crate::bus::Bus;
 use crate::core::fault::Fault;
 use crate::core::thumb::ThumbCode;
+use crate::peripheral::mpu::{MemoryAccess, MemoryProtectionUnit};
 
 use crate::{decoder::is_thumb32, Processor};
 
@@ -12,24 +12,34 @@ use crate::{decoder::is_thumb32, Processor};
 /// Fetching instructions
 pub trait Fetch {
     /// Fetch instruction from current PC (Program Counter) position,
-    /// decoding the possible thumb32 variant
-    fn fetch(&self, pc: u32) -> Result<ThumbCode, Fault>;
+    /// decoding the possible thumb32 variant. Fetches are checked by the
+    /// MPU, bus errors are `IBusErr` faults.
+    fn fetch(&mut self, pc: u32) -> Result<ThumbCode, Fault>;
 
     /// Fetch instruction from current PC (Program Counter) position,
     /// decoding the possible thumb32 variant. Do not fail on
     /// out of bounds access, just return undefined instruction.
-    fn fetch_non_fail(&self, pc: u32) -> ThumbCode;
+    /// The MPU is bypassed, like with debugger accesses.
+    fn fetch_non_fail(&mut self, pc: u32) -> ThumbCode;
+}
+
+impl Processor {
+    /// Fetch one halfword of an instruction
+    fn fetch_halfword(&mut self, address: u32) -> Result<u16, Fault> {
+        self.mpu_check(address, MemoryAccess::InstructionFetch)?;
+        self.bus_read16(address).map_err(|_| Fault::IBusErr)
+    }
 }
 
 impl Fetch for Processor {
     // Fetch next Thumb2-coded instruction from current
     // PC location. Depending on instruction type, fetches
     // one or two half-words.
-    fn fetch(&self, pc: u32) -> Result<ThumbCode, Fault> {
-        let hw = self.read16(pc)?;
+    fn fetch(&mut self, pc: u32) -> Result<ThumbCode, Fault> {
+        let hw = self.fetch_halfword(pc)?;
 
         if is_thumb32(hw) {
-            let hw2 = self.read16(pc + 2)?;
+            let hw2 = self.fetch_halfword(pc + 2)?;
             Ok(ThumbCode::Thumb32 {
                 opcode: (u32::from(hw) << 16) + u32::from(hw2),
             })
@@ -38,11 +48,11 @@ impl Fetch for Processor {
         }
     }
 
-    fn fetch_non_fail(&self, pc: u32) -> ThumbCode {
-        match self.read16(pc) {
+    fn fetch_non_fail(&mut self, pc: u32) -> ThumbCode {
+        match self.bus_read16(pc) {
             Ok(hw) => {
                 if is_thumb32(hw) {
-                    if let Ok(hw2) = self.read16(pc + 2) {
+                    if let Ok(hw2) = self.bus_read16(pc + 2) {
                         ThumbCode::Thumb32 {
                             opcode: (u32::from(hw) << 16) + u32::from(hw2),
                         }
@@ -391,6 +391,59 @@ pub struct VCmpParamsf64 {
     pub with_zero: bool,
 }
 
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct VSelParamsf32 {
+    pub sd: SingleReg,
+    pub sn: SingleReg,
+    pub sm: SingleReg,
+    pub cond: Condition,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct VSelParamsf64 {
+    pub dd: DoubleReg,
+    pub dn: DoubleReg,
+    pub dm: DoubleReg,
+    pub cond: Condition,
+}
+
+/// Rounding variants of VRINT, named by the mnemonic suffix
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum VRintMode {
+    /// Round to nearest, ties away from zero
+    A,
+    /// Round to nearest, ties to even
+    N,
+    /// Round towards plus infinity
+    P,
+    /// Round towards minus infinity
+    M,
+    /// Round with the FPSCR rounding mode
+    R,
+    /// Round with the FPSCR rounding mode, signal inexact results
+    X,
+    /// Round towards zero
+    Z,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct VRintParamsf32 {
+    pub sd: SingleReg,
+    pub sm: SingleReg,
+    pub mode: VRintMode,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct VRintParamsf64 {
+    pub dd: DoubleReg,
+    pub dm: DoubleReg,
+    pub mode: VRintMode,
+}
+
 #[allow(missing_docs)]
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct VCVTParams {
@@ -403,6 +456,29 @@ pub struct VCVTParams {
     pub round_nearest: bool,
 }
 
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct VCVTDirectedParams {
+    pub d: SingleReg,
+    pub m: ExtensionReg,
+    pub unsigned: bool,
+    pub mode: VRintMode,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct VCVTF32F64Params {
+    pub sd: SingleReg,
+    pub dm: DoubleReg,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct VCVTF64F32Params {
+    pub dd: DoubleReg,
+    pub sm: SingleReg,
+}
+
 #[allow(missing_docs)]
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct VMovRegParamsf64 {
@@ -462,6 +538,17 @@ pub struct Reg2RtRnImm32Params {
     pub imm32: u32,
 }
 
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct TtParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    /// alternate domain, TTA and TTAT
+    pub a: bool,
+    /// unprivileged, TTT and TTAT
+    pub t: bool,
+}
+
 #[allow(missing_docs)]
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct BfxParams {
@@ -494,6 +581,27 @@ pub struct Reg643232Params {
     pub rn: Reg,
 }
 
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg643232HighParams {
+    pub rdlo: Reg,
+    pub rdhi: Reg,
+    pub rm: Reg,
+    pub rn: Reg,
+    pub n_high: bool,
+    pub m_high: bool,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg643232DualParams {
+    pub rdlo: Reg,
+    pub rdhi: Reg,
+    pub rm: Reg,
+    pub rn: Reg,
+    pub m_swap: bool,
+}
+
 #[allow(missing_docs)]
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct Reg3HighParams {
@@ -515,6 +623,91 @@ pub struct Reg4HighParams {
     pub m_high: bool,
 }
 
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg3MHighParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub rm: Reg,
+    pub m_high: bool,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg4MHighParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub rm: Reg,
+    pub ra: Reg,
+    pub m_high: bool,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg3DualParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub rm: Reg,
+    pub m_swap: bool,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg4DualParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub rm: Reg,
+    pub ra: Reg,
+    pub m_swap: bool,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg3RoundParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub rm: Reg,
+    pub round: bool,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg4RoundParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub rm: Reg,
+    pub ra: Reg,
+    pub round: bool,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Reg3ShiftNoSetFlagsParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub rm: Reg,
+    pub shift_t: SRType,
+    pub shift_n: u8,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct SatParams {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub saturate_to: u8,
+    pub shift_t: SRType,
+    pub shift_n: u8,
+}
+
+#[allow(missing_docs)]
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Sat16Params {
+    pub rd: Reg,
+    pub rn: Reg,
+    pub saturate_to: u8,
+}
+
 #[allow(missing_docs)]
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct ParamsRegImm32 {
@@ -591,6 +784,14 @@ pub enum Instruction {
     BX {
         rm: Reg,
     },
+    /// Call a Non-secure subroutine
+    BLXNS {
+        rm: Reg,
+    },
+    /// Branch to a Non-secure target address
+    BXNS {
+        rm: Reg,
+    },
     /// Compare and branch on  Zero
     CBZ {
         params: ParamsRegImm32,
@@ -878,50 +1079,73 @@ pub enum Instruction {
     SMLA {
         params: Reg4HighParams,
     },
+    /// Signed Multiply Accumulate Dual
+    /// variants: SMLAD, SMLADX
+    SMLAD {
+        params: Reg4DualParams,
+    },
+    /// Signed Multiply Accumulate Long, halfwords
+    /// variants: SMLALBB, SMLALBT, SMLALTB, SMLALTT
+    SMLALxy {
+        params: Reg643232HighParams,
+    },
+    /// Signed Multiply Accumulate Long Dual
+    /// variants: SMLALD, SMLALDX
+    SMLALD {
+        params: Reg643232DualParams,
+    },
+    /// Signed Multiply Accumulate, word by halfword
+    /// variants: SMLAWB, SMLAWT
+    SMLAW {
+        params: Reg4MHighParams,
+    },
 
-    //SMLAL second variant?
-
-    // Signed Multiply Accumulate Long, halfwords
-    // SMLABB, SMLABT, SMLATT, SMLATB
-
-    // Signed Multiply Accumulate Dual
-    // SMLAD, SMLADX
-
-    // Signed Multiply Accumulate Long, halfwords
-    // SMLALBB, SMLALBT, SMLALTT, SMLALTB
-
-    // Signed Multiply Accumulate Long Dual
-    // SMLALD, SMLALDX
-
-    // Signed Multiply Accumulate, word by halfword
-    // SMLAWB, SMLAWT
-
-    // Signed Multiply Subtract Dual
-    // SMLSD, SMLSDX
-
-    // Signed Multiply Subtract Long Dual
-    // SMLSLD, SMLSLDX
-
-    // Signed most significant word Multiply Accumulate
-    // SMMLA, SMMLAR
+    /// Signed Multiply Subtract Dual
+    /// variants: SMLSD, SMLSDX
+    SMLSD {
+        params: Reg4DualParams,
+    },
+    /// Signed Multiply Subtract Long Dual
+    /// variants: SMLSLD, SMLSLDX
+    SMLSLD {
+        params: Reg643232DualParams,
+    },
 
-    // Signed most significant word Multiply Subtract
-    // SMMLS, SMMLSR
+    /// Signed most significant word Multiply Accumulate
+    /// variants: SMMLA, SMMLAR
+    SMMLA {
+        params: Reg4RoundParams,
+    },
 
-    // Signed most significant Word Multiply
-    // SMMUL, SMMULR
+    /// Signed most significant word Multiply Subtract
+    /// variants: SMMLS, SMMLSR
+    SMMLS {
+        params: Reg4RoundParams,
+    },
 
-    // Signed Dual Multiply Add
-    // SMUAD, SMUADX
+    /// Signed most significant Word Multiply
+    /// variants: SMMUL, SMMULR
+    SMMUL {
+        params: Reg3RoundParams,
+    },
 
-    // Signed Multiply, halfwords
-    // SMULBB, SMULBT, SMULTB, SMULTT
+    /// Signed Dual Multiply Add
+    /// variants: SMUAD, SMUADX
+    SMUAD {
+        params: Reg3DualParams,
+    },
 
-    // Signed Multiply, word by halfword
-    // SMULWB, SMULWT
+    /// Signed Multiply, word by halfword
+    /// variants: SMULWB, SMULWT
+    SMULW {
+        params: Reg3MHighParams,
+    },
 
-    // Signed Dual Multiply Subtract
-    // SMUSD, SMUSDX
+    /// Signed Dual Multiply Subtract
+    /// variants: SMUSD, SMUSDX
+    SMUSD {
+        params: Reg3DualParams,
+    },
 
     //
     // Subgroup: Unsigned Multiply instructions (ARMv7-M base architecture)
@@ -936,7 +1160,10 @@ pub enum Instruction {
     //
     // Subgroup: Unsigned multiply instructions (Armv7-M DSP extension)
     //
-    // UMAAL
+    /// Unsigned Multiply Accumulate Accumulate Long
+    UMAAL {
+        params: Reg643232Params,
+    },
 
     // --------------------------------------------
     //
@@ -947,22 +1174,46 @@ pub enum Instruction {
     //
     // Subgroup: Saturating instructions (ARMv7-M base arch)
     //
-    //SSAT
-    //USAT
+    /// Signed Saturate
+    SSAT {
+        params: SatParams,
+    },
+    /// Unsigned Saturate
+    USAT {
+        params: SatParams,
+    },
 
     //
     // Subgroup: Halfword saturating instructions, (ARMv7-M DSP extensions)
     //
-    //USAT16
-    //SSAT16
+    /// Unsigned Saturate, two halfwords
+    USAT16 {
+        params: Sat16Params,
+    },
+    /// Signed Saturate, two halfwords
+    SSAT16 {
+        params: Sat16Params,
+    },
 
     //
     // Subgroup: Saturating addition and subtraction instructions, Armv7-M DSP extension
     //
-    //QADD
-    //QSUB
-    //QDADD
-    //QDSUB
+    /// Saturating Add
+    QADD {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Subtract
+    QSUB {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Double and Add
+    QDADD {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Double and Subtract
+    QDSUB {
+        params: Reg3NoSetFlagsParams,
+    },
 
     // --------------------------------------------
     //
@@ -997,17 +1248,46 @@ pub enum Instruction {
     //
     // Subgroup: Packing and unpacking instructions, Armv7-M DSP extension
     //
-    //PKHBT, PKHTB
-    //SXTAB
-    //SXTAB16
-    //SXTAH
-    //SXTB16
+    /// Pack Halfword, bottom from Rn and top from shifted Rm
+    PKHBT {
+        params: Reg3ShiftNoSetFlagsParams,
+    },
+    /// Pack Halfword, top from Rn and bottom from shifted Rm
+    PKHTB {
+        params: Reg3ShiftNoSetFlagsParams,
+    },
+    /// Signed Extend and Add Byte
+    SXTAB {
+        params: Reg3UsizeParams,
+    },
+    /// Signed Extend and Add Byte 16
+    SXTAB16 {
+        params: Reg3UsizeParams,
+    },
+    /// Signed Extend and Add Halfword
+    SXTAH {
+        params: Reg3UsizeParams,
+    },
+    /// Signed Extend Byte 16
+    SXTB16 {
+        params: Reg2UsizeParams,
+    },
+    /// Unsigned Extend and Add Byte
     UXTAB {
         params: Reg3UsizeParams,
     },
-    //UXTAB16
-    //UXTAH
-    //UXTB16
+    /// Unsigned Extend and Add Byte 16
+    UXTAB16 {
+        params: Reg3UsizeParams,
+    },
+    /// Unsigned Extend and Add Halfword
+    UXTAH {
+        params: Reg3UsizeParams,
+    },
+    /// Unsigned Extend Byte 16
+    UXTB16 {
+        params: Reg2UsizeParams,
+    },
 
     // --------------------------------------------
     //
@@ -1028,15 +1308,150 @@ pub enum Instruction {
     // Group: Parallel add / sub (DSP extension)
     //
     // --------------------------------------------
-    //SADD16, QADD16, SHADD16, UADD16, UQADD16, UHADD16
-    //SASX, QASX, SHASX, UASX, UQASX, UHSX
-    //SSAX, QSAX, SHSAX, USAX, UQSAX, UHSAX
-    //SSUB16, QSUB16, SHSUB16, USUB16, UQSUB16, UHSUB16
-    //SADD8, QADD8, SHADD8, UADD8, UQADD8, UHADD8
-    //SSUB8, QSUB8, SHSUB8, USUB8, UQSUB8, UHSUB8
+    /// Signed Add 16
+    SADD16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Add 16
+    QADD16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Halving Add 16
+    SHADD16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Add 16
+    UADD16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Saturating Add 16
+    UQADD16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Halving Add 16
+    UHADD16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Add and Subtract with Exchange
+    SASX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Add and Subtract with Exchange
+    QASX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Halving Add and Subtract with Exchange
+    SHASX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Add and Subtract with Exchange
+    UASX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Saturating Add and Subtract with Exchange
+    UQASX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Halving Add and Subtract with Exchange
+    UHASX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Subtract and Add with Exchange
+    SSAX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Subtract and Add with Exchange
+    QSAX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Halving Subtract and Add with Exchange
+    SHSAX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Subtract and Add with Exchange
+    USAX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Saturating Subtract and Add with Exchange
+    UQSAX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Halving Subtract and Add with Exchange
+    UHSAX {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Subtract 16
+    SSUB16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Subtract 16
+    QSUB16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Halving Subtract 16
+    SHSUB16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Subtract 16
+    USUB16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Saturating Subtract 16
+    UQSUB16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Halving Subtract 16
+    UHSUB16 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Add 8
+    SADD8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Add 8
+    QADD8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Halving Add 8
+    SHADD8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Add 8
     UADD8 {
         params: Reg3NoSetFlagsParams,
     },
+    /// Unsigned Saturating Add 8
+    UQADD8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Halving Add 8
+    UHADD8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Subtract 8
+    SSUB8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Saturating Subtract 8
+    QSUB8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Signed Halving Subtract 8
+    SHSUB8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Subtract 8
+    USUB8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Saturating Subtract 8
+    UQSUB8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Halving Subtract 8
+    UHSUB8 {
+        params: Reg3NoSetFlagsParams,
+    },
 
     // --------------------------------------------
     //
@@ -1098,8 +1513,14 @@ pub enum Instruction {
     SEL {
         params: Reg3NoSetFlagsParams,
     },
-    //USAD8
-    //USADA8
+    /// Unsigned Sum of Absolute Differences
+    USAD8 {
+        params: Reg3NoSetFlagsParams,
+    },
+    /// Unsigned Sum of Absolute Differences and Accumulate
+    USADA8 {
+        params: Reg4NoSetFlagsParams,
+    },
 
     // --------------------------------------------
     //
@@ -1117,9 +1538,7 @@ pub enum Instruction {
     /// Change Processor State
     CPS {
         im: bool,
-        #[cfg(any(feature = "armv7m", feature = "armv7em"))]
         affect_pri: bool,
-        #[cfg(any(feature = "armv7m", feature = "armv7em"))]
         affect_fault: bool,
     },
 
@@ -1238,6 +1657,55 @@ pub enum Instruction {
         params: Reg3RdRtRnParams,
     },
 
+    /// Load-acquire word
+    LDA {
+        params: Reg2RtRnParams,
+    },
+    /// Load-acquire byte
+    LDAB {
+        params: Reg2RtRnParams,
+    },
+    /// Load-acquire halfword
+    LDAH {
+        params: Reg2RtRnParams,
+    },
+    /// Load-acquire exclusive word
+    LDAEX {
+        params: Reg2RtRnParams,
+    },
+    /// Load-acquire exclusive byte
+    LDAEXB {
+        params: Reg2RtRnParams,
+    },
+    /// Load-acquire exclusive halfword
+    LDAEXH {
+        params: Reg2RtRnParams,
+    },
+    /// Store-release word
+    STL {
+        params: Reg2RtRnParams,
+    },
+    /// Store-release byte
+    STLB {
+        params: Reg2RtRnParams,
+    },
+    /// Store-release halfword
+    STLH {
+        params: Reg2RtRnParams,
+    },
+    /// Store-release exclusive word
+    STLEX {
+        params: Reg3RdRtRnParams,
+    },
+    /// Store-release exclusive byte
+    STLEXB {
+        params: Reg3RdRtRnParams,
+    },
+    /// Store-release exclusive halfword
+    STLEXH {
+        params: Reg3RdRtRnParams,
+    },
+
     // --------------------------------------------
     //
     // Group: Load and Store Multiple instructions
@@ -1268,7 +1736,8 @@ pub enum Instruction {
     // Group: Miscellaneous instructions
     //
     // --------------------------------------------
-    //CLREX
+    /// Clear Exclusive
+    CLREX,
     //DBG
     /// Data Memory Barrier
     DMB,
@@ -1339,6 +1808,17 @@ pub enum Instruction {
     },
     // --------------------------------------------
     //
+    // Group: Security extension instructions
+    //
+    // --------------------------------------------
+    /// Secure gateway
+    SG,
+    /// Test target, security state and access permissions of an address
+    TT {
+        params: TtParams,
+    },
+    // --------------------------------------------
+    //
     // Group: Coprocessor instructions
     //
     // --------------------------------------------
@@ -1444,6 +1924,9 @@ pub enum Instruction {
     VMRS {
         rt: VMRSTarget,
     },
+    VMSR {
+        rt: Reg,
+    },
 
     // --------------------------------------------
     //
@@ -1471,21 +1954,117 @@ pub enum Instruction {
     VCVT {
         params: VCVTParams,
     },
-    //VDIV
-    //VFMA
-    //VFNMA
-    //VMAXNM
-    //VMLA
-    //VMOV
-    //VMOV
-    //VMUL
-    //VNEG
-    //VNMLA
-    //VRINTA
-    //VRINTZ
-    //VSEL
-    //VSQRT
-    //VSUB
+    VCVT_f32_f64 {
+        params: VCVTF32F64Params,
+    },
+    VCVT_f64_f32 {
+        params: VCVTF64F32Params,
+    },
+    VDIV_f32 {
+        params: VAddSubParamsf32,
+    },
+    VDIV_f64 {
+        params: VAddSubParamsf64,
+    },
+    VFMA_f32 {
+        params: VAddSubParamsf32,
+    },
+    VFMA_f64 {
+        params: VAddSubParamsf64,
+    },
+    VFMS_f32 {
+        params: VAddSubParamsf32,
+    },
+    VFMS_f64 {
+        params: VAddSubParamsf64,
+    },
+    VFNMA_f32 {
+        params: VAddSubParamsf32,
+    },
+    VFNMA_f64 {
+        params: VAddSubParamsf64,
+    },
+    VFNMS_f32 {
+        params: VAddSubParamsf32,
+    },
+    VFNMS_f64 {
+        params: VAddSubParamsf64,
+    },
+    VMAXNM_f32 {
+        params: VAddSubParamsf32,
+    },
+    VMAXNM_f64 {
+        params: VAddSubParamsf64,
+    },
+    VMINNM_f32 {
+        params: VAddSubParamsf32,
+    },
+    VMINNM_f64 {
+        params: VAddSubParamsf64,
+    },
+    VMLA_f32 {
+        params: VAddSubParamsf32,
+    },
+    VMLA_f64 {
+        params: VAddSubParamsf64,
+    },
+    VMLS_f32 {
+        params: VAddSubParamsf32,
+    },
+    VMLS_f64 {
+        params: VAddSubParamsf64,
+    },
+    VMUL_f32 {
+        params: VAddSubParamsf32,
+    },
+    VMUL_f64 {
+        params: VAddSubParamsf64,
+    },
+    VNEG_f32 {
+        params: VMovRegParamsf32,
+    },
+    VNEG_f64 {
+        params: VMovRegParamsf64,
+    },
+    VNMLA_f32 {
+        params: VAddSubParamsf32,
+    },
+    VNMLA_f64 {
+        params: VAddSubParamsf64,
+    },
+    VNMLS_f32 {
+        params: VAddSubParamsf32,
+    },
+    VNMLS_f64 {
+        params: VAddSubParamsf64,
+    },
+    VNMUL_f32 {
+        params: VAddSubParamsf32,
+    },
+    VNMUL_f64 {
+        params: VAddSubParamsf64,
+    },
+    VRINT_f32 {
+        params: VRintParamsf32,
+    },
+    VRINT_f64 {
+        params: VRintParamsf64,
+    },
+    VCVT_directed {
+        params: VCVTDirectedParams,
+    },
+    VSEL_f32 {
+        params: VSelParamsf32,
+    },
+    VSEL_f64 {
+        params: VSelParamsf64,
+    },
+    VSQRT_f32 {
+        params: VMovRegParamsf32,
+    },
+    VSQRT_f64 {
+        params: VMovRegParamsf64,
+    },
     VSUB_f32 {
         params: VAddSubParamsf32,
     },
@@ -1622,6 +2201,18 @@ fn fmt_add_imm(params: Reg2ImmParams, thumb32: bool) -> String {
     }
 }
 
+fn fmt_vrint_mode(mode: VRintMode) -> &'static str {
+    match mode {
+        VRintMode::A => "a",
+        VRintMode::N => "n",
+        VRintMode::P => "p",
+        VRintMode::M => "m",
+        VRintMode::R => "r",
+        VRintMode::X => "x",
+        VRintMode::Z => "z",
+    }
+}
+
 fn fmt_vcvt(params: VCVTParams) -> String {
     let fpscr_rounding = params.round_nearest;
     if params.to_integer {
@@ -1825,6 +2416,8 @@ impl fmt::Display for Instruction {
             Self::BL { imm32 } => write!(f, "bl 0x#{imm32:x}"),
             Self::BX { rm } => write!(f, "bx {rm}"),
             Self::BLX { rm } => write!(f, "blx {rm}"),
+            Self::BXNS { rm } => write!(f, "bxns {rm}"),
+            Self::BLXNS { rm } => write!(f, "blxns {rm}"),
             Self::BKPT { imm32 } => write!(f, "bkpt #{imm32}"),
 
             Self::BFI { params } => write!(
@@ -1877,9 +2470,6 @@ impl fmt::Display for Instruction {
                 }
             ),
 
-            #[cfg(feature = "armv6m")]
-            Self::CPS { im } => write!(f, "cps{} i", if im { "ID" } else { "IE" }),
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             Self::CPS {
                 im,
                 affect_pri,
@@ -1891,6 +2481,7 @@ impl fmt::Display for Instruction {
                 if affect_pri { "i" } else { "" },
                 if affect_fault { "f" } else { "" }
             ),
+            Self::CLREX => write!(f, "clrex"),
             Self::DMB => write!(f, "dmb"),
             Self::DSB => write!(f, "dsb"),
             Self::EOR_reg { params, thumb32 } => write!(
@@ -1970,6 +2561,12 @@ impl fmt::Display for Instruction {
             }
             Self::LDREXB { params } => write!(f, "ldrexb {}, {}", params.rt, params.rn),
             Self::LDREXH { params } => write!(f, "ldrexh {}, {}", params.rt, params.rn),
+            Self::LDA { params } => write!(f, "lda {}, [{}]", params.rt, params.rn),
+            Self::LDAB { params } => write!(f, "ldab {}, [{}]", params.rt, params.rn),
+            Self::LDAH { params } => write!(f, "ldah {}, [{}]", params.rt, params.rn),
+            Self::LDAEX { params } => write!(f, "ldaex {}, [{}]", params.rt, params.rn),
+            Self::LDAEXB { params } => write!(f, "ldaexb {}, [{}]", params.rt, params.rn),
+            Self::LDAEXH { params } => write!(f, "ldaexh {}, [{}]", params.rt, params.rn),
 
             Self::LDRB_imm { params, thumb32 } => format_adressing_mode("ldrb", f, params, thumb32),
             Self::LDRB_reg { params, thumb32 } => write!(
@@ -2064,6 +2661,111 @@ impl fmt::Display for Instruction {
                 params.rm,
                 params.ra
             ),
+            Self::SMLAD { params } => write!(
+                f,
+                "smlad{} {}, {}, {}, {}",
+                if params.m_swap { "X" } else { "" },
+                params.rd,
+                params.rn,
+                params.rm,
+                params.ra
+            ),
+            Self::SMLSD { params } => write!(
+                f,
+                "smlsd{} {}, {}, {}, {}",
+                if params.m_swap { "X" } else { "" },
+                params.rd,
+                params.rn,
+                params.rm,
+                params.ra
+            ),
+            Self::SMLALxy { params } => write!(
+                f,
+                "smlal{}{} {}, {}, {}, {}",
+                if params.n_high { "T" } else { "B" },
+                if params.m_high { "T" } else { "B" },
+                params.rdlo,
+                params.rdhi,
+                params.rn,
+                params.rm
+            ),
+            Self::SMLALD { params } => write!(
+                f,
+                "smlald{} {}, {}, {}, {}",
+                if params.m_swap { "X" } else { "" },
+                params.rdlo,
+                params.rdhi,
+                params.rn,
+                params.rm
+            ),
+            Self::SMLSLD { params } => write!(
+                f,
+                "smlsld{} {}, {}, {}, {}",
+                if params.m_swap { "X" } else { "" },
+                params.rdlo,
+                params.rdhi,
+                params.rn,
+                params.rm
+            ),
+            Self::SMUAD { params } => write!(
+                f,
+                "smuad{} {}, {}, {}",
+                if params.m_swap { "X" } else { "" },
+                params.rd,
+                params.rn,
+                params.rm
+            ),
+            Self::SMUSD { params } => write!(
+                f,
+                "smusd{} {}, {}, {}",
+                if params.m_swap { "X" } else { "" },
+                params.rd,
+                params.rn,
+                params.rm
+            ),
+            Self::SMLAW { params } => write!(
+                f,
+                "smlaw{} {}, {}, {}, {}",
+                if params.m_high { "T" } else { "B" },
+                params.rd,
+                params.rn,
+                params.rm,
+                params.ra
+            ),
+            Self::SMULW { params } => write!(
+                f,
+                "smulw{} {}, {}, {}",
+                if params.m_high { "T" } else { "B" },
+                params.rd,
+                params.rn,
+                params.rm
+            ),
+            Self::SMMLA { params } => write!(
+                f,
+                "smmla{} {}, {}, {}, {}",
+                if params.round { "R" } else { "" },
+                params.rd,
+                params.rn,
+                params.rm,
+                params.ra
+            ),
+            Self::SMMLS { params } => write!(
+                f,
+                "smmls{} {}, {}, {}, {}",
+                if params.round { "R" } else { "" },
+                params.rd,
+                params.rn,
+                params.rm,
+                params.ra
+            ),
+            Self::SMMUL { params } => write!(
+                f,
+                "smmul{} {}, {}, {}",
+                if params.round { "R" } else { "" },
+                params.rd,
+                params.rn,
+                params.rm
+            ),
             Self::MOV_reg { params, thumb32 } => write!(
                 f,
                 "mov{}{} {}, {}",
@@ -2281,6 +2983,18 @@ impl fmt::Display for Instruction {
             Self::STREXH { params } => {
                 write!(f, "strexh {}, {}, {} ", params.rd, params.rt, params.rn)
             }
+            Self::STL { params } => write!(f, "stl {}, [{}]", params.rt, params.rn),
+            Self::STLB { params } => write!(f, "stlb {}, [{}]", params.rt, params.rn),
+            Self::STLH { params } => write!(f, "stlh {}, [{}]", params.rt, params.rn),
+            Self::STLEX { params } => {
+                write!(f, "stlex {}, {}, [{}]", params.rd, params.rt, params.rn)
+            }
+            Self::STLEXB { params } => {
+                write!(f, "stlexb {}, {}, [{}]", params.rd, params.rt, params.rn)
+            }
+            Self::STLEXH { params } => {
+                write!(f, "stlexh {}, {}, [{}]", params.rd, params.rt, params.rn)
+            }
 
             Self::STRD_imm { params } => format_adressing_mode2("strd", f, params, true),
             Self::LDRD_imm { params } => format_adressing_mode2("ldrd", f, params, true),
@@ -2338,6 +3052,15 @@ impl fmt::Display for Instruction {
                 }
             ),
             Self::SVC { imm32 } => write!(f, "svc #{imm32}"),
+            Self::SG => write!(f, "sg"),
+            Self::TT { params } => write!(
+                f,
+                "tt{}{} {}, {}",
+                if params.a { "a" } else { "" },
+                if params.t { "t" } else { "" },
+                params.rd,
+                params.rn
+            ),
             Self::SXTH { params, thumb32 } => write!(
                 f,
                 "sxth{} {}, {}{}",
@@ -2390,9 +3113,164 @@ impl fmt::Display for Instruction {
                 imm32, ref opcode, ..
             } => write!(f, "udf {imm32} (opcode = {opcode})"),
 
+            Self::SADD16 { params } => {
+                write!(f, "sadd16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::QADD16 { params } => {
+                write!(f, "qadd16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SHADD16 { params } => {
+                write!(f, "shadd16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UADD16 { params } => {
+                write!(f, "uadd16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UQADD16 { params } => {
+                write!(f, "uqadd16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UHADD16 { params } => {
+                write!(f, "uhadd16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SASX { params } => {
+                write!(f, "sasx {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::QASX { params } => {
+                write!(f, "qasx {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SHASX { params } => {
+                write!(f, "shasx {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UASX { params } => {
+                write!(f, "uasx {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UQASX { params } => {
+                write!(f, "uqasx {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UHASX { params } => {
+                write!(f, "uhasx {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SSAX { params } => {
+                write!(f, "ssax {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::QSAX { params } => {
+                write!(f, "qsax {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SHSAX { params } => {
+                write!(f, "shsax {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::USAX { params } => {
+                write!(f, "usax {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UQSAX { params } => {
+                write!(f, "uqsax {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UHSAX { params } => {
+                write!(f, "uhsax {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SSUB16 { params } => {
+                write!(f, "ssub16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::QSUB16 { params } => {
+                write!(f, "qsub16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SHSUB16 { params } => {
+                write!(f, "shsub16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::USUB16 { params } => {
+                write!(f, "usub16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UQSUB16 { params } => {
+                write!(f, "uqsub16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UHSUB16 { params } => {
+                write!(f, "uhsub16 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SADD8 { params } => {
+                write!(f, "sadd8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::QADD8 { params } => {
+                write!(f, "qadd8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SHADD8 { params } => {
+                write!(f, "shadd8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
             Self::UADD8 { params } => {
                 write!(f, "uadd8 {}, {}, {}", params.rd, params.rn, params.rm)
             }
+            Self::UQADD8 { params } => {
+                write!(f, "uqadd8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UHADD8 { params } => {
+                write!(f, "uhadd8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SSUB8 { params } => {
+                write!(f, "ssub8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::QSUB8 { params } => {
+                write!(f, "qsub8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::SHSUB8 { params } => {
+                write!(f, "shsub8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::USUB8 { params } => {
+                write!(f, "usub8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UQSUB8 { params } => {
+                write!(f, "uqsub8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::UHSUB8 { params } => {
+                write!(f, "uhsub8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::QADD { params } => write!(f, "qadd {}, {}, {}", params.rd, params.rm, params.rn),
+            Self::QSUB { params } => write!(f, "qsub {}, {}, {}", params.rd, params.rm, params.rn),
+            Self::QDADD { params } => {
+                write!(f, "qdadd {}, {}, {}", params.rd, params.rm, params.rn)
+            }
+            Self::QDSUB { params } => {
+                write!(f, "qdsub {}, {}, {}", params.rd, params.rm, params.rn)
+            }
+            Self::SSAT { params } => write!(
+                f,
+                "ssat {}, #{}, {}{}",
+                params.rd,
+                params.saturate_to,
+                params.rn,
+                if params.shift_n > 0 {
+                    format!(", {:?} #{}", params.shift_t, params.shift_n)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::USAT { params } => write!(
+                f,
+                "usat {}, #{}, {}{}",
+                params.rd,
+                params.saturate_to,
+                params.rn,
+                if params.shift_n > 0 {
+                    format!(", {:?} #{}", params.shift_t, params.shift_n)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::SSAT16 { params } => write!(
+                f,
+                "ssat16 {}, #{}, {}",
+                params.rd, params.saturate_to, params.rn
+            ),
+            Self::USAT16 { params } => write!(
+                f,
+                "usat16 {}, #{}, {}",
+                params.rd, params.saturate_to, params.rn
+            ),
+            Self::USAD8 { params } => {
+                write!(f, "usad8 {}, {}, {}", params.rd, params.rn, params.rm)
+            }
+            Self::USADA8 { params } => write!(
+                f,
+                "usada8 {}, {}, {}, {}",
+                params.rd, params.rn, params.rm, params.ra
+            ),
             Self::SEL { params } => write!(f, "sel {}, {}, {}", params.rd, params.rn, params.rm),
             // ARMv7-M
             Self::UDIV { params } => write!(f, "udiv {}, {}, {}", params.rd, params.rn, params.rm),
@@ -2414,6 +3292,11 @@ impl fmt::Display for Instruction {
                 "smull {}, {}, {}, {}",
                 params.rdlo, params.rdhi, params.rn, params.rm
             ),
+            Self::UMAAL { params } => write!(
+                f,
+                "umaal {}, {}, {}, {}",
+                params.rdlo, params.rdhi, params.rn, params.rm
+            ),
             // ARMv7-M
             Self::MLA { params } => write!(
                 f,
@@ -2456,6 +3339,105 @@ impl fmt::Display for Instruction {
                     String::new()
                 }
             ),
+            Self::UXTAB16 { params } => write!(
+                f,
+                "uxtab16 {}, {}, {}{}",
+                params.rd,
+                params.rn,
+                params.rm,
+                if params.rotation > 0 {
+                    format!(", ROR #{}", params.rotation)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::SXTAB16 { params } => write!(
+                f,
+                "sxtab16 {}, {}, {}{}",
+                params.rd,
+                params.rn,
+                params.rm,
+                if params.rotation > 0 {
+                    format!(", ROR #{}", params.rotation)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::SXTAB { params } => write!(
+                f,
+                "sxtab {}, {}, {}{}",
+                params.rd,
+                params.rn,
+                params.rm,
+                if params.rotation > 0 {
+                    format!(", ROR #{}", params.rotation)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::SXTAH { params } => write!(
+                f,
+                "sxtah {}, {}, {}{}",
+                params.rd,
+                params.rn,
+                params.rm,
+                if params.rotation > 0 {
+                    format!(", ROR #{}", params.rotation)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::UXTAH { params } => write!(
+                f,
+                "uxtah {}, {}, {}{}",
+                params.rd,
+                params.rn,
+                params.rm,
+                if params.rotation > 0 {
+                    format!(", ROR #{}", params.rotation)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::UXTB16 { params } => write!(
+                f,
+                "uxtb16 {}, {}{}",
+                params.rd,
+                params.rm,
+                if params.rotation > 0 {
+                    format!(", ROR #{}", params.rotation)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::SXTB16 { params } => write!(
+                f,
+                "sxtb16 {}, {}{}",
+                params.rd,
+                params.rm,
+                if params.rotation > 0 {
+                    format!(", ROR #{}", params.rotation)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::PKHBT { params } => write!(
+                f,
+                "pkhbt {}, {}, {}{}",
+                params.rd,
+                params.rn,
+                params.rm,
+                if params.shift_n > 0 {
+                    format!(", LSL #{}", params.shift_n)
+                } else {
+                    String::new()
+                }
+            ),
+            Self::PKHTB { params } => write!(
+                f,
+                "pkhtb {}, {}, {}, ASR #{}",
+                params.rd, params.rn, params.rm, params.shift_n
+            ),
             Self::UXTH { params, thumb32 } => write!(
                 f,
                 "uxth{} {}, {}{}",
@@ -2582,6 +3564,7 @@ impl fmt::Display for Instruction {
             Self::VABS_f32 { params } => write!(f, "vabs.f32 {}, {}", params.sd, params.sm),
             Self::VABS_f64 { params } => write!(f, "vabs.f64 {}, {}", params.dd, params.dm),
             Self::VMRS { rt } => write!(f, "vmrs {}, fpscr", rt),
+            Self::VMSR { rt } => write!(f, "vmsr fpscr, {}", rt),
             Self::VCMP_f32 { params } => write!(
                 f,
                 "vcmp.f32 {}, {}",
@@ -2616,6 +3599,130 @@ impl fmt::Display for Instruction {
                 write!(f, "vsub.f64 {}, {}, {}", params.dd, params.dn, params.dm,)
             }
             Self::VCVT { params } => write!(f, "{}", fmt_vcvt(params)),
+            Self::VCVT_directed { params } => write!(
+                f,
+                "vcvt{}.{}.{} {}, {}",
+                fmt_vrint_mode(params.mode),
+                if params.unsigned { "u32" } else { "s32" },
+                match params.m {
+                    ExtensionReg::Single { .. } => "f32",
+                    ExtensionReg::Double { .. } => "f64",
+                },
+                params.d,
+                params.m
+            ),
+            Self::VCVT_f32_f64 { params } => {
+                write!(f, "vcvt.f32.f64 {}, {}", params.sd, params.dm)
+            }
+            Self::VCVT_f64_f32 { params } => {
+                write!(f, "vcvt.f64.f32 {}, {}", params.dd, params.sm)
+            }
+            Self::VDIV_f32 { params } => {
+                write!(f, "vdiv.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VDIV_f64 { params } => {
+                write!(f, "vdiv.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VFMA_f32 { params } => {
+                write!(f, "vfma.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VFMA_f64 { params } => {
+                write!(f, "vfma.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VFMS_f32 { params } => {
+                write!(f, "vfms.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VFMS_f64 { params } => {
+                write!(f, "vfms.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VFNMA_f32 { params } => {
+                write!(f, "vfnma.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VFNMA_f64 { params } => {
+                write!(f, "vfnma.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VFNMS_f32 { params } => {
+                write!(f, "vfnms.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VFNMS_f64 { params } => {
+                write!(f, "vfnms.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VMAXNM_f32 { params } => {
+                write!(f, "vmaxnm.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VMAXNM_f64 { params } => {
+                write!(f, "vmaxnm.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VMINNM_f32 { params } => {
+                write!(f, "vminnm.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VMINNM_f64 { params } => {
+                write!(f, "vminnm.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VMLA_f32 { params } => {
+                write!(f, "vmla.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VMLA_f64 { params } => {
+                write!(f, "vmla.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VMLS_f32 { params } => {
+                write!(f, "vmls.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VMLS_f64 { params } => {
+                write!(f, "vmls.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VMUL_f32 { params } => {
+                write!(f, "vmul.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VMUL_f64 { params } => {
+                write!(f, "vmul.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VNMLA_f32 { params } => {
+                write!(f, "vnmla.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VNMLA_f64 { params } => {
+                write!(f, "vnmla.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VNMLS_f32 { params } => {
+                write!(f, "vnmls.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VNMLS_f64 { params } => {
+                write!(f, "vnmls.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VNMUL_f32 { params } => {
+                write!(f, "vnmul.f32 {}, {}, {}", params.sd, params.sn, params.sm)
+            }
+            Self::VNMUL_f64 { params } => {
+                write!(f, "vnmul.f64 {}, {}, {}", params.dd, params.dn, params.dm)
+            }
+            Self::VNEG_f32 { params } => write!(f, "vneg.f32 {}, {}", params.sd, params.sm),
+            Self::VNEG_f64 { params } => write!(f, "vneg.f64 {}, {}", params.dd, params.dm),
+            Self::VSQRT_f32 { params } => write!(f, "vsqrt.f32 {}, {}", params.sd, params.sm),
+            Self::VSQRT_f64 { params } => write!(f, "vsqrt.f64 {}, {}", params.dd, params.dm),
+            Self::VRINT_f32 { params } => write!(
+                f,
+                "vrint{}.f32 {}, {}",
+                fmt_vrint_mode(params.mode),
+                params.sd,
+                params.sm
+            ),
+            Self::VRINT_f64 { params } => write!(
+                f,
+                "vrint{}.f64 {}, {}",
+                fmt_vrint_mode(params.mode),
+                params.dd,
+                params.dm
+            ),
+            Self::VSEL_f32 { params } => write!(
+                f,
+                "vsel{}.f32 {}, {}, {}",
+                params.cond, params.sd, params.sn, params.sm
+            ),
+            Self::VSEL_f64 { params } => write!(
+                f,
+                "vsel{}.f64 {}, {}, {}",
+                params.cond, params.dd, params.dn, params.dm
+            ),
 
             Self::WFE { .. } => write!(f, "wfe"),
             Self::WFI { .. } => write!(f, "wfi"),
@@ -2695,12 +3802,14 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::BKPT { .. } => 2,
         Instruction::BL { .. } => 4,
         Instruction::BLX { .. } => 2,
+        Instruction::BLXNS { .. } => 2,
         Instruction::BX { .. } => 2,
+        Instruction::BXNS { .. } => 2,
 
         Instruction::CBZ { .. } => 2,
         Instruction::CBNZ { .. } => 2,
         //CDP
-        //CLREX
+        Instruction::CLREX => 4,
         Instruction::CLZ { .. } => 4,
         Instruction::CMN_imm { .. } => 4,
         Instruction::CMN_reg { thumb32, .. } => isize_t(*thumb32),
@@ -2717,6 +3826,12 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::ISB { .. } => 4,
         Instruction::IT { .. } => 2,
 
+        Instruction::LDA { .. } => 4,
+        Instruction::LDAB { .. } => 4,
+        Instruction::LDAEX { .. } => 4,
+        Instruction::LDAEXB { .. } => 4,
+        Instruction::LDAEXH { .. } => 4,
+        Instruction::LDAH { .. } => 4,
         Instruction::LDC_imm { .. } => 4,
         Instruction::LDC2_imm { .. } => 4,
         Instruction::LDM { thumb32, .. } => isize_t(*thumb32),
@@ -2771,7 +3886,8 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::ORR_imm { .. } => 4,
         Instruction::ORR_reg { thumb32, .. } => isize_t(*thumb32),
 
-        //PKHBT, PKHTB
+        Instruction::PKHBT { .. } => 4,
+        Instruction::PKHTB { .. } => 4,
         Instruction::PLD_imm { .. } => 4,
         Instruction::PLD_lit { .. } => 4,
         Instruction::PLD_reg { .. } => 4,
@@ -2780,16 +3896,16 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::POP { thumb32, .. } => isize_t(*thumb32),
         Instruction::PUSH { thumb32, .. } => isize_t(*thumb32),
 
-        //QADD16
-        //QADD8
-        //QASX
-        //QSAX
-        //QADD
-        //QSUB
-        //QDADD
-        //QDSUB
-        //QSUB16
-        //QSUB8
+        Instruction::QADD16 { .. } => 4,
+        Instruction::QADD8 { .. } => 4,
+        Instruction::QASX { .. } => 4,
+        Instruction::QSAX { .. } => 4,
+        Instruction::QADD { .. } => 4,
+        Instruction::QSUB { .. } => 4,
+        Instruction::QDADD { .. } => 4,
+        Instruction::QDSUB { .. } => 4,
+        Instruction::QSUB16 { .. } => 4,
+        Instruction::QSUB8 { .. } => 4,
 
         //RBIT
         Instruction::REV { thumb32, .. } => isize_t(*thumb32),
@@ -2800,43 +3916,50 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::RRX { .. } => 4,
         Instruction::RSB_imm { thumb32, .. } => isize_t(*thumb32),
         Instruction::RSB_reg { thumb32, .. } => 4,
-        //SADD16
-        //SADD8
-        //SASX
+        Instruction::SADD16 { .. } => 4,
+        Instruction::SADD8 { .. } => 4,
+        Instruction::SASX { .. } => 4,
         Instruction::SBC_imm { .. } => 4,
         Instruction::SBC_reg { thumb32, .. } => isize_t(*thumb32),
         Instruction::SBFX { .. } => 4,
         Instruction::SDIV { .. } => 4,
         Instruction::SEL { .. } => 4,
         Instruction::SEV { thumb32, .. } => isize_t(*thumb32),
-        //SHADD16
-        //SHADD8
-        //SHASX
-        //SHSAX
-        //SHSUB16
-        //SHSUB8
+        Instruction::SHADD16 { .. } => 4,
+        Instruction::SHADD8 { .. } => 4,
+        Instruction::SHASX { .. } => 4,
+        Instruction::SHSAX { .. } => 4,
+        Instruction::SHSUB16 { .. } => 4,
+        Instruction::SHSUB8 { .. } => 4,
         Instruction::SMLA { .. } => 4,
-        //SMLAD
+        Instruction::SMLAD { .. } => 4,
         Instruction::SMLAL { .. } => 4,
-        //SMLAL second variant?
-        //SMLALD
-        //SMLAW
-        //SMLSD
-        //SMLSLD
-        //SMMLA
-        //SMMLS
-        //SMMUL
-        //SMUAD
+        Instruction::SMLALxy { .. } => 4,
+        Instruction::SMLALD { .. } => 4,
+        Instruction::SMLAW { .. } => 4,
+        Instruction::SMLSD { .. } => 4,
+        Instruction::SMLSLD { .. } => 4,
+        Instruction::SMMLA { .. } => 4,
+        Instruction::SMMLS { .. } => 4,
+        Instruction::SMMUL { .. } => 4,
+        Instruction::SMUAD { .. } => 4,
         Instruction::SMUL { .. } => 4,
         Instruction::SMULL { .. } => 4,
-        //SMULW
-        //SMUSD
-        //SSAT
-        //SSAT16
-        //SSAX
-        //SSUB16
-        //SSUB8
+        Instruction::SMULW { .. } => 4,
+        Instruction::SMUSD { .. } => 4,
+        Instruction::SSAT { .. } => 4,
+        Instruction::SSAT16 { .. } => 4,
+        Instruction::SSAX { .. } => 4,
+        Instruction::SSUB16 { .. } => 4,
+        Instruction::SSUB8 { .. } => 4,
+        Instruction::SG => 4,
         //STC, STC2
+        Instruction::STL { .. } => 4,
+        Instruction::STLB { .. } => 4,
+        Instruction::STLEX { .. } => 4,
+        Instruction::STLEXB { .. } => 4,
+        Instruction::STLEXH { .. } => 4,
+        Instruction::STLH { .. } => 4,
         Instruction::STM { thumb32, .. } => isize_t(*thumb32),
         Instruction::STMDB { .. } => 4,
         Instruction::STR_imm { thumb32, .. } => isize_t(*thumb32),
@@ -2855,14 +3978,15 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::SUB_imm { thumb32, .. } => isize_t(*thumb32),
         Instruction::SUB_reg { thumb32, .. } => isize_t(*thumb32),
         Instruction::SVC { .. } => 2,
-        //SXTAB
-        //SXTAB16
-        //SXTAH
+        Instruction::SXTAB { .. } => 4,
+        Instruction::SXTAB16 { .. } => 4,
+        Instruction::SXTAH { .. } => 4,
         Instruction::SXTB { thumb32, .. } => isize_t(*thumb32),
-        //SXTB16
+        Instruction::SXTB16 { .. } => 4,
         Instruction::SXTH { thumb32, .. } => isize_t(*thumb32),
 
         Instruction::TBB { .. } => 4,
+        Instruction::TT { .. } => 4,
         Instruction::TBH { .. } => 4,
         Instruction::TEQ_imm { .. } => 4,
         Instruction::TEQ_reg { .. } => 4,
@@ -2870,37 +3994,38 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::TST_reg { thumb32, .. } => isize_t(*thumb32),
 
         Instruction::UADD8 { .. } => 4,
-        //UADD16
-        //UASX
+        Instruction::UADD16 { .. } => 4,
+        Instruction::UASX { .. } => 4,
         Instruction::UBFX { .. } => 4,
         Instruction::UDF { thumb32, .. } => isize_t(*thumb32),
         Instruction::UDIV { .. } => 4,
-        //UHADD16
-        //UHADD8
-        //UHASX
-        //UHSAX
-        //UHSUB16
-        //UHSUB8
-        //UMAAL
+        Instruction::UHADD16 { .. } => 4,
+        Instruction::UHADD8 { .. } => 4,
+        Instruction::UHASX { .. } => 4,
+        Instruction::UHSAX { .. } => 4,
+        Instruction::UHSUB16 { .. } => 4,
+        Instruction::UHSUB8 { .. } => 4,
+        Instruction::UMAAL { .. } => 4,
         Instruction::UMLAL { .. } => 4,
         Instruction::UMULL { .. } => 4,
-        //UQADD16
-        //UQADD8
-        //UQASX
-        //UQSAX
-        //UQSUB16
-        //UQSUB8
-        //USAD8
-        //USADA8
-        //USAT
-        //USAT16
-        //USAX
-        //USUB16
-        //USUB8
+        Instruction::UQADD16 { .. } => 4,
+        Instruction::UQADD8 { .. } => 4,
+        Instruction::UQASX { .. } => 4,
+        Instruction::UQSAX { .. } => 4,
+        Instruction::UQSUB16 { .. } => 4,
+        Instruction::UQSUB8 { .. } => 4,
+        Instruction::USAD8 { .. } => 4,
+        Instruction::USADA8 { .. } => 4,
+        Instruction::USAT { .. } => 4,
+        Instruction::USAT16 { .. } => 4,
+        Instruction::USAX { .. } => 4,
+        Instruction::USUB16 { .. } => 4,
+        Instruction::USUB8 { .. } => 4,
         Instruction::UXTAB { .. } => 4,
-        //UXTAB16
-        //UXTAH
+        Instruction::UXTAB16 { .. } => 4,
+        Instruction::UXTAH { .. } => 4,
         Instruction::UXTB { thumb32, .. } => isize_t(*thumb32),
+        Instruction::UXTB16 { .. } => 4,
         Instruction::UXTH { thumb32, .. } => isize_t(*thumb32),
 
         Instruction::VABS_f32 { .. } => 4,
@@ -2913,18 +4038,30 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::VCMP_f64 { .. } => 4,
         //VCVTX
         Instruction::VCVT { .. } => 4,
+        Instruction::VCVT_directed { .. } => 4,
+        Instruction::VCVT_f32_f64 { .. } => 4,
+        Instruction::VCVT_f64_f32 { .. } => 4,
         //VCVTB
         //VCVTT
-        //VDIV
-        //VFMA
-        //VFMS
-        //VFNMA
-        //VFNMS
+        Instruction::VDIV_f32 { .. } => 4,
+        Instruction::VDIV_f64 { .. } => 4,
+        Instruction::VFMA_f32 { .. } => 4,
+        Instruction::VFMA_f64 { .. } => 4,
+        Instruction::VFMS_f32 { .. } => 4,
+        Instruction::VFMS_f64 { .. } => 4,
+        Instruction::VFNMA_f32 { .. } => 4,
+        Instruction::VFNMA_f64 { .. } => 4,
+        Instruction::VFNMS_f32 { .. } => 4,
+        Instruction::VFNMS_f64 { .. } => 4,
         //VLDM
-        //VMAXNM
-        //VMINNM
-        //VMLA
-        //VMLS
+        Instruction::VMAXNM_f32 { .. } => 4,
+        Instruction::VMAXNM_f64 { .. } => 4,
+        Instruction::VMINNM_f32 { .. } => 4,
+        Instruction::VMINNM_f64 { .. } => 4,
+        Instruction::VMLA_f32 { .. } => 4,
+        Instruction::VMLA_f64 { .. } => 4,
+        Instruction::VMLS_f32 { .. } => 4,
+        Instruction::VMLS_f64 { .. } => 4,
         Instruction::VMOV_imm_32 { .. } => 4,
         Instruction::VMOV_imm_64 { .. } => 4,
         Instruction::VMOV_reg_f32 { .. } => 4,
@@ -2936,17 +4073,25 @@ pub fn instruction_size(instruction: &Instruction) -> usize {
         Instruction::VMOV_cr2_dp { .. } => 4,
 
         Instruction::VMRS { .. } => 4,
-        //VMSR
-        //VMUL
-        //VNEG
-        //VNMLA,VNMLS, VNMUL
+        Instruction::VMSR { .. } => 4,
+        Instruction::VMUL_f32 { .. } => 4,
+        Instruction::VMUL_f64 { .. } => 4,
+        Instruction::VNEG_f32 { .. } => 4,
+        Instruction::VNEG_f64 { .. } => 4,
+        Instruction::VNMLA_f32 { .. } => 4,
+        Instruction::VNMLA_f64 { .. } => 4,
+        Instruction::VNMLS_f32 { .. } => 4,
+        Instruction::VNMLS_f64 { .. } => 4,
+        Instruction::VNMUL_f32 { .. } => 4,
+        Instruction::VNMUL_f64 { .. } => 4,
         Instruction::VPUSH { .. } => 4,
         Instruction::VPOP { .. } => 4,
-        //VRINTA, VRINTN, VRINTP, VRiNTM
-        //VRINTX,
-        //VRINTZ, VRINTR
-        //VSEL
-        //VSQRT
+        Instruction::VRINT_f32 { .. } => 4,
+        Instruction::VRINT_f64 { .. } => 4,
+        Instruction::VSEL_f32 { .. } => 4,
+        Instruction::VSEL_f64 { .. } => 4,
+        Instruction::VSQRT_f32 { .. } => 4,
+        Instruction::VSQRT_f64 { .. } => 4,
         Instruction::VSTM_T1 { .. } => 4,
         Instruction::VSTM_T2 { .. } => 4,
         //VSTR
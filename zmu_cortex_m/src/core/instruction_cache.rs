@@ -0,0 +1,106 @@
+//!
+//! Cache of decoded instructions
+//!
+
+use crate::core::instruction::Instruction;
+
+/// Halfwords per cache page
+const PAGE_SIZE: usize = 1024;
+
+/// Decoded instruction and its size in bytes
+pub type CachedInstruction = (Instruction, usize);
+
+type Page = Box<[Option<CachedInstruction>]>;
+
+#[derive(Default)]
+///
+/// Decoded instructions of the boot flash, indexed by the halfword address.
+/// Pages are allocated and instructions decoded on their first execution,
+/// so that the memory use follows the executed code instead of the image size.
+///
+pub struct InstructionCache {
+    pages: Vec<Option<Page>>,
+}
+
+impl InstructionCache {
+    ///
+    /// Empty cache for the code from address 0 to `size` bytes
+    ///
+    pub fn new(size: usize) -> Self {
+        Self {
+            pages: vec![None; size.div_ceil(2 * PAGE_SIZE)],
+        }
+    }
+
+    ///
+    /// Cached instruction at the address
+    ///
+    #[inline(always)]
+    pub fn get(&self, address: u32) -> Option<&CachedInstruction> {
+        let index = (address >> 1) as usize;
+        self.pages.get(index / PAGE_SIZE)?.as_ref()?[index % PAGE_SIZE].as_ref()
+    }
+
+    ///
+    /// Store a decoded instruction, ignored outside of the cached code
+    ///
+    pub fn insert(&mut self, address: u32, instruction: CachedInstruction) {
+        let index = (address >> 1) as usize;
+        if let Some(page) = self.pages.get_mut(index / PAGE_SIZE) {
+            let page = page.get_or_insert_with(|| vec![None; PAGE_SIZE].into_boxed_slice());
+            page[index % PAGE_SIZE] = Some(instruction);
+        }
+    }
+
+    ///
+    /// Drop the instructions overlapping a write of `size` bytes to the address,
+    /// they are decoded again on their next execution
+    ///
+    pub fn invalidate(&mut self, address: u32, size: u32) {
+        if size == 0 {
+            return;
+        }
+        // a thumb32 instruction at the previous halfword contains the first written byte
+        let first = (address >> 1).saturating_sub(1) as usize;
+        let last = (address.saturating_add(size - 1) >> 1) as usize;
+        for index in first..=last {
+            if let Some(Some(page)) = self.pages.get_mut(index / PAGE_SIZE) {
+                page[index % PAGE_SIZE] = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_invalidate() {
+        // Arrange
+        let mut cache = InstructionCache::new(0x1_0000);
+        let thumb32 = (Instruction::DMB, 4);
+
+        // Act
+        cache.insert(0x0000_0800, thumb32);
+        cache.insert(0x0002_0000, thumb32);
+
+        // Assert: pages are allocated on demand
+        assert_eq!(cache.get(0x0000_0800), Some(&thumb32));
+        assert_eq!(cache.get(0x0000_0802), None);
+        assert_eq!(cache.get(0x0002_0000), None);
+        assert_eq!(cache.pages.iter().filter(|page| page.is_some()).count(), 1);
+
+        // Act: empty write
+        cache.invalidate(0x0000_0802, 0);
+
+        // Assert
+        assert_eq!(cache.get(0x0000_0800), Some(&thumb32));
+
+        // Act: write to the second halfword of the instruction
+        cache.invalidate(0x0000_0803, 1);
+
+        // Assert
+        assert_eq!(cache.get(0x0000_0800), None);
+    }
+}
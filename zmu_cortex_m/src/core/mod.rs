@@ -3,15 +3,19 @@
 //!
 
 pub mod bits;
+pub mod block_cache;
 pub mod condition;
 pub mod exception;
 pub mod fault;
 pub mod fetch;
 pub mod instruction;
+pub mod instruction_cache;
 pub mod monitor;
 pub mod operation;
+pub mod profile;
 pub mod register;
 pub mod reset;
+pub mod security;
 pub mod thumb;
 
 // FP extension registers
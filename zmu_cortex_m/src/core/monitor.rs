@@ -21,12 +21,61 @@ pub trait Monitor {
     /// sets a monitor for load exclusive operation
     ///
     fn set_exclusive_monitors(&mut self, address: u32, size: usize);
+
+    ///
+    /// clears the local monitor, the next store exclusive fails (CLREX,
+    /// exception entry and return)
+    ///
+    fn clear_exclusive_local(&mut self);
 }
 
 impl Monitor for Processor {
-    fn exclusive_monitors_pass(&mut self, _address: u32, _size: usize) -> bool {
-        true
+    fn exclusive_monitors_pass(&mut self, address: u32, size: usize) -> bool {
+        // store exclusive always returns the monitor to the open access state
+        self.exclusive_monitor.take() == Some((address, size))
+    }
+
+    fn set_exclusive_monitors(&mut self, address: u32, size: usize) {
+        self.exclusive_monitor = Some((address, size));
     }
 
-    fn set_exclusive_monitors(&mut self, _address: u32, _size: usize) {}
+    fn clear_exclusive_local(&mut self) {
+        self.exclusive_monitor = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_exclusive_without_load_exclusive() {
+        // Arrange
+        let mut processor = Processor::new();
+
+        // Assert
+        assert!(!processor.exclusive_monitors_pass(0x2000_0000, 4));
+    }
+
+    #[test]
+    fn test_monitor_tracks_address_and_size() {
+        // Arrange
+        let mut processor = Processor::new();
+
+        // Act + Assert
+        processor.set_exclusive_monitors(0x2000_0000, 4);
+        assert!(processor.exclusive_monitors_pass(0x2000_0000, 4));
+        // a store exclusive clears the reservation
+        assert!(!processor.exclusive_monitors_pass(0x2000_0000, 4));
+
+        processor.set_exclusive_monitors(0x2000_0000, 4);
+        assert!(!processor.exclusive_monitors_pass(0x2000_0004, 4));
+
+        processor.set_exclusive_monitors(0x2000_0000, 4);
+        assert!(!processor.exclusive_monitors_pass(0x2000_0000, 1));
+
+        processor.set_exclusive_monitors(0x2000_0000, 2);
+        processor.clear_exclusive_local();
+        assert!(!processor.exclusive_monitors_pass(0x2000_0000, 2));
+    }
 }
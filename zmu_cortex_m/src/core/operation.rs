@@ -86,16 +86,47 @@ pub fn sign_extend(word: u32, topbit: usize, size: usize) -> u64 {
 ///
 pub fn add_with_carry(x: u32, y: u32, carry_in: bool) -> (u32, bool, bool) {
     let unsigned_sum = u64::from(x) + u64::from(y) + u64::from(carry_in);
-    let signed_sum = (x as i32)
-        .wrapping_add(y as i32)
-        .wrapping_add(i32::from(carry_in));
+    let signed_sum = i64::from(x as i32) + i64::from(y as i32) + i64::from(carry_in);
     let result = (unsigned_sum & 0xffff_ffff) as u32; // same value as signed_sum<N-1:0>
     let carry_out = u64::from(result) != unsigned_sum;
-    let overflow = (result as i32) != signed_sum;
+    let overflow = i64::from(result as i32) != signed_sum;
 
     (result, carry_out, overflow)
 }
 
+///
+/// Saturate a signed value to a range of n bits
+///
+/// return tuple of (result, saturated)
+///
+pub fn signed_sat_q(i: i64, n: usize) -> (i64, bool) {
+    let max = (1_i64 << (n - 1)) - 1;
+    let min = -(1_i64 << (n - 1));
+    if i > max {
+        (max, true)
+    } else if i < min {
+        (min, true)
+    } else {
+        (i, false)
+    }
+}
+
+///
+/// Saturate a signed value to a range of n bit unsigned value
+///
+/// return tuple of (result, saturated)
+///
+pub fn unsigned_sat_q(i: i64, n: usize) -> (i64, bool) {
+    let max = (1_i64 << n) - 1;
+    if i > max {
+        (max, true)
+    } else if i < 0 {
+        (0, true)
+    } else {
+        (i, false)
+    }
+}
+
 ///
 /// This function performs the condition test for an instruction, based on:
 /// • the two Thumb conditional branch encodings, encodings T1 and T3 of the B instruction
@@ -465,6 +496,31 @@ mod tests {
         assert!(carry);
         assert!(!overflow);
     }
+
+    #[test]
+    fn test_add_with_carry_overflow() {
+        let (result, carry, overflow) = add_with_carry(0x7fff_ffff, 0, true);
+        assert_eq!(result, 0x8000_0000);
+        assert!(!carry);
+        assert!(overflow);
+
+        // 0x8000_0000 - 1
+        let (result, carry, overflow) = add_with_carry(0x8000_0000, 0xffff_fffe, true);
+        assert_eq!(result, 0x7fff_ffff);
+        assert!(carry);
+        assert!(overflow);
+    }
+    #[test]
+    fn test_sat_q() {
+        assert_eq!(signed_sat_q(0x7fff_ffff + 1, 32), (0x7fff_ffff, true));
+        assert_eq!(signed_sat_q(-129, 8), (-128, true));
+        assert_eq!(signed_sat_q(-128, 8), (-128, false));
+        assert_eq!(unsigned_sat_q(-1, 16), (0, true));
+        assert_eq!(unsigned_sat_q(0x1_0000, 16), (0xffff, true));
+        assert_eq!(unsigned_sat_q(0x100, 0), (0, true));
+        assert_eq!(unsigned_sat_q(0xff, 8), (0xff, false));
+    }
+
     #[test]
     fn test_build_imm_6_11() {
         assert_eq!(build_imm_6_11(0xF000_80C4), 0xc4 << 1);
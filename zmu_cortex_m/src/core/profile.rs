@@ -0,0 +1,323 @@
+//!
+//! Architecture profile of the simulated core
+//!
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Architecture variants of the M-profile
+///
+pub enum Architecture {
+    ///
+    /// Armv6-M baseline: Cortex-M0, M0+ and M1
+    ///
+    ArmV6M,
+    ///
+    /// Armv7-M mainline: Cortex-M3
+    ///
+    ArmV7M,
+    ///
+    /// Armv7-M with the DSP extension: Cortex-M4 and M7
+    ///
+    ArmV7EM,
+    ///
+    /// Armv8-M Baseline with the security extension: Cortex-M23
+    ///
+    ArmV8MBaseline,
+    ///
+    /// Armv8-M Mainline with the DSP and security extensions: Cortex-M33
+    ///
+    ArmV8MMainline,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Floating point extension
+///
+pub enum Fpu {
+    ///
+    /// No floating point unit
+    ///
+    None,
+    ///
+    /// `FPv4-SP`, single precision only
+    ///
+    SinglePrecision,
+    ///
+    /// `FPv5`, single and double precision
+    ///
+    DoublePrecision,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Architecture profile: decides which instructions, registers and
+/// exception features are available at runtime.
+///
+pub struct ArchProfile {
+    ///
+    /// Architecture variant
+    ///
+    pub architecture: Architecture,
+    ///
+    /// Floating point extension
+    ///
+    pub fpu: Fpu,
+}
+
+///
+/// Core names accepted by `ArchProfile::from_core_name`
+///
+pub const CORE_NAMES: [&str; 9] = [
+    "cortex-m0",
+    "cortex-m0+",
+    "cortex-m1",
+    "cortex-m3",
+    "cortex-m4",
+    "cortex-m4f",
+    "cortex-m7",
+    "cortex-m23",
+    "cortex-m33",
+];
+
+impl ArchProfile {
+    ///
+    /// Cortex-M0 profile
+    ///
+    pub const CORTEX_M0: Self = Self::new(Architecture::ArmV6M, Fpu::None);
+    ///
+    /// Cortex-M3 profile
+    ///
+    pub const CORTEX_M3: Self = Self::new(Architecture::ArmV7M, Fpu::None);
+    ///
+    /// Cortex-M4 profile, without FPU
+    ///
+    pub const CORTEX_M4: Self = Self::new(Architecture::ArmV7EM, Fpu::None);
+    ///
+    /// Cortex-M4F profile, single precision FPU
+    ///
+    pub const CORTEX_M4F: Self = Self::new(Architecture::ArmV7EM, Fpu::SinglePrecision);
+    ///
+    /// Cortex-M7 profile, double precision FPU
+    ///
+    pub const CORTEX_M7: Self = Self::new(Architecture::ArmV7EM, Fpu::DoublePrecision);
+    ///
+    /// Cortex-M23 profile, with the security extension
+    ///
+    pub const CORTEX_M23: Self = Self::new(Architecture::ArmV8MBaseline, Fpu::None);
+    ///
+    /// Cortex-M33 profile, with the security extension and a single precision FPU
+    ///
+    pub const CORTEX_M33: Self = Self::new(Architecture::ArmV8MMainline, Fpu::SinglePrecision);
+
+    ///
+    /// Create profile from architecture and FPU
+    ///
+    pub const fn new(architecture: Architecture, fpu: Fpu) -> Self {
+        Self { architecture, fpu }
+    }
+
+    ///
+    /// Profile of a core, by its name, eg. "cortex-m4f"
+    ///
+    pub fn from_core_name(name: &str) -> Option<Self> {
+        match name {
+            "cortex-m0" | "cortex-m0+" | "cortex-m1" => Some(Self::CORTEX_M0),
+            "cortex-m3" => Some(Self::CORTEX_M3),
+            "cortex-m4" => Some(Self::CORTEX_M4),
+            "cortex-m4f" => Some(Self::CORTEX_M4F),
+            "cortex-m7" => Some(Self::CORTEX_M7),
+            "cortex-m23" => Some(Self::CORTEX_M23),
+            "cortex-m33" => Some(Self::CORTEX_M33),
+            _ => None,
+        }
+    }
+
+    ///
+    /// True for Armv7-M and later: the full Thumb-2 instruction set,
+    /// FAULTMASK, BASEPRI, configurable fault handlers and STIR.
+    ///
+    pub fn is_mainline(&self) -> bool {
+        !matches!(
+            self.architecture,
+            Architecture::ArmV6M | Architecture::ArmV8MBaseline
+        )
+    }
+
+    ///
+    /// True for Armv8-M: the stack limit registers and the load-acquire and
+    /// store-release instructions. Baseline also gets MOVW, MOVT, B.W, CBZ,
+    /// CBNZ, hardware divide and the exclusive accesses.
+    ///
+    pub fn is_armv8m(&self) -> bool {
+        matches!(
+            self.architecture,
+            Architecture::ArmV8MBaseline | Architecture::ArmV8MMainline
+        )
+    }
+
+    ///
+    /// True if the security extension (`TrustZone`) is present: Secure and
+    /// Non-secure states, the SAU and the SG, BXNS, BLXNS and TT instructions
+    ///
+    pub fn has_security(&self) -> bool {
+        self.is_armv8m()
+    }
+
+    ///
+    /// True if the DSP extension instructions are available
+    ///
+    pub fn has_dsp(&self) -> bool {
+        matches!(
+            self.architecture,
+            Architecture::ArmV7EM | Architecture::ArmV8MMainline
+        )
+    }
+
+    ///
+    /// True if a floating point unit is present
+    ///
+    pub fn has_fpu(&self) -> bool {
+        self.fpu != Fpu::None
+    }
+
+    ///
+    /// True if the floating point unit supports double precision
+    ///
+    pub fn has_double_precision(&self) -> bool {
+        self.fpu == Fpu::DoublePrecision
+    }
+
+    ///
+    /// True if the `FPv5` additions are available: VSEL, VMAXNM, VMINNM, VRINT
+    /// and VCVT{A,N,P,M}. Cortex-M7 has `FPv5-DP`, the Armv8-M Mainline cores
+    /// have `FPv5-SP` or `FPv5-DP`.
+    ///
+    pub fn has_fpv5(&self) -> bool {
+        match self.architecture {
+            Architecture::ArmV8MMainline => self.has_fpu(),
+            _ => self.fpu == Fpu::DoublePrecision,
+        }
+    }
+
+    ///
+    /// True if the `PMSAv7` Memory Protection Unit is present. The `PMSAv8`
+    /// MPU of the Armv8-M cores is not modelled, its registers are RAZ/WI.
+    ///
+    pub fn has_mpu(&self) -> bool {
+        matches!(
+            self.architecture,
+            Architecture::ArmV7M | Architecture::ArmV7EM
+        )
+    }
+
+    ///
+    /// CPUID value of a representative core of the profile
+    ///
+    pub fn cpuid(&self) -> u32 {
+        match (self.architecture, self.fpu) {
+            (Architecture::ArmV6M, _) => 0x410C_C200,
+            (Architecture::ArmV7M, _) => 0x412F_C231,
+            (Architecture::ArmV7EM, Fpu::DoublePrecision) => 0x411F_C270,
+            (Architecture::ArmV7EM, _) => 0x410F_C241,
+            (Architecture::ArmV8MBaseline, _) => 0x411C_D200,
+            (Architecture::ArmV8MMainline, _) => 0x410F_D210,
+        }
+    }
+
+    ///
+    /// Media and VFP feature registers (MVFR0, MVFR1, MVFR2)
+    ///
+    /// `FPv4-SP` has no MVFR2, `FPv5` reports VRINT, VSEL and VMAXNM in it.
+    ///
+    pub fn mvfr(&self) -> (u32, u32, u32) {
+        match self.fpu {
+            Fpu::None => (0, 0, 0),
+            Fpu::SinglePrecision if self.has_fpv5() => (0x1011_0021, 0x1100_0011, 0x0000_0040),
+            Fpu::SinglePrecision => (0x1011_0021, 0x1100_0011, 0),
+            Fpu::DoublePrecision => (0x1011_0221, 0x1200_0011, 0x0000_0040),
+        }
+    }
+}
+
+impl Default for ArchProfile {
+    ///
+    /// Profile used when no core is selected. Builds for Armv7-M and later
+    /// select Cortex-M7, the superset profile, so that all decoded instructions
+    /// are available.
+    ///
+    fn default() -> Self {
+        if cfg!(feature = "armv6m") {
+            Self::CORTEX_M0
+        } else {
+            Self::CORTEX_M7
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_core_name() {
+        for name in CORE_NAMES {
+            assert!(ArchProfile::from_core_name(name).is_some());
+        }
+        assert_eq!(
+            ArchProfile::from_core_name("cortex-m0+"),
+            Some(ArchProfile::CORTEX_M0)
+        );
+        assert!(!ArchProfile::CORTEX_M0.is_mainline());
+        assert!(ArchProfile::CORTEX_M4.has_dsp());
+        assert!(!ArchProfile::CORTEX_M4.has_fpu());
+        assert!(!ArchProfile::CORTEX_M4F.has_double_precision());
+        assert!(ArchProfile::CORTEX_M7.has_double_precision());
+        assert!(!ArchProfile::CORTEX_M0.has_mpu());
+        assert!(ArchProfile::CORTEX_M3.has_mpu());
+        assert!(!ArchProfile::CORTEX_M23.is_mainline());
+        assert!(ArchProfile::CORTEX_M23.has_security());
+        assert!(!ArchProfile::CORTEX_M7.has_security());
+        assert!(ArchProfile::CORTEX_M33.is_mainline());
+        assert!(ArchProfile::CORTEX_M33.has_dsp());
+        assert!(!ArchProfile::CORTEX_M33.has_mpu());
+        assert_eq!(ArchProfile::from_core_name("cortex-m55"), None);
+    }
+
+    #[test]
+    fn test_default() {
+        // all decoded instructions are available without a selected core
+        let expected = if cfg!(feature = "armv6m") {
+            ArchProfile::CORTEX_M0
+        } else {
+            ArchProfile::CORTEX_M7
+        };
+        assert_eq!(ArchProfile::default(), expected);
+    }
+
+    #[test]
+    fn test_mvfr() {
+        assert_eq!(ArchProfile::CORTEX_M4.mvfr(), (0, 0, 0));
+        assert_eq!(
+            ArchProfile::CORTEX_M4F.mvfr(),
+            (0x1011_0021, 0x1100_0011, 0)
+        );
+        // double precision registers and arithmetic, FPv5 misc instructions
+        assert_eq!(
+            ArchProfile::CORTEX_M7.mvfr(),
+            (0x1011_0221, 0x1200_0011, 0x0000_0040)
+        );
+        // FPv5-SP
+        assert_eq!(
+            ArchProfile::CORTEX_M33.mvfr(),
+            (0x1011_0021, 0x1100_0011, 0x0000_0040)
+        );
+    }
+
+    #[test]
+    fn test_has_fpv5() {
+        assert!(!ArchProfile::CORTEX_M4F.has_fpv5());
+        assert!(ArchProfile::CORTEX_M7.has_fpv5());
+        assert!(ArchProfile::CORTEX_M33.has_fpv5());
+        assert!(!ArchProfile::CORTEX_M23.has_fpv5());
+    }
+}
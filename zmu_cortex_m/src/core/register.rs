@@ -5,6 +5,7 @@
 use crate::core::bits::Bits;
 use crate::core::exception::ExceptionHandling;
 use crate::core::fault::Fault;
+use crate::core::security::{SecurityExtension, FNC_RETURN};
 use crate::Processor;
 use crate::ProcessorMode;
 use enum_as_inner::EnumAsInner;
@@ -65,6 +66,12 @@ pub trait BaseReg {
     ///
     fn get_psp(&self) -> u32;
 
+    ///
+    /// Armv8-M stack limit check of a new value for the current stack pointer.
+    /// Values below MSPLIM or PSPLIM are `Stkof` faults.
+    ///
+    fn check_sp_limit(&self, value: u32) -> Result<(), Fault>;
+
     ///
     /// Increment PC by a value
     ///
@@ -127,7 +134,9 @@ impl BaseReg for Processor {
     }
 
     fn bx_write_pc(&mut self, address: u32) -> Result<(), Fault> {
-        if self.mode == ProcessorMode::HandlerMode && (address.get_bits(28..32) == 0b1111) {
+        if self.profile.has_security() && !self.secure && address | 1 == FNC_RETURN {
+            self.function_return()
+        } else if self.mode == ProcessorMode::HandlerMode && (address.get_bits(28..32) == 0b1111) {
             self.exception_return(address.get_bits(0..28))
         } else {
             self.blx_write_pc(address);
@@ -216,6 +225,19 @@ impl BaseReg for Processor {
         self.psp
     }
 
+    fn check_sp_limit(&self, value: u32) -> Result<(), Fault> {
+        let limit = if self.control.sp_sel {
+            self.psplim
+        } else {
+            self.msplim
+        };
+        if self.profile.is_armv8m() && value < limit {
+            Err(Fault::Stkof)
+        } else {
+            Ok(())
+        }
+    }
+
     fn add_pc(&mut self, value: u32) {
         self.pc += value;
     }
@@ -533,25 +555,13 @@ impl Epsr for PSR {
 }
 
 impl Ipsr for PSR {
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn get_isr_number(&self) -> usize {
         self.value.get_bits(0..9) as usize
     }
 
-    #[cfg(feature = "armv6m")]
-    fn get_isr_number(&self) -> usize {
-        (*self).value.get_bits(0..6) as usize
-    }
-
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn set_isr_number(&mut self, exception_number: usize) {
         self.value.set_bits(0..9, exception_number as u32);
     }
-
-    #[cfg(feature = "armv6m")]
-    fn set_isr_number(&mut self, exception_number: usize) {
-        self.value.set_bits(0..6, exception_number as u32);
-    }
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -1107,18 +1117,22 @@ impl fmt::Display for SpecialReg {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 /// CONTROL register parts
 pub struct Control {
     /// Thread mode priviledge level
     pub n_priv: bool,
     /// selection of current active stack pointer, true = PSP, false = MSP
     pub sp_sel: bool,
+    /// floating point context active, FP state is stacked on exception entry
+    pub fpca: bool,
 }
 
 impl From<Control> for u8 {
     fn from(control: Control) -> Self {
-        Self::from(control.n_priv) + (Self::from(control.sp_sel) << 1)
+        Self::from(control.n_priv)
+            + (Self::from(control.sp_sel) << 1)
+            + (Self::from(control.fpca) << 2)
     }
 }
 
@@ -2,10 +2,13 @@
 //! Processor Reset logic
 //!
 
-use crate::bus::Bus;
 use crate::core::exception::ExceptionHandling;
 use crate::core::fault::Fault;
+use crate::core::monitor::Monitor;
 use crate::core::register::{BaseReg, PSR};
+use crate::core::security::{BankedPending, BankedRegisters, SecurityExtension};
+use crate::peripheral::mpu::MPU_REGIONS;
+use crate::peripheral::sau::SAU_REGIONS;
 use crate::Processor;
 use crate::ProcessorMode;
 
@@ -19,6 +22,14 @@ pub trait Reset {
 
 impl Reset for Processor {
     fn reset(&mut self) -> Result<(), Fault> {
+        // reset is taken in Secure state, the Non-secure registers are reset in the bank
+        if self.profile.has_security() {
+            self.set_security_state(true);
+        }
+        let vtor_ns = self.banked.vtor;
+        self.banked = BankedRegisters::new();
+        self.banked.vtor = vtor_ns;
+
         // All basic registers to zero.
         for r in &mut self.r0_12 {
             *r = 0;
@@ -26,12 +37,16 @@ impl Reset for Processor {
 
         // Main stack pointer is read via vector table
         let vtor = self.vtor;
-        let sp = self.read32(vtor)? & 0xffff_fffc;
+        let sp = self.bus_read32(vtor)? & 0xffff_fffc;
         self.set_msp(sp);
 
         // Process stack pointer to zero
         self.set_psp(0);
 
+        // stack limits disabled
+        self.msplim = 0;
+        self.psplim = 0;
+
         // Link Register
         self.lr = 0;
 
@@ -42,23 +57,54 @@ impl Reset for Processor {
         self.psr = PSR { value: 0 };
         self.primask = false;
 
-        #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-        {
-            self.faultmask = false;
-        }
+        self.faultmask = false;
         self.basepri = 0;
         self.control.sp_sel = false;
         self.control.n_priv = false;
+        self.control.fpca = false;
 
         //TODO self.scs.reset();
         self.exceptions_reset();
+        self.banked_pending = BankedPending::default();
+
+        // fault handler enables and fault status
+        self.shcsr = 0;
+        self.cfsr = 0;
+        self.hfsr = 0;
+
+        // automatic and lazy FP state preservation enabled
+        self.fpccr = if self.profile.has_fpu() {
+            0xc000_0000
+        } else {
+            0
+        };
+        self.fpdscr = 0;
+
+        // MPU disabled, regions cleared
+        self.mpu_ctrl = 0;
+        self.mpu_rnr = 0;
+        self.mpu_rbar = [0; MPU_REGIONS];
+        self.mpu_rasr = [0; MPU_REGIONS];
+
+        // SAU disabled, all memory Secure, interrupts target Secure state
+        self.sau_ctrl = 0;
+        self.sau_rnr = 0;
+        self.sau_rbar = [0; SAU_REGIONS];
+        self.sau_rlar = [0; SAU_REGIONS];
+        self.sfsr = 0;
+        self.sfar = 0;
+        self.nvic_interrupt_target_ns = [0; 16];
+
+        // reset is the way out of the lockup state
+        self.lockup = None;
 
         //self.event_reg.clear();
+        self.clear_exclusive_local();
 
         self.itstate = 0;
         self.execution_priority = self.get_execution_priority();
 
-        let reset_vector = self.read32(vtor + 4)?;
+        let reset_vector = self.bus_read32(vtor + 4)?;
         self.blx_write_pc(reset_vector);
         Ok(())
     }
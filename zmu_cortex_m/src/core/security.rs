@@ -0,0 +1,336 @@
+//!
+//! Armv8-M security extension: banked registers and the transitions
+//! between the Secure and Non-secure states
+//!
+
+use crate::bus::Bus;
+use crate::core::bits::Bits;
+use crate::core::exception::{Exception, ExceptionHandling};
+use crate::core::fault::Fault;
+use crate::core::register::{BaseReg, Control, Ipsr, Reg};
+use crate::Processor;
+use crate::ProcessorMode;
+use std::mem;
+
+/// Return address of a Non-secure function called with BLXNS. Branching to it
+/// returns to the Secure caller.
+pub const FNC_RETURN: u32 = 0xFEFF_FFFF;
+
+/// `AIRCR.BFHFNMINS`: `BusFault`, `HardFault` and NMI are taken in Non-secure state
+pub const AIRCR_BFHFNMINS: usize = 13;
+
+#[derive(Debug, Copy, Clone)]
+///
+/// Registers of the security state that is not currently executing
+///
+pub struct BankedRegisters {
+    /// Main stack pointer
+    pub msp: u32,
+    /// Process stack pointer
+    pub psp: u32,
+    /// Main stack limit
+    pub msplim: u32,
+    /// Process stack limit
+    pub psplim: u32,
+    /// CONTROL, FPCA is shared by the states
+    pub control: Control,
+    /// Interrupt priority mask
+    pub primask: bool,
+    /// Fault mask
+    pub faultmask: bool,
+    /// Base priority
+    pub basepri: u8,
+    /// Vector table offset
+    pub vtor: u32,
+}
+
+impl BankedRegisters {
+    ///
+    /// Registers in their reset state
+    ///
+    pub fn new() -> Self {
+        Self {
+            msp: 0,
+            psp: 0,
+            msplim: 0,
+            psplim: 0,
+            control: Control {
+                n_priv: false,
+                sp_sel: false,
+                fpca: false,
+            },
+            primask: false,
+            faultmask: false,
+            basepri: 0,
+            vtor: 0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+///
+/// Pending `PendSV` and `SysTick` of each security state, indexed by the
+/// security state, Non-secure first. Both states share one exception slot,
+/// which stays pending while either state has the exception pending.
+///
+pub struct BankedPending {
+    /// `PendSV` pended by ICSR.PENDSVSET
+    pub pendsv: [bool; 2],
+    /// `SysTick` pended by the timer or ICSR.PENDSTSET
+    pub systick: [bool; 2],
+}
+
+impl BankedPending {
+    fn get_mut(&mut self, exception: Exception) -> Option<&mut [bool; 2]> {
+        match exception {
+            Exception::PendSV => Some(&mut self.pendsv),
+            Exception::SysTick => Some(&mut self.systick),
+            _ => None,
+        }
+    }
+
+    fn get(self, exception: Exception) -> Option<[bool; 2]> {
+        match exception {
+            Exception::PendSV => Some(self.pendsv),
+            Exception::SysTick => Some(self.systick),
+            _ => None,
+        }
+    }
+}
+
+///
+/// Security state handling
+///
+pub trait SecurityExtension {
+    ///
+    /// Switch to the Secure or Non-secure state, swapping the banked registers
+    ///
+    fn set_security_state(&mut self, secure: bool);
+
+    ///
+    /// True if the exception is taken in Secure state. Interrupts target the
+    /// state selected in `NVIC_ITNS`, `HardFault`, NMI and `BusFault` follow
+    /// `AIRCR.BFHFNMINS`. `PendSV` and `SysTick` target the state that pended
+    /// them, Secure first if both did. The other banked exceptions are taken
+    /// in the current state.
+    ///
+    fn exception_targets_secure(&self, exception: Exception) -> bool;
+
+    ///
+    /// Pend `PendSV` or `SysTick` of the Secure or Non-secure state
+    ///
+    fn set_banked_exception_pending(&mut self, exception: Exception, secure: bool);
+
+    ///
+    /// Clear the pending `PendSV` or `SysTick` of the Secure or Non-secure state
+    ///
+    fn clear_banked_exception_pending(&mut self, exception: Exception, secure: bool);
+
+    ///
+    /// Take the pending instance of `PendSV` or `SysTick` of a state, the
+    /// exception stays pending for the other state
+    ///
+    fn banked_exception_taken(&mut self, exception: Exception, secure: bool);
+
+    ///
+    /// Call a Non-secure function from Secure state (BLXNS). The return address
+    /// is saved to the Secure stack and LR is set to `FNC_RETURN`.
+    ///
+    fn non_secure_call(&mut self, target: u32, return_address: u32) -> Result<(), Fault>;
+
+    ///
+    /// Return to the Secure caller of a Non-secure function, on a branch to `FNC_RETURN`
+    ///
+    fn function_return(&mut self) -> Result<(), Fault>;
+}
+
+impl SecurityExtension for Processor {
+    fn set_security_state(&mut self, secure: bool) {
+        if secure == self.secure {
+            return;
+        }
+        let banked = &mut self.banked;
+        mem::swap(&mut self.msp, &mut banked.msp);
+        mem::swap(&mut self.psp, &mut banked.psp);
+        mem::swap(&mut self.msplim, &mut banked.msplim);
+        mem::swap(&mut self.psplim, &mut banked.psplim);
+        mem::swap(&mut self.control.n_priv, &mut banked.control.n_priv);
+        mem::swap(&mut self.control.sp_sel, &mut banked.control.sp_sel);
+        mem::swap(&mut self.primask, &mut banked.primask);
+        mem::swap(&mut self.faultmask, &mut banked.faultmask);
+        mem::swap(&mut self.basepri, &mut banked.basepri);
+        mem::swap(&mut self.vtor, &mut banked.vtor);
+        self.secure = secure;
+    }
+
+    fn exception_targets_secure(&self, exception: Exception) -> bool {
+        if !self.profile.has_security() {
+            return false;
+        }
+        match exception {
+            Exception::Reset | Exception::SecureFault => true,
+            Exception::NMI | Exception::HardFault | Exception::BusFault => {
+                !self.aircr.get_bit(AIRCR_BFHFNMINS)
+            }
+            Exception::Interrupt { n } => !self.nvic_interrupt_target_ns[n / 32].get_bit(n % 32),
+            _ => match self.banked_pending.get(exception) {
+                Some([_, true]) => true,
+                Some([true, false]) => false,
+                _ => self.secure,
+            },
+        }
+    }
+
+    fn set_banked_exception_pending(&mut self, exception: Exception, secure: bool) {
+        if self.profile.has_security() {
+            if let Some(pending) = self.banked_pending.get_mut(exception) {
+                pending[usize::from(secure)] = true;
+            }
+        }
+        self.set_exception_pending(exception);
+    }
+
+    fn clear_banked_exception_pending(&mut self, exception: Exception, secure: bool) {
+        if self.profile.has_security() {
+            if let Some(pending) = self.banked_pending.get_mut(exception) {
+                pending[usize::from(secure)] = false;
+                if pending[usize::from(!secure)] {
+                    return;
+                }
+            }
+        }
+        self.clear_pending_exception(exception);
+    }
+
+    fn banked_exception_taken(&mut self, exception: Exception, secure: bool) {
+        if let Some(pending) = self.banked_pending.get_mut(exception) {
+            pending[usize::from(secure)] = false;
+            if pending[usize::from(!secure)] {
+                self.set_exception_pending(exception);
+            }
+        }
+    }
+
+    fn non_secure_call(&mut self, target: u32, return_address: u32) -> Result<(), Fault> {
+        let sp = self.get_r(Reg::SP).wrapping_sub(8);
+        self.check_sp_limit(sp)?;
+        // partial PSR: the exception number is hidden from the Non-secure function
+        let partial_psr = self.psr.get_isr_number() as u32;
+        self.write32(sp, return_address)?;
+        self.write32(sp.wrapping_add(4), partial_psr)?;
+        self.set_r(Reg::SP, sp);
+
+        self.set_r(Reg::LR, FNC_RETURN);
+        if self.mode == ProcessorMode::HandlerMode {
+            self.psr.set_isr_number(1);
+        }
+        self.set_security_state(false);
+        self.branch_write_pc(target);
+        Ok(())
+    }
+
+    fn function_return(&mut self) -> Result<(), Fault> {
+        self.set_security_state(true);
+        let sp = self.get_r(Reg::SP);
+        let return_address = self.read32(sp)?;
+        let partial_psr = self.read32(sp.wrapping_add(4))?;
+        self.set_r(Reg::SP, sp.wrapping_add(8));
+
+        if self.mode == ProcessorMode::HandlerMode {
+            self.psr.set_isr_number(partial_psr.get_bits(0..9) as usize);
+        }
+        self.branch_write_pc(return_address);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::exception::ExceptionHandling;
+    use crate::core::profile::ArchProfile;
+    use crate::core::reset::Reset;
+    use crate::peripheral::systick::SysTick;
+
+    #[test]
+    fn test_banked_registers() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_1000;
+        processor.primask = true;
+        processor.vtor = 0x1000_0000;
+
+        // Act
+        processor.set_security_state(false);
+        processor.msp = 0x2000_8000;
+        processor.vtor = 0x0002_0000;
+
+        // Assert: Non-secure registers are separate
+        assert!(!processor.primask);
+        processor.set_security_state(true);
+        assert_eq!(processor.msp, 0x2000_1000);
+        assert_eq!(processor.vtor, 0x1000_0000);
+        assert_eq!(processor.banked.msp, 0x2000_8000);
+        assert_eq!(processor.banked.vtor, 0x0002_0000);
+    }
+
+    #[test]
+    fn test_non_secure_call_and_return() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_1000;
+        processor.sau_ctrl = 0b10;
+
+        // Act
+        processor.non_secure_call(0x0000_4000, 0x0000_0123).unwrap();
+
+        // Assert
+        assert!(!processor.secure);
+        assert_eq!(processor.get_r(Reg::LR), FNC_RETURN);
+        assert_eq!(processor.get_pc(), 0x0000_4000);
+        assert_eq!(processor.banked.msp, 0x2000_1000 - 8);
+
+        // Act: Non-secure function returns
+        processor.bx_write_pc(FNC_RETURN).unwrap();
+
+        // Assert
+        assert!(processor.secure);
+        assert_eq!(processor.msp, 0x2000_1000);
+        assert_eq!(processor.get_pc(), 0x0000_0122);
+    }
+
+    #[test]
+    fn test_non_secure_systick_in_secure_function() {
+        // Arrange: Non-secure vector table and SysTick set up by Non-secure code
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_1000;
+        processor
+            .write32(0x2000_0400 + 15 * 4, 0x0000_4001)
+            .unwrap();
+        processor.set_security_state(false);
+        processor.msp = 0x2000_8000;
+        processor.vtor = 0x2000_0400;
+        processor.write32(0xE000_E014, 100).unwrap();
+        processor.write32(0xE000_E010, 0b11).unwrap();
+
+        // Act: the timer expires while a Secure function runs
+        processor.set_security_state(true);
+        processor.syst_cvr = 1;
+        processor.syst_step(1);
+        processor.check_exceptions();
+
+        // Assert: Non-secure handler on the Non-secure stack, Secure context
+        // stacked on the Secure stack
+        assert!(!processor.secure);
+        assert_eq!(processor.get_pc(), 0x0000_4000);
+        assert_eq!(processor.msp, 0x2000_8000);
+        assert!(processor.banked.msp < 0x2000_1000);
+        assert!(!processor.banked_pending.systick[0]);
+    }
+}
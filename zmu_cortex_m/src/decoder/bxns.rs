@@ -0,0 +1,9 @@
+use crate::core::{bits::Bits, instruction::Instruction};
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_BXNS_t1(command: u16) -> Instruction {
+    Instruction::BXNS {
+        rm: From::from(command.get_bits(3..7)),
+    }
+}
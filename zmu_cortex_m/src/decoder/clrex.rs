@@ -1,10 +1,6 @@
 use crate::core::instruction::Instruction;
 
 #[allow(non_snake_case)]
-pub fn decode_CLREX_t1(opcode: u32) -> Instruction {
-    Instruction::UDF {
-        imm32: 0,
-        opcode: opcode.into(),
-        thumb32: true,
-    }
+pub fn decode_CLREX_t1(_opcode: u32) -> Instruction {
+    Instruction::CLREX
 }
@@ -1,13 +1,18 @@
 use crate::core::instruction::{
     AddressingMode, BfcParams, BfiParams, BfxParams, CondBranchParams, Imm32Carry, MovtParams,
     ParamsRegImm32, Reg2DoubleParams, Reg2FullParams, Reg2ImmCarryParams, Reg2ImmParams,
-    Reg2Params, Reg2RdRmParams, Reg2RnRmParams, Reg2RtRnImm32Params, Reg2ShiftNParams,
-    Reg2ShiftNoSetFlagsParams, Reg2ShiftParams, Reg2UsizeParams, Reg3FullParams, Reg3HighParams,
-    Reg3NoSetFlagsParams, Reg3Params, Reg3RdRtRnImm32Params, Reg3ShiftParams, Reg3UsizeParams,
-    Reg4HighParams, Reg4NoSetFlagsParams, Reg643232Params, RegImm32AddParams,
-    RegImmCarryNoSetFlagsParams, RegImmCarryParams, RegImmParams, SRType, SetFlags,
-    VAddSubParamsf32, VCVTParams, VCmpParamsf32, VMRSTarget, VMovCr2DpParams, VMovCrSpParams,
-    VMovImmParams32, VMovImmParams64, VMovRegParamsf32,
+    Reg2Params, Reg2RdRmParams, Reg2RnRmParams, Reg2RtRnImm32Params, Reg2RtRnParams,
+    Reg2ShiftNParams, Reg2ShiftNoSetFlagsParams, Reg2ShiftParams, Reg2UsizeParams, Reg3DualParams,
+    Reg3FullParams, Reg3HighParams, Reg3MHighParams, Reg3NoSetFlagsParams, Reg3Params,
+    Reg3RdRtRnImm32Params, Reg3RdRtRnParams, Reg3RoundParams, Reg3ShiftNoSetFlagsParams,
+    Reg3ShiftParams, Reg3UsizeParams, Reg4DualParams, Reg4HighParams, Reg4MHighParams,
+    Reg4NoSetFlagsParams, Reg4RoundParams, Reg643232DualParams, Reg643232HighParams,
+    Reg643232Params, RegImm32AddParams, RegImmCarryNoSetFlagsParams, RegImmCarryParams,
+    RegImmParams, SRType, Sat16Params, SatParams, SetFlags, TtParams, VAddSubParamsf32,
+    VAddSubParamsf64, VCVTDirectedParams, VCVTF32F64Params, VCVTF64F32Params, VCVTParams,
+    VCmpParamsf32, VMRSTarget, VMovCr2DpParams, VMovCr2Sp2Params, VMovCrScalarParams,
+    VMovCrSpParams, VMovImmParams32, VMovImmParams64, VMovRegParamsf32, VMovRegParamsf64,
+    VRintMode, VRintParamsf32, VRintParamsf64, VSelParamsf32, VSelParamsf64,
 };
 
 use crate::core::instruction::VLoadAndStoreParams;
@@ -1127,14 +1132,6 @@ fn test_decode_mrs() {
     );
 }*/
 
-#[cfg(feature = "armv6m")]
-#[test]
-fn test_decode_cpsid() {
-    // CPSID i
-    assert_eq!(decode_16(0xB672), Instruction::CPS { im: true });
-}
-
-#[cfg(any(feature = "armv7m", feature = "armv7em"))]
 #[test]
 fn test_decode_cpsid() {
     // CPSID i
@@ -2842,6 +2839,12 @@ fn test_decode_dmb() {
     assert_eq!(decode_32(0xf3bf_8f5f), Instruction::DMB);
 }
 
+#[test]
+fn test_decode_clrex() {
+    //  f3bf 8f2f       clrex
+    assert_eq!(decode_32(0xf3bf_8f2f), Instruction::CLREX);
+}
+
 #[test]
 fn test_decode_ldrex() {
     //  e850 3f00       ldrex   r3, [r0]
@@ -3040,6 +3043,56 @@ fn test_decode_vmov_cr2_dp() {
     );
 }
 
+#[test]
+fn test_decode_vmov_cr2_sp2() {
+    //  ec55 4a31       vmov    r4, r5, s3, s4
+
+    assert_eq!(
+        decode_32(0xec55_4a31),
+        Instruction::VMOV_cr2_sp2 {
+            params: VMovCr2Sp2Params {
+                to_arm_registers: true,
+                rt: Reg::R4,
+                rt2: Reg::R5,
+                sm: SingleReg::S3,
+                sm1: SingleReg::S4,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vmov_cr_scalar() {
+    //  ee27 2b10       vmov.32 d7[1], r2
+
+    assert_eq!(
+        decode_32(0xee27_2b10),
+        Instruction::VMOV_cr_scalar {
+            params: VMovCrScalarParams {
+                rt: Reg::R2,
+                dd: DoubleReg::D7,
+                x: true,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vmov_scalar_cr() {
+    //  ee17 3b10       vmov.32 r3, d7[0]
+
+    assert_eq!(
+        decode_32(0xee17_3b10),
+        Instruction::VMOV_scalar_cr {
+            params: VMovCrScalarParams {
+                rt: Reg::R3,
+                dd: DoubleReg::D7,
+                x: false,
+            }
+        }
+    );
+}
+
 #[test]
 fn test_decode_vmov_reg_f32() {
     //eeb0 0a4a       vmov.f32        s0, s20
@@ -3163,6 +3216,43 @@ fn test_decode_vmrs() {
     );
 }
 
+#[test]
+fn test_decode_vmsr() {
+    //0xeee1 3a10       vmsr    fpscr, r3
+
+    assert_eq!(decode_32(0xeee13a10), Instruction::VMSR { rt: Reg::R3 });
+}
+
+#[test]
+fn test_decode_vcvt_f32_f64() {
+    // eef7 1bc7       vcvt.f32.f64    s3, d7
+
+    assert_eq!(
+        decode_32(0xeef71bc7),
+        Instruction::VCVT_f32_f64 {
+            params: VCVTF32F64Params {
+                sd: SingleReg::S3,
+                dm: DoubleReg::D7,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vcvt_f64_f32() {
+    // eeb7 7ae1       vcvt.f64.f32    d7, s3
+
+    assert_eq!(
+        decode_32(0xeeb77ae1),
+        Instruction::VCVT_f64_f32 {
+            params: VCVTF64F32Params {
+                dd: DoubleReg::D7,
+                sm: SingleReg::S3,
+            }
+        }
+    );
+}
+
 #[test]
 fn test_decode_vadd_f32() {
     // ee77 5a26       vadd.f32        s11, s14, s13
@@ -3196,23 +3286,1281 @@ fn test_decode_vsub_f32() {
 }
 
 #[test]
-fn test_decode_vct() {
-    //eefd 7ac0       vcvt.s32.f32    s15, s0
+fn test_decode_vmul_f32() {
+    // ee67 7a26       vmul.f32        s15, s14, s13
 
     assert_eq!(
-        decode_32(0xeefd7ac0),
-        Instruction::VCVT {
-            params: VCVTParams {
-                d: ExtensionReg::Single {
-                    reg: SingleReg::S15
-                },
-                m: ExtensionReg::Single { reg: SingleReg::S0 },
-                dp_operation: false,
-                to_integer: true,
-                unsigned: false,
-                round_nearest: false,
-                round_zero: true,
+        decode_32(0xee677a26),
+        Instruction::VMUL_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S15,
+                sn: SingleReg::S14,
+                sm: SingleReg::S13,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vmul_f64() {
+    // ee26 7b05       vmul.f64        d7, d6, d5
+
+    assert_eq!(
+        decode_32(0xee267b05),
+        Instruction::VMUL_f64 {
+            params: VAddSubParamsf64 {
+                dd: DoubleReg::D7,
+                dn: DoubleReg::D6,
+                dm: DoubleReg::D5,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vnmul_f32() {
+    // ee61 0a61       vnmul.f32        s1, s2, s3
+
+    assert_eq!(
+        decode_32(0xee610a61),
+        Instruction::VNMUL_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S1,
+                sn: SingleReg::S2,
+                sm: SingleReg::S3,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vdiv_f32() {
+    // eec7 7a27       vdiv.f32        s15, s14, s15
+
+    assert_eq!(
+        decode_32(0xeec77a27),
+        Instruction::VDIV_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S15,
+                sn: SingleReg::S14,
+                sm: SingleReg::S15,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vmla_f32() {
+    // ee00 0a81       vmla.f32        s0, s1, s2
+
+    assert_eq!(
+        decode_32(0xee000a81),
+        Instruction::VMLA_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S0,
+                sn: SingleReg::S1,
+                sm: SingleReg::S2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vmls_f32() {
+    // ee42 1a62       vmls.f32        s3, s4, s5
+
+    assert_eq!(
+        decode_32(0xee421a62),
+        Instruction::VMLS_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S3,
+                sn: SingleReg::S4,
+                sm: SingleReg::S5,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vnmla_f64() {
+    // ee12 1b43       vnmla.f64        d1, d2, d3
+
+    assert_eq!(
+        decode_32(0xee121b43),
+        Instruction::VNMLA_f64 {
+            params: VAddSubParamsf64 {
+                dd: DoubleReg::D1,
+                dn: DoubleReg::D2,
+                dm: DoubleReg::D3,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vnmls_f32() {
+    // ee13 3a84       vnmls.f32        s6, s7, s8
+
+    assert_eq!(
+        decode_32(0xee133a84),
+        Instruction::VNMLS_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S6,
+                sn: SingleReg::S7,
+                sm: SingleReg::S8,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vfma_f32() {
+    // eee0 7a20       vfma.f32        s15, s0, s1
+
+    assert_eq!(
+        decode_32(0xeee07a20),
+        Instruction::VFMA_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S15,
+                sn: SingleReg::S0,
+                sm: SingleReg::S1,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vfms_f64() {
+    // eea5 4b46       vfms.f64        d4, d5, d6
+
+    assert_eq!(
+        decode_32(0xeea54b46),
+        Instruction::VFMS_f64 {
+            params: VAddSubParamsf64 {
+                dd: DoubleReg::D4,
+                dn: DoubleReg::D5,
+                dm: DoubleReg::D6,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vfnma_f32() {
+    // eed5 4a65       vfnma.f32        s9, s10, s11
+
+    assert_eq!(
+        decode_32(0xeed54a65),
+        Instruction::VFNMA_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S9,
+                sn: SingleReg::S10,
+                sm: SingleReg::S11,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vfnms_f32() {
+    // ee96 6a87       vfnms.f32        s12, s13, s14
+
+    assert_eq!(
+        decode_32(0xee966a87),
+        Instruction::VFNMS_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S12,
+                sn: SingleReg::S13,
+                sm: SingleReg::S14,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vsqrt_f32() {
+    // eef1 7ac7       vsqrt.f32        s15, s14
+
+    assert_eq!(
+        decode_32(0xeef17ac7),
+        Instruction::VSQRT_f32 {
+            params: VMovRegParamsf32 {
+                sd: SingleReg::S15,
+                sm: SingleReg::S14,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vsqrt_f64() {
+    // eeb1 0bc1       vsqrt.f64        d0, d1
+
+    assert_eq!(
+        decode_32(0xeeb10bc1),
+        Instruction::VSQRT_f64 {
+            params: VMovRegParamsf64 {
+                dd: DoubleReg::D0,
+                dm: DoubleReg::D1,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vneg_f32() {
+    // eeb1 0a6f       vneg.f32        s0, s31
+
+    assert_eq!(
+        decode_32(0xeeb10a6f),
+        Instruction::VNEG_f32 {
+            params: VMovRegParamsf32 {
+                sd: SingleReg::S0,
+                sm: SingleReg::S31,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vneg_f64() {
+    // eeb1 2b43       vneg.f64        d2, d3
+
+    assert_eq!(
+        decode_32(0xeeb12b43),
+        Instruction::VNEG_f64 {
+            params: VMovRegParamsf64 {
+                dd: DoubleReg::D2,
+                dm: DoubleReg::D3,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vselge_f32() {
+    // fe20 0a81       vselge.f32        s0, s1, s2
+
+    assert_eq!(
+        decode_32(0xfe200a81),
+        Instruction::VSEL_f32 {
+            params: VSelParamsf32 {
+                sd: SingleReg::S0,
+                sn: SingleReg::S1,
+                sm: SingleReg::S2,
+                cond: Condition::GE,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vselgt_f64() {
+    // fe31 0b02       vselgt.f64        d0, d1, d2
+
+    assert_eq!(
+        decode_32(0xfe310b02),
+        Instruction::VSEL_f64 {
+            params: VSelParamsf64 {
+                dd: DoubleReg::D0,
+                dn: DoubleReg::D1,
+                dm: DoubleReg::D2,
+                cond: Condition::GT,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vmaxnm_f32() {
+    // fe82 2a83       vmaxnm.f32        s4, s5, s6
+
+    assert_eq!(
+        decode_32(0xfe822a83),
+        Instruction::VMAXNM_f32 {
+            params: VAddSubParamsf32 {
+                sd: SingleReg::S4,
+                sn: SingleReg::S5,
+                sm: SingleReg::S6,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vminnm_f64() {
+    // fe85 4b46       vminnm.f64        d4, d5, d6
+
+    assert_eq!(
+        decode_32(0xfe854b46),
+        Instruction::VMINNM_f64 {
+            params: VAddSubParamsf64 {
+                dd: DoubleReg::D4,
+                dn: DoubleReg::D5,
+                dm: DoubleReg::D6,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vrinta_f32() {
+    // feb8 0a60       vrinta.f32        s0, s1
+
+    assert_eq!(
+        decode_32(0xfeb80a60),
+        Instruction::VRINT_f32 {
+            params: VRintParamsf32 {
+                sd: SingleReg::S0,
+                sm: SingleReg::S1,
+                mode: VRintMode::A,
             }
         }
     );
 }
+
+#[test]
+fn test_decode_vrintm_f64() {
+    // febb 0b41       vrintm.f64        d0, d1
+
+    assert_eq!(
+        decode_32(0xfebb0b41),
+        Instruction::VRINT_f64 {
+            params: VRintParamsf64 {
+                dd: DoubleReg::D0,
+                dm: DoubleReg::D1,
+                mode: VRintMode::M,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vcvta_s32_f32() {
+    // febc 0ae0       vcvta.s32.f32     s0, s1
+
+    assert_eq!(
+        decode_32(0xfebc0ae0),
+        Instruction::VCVT_directed {
+            params: VCVTDirectedParams {
+                d: SingleReg::S0,
+                m: ExtensionReg::Single { reg: SingleReg::S1 },
+                unsigned: false,
+                mode: VRintMode::A,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vcvtm_u32_f64() {
+    // febf 1b43       vcvtm.u32.f64     s2, d3
+
+    assert_eq!(
+        decode_32(0xfebf1b43),
+        Instruction::VCVT_directed {
+            params: VCVTDirectedParams {
+                d: SingleReg::S2,
+                m: ExtensionReg::Double { reg: DoubleReg::D3 },
+                unsigned: true,
+                mode: VRintMode::M,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vrintr_f32() {
+    // eeb6 1a61       vrintr.f32        s2, s3
+
+    assert_eq!(
+        decode_32(0xeeb61a61),
+        Instruction::VRINT_f32 {
+            params: VRintParamsf32 {
+                sd: SingleReg::S2,
+                sm: SingleReg::S3,
+                mode: VRintMode::R,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vrintz_f32() {
+    // eeb6 2ae2       vrintz.f32        s4, s5
+
+    assert_eq!(
+        decode_32(0xeeb62ae2),
+        Instruction::VRINT_f32 {
+            params: VRintParamsf32 {
+                sd: SingleReg::S4,
+                sm: SingleReg::S5,
+                mode: VRintMode::Z,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vrintx_f64() {
+    // eeb7 3b44       vrintx.f64        d3, d4
+
+    assert_eq!(
+        decode_32(0xeeb73b44),
+        Instruction::VRINT_f64 {
+            params: VRintParamsf64 {
+                dd: DoubleReg::D3,
+                dm: DoubleReg::D4,
+                mode: VRintMode::X,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vrintn_f32() {
+    // feb9 3a63       vrintn.f32        s6, s7
+
+    assert_eq!(
+        decode_32(0xfeb93a63),
+        Instruction::VRINT_f32 {
+            params: VRintParamsf32 {
+                sd: SingleReg::S6,
+                sm: SingleReg::S7,
+                mode: VRintMode::N,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vrintp_f32() {
+    // feba 4a64       vrintp.f32        s8, s9
+
+    assert_eq!(
+        decode_32(0xfeba4a64),
+        Instruction::VRINT_f32 {
+            params: VRintParamsf32 {
+                sd: SingleReg::S8,
+                sm: SingleReg::S9,
+                mode: VRintMode::P,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_vct() {
+    //eefd 7ac0       vcvt.s32.f32    s15, s0
+
+    assert_eq!(
+        decode_32(0xeefd7ac0),
+        Instruction::VCVT {
+            params: VCVTParams {
+                d: ExtensionReg::Single {
+                    reg: SingleReg::S15
+                },
+                m: ExtensionReg::Single { reg: SingleReg::S0 },
+                dp_operation: false,
+                to_integer: true,
+                unsigned: false,
+                round_nearest: false,
+                round_zero: true,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smladx() {
+    // fb21 3012       smladx  r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfb21_3012),
+        Instruction::SMLAD {
+            params: Reg4DualParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                ra: Reg::R3,
+                m_swap: true,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smuad() {
+    // fb21 f002       smuad   r0, r1, r2
+    assert_eq!(
+        decode_32(0xfb21_f002),
+        Instruction::SMUAD {
+            params: Reg3DualParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                m_swap: false,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smlsd() {
+    // fb45 7406       smlsd   r4, r5, r6, r7
+    assert_eq!(
+        decode_32(0xfb45_7406),
+        Instruction::SMLSD {
+            params: Reg4DualParams {
+                rd: Reg::R4,
+                rn: Reg::R5,
+                rm: Reg::R6,
+                ra: Reg::R7,
+                m_swap: false,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smusdx() {
+    // fb45 f416       smusdx  r4, r5, r6
+    assert_eq!(
+        decode_32(0xfb45_f416),
+        Instruction::SMUSD {
+            params: Reg3DualParams {
+                rd: Reg::R4,
+                rn: Reg::R5,
+                rm: Reg::R6,
+                m_swap: true,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smlawt() {
+    // fb31 3012       smlawt  r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfb31_3012),
+        Instruction::SMLAW {
+            params: Reg4MHighParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                ra: Reg::R3,
+                m_high: true,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smulwb() {
+    // fb31 f002       smulwb  r0, r1, r2
+    assert_eq!(
+        decode_32(0xfb31_f002),
+        Instruction::SMULW {
+            params: Reg3MHighParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                m_high: false,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smmlar() {
+    // fb51 3012       smmlar  r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfb51_3012),
+        Instruction::SMMLA {
+            params: Reg4RoundParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                ra: Reg::R3,
+                round: true,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smmls() {
+    // fb61 3002       smmls   r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfb61_3002),
+        Instruction::SMMLS {
+            params: Reg4RoundParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                ra: Reg::R3,
+                round: false,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smmul() {
+    // fb51 f002       smmul   r0, r1, r2
+    assert_eq!(
+        decode_32(0xfb51_f002),
+        Instruction::SMMUL {
+            params: Reg3RoundParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                round: false,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_umaal() {
+    // fbe2 0163       umaal   r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfbe2_0163),
+        Instruction::UMAAL {
+            params: Reg643232Params {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R3,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_usad8() {
+    // fb71 f002       usad8   r0, r1, r2
+    assert_eq!(
+        decode_32(0xfb71_f002),
+        Instruction::USAD8 {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_usada8() {
+    // fb71 3002       usada8  r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfb71_3002),
+        Instruction::USADA8 {
+            params: Reg4NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                ra: Reg::R3,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_qadd() {
+    // fa82 f081       qadd    r0, r1, r2
+    assert_eq!(
+        decode_32(0xfa82_f081),
+        Instruction::QADD {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R2,
+                rm: Reg::R1,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_qdsub() {
+    // fa82 f0b1       qdsub   r0, r1, r2
+    assert_eq!(
+        decode_32(0xfa82_f0b1),
+        Instruction::QDSUB {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R2,
+                rm: Reg::R1,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_sadd16() {
+    // fa91 f002       sadd16  r0, r1, r2
+    assert_eq!(
+        decode_32(0xfa91_f002),
+        Instruction::SADD16 {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_uqsub8() {
+    // fac1 f052       uqsub8  r0, r1, r2
+    assert_eq!(
+        decode_32(0xfac1_f052),
+        Instruction::UQSUB8 {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_shasx() {
+    // faa1 f022       shasx   r0, r1, r2
+    assert_eq!(
+        decode_32(0xfaa1_f022),
+        Instruction::SHASX {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_usax() {
+    // fae1 f042       usax    r0, r1, r2
+    assert_eq!(
+        decode_32(0xfae1_f042),
+        Instruction::USAX {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_ssat() {
+    // f321 00c7       ssat    r0, #8, r1, asr #3
+    assert_eq!(
+        decode_32(0xf321_00c7),
+        Instruction::SSAT {
+            params: SatParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                saturate_to: 8,
+                shift_t: SRType::ASR,
+                shift_n: 3,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_usat() {
+    // f381 0087       usat    r0, #7, r1, lsl #2
+    assert_eq!(
+        decode_32(0xf381_0087),
+        Instruction::USAT {
+            params: SatParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                saturate_to: 7,
+                shift_t: SRType::LSL,
+                shift_n: 2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_ssat16() {
+    // f321 0007       ssat16  r0, #8, r1
+    assert_eq!(
+        decode_32(0xf321_0007),
+        Instruction::SSAT16 {
+            params: Sat16Params {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                saturate_to: 8,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_usat16() {
+    // f3a1 000f       usat16  r0, #15, r1
+    assert_eq!(
+        decode_32(0xf3a1_000f),
+        Instruction::USAT16 {
+            params: Sat16Params {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                saturate_to: 15,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_pkhbt() {
+    // eac1 2002       pkhbt   r0, r1, r2, lsl #8
+    assert_eq!(
+        decode_32(0xeac1_2002),
+        Instruction::PKHBT {
+            params: Reg3ShiftNoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                shift_t: SRType::LSL,
+                shift_n: 8,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_pkhtb() {
+    // eac1 4022       pkhtb   r0, r1, r2, asr #16
+    assert_eq!(
+        decode_32(0xeac1_4022),
+        Instruction::PKHTB {
+            params: Reg3ShiftNoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                shift_t: SRType::ASR,
+                shift_n: 16,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_sxtab16() {
+    // fa21 f092       sxtab16 r0, r1, r2, ror #8
+    assert_eq!(
+        decode_32(0xfa21_f092),
+        Instruction::SXTAB16 {
+            params: Reg3UsizeParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                rotation: 8,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_sxtb16() {
+    // fa2f f082       sxtb16  r0, r2
+    assert_eq!(
+        decode_32(0xfa2f_f082),
+        Instruction::SXTB16 {
+            params: Reg2UsizeParams {
+                rd: Reg::R0,
+                rm: Reg::R2,
+                rotation: 0,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_uxtab16() {
+    // fa31 f082       uxtab16 r0, r1, r2
+    assert_eq!(
+        decode_32(0xfa31_f082),
+        Instruction::UXTAB16 {
+            params: Reg3UsizeParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                rotation: 0,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_uxtb16() {
+    // fa3f f0a2       uxtb16  r0, r2, ror #16
+    assert_eq!(
+        decode_32(0xfa3f_f0a2),
+        Instruction::UXTB16 {
+            params: Reg2UsizeParams {
+                rd: Reg::R0,
+                rm: Reg::R2,
+                rotation: 16,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smlal() {
+    // fbc2 0103       smlal   r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfbc2_0103),
+        Instruction::SMLAL {
+            params: Reg643232Params {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R3,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smlalxy() {
+    // fbc2 0193       smlalbt r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfbc2_0193),
+        Instruction::SMLALxy {
+            params: Reg643232HighParams {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R3,
+                n_high: false,
+                m_high: true,
+            }
+        }
+    );
+    // fbc6 45a7       smlaltb r4, r5, r6, r7
+    assert_eq!(
+        decode_32(0xfbc6_45a7),
+        Instruction::SMLALxy {
+            params: Reg643232HighParams {
+                rdlo: Reg::R4,
+                rdhi: Reg::R5,
+                rn: Reg::R6,
+                rm: Reg::R7,
+                n_high: true,
+                m_high: false,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smlald() {
+    // fbc2 01d3       smlaldx r0, r1, r2, r3
+    assert_eq!(
+        decode_32(0xfbc2_01d3),
+        Instruction::SMLALD {
+            params: Reg643232DualParams {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R3,
+                m_swap: true,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_smlsld() {
+    // fbd6 45c7       smlsld  r4, r5, r6, r7
+    assert_eq!(
+        decode_32(0xfbd6_45c7),
+        Instruction::SMLSLD {
+            params: Reg643232DualParams {
+                rdlo: Reg::R4,
+                rdhi: Reg::R5,
+                rn: Reg::R6,
+                rm: Reg::R7,
+                m_swap: false,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_sxtab() {
+    // fa41 f092       sxtab   r0, r1, r2, ror #8
+    assert_eq!(
+        decode_32(0xfa41_f092),
+        Instruction::SXTAB {
+            params: Reg3UsizeParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                rotation: 8,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_sxtah() {
+    // fa01 f082       sxtah   r0, r1, r2
+    assert_eq!(
+        decode_32(0xfa01_f082),
+        Instruction::SXTAH {
+            params: Reg3UsizeParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                rotation: 0,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_uxtah() {
+    // fa14 f3a5       uxtah   r3, r4, r5, ror #16
+    assert_eq!(
+        decode_32(0xfa14_f3a5),
+        Instruction::UXTAH {
+            params: Reg3UsizeParams {
+                rd: Reg::R3,
+                rn: Reg::R4,
+                rm: Reg::R5,
+                rotation: 16,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_profile() {
+    let mut processor = Processor::new();
+
+    // ldr.w r1, [r0], #4 is not available on Armv6-M
+    let ldr_w = ThumbCode::from(0xf850_1b04_u32);
+    processor.profile(ArchProfile::CORTEX_M0);
+    assert!(matches!(
+        processor.decode(ldr_w),
+        Instruction::UDF { thumb32: true, .. }
+    ));
+    processor.profile(ArchProfile::CORTEX_M3);
+    assert!(matches!(
+        processor.decode(ldr_w),
+        Instruction::LDR_imm { .. }
+    ));
+
+    // uadd8 r2, r2, r12 needs the DSP extension
+    let uadd8 = ThumbCode::from(0xfa82_f24c_u32);
+    assert!(matches!(processor.decode(uadd8), Instruction::UDF { .. }));
+    processor.profile(ArchProfile::CORTEX_M4);
+    assert!(matches!(processor.decode(uadd8), Instruction::UADD8 { .. }));
+
+    // ssat r0, #8, r1, asr #3 is in the base Armv7-M, ssat16 needs the DSP extension
+    let ssat = ThumbCode::from(0xf321_00c7_u32);
+    let ssat16 = ThumbCode::from(0xf321_0007_u32);
+    processor.profile(ArchProfile::CORTEX_M3);
+    assert!(matches!(processor.decode(ssat), Instruction::SSAT { .. }));
+    assert!(matches!(processor.decode(ssat16), Instruction::UDF { .. }));
+    processor.profile(ArchProfile::CORTEX_M4);
+    assert!(matches!(
+        processor.decode(ssat16),
+        Instruction::SSAT16 { .. }
+    ));
+
+    // vadd.f32 needs the FPU
+    let vadd = ThumbCode::from(0xee77_5a26_u32);
+    assert!(matches!(processor.decode(vadd), Instruction::UDF { .. }));
+    processor.profile(ArchProfile::CORTEX_M4F);
+    assert!(matches!(
+        processor.decode(vadd),
+        Instruction::VADD_f32 { .. }
+    ));
+
+    // vmul.f64 needs double precision, vselge.f32 needs FPv5
+    let vmul_f64 = ThumbCode::from(0xee26_7b05_u32);
+    let vselge = ThumbCode::from(0xfe20_0a81_u32);
+    let vcvt_f64_f32 = ThumbCode::from(0xeeb7_7ae1_u32);
+    assert!(matches!(
+        processor.decode(vmul_f64),
+        Instruction::UDF { .. }
+    ));
+    assert!(matches!(
+        processor.decode(vcvt_f64_f32),
+        Instruction::UDF { .. }
+    ));
+    assert!(matches!(processor.decode(vselge), Instruction::UDF { .. }));
+    processor.profile(ArchProfile::CORTEX_M7);
+    assert!(matches!(
+        processor.decode(vmul_f64),
+        Instruction::VMUL_f64 { .. }
+    ));
+    assert!(matches!(
+        processor.decode(vcvt_f64_f32),
+        Instruction::VCVT_f64_f32 { .. }
+    ));
+    assert!(matches!(
+        processor.decode(vselge),
+        Instruction::VSEL_f32 { .. }
+    ));
+
+    // Cortex-M33 has FPv5-SP: the single precision FPv5 additions decode,
+    // their double precision forms do not
+    let vmaxnm_f32 = ThumbCode::from(0xfe82_2a83_u32);
+    let vcvta_f32 = ThumbCode::from(0xfebc_0ae0_u32);
+    let vcvtm_f64 = ThumbCode::from(0xfebf_1b43_u32);
+    processor.profile(ArchProfile::CORTEX_M33);
+    assert!(matches!(
+        processor.decode(vmaxnm_f32),
+        Instruction::VMAXNM_f32 { .. }
+    ));
+    assert!(matches!(
+        processor.decode(vcvta_f32),
+        Instruction::VCVT_directed { .. }
+    ));
+    assert!(matches!(
+        processor.decode(vcvtm_f64),
+        Instruction::UDF { .. }
+    ));
+    assert!(matches!(
+        processor.decode(vmul_f64),
+        Instruction::UDF { .. }
+    ));
+}
+
+#[test]
+fn test_decode_sg() {
+    // e97f e97f       sg
+    assert_eq!(decode_32(0xe97f_e97f), Instruction::SG);
+}
+
+#[test]
+fn test_decode_bxns() {
+    // 471c            bxns    r3
+    assert_eq!(decode_16(0x471c), Instruction::BXNS { rm: Reg::R3 });
+}
+
+#[test]
+fn test_decode_blxns() {
+    // 47a4            blxns   r4
+    assert_eq!(decode_16(0x47a4), Instruction::BLXNS { rm: Reg::R4 });
+}
+
+#[test]
+fn test_decode_tt() {
+    // e842 f100       tt      r1, r2
+    assert_eq!(
+        decode_32(0xe842_f100),
+        Instruction::TT {
+            params: TtParams {
+                rd: Reg::R1,
+                rn: Reg::R2,
+                a: false,
+                t: false,
+            }
+        }
+    );
+    // e842 f1c0       ttat    r1, r2
+    assert_eq!(
+        decode_32(0xe842_f1c0),
+        Instruction::TT {
+            params: TtParams {
+                rd: Reg::R1,
+                rn: Reg::R2,
+                a: true,
+                t: true,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_lda() {
+    // e8d2 1faf       lda     r1, [r2]
+    assert_eq!(
+        decode_32(0xe8d2_1faf),
+        Instruction::LDA {
+            params: Reg2RtRnParams {
+                rt: Reg::R1,
+                rn: Reg::R2,
+            }
+        }
+    );
+    // e8d2 1fcf       ldaexb  r1, [r2]
+    assert_eq!(
+        decode_32(0xe8d2_1fcf),
+        Instruction::LDAEXB {
+            params: Reg2RtRnParams {
+                rt: Reg::R1,
+                rn: Reg::R2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_stl() {
+    // e8c2 1f9f       stlh    r1, [r2]
+    assert_eq!(
+        decode_32(0xe8c2_1f9f),
+        Instruction::STLH {
+            params: Reg2RtRnParams {
+                rt: Reg::R1,
+                rn: Reg::R2,
+            }
+        }
+    );
+    // e8c2 1fe3       stlex   r3, r1, [r2]
+    assert_eq!(
+        decode_32(0xe8c2_1fe3),
+        Instruction::STLEX {
+            params: Reg3RdRtRnParams {
+                rd: Reg::R3,
+                rt: Reg::R1,
+                rn: Reg::R2,
+            }
+        }
+    );
+}
+
+#[test]
+fn test_decode_armv8m_profile() {
+    let mut processor = Processor::new();
+
+    // sdiv r0, r1, r2 and movw r0, #0x1234 are in Armv8-M Baseline, not in Armv6-M
+    let sdiv = ThumbCode::from(0xfb91_f0f2_u32);
+    let movw = ThumbCode::from(0xf241_2034_u32);
+    processor.profile(ArchProfile::CORTEX_M0);
+    assert!(matches!(processor.decode(sdiv), Instruction::UDF { .. }));
+    assert!(matches!(processor.decode(movw), Instruction::UDF { .. }));
+    processor.profile(ArchProfile::CORTEX_M23);
+    assert!(matches!(processor.decode(sdiv), Instruction::SDIV { .. }));
+    assert!(matches!(
+        processor.decode(movw),
+        Instruction::MOV_imm { .. }
+    ));
+
+    // ldr.w is Mainline only
+    let ldr_w = ThumbCode::from(0xf850_1b04_u32);
+    assert!(matches!(processor.decode(ldr_w), Instruction::UDF { .. }));
+
+    // sg needs the security extension
+    let sg = ThumbCode::from(0xe97f_e97f_u32);
+    assert_eq!(processor.decode(sg), Instruction::SG);
+    processor.profile(ArchProfile::CORTEX_M4);
+    assert!(matches!(processor.decode(sg), Instruction::UDF { .. }));
+}
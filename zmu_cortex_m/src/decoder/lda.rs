@@ -0,0 +1,62 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg2RtRnParams};
+
+#[allow(non_snake_case)]
+pub fn decode_LDA_t1(opcode: u32) -> Instruction {
+    Instruction::LDA {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_LDAB_t1(opcode: u32) -> Instruction {
+    Instruction::LDAB {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_LDAH_t1(opcode: u32) -> Instruction {
+    Instruction::LDAH {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_LDAEX_t1(opcode: u32) -> Instruction {
+    Instruction::LDAEX {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_LDAEXB_t1(opcode: u32) -> Instruction {
+    Instruction::LDAEXB {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_LDAEXH_t1(opcode: u32) -> Instruction {
+    Instruction::LDAEXH {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
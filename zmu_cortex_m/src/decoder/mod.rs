@@ -4,7 +4,8 @@
 //!
 
 use crate::core::bits::Bits;
-use crate::core::instruction::Instruction;
+use crate::core::instruction::{Imm32Carry, Instruction, RegImmCarryParams};
+use crate::core::profile::ArchProfile;
 
 //#[cfg(test)]
 //use crate::core::register::SpecialReg;
@@ -38,6 +39,8 @@ mod bkpt;
 mod bl;
 mod blx;
 mod bx;
+mod bxns;
+mod blxns;
 
 mod clz;
 mod cmn;
@@ -53,6 +56,7 @@ mod eor;
 mod isb;
 mod it;
 
+mod lda;
 mod ldc;
 mod ldm;
 mod ldr;
@@ -76,11 +80,15 @@ mod nop;
 mod orn;
 mod orr;
 
+mod parallel_add_sub;
+mod pkh;
 mod pld;
 mod pli;
 mod pop;
 mod push;
 
+mod qadd;
+
 mod rbit;
 mod rev;
 mod ror;
@@ -91,28 +99,37 @@ mod sbc;
 mod sdiv;
 mod sel;
 mod sev;
+mod sg;
 mod smla;
+mod smlad;
 mod smlal;
+mod smlaw;
+mod smmla;
+mod smuad;
 mod smul;
 mod smull;
 mod stc;
+mod stl;
 mod stm;
 mod str;
 mod strex;
 mod sub;
 mod sxt;
+mod sxtab;
 
 mod tbb;
 mod tbh;
 mod teq;
 mod tst;
+mod tt;
 
 mod movt;
-mod uadd8;
 mod udiv;
+mod umaal;
 mod umlal;
 mod umull;
 mod uxt;
+mod usad8;
 mod uxtab;
 
 mod vabs;
@@ -121,11 +138,17 @@ mod vcmp;
 mod vldr;
 mod vmov;
 mod vmrs;
+mod vmsr;
 mod vpop;
 mod vpush;
 mod vstm;
 mod vstr;
 mod vcvt;
+mod vfp_arith;
+mod vneg;
+mod vrint;
+mod vsel;
+mod vsqrt;
 
 use {
     crate::decoder::str::{
@@ -151,7 +174,9 @@ use {
     bkpt::decode_BKPT_t1,
     bl::decode_BL_t1,
     blx::decode_BLX_t1,
+    blxns::decode_BLXNS_t1,
     bx::decode_BX_t1,
+    bxns::decode_BXNS_t1,
     cbz::decode_CBZ_t1,
     clrex::decode_CLREX_t1,
     clz::decode_CLZ_t1,
@@ -168,6 +193,10 @@ use {
     eor::{decode_EOR_imm_t1, decode_EOR_reg_t1, decode_EOR_reg_t2},
     isb::decode_ISB_t1,
     it::decode_IT_t1,
+    lda::{
+        decode_LDAB_t1, decode_LDAEXB_t1, decode_LDAEXH_t1, decode_LDAEX_t1, decode_LDAH_t1,
+        decode_LDA_t1,
+    },
     ldc::{decode_LDC2_imm_t2, decode_LDC2_lit_t2, decode_LDC_imm_t1, decode_LDC_lit_t1},
     ldm::{decode_LDMDB_t1, decode_LDM_t1, decode_LDM_t2},
     ldr::{
@@ -212,10 +241,22 @@ use {
     nop::{decode_NOP_t1, decode_NOP_t2},
     orn::{decode_ORN_imm_t1, decode_ORN_reg_t1},
     orr::{decode_ORR_imm_t1, decode_ORR_reg_t1, decode_ORR_reg_t2},
+    parallel_add_sub::{
+        decode_QADD16_t1, decode_QADD8_t1, decode_QASX_t1, decode_QSAX_t1, decode_QSUB16_t1,
+        decode_QSUB8_t1, decode_SADD16_t1, decode_SADD8_t1, decode_SASX_t1, decode_SHADD16_t1,
+        decode_SHADD8_t1, decode_SHASX_t1, decode_SHSAX_t1, decode_SHSUB16_t1, decode_SHSUB8_t1,
+        decode_SSAX_t1, decode_SSUB16_t1, decode_SSUB8_t1, decode_UADD16_t1, decode_UADD8_t1,
+        decode_UASX_t1, decode_UHADD16_t1, decode_UHADD8_t1, decode_UHASX_t1, decode_UHSAX_t1,
+        decode_UHSUB16_t1, decode_UHSUB8_t1, decode_UQADD16_t1, decode_UQADD8_t1, decode_UQASX_t1,
+        decode_UQSAX_t1, decode_UQSUB16_t1, decode_UQSUB8_t1, decode_USAX_t1, decode_USUB16_t1,
+        decode_USUB8_t1,
+    },
+    pkh::{decode_PKHBT_t1, decode_PKHTB_t1},
     pld::{decode_PLD_imm_t1, decode_PLD_imm_t2, decode_PLD_lit_t1, decode_PLD_reg_t1},
     pli::{decode_PLI_lit_imm_t1, decode_PLI_lit_imm_t2, decode_PLI_lit_imm_t3, decode_PLI_reg_t1},
     pop::{decode_POP_reg_t1, decode_POP_t2, decode_POP_t3},
     push::{decode_PUSH_t1, decode_PUSH_t2, decode_PUSH_t3},
+    qadd::{decode_QADD_t1, decode_QDADD_t1, decode_QDSUB_t1, decode_QSUB_t1},
     rbit::decode_RBIT_t1,
     rev::{
         decode_REV16_t1, decode_REV16_t2, decode_REVSH_t1, decode_REVSH_t2, decode_REV_t1,
@@ -229,12 +270,21 @@ use {
     sdiv::decode_SDIV_t1,
     sel::decode_SEL_t1,
     sev::{decode_SEV_t1, decode_SEV_t2},
+    sg::decode_SG_t1,
     smla::decode_SMLA_t1,
-    smlal::decode_SMLAL_t1,
+    smlad::{decode_SMLAD_t1, decode_SMLSD_t1},
+    smlal::{decode_SMLALD_t1, decode_SMLALxy_t1, decode_SMLAL_t1, decode_SMLSLD_t1},
+    smlaw::{decode_SMLAW_t1, decode_SMULW_t1},
+    smmla::{decode_SMMLA_t1, decode_SMMLS_t1, decode_SMMUL_t1},
+    smuad::{decode_SMUAD_t1, decode_SMUSD_t1},
     smul::decode_SMUL_t1,
     smull::decode_SMULL_t1,
-    ssat::decode_SSAT_t1,
+    ssat::{decode_SSAT16_t1, decode_SSAT_t1},
     stc::{decode_STC2_t2, decode_STC_t1},
+    stl::{
+        decode_STLB_t1, decode_STLEXB_t1, decode_STLEXH_t1, decode_STLEX_t1, decode_STLH_t1,
+        decode_STL_t1,
+    },
     stm::{decode_STMDB_t1, decode_STM_t1, decode_STM_t2},
     strex::{decode_STREXB_t1, decode_STREXH_t1, decode_STREX_t1},
     sub::{
@@ -242,19 +292,22 @@ use {
         decode_SUB_imm_t2, decode_SUB_imm_t3, decode_SUB_imm_t4, decode_SUB_reg_t1,
         decode_SUB_reg_t2,
     },
-    sxt::{decode_SXTB_t1, decode_SXTB_t2, decode_SXTH_t1, decode_SXTH_t2},
+    sxt::{decode_SXTB16_t1, decode_SXTB_t1, decode_SXTB_t2, decode_SXTH_t1, decode_SXTH_t2},
+    sxtab::{decode_SXTAB16_t1, decode_SXTAB_t1, decode_SXTAH_t1},
     tbb::decode_TBB_t1,
     tbh::decode_TBH_t1,
     teq::{decode_TEQ_imm_t1, decode_TEQ_reg_t1},
     tst::{decode_TST_imm_t1, decode_TST_reg_t1, decode_TST_reg_t2},
-    uadd8::decode_UADD8_t1,
+    tt::decode_TT_t1,
     ubfx::decode_UBFX_t1,
     udiv::decode_UDIV_t1,
+    umaal::decode_UMAAL_t1,
     umlal::decode_UMLAL_t1,
     umull::decode_UMULL_t1,
-    usat::decode_USAT_t1,
-    uxt::{decode_UXTB_t1, decode_UXTB_t2, decode_UXTH_t1, decode_UXTH_t2},
-    uxtab::decode_UXTAB_t1,
+    usad8::{decode_USAD8_t1, decode_USADA8_t1},
+    usat::{decode_USAT16_t1, decode_USAT_t1},
+    uxt::{decode_UXTB16_t1, decode_UXTB_t1, decode_UXTB_t2, decode_UXTH_t1, decode_UXTH_t2},
+    uxtab::{decode_UXTAB16_t1, decode_UXTAB_t1, decode_UXTAH_t1},
     wfe::{decode_WFE_t1, decode_WFE_t2},
     wfi::{decode_WFI_t1, decode_WFI_t2},
     yield_::{decode_YIELD_t1, decode_YIELD_t2},
@@ -265,6 +318,18 @@ use crate::Processor;
 use {
     vabs::decode_VABS_t1,
     vadd_vsub::{decode_VADD_t1, decode_VSUB_t1},
+    vfp_arith::{
+        decode_VDIV_t1, decode_VFMA_t1, decode_VFMS_t1, decode_VFNMA_t1, decode_VFNMS_t1,
+        decode_VMAXNM_t1, decode_VMINNM_t1, decode_VMLA_t1, decode_VMLS_t1, decode_VMUL_t1,
+        decode_VNMLA_t1, decode_VNMLS_t1, decode_VNMUL_t1,
+    },
+    vneg::decode_VNEG_t1,
+    vrint::{
+        decode_VRINTA_t1, decode_VRINTM_t1, decode_VRINTN_t1, decode_VRINTP_t1, decode_VRINTR_t1,
+        decode_VRINTX_t1, decode_VRINTZ_t1,
+    },
+    vsel::decode_VSEL_t1,
+    vsqrt::decode_VSQRT_t1,
     vcmp::{decode_VCMP_t1, decode_VCMP_t2},
     vldr::{decode_VLDR_t1, decode_VLDR_t2},
     vmov::decode_VMOV_cr2_dp,
@@ -275,13 +340,17 @@ use {
     vmov::decode_VMOV_reg,
     vmov::decode_VMOV_scalar_cr,
     vmrs::decode_VMRS,
+    vmsr::decode_VMSR,
     vpop::decode_VPOP_t1,
     vpop::decode_VPOP_t2,
     vpush::decode_VPUSH_t1,
     vpush::decode_VPUSH_t2,
     vstm::{decode_VSTM_t1, decode_VSTM_t2},
     vstr::{decode_VSTR_t1, decode_VSTR_t2},
-    vcvt::decode_VCVT_t1
+    vcvt::{
+        decode_VCVTA_t1, decode_VCVTM_t1, decode_VCVTN_t1, decode_VCVTP_t1, decode_VCVT_ds_t1,
+        decode_VCVT_t1,
+    },
 };
 
 ///
@@ -296,11 +365,220 @@ pub trait Decoder {
 
 impl Decoder for Processor {
     fn decode(&self, code: ThumbCode) -> Instruction {
-        match code {
-            ThumbCode::Thumb32 { opcode } => decode_32(opcode),
-            ThumbCode::Thumb16 { opcode } => decode_16(opcode),
-            ThumbCode::Undefined => Instruction::UDF {imm32: 0, opcode: code, thumb32: false},
+        let (instruction, thumb32) = match code {
+            ThumbCode::Thumb32 { opcode } => (decode_32(opcode), true),
+            ThumbCode::Thumb16 { opcode } => (decode_16(opcode), false),
+            ThumbCode::Undefined => return Instruction::UDF {imm32: 0, opcode: code, thumb32: false},
+        };
+        if is_available(self.profile, &instruction, thumb32) {
+            instruction
+        } else {
+            Instruction::UDF {imm32: 0, opcode: code, thumb32}
+        }
+    }
+}
+
+///
+/// Check if the decoded instruction exists in the given architecture profile.
+/// Unavailable instructions are executed as undefined.
+///
+fn is_available(profile: ArchProfile, instruction: &Instruction, thumb32: bool) -> bool {
+    match instruction {
+        Instruction::SG
+        | Instruction::TT { .. }
+        | Instruction::BXNS { .. }
+        | Instruction::BLXNS { .. } => return profile.has_security(),
+        Instruction::LDA { .. }
+        | Instruction::LDAB { .. }
+        | Instruction::LDAH { .. }
+        | Instruction::LDAEX { .. }
+        | Instruction::LDAEXB { .. }
+        | Instruction::LDAEXH { .. }
+        | Instruction::STL { .. }
+        | Instruction::STLB { .. }
+        | Instruction::STLH { .. }
+        | Instruction::STLEX { .. }
+        | Instruction::STLEXB { .. }
+        | Instruction::STLEXH { .. } => return profile.is_armv8m(),
+        _ => (),
+    }
+
+    if !profile.is_mainline() {
+        // Armv6-M has only a handful of 32-bit instructions, Armv8-M Baseline
+        // adds the wide branch, CBZ, MOVW/MOVT, divide and exclusive access
+        return match instruction {
+            Instruction::BL { .. }
+            | Instruction::DMB
+            | Instruction::DSB
+            | Instruction::ISB
+            | Instruction::MRS { .. }
+            | Instruction::MSR_reg { .. }
+            | Instruction::UDF { .. } => true,
+            Instruction::CBZ { .. }
+            | Instruction::CBNZ { .. }
+            | Instruction::MOVT { .. }
+            | Instruction::SDIV { .. }
+            | Instruction::UDIV { .. }
+            | Instruction::CLREX
+            | Instruction::LDREX { .. }
+            | Instruction::LDREXB { .. }
+            | Instruction::LDREXH { .. }
+            | Instruction::STREX { .. }
+            | Instruction::STREXB { .. }
+            | Instruction::STREXH { .. } => profile.is_armv8m(),
+            Instruction::B_t24 { .. } => !thumb32 || profile.is_armv8m(),
+            Instruction::MOV_imm {
+                params:
+                    RegImmCarryParams {
+                        imm32: Imm32Carry::NoCarry { .. },
+                        ..
+                    },
+                ..
+            } if thumb32 => profile.is_armv8m(),
+            Instruction::IT { .. } => false,
+            _ => !thumb32,
+        };
+    }
+
+    match instruction {
+        Instruction::SMUL { .. }
+        | Instruction::SMLA { .. }
+        | Instruction::SMLAD { .. }
+        | Instruction::SMLALxy { .. }
+        | Instruction::SMLALD { .. }
+        | Instruction::SMLAW { .. }
+        | Instruction::SMLSD { .. }
+        | Instruction::SMLSLD { .. }
+        | Instruction::SMMLA { .. }
+        | Instruction::SMMLS { .. }
+        | Instruction::SMMUL { .. }
+        | Instruction::SMUAD { .. }
+        | Instruction::SMULW { .. }
+        | Instruction::SMUSD { .. }
+        | Instruction::UMAAL { .. }
+        | Instruction::SSAT16 { .. }
+        | Instruction::USAT16 { .. }
+        | Instruction::QADD { .. }
+        | Instruction::QSUB { .. }
+        | Instruction::QDADD { .. }
+        | Instruction::QDSUB { .. }
+        | Instruction::PKHBT { .. }
+        | Instruction::PKHTB { .. }
+        | Instruction::SXTAB { .. }
+        | Instruction::SXTAB16 { .. }
+        | Instruction::SXTAH { .. }
+        | Instruction::SXTB16 { .. }
+        | Instruction::UXTAB { .. }
+        | Instruction::UXTAB16 { .. }
+        | Instruction::UXTAH { .. }
+        | Instruction::UXTB16 { .. }
+        | Instruction::SADD16 { .. }
+        | Instruction::QADD16 { .. }
+        | Instruction::SHADD16 { .. }
+        | Instruction::UADD16 { .. }
+        | Instruction::UQADD16 { .. }
+        | Instruction::UHADD16 { .. }
+        | Instruction::SASX { .. }
+        | Instruction::QASX { .. }
+        | Instruction::SHASX { .. }
+        | Instruction::UASX { .. }
+        | Instruction::UQASX { .. }
+        | Instruction::UHASX { .. }
+        | Instruction::SSAX { .. }
+        | Instruction::QSAX { .. }
+        | Instruction::SHSAX { .. }
+        | Instruction::USAX { .. }
+        | Instruction::UQSAX { .. }
+        | Instruction::UHSAX { .. }
+        | Instruction::SSUB16 { .. }
+        | Instruction::QSUB16 { .. }
+        | Instruction::SHSUB16 { .. }
+        | Instruction::USUB16 { .. }
+        | Instruction::UQSUB16 { .. }
+        | Instruction::UHSUB16 { .. }
+        | Instruction::SADD8 { .. }
+        | Instruction::QADD8 { .. }
+        | Instruction::SHADD8 { .. }
+        | Instruction::UADD8 { .. }
+        | Instruction::UQADD8 { .. }
+        | Instruction::UHADD8 { .. }
+        | Instruction::SSUB8 { .. }
+        | Instruction::QSUB8 { .. }
+        | Instruction::SHSUB8 { .. }
+        | Instruction::USUB8 { .. }
+        | Instruction::UQSUB8 { .. }
+        | Instruction::UHSUB8 { .. }
+        | Instruction::SEL { .. }
+        | Instruction::USAD8 { .. }
+        | Instruction::USADA8 { .. } => profile.has_dsp(),
+        Instruction::VCVT_directed { params } if params.m.as_double().is_some() => {
+            profile.has_double_precision()
         }
+        Instruction::VSEL_f32 { .. }
+        | Instruction::VSEL_f64 { .. }
+        | Instruction::VMAXNM_f32 { .. }
+        | Instruction::VMAXNM_f64 { .. }
+        | Instruction::VMINNM_f32 { .. }
+        | Instruction::VMINNM_f64 { .. }
+        | Instruction::VRINT_f32 { .. }
+        | Instruction::VRINT_f64 { .. }
+        | Instruction::VCVT_directed { .. } => profile.has_fpv5(),
+        Instruction::VABS_f64 { .. }
+        | Instruction::VADD_f64 { .. }
+        | Instruction::VCMP_f64 { .. }
+        | Instruction::VSUB_f64 { .. }
+        | Instruction::VMUL_f64 { .. }
+        | Instruction::VNMUL_f64 { .. }
+        | Instruction::VDIV_f64 { .. }
+        | Instruction::VMLA_f64 { .. }
+        | Instruction::VMLS_f64 { .. }
+        | Instruction::VNMLA_f64 { .. }
+        | Instruction::VNMLS_f64 { .. }
+        | Instruction::VFMA_f64 { .. }
+        | Instruction::VFMS_f64 { .. }
+        | Instruction::VFNMA_f64 { .. }
+        | Instruction::VFNMS_f64 { .. }
+        | Instruction::VNEG_f64 { .. }
+        | Instruction::VSQRT_f64 { .. }
+        | Instruction::VMOV_imm_64 { .. }
+        | Instruction::VMOV_reg_f64 { .. }
+        | Instruction::VCVT_f32_f64 { .. }
+        | Instruction::VCVT_f64_f32 { .. } => profile.has_double_precision(),
+        Instruction::VCVT { params } if params.dp_operation => profile.has_double_precision(),
+        Instruction::VLDR { .. }
+        | Instruction::VSTR { .. }
+        | Instruction::VPUSH { .. }
+        | Instruction::VPOP { .. }
+        | Instruction::VSTM_T1 { .. }
+        | Instruction::VSTM_T2 { .. }
+        | Instruction::VMOV_imm_32 { .. }
+        | Instruction::VMOV_reg_f32 { .. }
+        | Instruction::VMOV_cr_scalar { .. }
+        | Instruction::VMOV_scalar_cr { .. }
+        | Instruction::VMOV_cr_sp { .. }
+        | Instruction::VMOV_cr2_sp2 { .. }
+        | Instruction::VMOV_cr2_dp { .. }
+        | Instruction::VMRS { .. }
+        | Instruction::VMSR { .. }
+        | Instruction::VABS_f32 { .. }
+        | Instruction::VADD_f32 { .. }
+        | Instruction::VCMP_f32 { .. }
+        | Instruction::VCVT { .. }
+        | Instruction::VSUB_f32 { .. }
+        | Instruction::VMUL_f32 { .. }
+        | Instruction::VNMUL_f32 { .. }
+        | Instruction::VDIV_f32 { .. }
+        | Instruction::VMLA_f32 { .. }
+        | Instruction::VMLS_f32 { .. }
+        | Instruction::VNMLA_f32 { .. }
+        | Instruction::VNMLS_f32 { .. }
+        | Instruction::VFMA_f32 { .. }
+        | Instruction::VFMS_f32 { .. }
+        | Instruction::VFNMA_f32 { .. }
+        | Instruction::VFNMS_f32 { .. }
+        | Instruction::VNEG_f32 { .. }
+        | Instruction::VSQRT_f32 { .. } => profile.has_fpu(),
+        _ => true,
     }
 }
 
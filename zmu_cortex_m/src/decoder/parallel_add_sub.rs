@@ -0,0 +1,262 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg3NoSetFlagsParams};
+
+fn decode_params(opcode: u32) -> Reg3NoSetFlagsParams {
+    Reg3NoSetFlagsParams {
+        rd: opcode.get_bits(8..12).into(),
+        rn: opcode.get_bits(16..20).into(),
+        rm: opcode.get_bits(0..4).into(),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SADD16_t1(opcode: u32) -> Instruction {
+    Instruction::SADD16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QADD16_t1(opcode: u32) -> Instruction {
+    Instruction::QADD16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SHADD16_t1(opcode: u32) -> Instruction {
+    Instruction::SHADD16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UADD16_t1(opcode: u32) -> Instruction {
+    Instruction::UADD16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UQADD16_t1(opcode: u32) -> Instruction {
+    Instruction::UQADD16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UHADD16_t1(opcode: u32) -> Instruction {
+    Instruction::UHADD16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SASX_t1(opcode: u32) -> Instruction {
+    Instruction::SASX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QASX_t1(opcode: u32) -> Instruction {
+    Instruction::QASX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SHASX_t1(opcode: u32) -> Instruction {
+    Instruction::SHASX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UASX_t1(opcode: u32) -> Instruction {
+    Instruction::UASX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UQASX_t1(opcode: u32) -> Instruction {
+    Instruction::UQASX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UHASX_t1(opcode: u32) -> Instruction {
+    Instruction::UHASX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SSAX_t1(opcode: u32) -> Instruction {
+    Instruction::SSAX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QSAX_t1(opcode: u32) -> Instruction {
+    Instruction::QSAX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SHSAX_t1(opcode: u32) -> Instruction {
+    Instruction::SHSAX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_USAX_t1(opcode: u32) -> Instruction {
+    Instruction::USAX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UQSAX_t1(opcode: u32) -> Instruction {
+    Instruction::UQSAX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UHSAX_t1(opcode: u32) -> Instruction {
+    Instruction::UHSAX {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SSUB16_t1(opcode: u32) -> Instruction {
+    Instruction::SSUB16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QSUB16_t1(opcode: u32) -> Instruction {
+    Instruction::QSUB16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SHSUB16_t1(opcode: u32) -> Instruction {
+    Instruction::SHSUB16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_USUB16_t1(opcode: u32) -> Instruction {
+    Instruction::USUB16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UQSUB16_t1(opcode: u32) -> Instruction {
+    Instruction::UQSUB16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UHSUB16_t1(opcode: u32) -> Instruction {
+    Instruction::UHSUB16 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SADD8_t1(opcode: u32) -> Instruction {
+    Instruction::SADD8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QADD8_t1(opcode: u32) -> Instruction {
+    Instruction::QADD8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SHADD8_t1(opcode: u32) -> Instruction {
+    Instruction::SHADD8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UADD8_t1(opcode: u32) -> Instruction {
+    Instruction::UADD8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UQADD8_t1(opcode: u32) -> Instruction {
+    Instruction::UQADD8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UHADD8_t1(opcode: u32) -> Instruction {
+    Instruction::UHADD8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SSUB8_t1(opcode: u32) -> Instruction {
+    Instruction::SSUB8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QSUB8_t1(opcode: u32) -> Instruction {
+    Instruction::QSUB8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SHSUB8_t1(opcode: u32) -> Instruction {
+    Instruction::SHSUB8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_USUB8_t1(opcode: u32) -> Instruction {
+    Instruction::USUB8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UQSUB8_t1(opcode: u32) -> Instruction {
+    Instruction::UQSUB8 {
+        params: decode_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_UHSUB8_t1(opcode: u32) -> Instruction {
+    Instruction::UHSUB8 {
+        params: decode_params(opcode),
+    }
+}
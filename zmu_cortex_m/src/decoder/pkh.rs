@@ -0,0 +1,35 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg3ShiftNoSetFlagsParams};
+use crate::core::operation::decode_imm_shift;
+use crate::core::register::Reg;
+
+#[allow(non_snake_case)]
+fn decode_PKH_params(opcode: u32) -> Reg3ShiftNoSetFlagsParams {
+    let imm3: u8 = opcode.get_bits(12..15) as u8;
+    let imm2: u8 = opcode.get_bits(6..8) as u8;
+    let tb: u8 = u8::from(opcode.get_bit(5));
+
+    let (shift_t, shift_n) = decode_imm_shift(tb << 1, (imm3 << 2) + imm2);
+
+    Reg3ShiftNoSetFlagsParams {
+        rd: Reg::from(opcode.get_bits(8..12) as u8),
+        rn: Reg::from(opcode.get_bits(16..20) as u8),
+        rm: Reg::from(opcode.get_bits(0..4) as u8),
+        shift_t,
+        shift_n,
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_PKHBT_t1(opcode: u32) -> Instruction {
+    Instruction::PKHBT {
+        params: decode_PKH_params(opcode),
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_PKHTB_t1(opcode: u32) -> Instruction {
+    Instruction::PKHTB {
+        params: decode_PKH_params(opcode),
+    }
+}
@@ -0,0 +1,47 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg3NoSetFlagsParams};
+use crate::core::register::Reg;
+
+#[allow(non_snake_case)]
+pub fn decode_QADD_t1(opcode: u32) -> Instruction {
+    Instruction::QADD {
+        params: Reg3NoSetFlagsParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QSUB_t1(opcode: u32) -> Instruction {
+    Instruction::QSUB {
+        params: Reg3NoSetFlagsParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QDADD_t1(opcode: u32) -> Instruction {
+    Instruction::QDADD {
+        params: Reg3NoSetFlagsParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_QDSUB_t1(opcode: u32) -> Instruction {
+    Instruction::QDSUB {
+        params: Reg3NoSetFlagsParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+        },
+    }
+}
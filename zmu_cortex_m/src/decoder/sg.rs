@@ -0,0 +1,6 @@
+use crate::core::instruction::Instruction;
+
+#[allow(non_snake_case)]
+pub fn decode_SG_t1(_opcode: u32) -> Instruction {
+    Instruction::SG
+}
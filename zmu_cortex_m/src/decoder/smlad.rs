@@ -0,0 +1,29 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg4DualParams};
+use crate::core::register::Reg;
+
+#[allow(non_snake_case)]
+pub fn decode_SMLAD_t1(opcode: u32) -> Instruction {
+    Instruction::SMLAD {
+        params: Reg4DualParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            ra: Reg::from(opcode.get_bits(12..16) as u8),
+            m_swap: opcode.get_bit(4),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SMLSD_t1(opcode: u32) -> Instruction {
+    Instruction::SMLSD {
+        params: Reg4DualParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            ra: Reg::from(opcode.get_bits(12..16) as u8),
+            m_swap: opcode.get_bit(4),
+        },
+    }
+}
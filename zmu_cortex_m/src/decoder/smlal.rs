@@ -1,6 +1,6 @@
 use crate::core::{
     bits::Bits,
-    instruction::{Instruction, Reg643232Params},
+    instruction::{Instruction, Reg643232DualParams, Reg643232HighParams, Reg643232Params},
     register::Reg,
 };
 
@@ -13,9 +13,49 @@ pub fn decode_SMLAL_t1(opcode: u32) -> Instruction {
     Instruction::SMLAL {
         params: Reg643232Params {
             rm: Reg::from(reg_rm),
-            rdlo: Reg::from(reg_rd_hi),
-            rdhi: Reg::from(reg_rd_lo),
+            rdlo: Reg::from(reg_rd_lo),
+            rdhi: Reg::from(reg_rd_hi),
             rn: Reg::from(reg_rn),
         },
     }
 }
+
+#[allow(non_snake_case)]
+pub fn decode_SMLALxy_t1(opcode: u32) -> Instruction {
+    Instruction::SMLALxy {
+        params: Reg643232HighParams {
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            rdhi: Reg::from(opcode.get_bits(8..12) as u8),
+            rdlo: Reg::from(opcode.get_bits(12..16) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            m_high: opcode.get_bit(4),
+            n_high: opcode.get_bit(5),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SMLALD_t1(opcode: u32) -> Instruction {
+    Instruction::SMLALD {
+        params: Reg643232DualParams {
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            rdhi: Reg::from(opcode.get_bits(8..12) as u8),
+            rdlo: Reg::from(opcode.get_bits(12..16) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            m_swap: opcode.get_bit(4),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SMLSLD_t1(opcode: u32) -> Instruction {
+    Instruction::SMLSLD {
+        params: Reg643232DualParams {
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            rdhi: Reg::from(opcode.get_bits(8..12) as u8),
+            rdlo: Reg::from(opcode.get_bits(12..16) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            m_swap: opcode.get_bit(4),
+        },
+    }
+}
@@ -0,0 +1,28 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg3MHighParams, Reg4MHighParams};
+use crate::core::register::Reg;
+
+#[allow(non_snake_case)]
+pub fn decode_SMLAW_t1(opcode: u32) -> Instruction {
+    Instruction::SMLAW {
+        params: Reg4MHighParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            ra: Reg::from(opcode.get_bits(12..16) as u8),
+            m_high: opcode.get_bit(4),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SMULW_t1(opcode: u32) -> Instruction {
+    Instruction::SMULW {
+        params: Reg3MHighParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            m_high: opcode.get_bit(4),
+        },
+    }
+}
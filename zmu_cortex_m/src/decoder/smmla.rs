@@ -0,0 +1,41 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg3RoundParams, Reg4RoundParams};
+use crate::core::register::Reg;
+
+#[allow(non_snake_case)]
+pub fn decode_SMMLA_t1(opcode: u32) -> Instruction {
+    Instruction::SMMLA {
+        params: Reg4RoundParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            ra: Reg::from(opcode.get_bits(12..16) as u8),
+            round: opcode.get_bit(4),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SMMLS_t1(opcode: u32) -> Instruction {
+    Instruction::SMMLS {
+        params: Reg4RoundParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            ra: Reg::from(opcode.get_bits(12..16) as u8),
+            round: opcode.get_bit(4),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SMMUL_t1(opcode: u32) -> Instruction {
+    Instruction::SMMUL {
+        params: Reg3RoundParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            round: opcode.get_bit(4),
+        },
+    }
+}
@@ -0,0 +1,27 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg3DualParams};
+use crate::core::register::Reg;
+
+#[allow(non_snake_case)]
+pub fn decode_SMUAD_t1(opcode: u32) -> Instruction {
+    Instruction::SMUAD {
+        params: Reg3DualParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            m_swap: opcode.get_bit(4),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SMUSD_t1(opcode: u32) -> Instruction {
+    Instruction::SMUSD {
+        params: Reg3DualParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            m_swap: opcode.get_bit(4),
+        },
+    }
+}
@@ -1,10 +1,32 @@
-use crate::core::instruction::Instruction;
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Sat16Params, SatParams};
+use crate::core::operation::decode_imm_shift;
+use crate::core::register::Reg;
 
 #[allow(non_snake_case)]
 pub fn decode_SSAT_t1(opcode: u32) -> Instruction {
-    Instruction::UDF {
-        imm32: 0,
-        opcode: opcode.into(),
-        thumb32: true,
+    let imm3 = opcode.get_bits(12..15) as u8;
+    let imm2 = opcode.get_bits(6..8) as u8;
+    let sh = u8::from(opcode.get_bit(21));
+    let (shift_t, shift_n) = decode_imm_shift(sh << 1, (imm3 << 2) + imm2);
+    Instruction::SSAT {
+        params: SatParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            saturate_to: opcode.get_bits(0..5) as u8 + 1,
+            shift_t,
+            shift_n,
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_SSAT16_t1(opcode: u32) -> Instruction {
+    Instruction::SSAT16 {
+        params: Sat16Params {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            saturate_to: opcode.get_bits(0..4) as u8 + 1,
+        },
     }
 }
@@ -0,0 +1,65 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg2RtRnParams, Reg3RdRtRnParams};
+
+#[allow(non_snake_case)]
+pub fn decode_STL_t1(opcode: u32) -> Instruction {
+    Instruction::STL {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_STLB_t1(opcode: u32) -> Instruction {
+    Instruction::STLB {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_STLH_t1(opcode: u32) -> Instruction {
+    Instruction::STLH {
+        params: Reg2RtRnParams {
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_STLEX_t1(opcode: u32) -> Instruction {
+    Instruction::STLEX {
+        params: Reg3RdRtRnParams {
+            rd: From::from(opcode.get_bits(0..4) as u8),
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_STLEXB_t1(opcode: u32) -> Instruction {
+    Instruction::STLEXB {
+        params: Reg3RdRtRnParams {
+            rd: From::from(opcode.get_bits(0..4) as u8),
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_STLEXH_t1(opcode: u32) -> Instruction {
+    Instruction::STLEXH {
+        params: Reg3RdRtRnParams {
+            rd: From::from(opcode.get_bits(0..4) as u8),
+            rt: From::from(opcode.get_bits(12..16) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+        },
+    }
+}
@@ -51,3 +51,14 @@ pub fn decode_SXTH_t2(opcode: u32) -> Instruction {
         thumb32: true,
     }
 }
+
+#[allow(non_snake_case)]
+pub fn decode_SXTB16_t1(opcode: u32) -> Instruction {
+    Instruction::SXTB16 {
+        params: Reg2UsizeParams {
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rotation: ((opcode.get_bits(4..6) as u8) << 3) as usize,
+        },
+    }
+}
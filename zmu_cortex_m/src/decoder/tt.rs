@@ -0,0 +1,14 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, TtParams};
+
+#[allow(non_snake_case)]
+pub fn decode_TT_t1(opcode: u32) -> Instruction {
+    Instruction::TT {
+        params: TtParams {
+            rd: From::from(opcode.get_bits(8..12) as u8),
+            rn: From::from(opcode.get_bits(16..20) as u8),
+            a: opcode.get_bit(7),
+            t: opcode.get_bit(6),
+        },
+    }
+}
@@ -0,0 +1,26 @@
+use crate::core::bits::Bits;
+use crate::core::instruction::{Instruction, Reg3NoSetFlagsParams, Reg4NoSetFlagsParams};
+use crate::core::register::Reg;
+
+#[allow(non_snake_case)]
+pub fn decode_USAD8_t1(opcode: u32) -> Instruction {
+    Instruction::USAD8 {
+        params: Reg3NoSetFlagsParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+pub fn decode_USADA8_t1(opcode: u32) -> Instruction {
+    Instruction::USADA8 {
+        params: Reg4NoSetFlagsParams {
+            rd: Reg::from(opcode.get_bits(8..12) as u8),
+            rn: Reg::from(opcode.get_bits(16..20) as u8),
+            rm: Reg::from(opcode.get_bits(0..4) as u8),
+            ra: Reg::from(opcode.get_bits(12..16) as u8),
+        },
+    }
+}
@@ -1,6 +1,8 @@
 use crate::core::{
     bits::Bits,
-    instruction::{Instruction, VCVTParams},
+    instruction::{
+        Instruction, VCVTDirectedParams, VCVTF32F64Params, VCVTF64F32Params, VCVTParams, VRintMode,
+    },
     register::{DoubleReg, ExtensionReg, SingleReg},
 };
 
@@ -79,3 +81,80 @@ pub fn decode_VCVT_t1(opcode: u32) -> Instruction {
         }
     }
 }
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VCVT_ds_t1(opcode: u32) -> Instruction {
+    let vm = opcode.get_bits(0..4) as u8;
+    let M = u8::from(opcode.get_bit(5));
+    let double_to_single = opcode.get_bit(8);
+    let vd = opcode.get_bits(12..16) as u8;
+    let D = u8::from(opcode.get_bit(22));
+
+    if double_to_single {
+        Instruction::VCVT_f32_f64 {
+            params: VCVTF32F64Params {
+                sd: SingleReg::from(vd << 1 | D),
+                dm: DoubleReg::from(M << 4 | vm),
+            },
+        }
+    } else {
+        Instruction::VCVT_f64_f32 {
+            params: VCVTF64F32Params {
+                dd: DoubleReg::from(D << 4 | vd),
+                sm: SingleReg::from(vm << 1 | M),
+            },
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn decode_VCVT_directed(opcode: u32, mode: VRintMode) -> Instruction {
+    let vm = opcode.get_bits(0..4) as u8;
+    let M = u8::from(opcode.get_bit(5));
+    let signed = opcode.get_bit(7);
+    let sz = opcode.get_bit(8);
+    let vd = opcode.get_bits(12..16) as u8;
+    let D = u8::from(opcode.get_bit(22));
+
+    Instruction::VCVT_directed {
+        params: VCVTDirectedParams {
+            d: SingleReg::from(vd << 1 | D),
+            m: if sz {
+                ExtensionReg::Double {
+                    reg: DoubleReg::from(M << 4 | vm),
+                }
+            } else {
+                ExtensionReg::Single {
+                    reg: SingleReg::from(vm << 1 | M),
+                }
+            },
+            unsigned: !signed,
+            mode,
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VCVTA_t1(opcode: u32) -> Instruction {
+    decode_VCVT_directed(opcode, VRintMode::A)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VCVTN_t1(opcode: u32) -> Instruction {
+    decode_VCVT_directed(opcode, VRintMode::N)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VCVTP_t1(opcode: u32) -> Instruction {
+    decode_VCVT_directed(opcode, VRintMode::P)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VCVTM_t1(opcode: u32) -> Instruction {
+    decode_VCVT_directed(opcode, VRintMode::M)
+}
@@ -0,0 +1,219 @@
+use crate::core::{
+    bits::Bits,
+    instruction::{Instruction, VAddSubParamsf32, VAddSubParamsf64},
+    register::{DoubleReg, SingleReg},
+};
+
+#[allow(non_snake_case)]
+fn decode_params_f32(opcode: u32) -> VAddSubParamsf32 {
+    let D = u8::from(opcode.get_bit(22));
+    let vn = opcode.get_bits(16..20) as u8;
+    let vd = opcode.get_bits(12..16) as u8;
+    let N = u8::from(opcode.get_bit(7));
+    let M = u8::from(opcode.get_bit(5));
+    let vm = opcode.get_bits(0..4) as u8;
+
+    VAddSubParamsf32 {
+        sd: SingleReg::from(vd << 1 | D),
+        sn: SingleReg::from(vn << 1 | N),
+        sm: SingleReg::from(vm << 1 | M),
+    }
+}
+
+#[allow(non_snake_case)]
+fn decode_params_f64(opcode: u32) -> VAddSubParamsf64 {
+    let D = u8::from(opcode.get_bit(22));
+    let vn = opcode.get_bits(16..20) as u8;
+    let vd = opcode.get_bits(12..16) as u8;
+    let N = u8::from(opcode.get_bit(7));
+    let M = u8::from(opcode.get_bit(5));
+    let vm = opcode.get_bits(0..4) as u8;
+
+    VAddSubParamsf64 {
+        dd: DoubleReg::from(D << 4 | vd),
+        dn: DoubleReg::from(N << 4 | vn),
+        dm: DoubleReg::from(M << 4 | vm),
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VMUL_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VMUL_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VMUL_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VNMUL_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VNMUL_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VNMUL_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VDIV_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VDIV_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VDIV_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VMLA_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VMLA_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VMLA_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VMLS_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VMLS_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VMLS_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VNMLA_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VNMLA_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VNMLA_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VNMLS_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VNMLS_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VNMLS_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VFMA_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VFMA_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VFMA_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VFMS_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VFMS_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VFMS_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VFNMA_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VFNMA_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VFNMA_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VFNMS_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VFNMS_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VFNMS_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VMAXNM_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VMAXNM_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VMAXNM_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VMINNM_t1(opcode: u32) -> Instruction {
+    if opcode.get_bit(8) {
+        Instruction::VMINNM_f64 {
+            params: decode_params_f64(opcode),
+        }
+    } else {
+        Instruction::VMINNM_f32 {
+            params: decode_params_f32(opcode),
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use crate::core::{
     bits::Bits,
     instruction::{
-        Instruction, VMovCr2DpParams, VMovCrSpParams, VMovImmParams32, VMovImmParams64,
-        VMovRegParamsf32, VMovRegParamsf64,
+        Instruction, VMovCr2DpParams, VMovCr2Sp2Params, VMovCrScalarParams, VMovCrSpParams,
+        VMovImmParams32, VMovImmParams64, VMovRegParamsf32, VMovRegParamsf64,
     },
     register::{DoubleReg, Reg, SingleReg},
 };
@@ -141,14 +141,47 @@ pub fn decode_VMOV_reg(opcode: u32) -> Instruction {
 
 #[allow(non_snake_case)]
 #[inline(always)]
-pub fn decode_VMOV_cr_scalar(_opcode: u32) -> Instruction {
-    unimplemented!()
+pub fn decode_VMOV_cr_scalar(opcode: u32) -> Instruction {
+    match decode_scalar_params(opcode) {
+        Some(params) => Instruction::VMOV_cr_scalar { params },
+        None => Instruction::UDF {
+            opcode: opcode.into(),
+            thumb32: true,
+            imm32: 0,
+        },
+    }
 }
 
 #[allow(non_snake_case)]
 #[inline(always)]
-pub fn decode_VMOV_scalar_cr(_opcode: u32) -> Instruction {
-    unimplemented!()
+pub fn decode_VMOV_scalar_cr(opcode: u32) -> Instruction {
+    match decode_scalar_params(opcode) {
+        Some(params) => Instruction::VMOV_scalar_cr { params },
+        None => Instruction::UDF {
+            opcode: opcode.into(),
+            thumb32: true,
+            imm32: 0,
+        },
+    }
+}
+
+#[allow(non_snake_case)]
+fn decode_scalar_params(opcode: u32) -> Option<VMovCrScalarParams> {
+    let rt = Reg::from(opcode.get_bits(12..16) as u8);
+
+    // rt 15 and 13 are unpredictable:
+    if rt == Reg::PC || rt == Reg::SP {
+        return None;
+    }
+
+    let D = u8::from(opcode.get_bit(7));
+    let vd = opcode.get_bits(16..20) as u8;
+
+    Some(VMovCrScalarParams {
+        rt,
+        dd: DoubleReg::from(D << 4 | vd),
+        x: opcode.get_bit(21),
+    })
 }
 
 #[allow(non_snake_case)]
@@ -179,8 +212,40 @@ pub fn decode_VMOV_cr_sp(opcode: u32) -> Instruction {
 
 #[allow(non_snake_case)]
 #[inline(always)]
-pub fn decode_VMOV_cr2_sp2(_opcode: u32) -> Instruction {
-    unimplemented!()
+pub fn decode_VMOV_cr2_sp2(opcode: u32) -> Instruction {
+    let rt = Reg::from(opcode.get_bits(12..16) as u8);
+    let rt2 = Reg::from(opcode.get_bits(16..20) as u8);
+    let op = opcode.get_bit(20);
+
+    let M = u8::from(opcode.get_bit(5));
+    let vm = opcode.get_bits(0..4) as u8;
+    let m = vm << 1 | M;
+
+    // rt&rt2 15 and 13, s31 as the first register and
+    // same destination registers are unpredictable:
+    if rt == Reg::PC
+        || rt == Reg::SP
+        || rt2 == Reg::PC
+        || rt2 == Reg::SP
+        || m == 31
+        || (op && rt == rt2)
+    {
+        return Instruction::UDF {
+            opcode: opcode.into(),
+            thumb32: true,
+            imm32: 0,
+        };
+    }
+
+    Instruction::VMOV_cr2_sp2 {
+        params: VMovCr2Sp2Params {
+            to_arm_registers: op,
+            rt,
+            rt2,
+            sm: SingleReg::from(m),
+            sm1: SingleReg::from(m + 1),
+        },
+    }
 }
 
 #[allow(non_snake_case)]
@@ -0,0 +1,9 @@
+use crate::core::{bits::Bits, instruction::Instruction, register::Reg};
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VMSR(opcode: u32) -> Instruction {
+    Instruction::VMSR {
+        rt: Reg::from(opcode.get_bits(12..16) as u8),
+    }
+}
@@ -21,15 +21,12 @@ pub fn decode_VPOP_t1(opcode: u32) -> Instruction {
         };
     }
 
-    #[cfg(feature = "VFPSmallRegisterBank")]
-    {
-        if d + regs > 16 {
-            return Instruction::UDF {
-                imm32: 0,
-                opcode: opcode.into(),
-                thumb32: true,
-            };
-        }
+    if d + regs > 16 {
+        return Instruction::UDF {
+            imm32: 0,
+            opcode: opcode.into(),
+            thumb32: true,
+        };
     }
 
     let mut double_regs = EnumSet::new();
@@ -0,0 +1,75 @@
+use crate::core::{
+    bits::Bits,
+    instruction::{Instruction, VRintMode, VRintParamsf32, VRintParamsf64},
+    register::{DoubleReg, SingleReg},
+};
+
+#[allow(non_snake_case)]
+fn decode_VRINT(opcode: u32, mode: VRintMode) -> Instruction {
+    let sz = opcode.get_bit(8);
+
+    let D = u8::from(opcode.get_bit(22));
+    let vd = opcode.get_bits(12..16) as u8;
+    let vm = opcode.get_bits(0..4) as u8;
+    let M = u8::from(opcode.get_bit(5));
+
+    if sz {
+        Instruction::VRINT_f64 {
+            params: VRintParamsf64 {
+                dd: DoubleReg::from(D << 4 | vd),
+                dm: DoubleReg::from(M << 4 | vm),
+                mode,
+            },
+        }
+    } else {
+        Instruction::VRINT_f32 {
+            params: VRintParamsf32 {
+                sd: SingleReg::from(vd << 1 | D),
+                sm: SingleReg::from(vm << 1 | M),
+                mode,
+            },
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VRINTA_t1(opcode: u32) -> Instruction {
+    decode_VRINT(opcode, VRintMode::A)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VRINTN_t1(opcode: u32) -> Instruction {
+    decode_VRINT(opcode, VRintMode::N)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VRINTP_t1(opcode: u32) -> Instruction {
+    decode_VRINT(opcode, VRintMode::P)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VRINTM_t1(opcode: u32) -> Instruction {
+    decode_VRINT(opcode, VRintMode::M)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VRINTR_t1(opcode: u32) -> Instruction {
+    decode_VRINT(opcode, VRintMode::R)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VRINTX_t1(opcode: u32) -> Instruction {
+    decode_VRINT(opcode, VRintMode::X)
+}
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VRINTZ_t1(opcode: u32) -> Instruction {
+    decode_VRINT(opcode, VRintMode::Z)
+}
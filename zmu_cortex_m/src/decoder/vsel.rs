@@ -0,0 +1,43 @@
+use crate::core::{
+    bits::Bits,
+    condition::Condition,
+    instruction::{Instruction, VSelParamsf32, VSelParamsf64},
+    register::{DoubleReg, SingleReg},
+};
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn decode_VSEL_t1(opcode: u32) -> Instruction {
+    let D = u8::from(opcode.get_bit(22));
+    let cc = opcode.get_bits(20..22) as u16;
+    let vn = opcode.get_bits(16..20) as u8;
+    let vd = opcode.get_bits(12..16) as u8;
+    let sz = opcode.get_bit(8);
+    let N = u8::from(opcode.get_bit(7));
+    let M = u8::from(opcode.get_bit(5));
+    let vm = opcode.get_bits(0..4) as u8;
+
+    // cond = cc:(cc<1> XOR cc<0>):'0', one of EQ, VS, GE or GT
+    let cond_bits = cc << 2 | u16::from(cc.get_bit(1) ^ cc.get_bit(0)) << 1;
+    let cond = Condition::from_u16(cond_bits).unwrap();
+
+    if sz {
+        Instruction::VSEL_f64 {
+            params: VSelParamsf64 {
+                dd: DoubleReg::from(D << 4 | vd),
+                dn: DoubleReg::from(N << 4 | vn),
+                dm: DoubleReg::from(M << 4 | vm),
+                cond,
+            },
+        }
+    } else {
+        Instruction::VSEL_f32 {
+            params: VSelParamsf32 {
+                sd: SingleReg::from(vd << 1 | D),
+                sn: SingleReg::from(vn << 1 | N),
+                sm: SingleReg::from(vm << 1 | M),
+                cond,
+            },
+        }
+    }
+}
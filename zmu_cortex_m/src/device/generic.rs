@@ -19,11 +19,11 @@ impl Device {
 }
 
 impl Bus for Device {
-    fn read8(&self, _bus_addr: u32) -> Result<u8, Fault> {
+    fn read8(&mut self, _bus_addr: u32) -> Result<u8, Fault> {
         Ok(0)
     }
 
-    fn read16(&self, _bus_addr: u32) -> Result<u16, Fault> {
+    fn read16(&mut self, _bus_addr: u32) -> Result<u16, Fault> {
         Ok(0)
     }
 
@@ -2,5 +2,94 @@
 //! Devices
 //!
 
+use crate::bus::Bus;
+use crate::core::fault::Fault;
+
 pub mod generic;
 pub mod stm32f1xx;
+
+///
+/// Names of the device models that can be created with `create_device`
+///
+pub const DEVICE_MODELS: [&str; 2] = ["generic", "stm32f1xx"];
+
+///
+/// Create a device model by name. If base address is given, the
+/// model is relocated from its default base address to the given one.
+///
+pub fn create_device(model: &str, base_address: Option<u32>) -> Option<Box<dyn Bus>> {
+    let (device, default_base_address): (Box<dyn Bus>, u32) = match model {
+        "generic" => (Box::new(generic::Device::new()), 0),
+        "stm32f1xx" => (
+            Box::new(stm32f1xx::Device::new()),
+            stm32f1xx::DEFAULT_BASE_ADDRESS,
+        ),
+        _ => return None,
+    };
+
+    match base_address {
+        Some(base_address) if base_address != default_base_address => {
+            Some(Box::new(RelocatedDevice {
+                offset: base_address.wrapping_sub(default_base_address),
+                device,
+            }))
+        }
+        _ => Some(device),
+    }
+}
+
+///
+/// Device model accessed from other than its default base address
+///
+struct RelocatedDevice {
+    offset: u32,
+    device: Box<dyn Bus>,
+}
+
+impl Bus for RelocatedDevice {
+    fn read8(&mut self, addr: u32) -> Result<u8, Fault> {
+        self.device.read8(addr.wrapping_sub(self.offset))
+    }
+
+    fn read16(&mut self, addr: u32) -> Result<u16, Fault> {
+        self.device.read16(addr.wrapping_sub(self.offset))
+    }
+
+    fn read32(&mut self, addr: u32) -> Result<u32, Fault> {
+        self.device.read32(addr.wrapping_sub(self.offset))
+    }
+
+    fn write32(&mut self, addr: u32, value: u32) -> Result<(), Fault> {
+        self.device.write32(addr.wrapping_sub(self.offset), value)
+    }
+
+    fn write16(&mut self, addr: u32, value: u16) -> Result<(), Fault> {
+        self.device.write16(addr.wrapping_sub(self.offset), value)
+    }
+
+    fn write8(&mut self, addr: u32, value: u8) -> Result<(), Fault> {
+        self.device.write8(addr.wrapping_sub(self.offset), value)
+    }
+
+    fn in_range(&self, addr: u32) -> bool {
+        self.device.in_range(addr.wrapping_sub(self.offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_device() {
+        assert!(create_device("generic", None).is_some());
+        assert!(create_device("unknown", None).is_none());
+
+        let device = create_device("stm32f1xx", None).unwrap();
+        assert!(device.in_range(0x4000_0000));
+
+        let device = create_device("stm32f1xx", Some(0x5000_0000)).unwrap();
+        assert!(device.in_range(0x5000_0000));
+        assert!(!device.in_range(0x4000_0000));
+    }
+}
@@ -7,6 +7,9 @@ use crate::core::bits::Bits;
 
 const PERIPH_BASE: u32 = 0x4000_0000;
 
+/// Base address of the peripheral region of the device
+pub const DEFAULT_BASE_ADDRESS: u32 = PERIPH_BASE;
+
 const APB1PERIPH_BASE: u32 = PERIPH_BASE;
 const APB2PERIPH_BASE: u32 = PERIPH_BASE + 0x10000;
 const AHBPERIPH_BASE: u32 = PERIPH_BASE + 0x20000;
@@ -703,7 +706,7 @@ impl RCC for Device {
             0x1C => self.rcc.APB1ENR = value,
             0x20 => self.rcc.BDCR = value,
             0x24 => self.rcc.CSR = value,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         }
 
         Ok(())
@@ -721,7 +724,7 @@ impl RCC for Device {
             0x1C => self.rcc.APB1ENR,
             0x20 => self.rcc.BDCR,
             0x24 => self.rcc.CSR,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         };
 
         Ok(result)
@@ -739,7 +742,7 @@ impl AFIO for Device {
             0x14 => self.afio.EXTICR[3] = value,
             //0x18 => self.rcc.APB2ENR = value,
             0x1C => self.afio.MAPR = value,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         }
 
         Ok(())
@@ -755,7 +758,7 @@ impl AFIO for Device {
             0x14 => self.afio.EXTICR[3],
             //0x18 => self.rcc.APB2ENR,
             0x1C => self.afio.MAPR2,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         };
 
         Ok(result)
@@ -782,7 +785,7 @@ impl GPIO for Device {
                 self.gpio[index].ODR = (odr | odr_reset_bits) & !odr;
             }
             0x18 => self.gpio[index].LCKR = value & 0x1_ffff,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         }
         Ok(())
     }
@@ -796,7 +799,7 @@ impl GPIO for Device {
             0x10 => 0,
             0x14 => 0,
             0x18 => self.gpio[index].LCKR,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         };
 
         Ok(result)
@@ -818,7 +821,7 @@ impl FLASH for Device {
                 // PRFTBE -> PRFTBS
                 self.flash.ACR.set_bit(5, self.flash.ACR.get_bit(4));
             }
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         }
 
         Ok(())
@@ -827,7 +830,7 @@ impl FLASH for Device {
     fn flash_read32(&mut self, offset: u32) -> Result<u32, Fault> {
         let result = match offset {
             0x0 => self.flash.ACR,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         };
 
         Ok(result)
@@ -848,7 +851,7 @@ impl TIM for Device {
 
         match offset {
             0x0 => self.tim1_8[index].gp.gp.min.CR1 = value,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         }
 
         Ok(())
@@ -866,7 +869,7 @@ impl TIM for Device {
 
         let result = match offset {
             0x0 => self.tim1_8[index].gp.gp.min.CR1,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         };
 
         Ok(result)
@@ -891,7 +894,7 @@ impl TIM for Device {
             0x1c => self.tim2_5[index].CCMR2 = value & 0xffff,
             0x28 => self.tim2_5[index].gp.min.PSC = value & 0xffff,
             0x2c => self.tim2_5[index].gp.min.ARR = value & 0xffff,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         }
 
         Ok(())
@@ -915,7 +918,7 @@ impl TIM for Device {
             0x1c => self.tim2_5[index].CCMR2,
             0x28 => self.tim2_5[index].gp.min.PSC,
             0x2C => self.tim2_5[index].gp.min.ARR,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         };
 
         Ok(result)
@@ -938,7 +941,7 @@ impl TIM for Device {
 
         match offset {
             0x0 => self.tim9_14[index].min.CR1 = value,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         }
 
         Ok(())
@@ -960,7 +963,7 @@ impl TIM for Device {
 
         let result = match offset {
             0x0 => self.tim9_14[index].min.CR1,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         };
 
         Ok(result)
@@ -979,7 +982,7 @@ impl TIM for Device {
 
         match offset {
             0x0 => self.tim6_7[index].min.CR1 = value,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         }
 
         Ok(())
@@ -993,7 +996,7 @@ impl TIM for Device {
 
         let result = match offset {
             0x0 => self.tim6_7[index].min.CR1,
-            _ => return Err(Fault::DAccViol),
+            _ => return Err(Fault::Preciserr),
         };
 
         Ok(result)
@@ -1001,12 +1004,12 @@ impl TIM for Device {
 }
 
 impl Bus for Device {
-    fn read8(&self, bus_addr: u32) -> Result<u8, Fault> {
+    fn read8(&mut self, bus_addr: u32) -> Result<u8, Fault> {
         println!("read8 0x{bus_addr:x}");
         Ok(0)
     }
 
-    fn read16(&self, bus_addr: u32) -> Result<u16, Fault> {
+    fn read16(&mut self, bus_addr: u32) -> Result<u16, Fault> {
         println!("read16 0x{bus_addr:x}");
         Ok(0)
     }
@@ -1078,7 +1081,7 @@ impl Bus for Device {
                 GeneralPurposeTimer2Type::TIM14,
                 bus_addr - TIM14_BASE,
             ),
-            _ => Err(Fault::DAccViol),
+            _ => Err(Fault::Preciserr),
         }
     }
 
@@ -1161,7 +1164,7 @@ impl Bus for Device {
                 addr - TIM14_BASE,
                 value,
             ),
-            _ => Err(Fault::DAccViol),
+            _ => Err(Fault::Preciserr),
         }
     }
 
@@ -25,6 +25,7 @@ impl IsaException for Processor {
 
     fn exec_bkpt(&mut self, imm32: u32) -> ExecuteResult {
         if imm32 == 0xab {
+            self.semihost_call_count += 1;
             let r0 = self.get_r(Reg::R0);
             let r1 = self.get_r(Reg::R1);
             let semihost_cmd = decode_semihostcmd(r0, r1, self)?;
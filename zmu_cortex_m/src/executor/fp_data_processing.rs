@@ -1,16 +1,32 @@
-use crate::core::fpregister::Fpscr;
+use crate::core::fpregister::{FPSCRRounding, Fpscr};
 use crate::core::instruction::{
-    VAddSubParamsf32, VAddSubParamsf64, VCVTParams, VCmpParamsf32, VCmpParamsf64, VMovRegParamsf32, VMovRegParamsf64
+    VAddSubParamsf32, VAddSubParamsf64, VCVTDirectedParams, VCVTF32F64Params, VCVTF64F32Params,
+    VCVTParams, VCmpParamsf32, VCmpParamsf64, VMovRegParamsf32, VMovRegParamsf64, VRintMode,
+    VRintParamsf32, VRintParamsf64, VSelParamsf32, VSelParamsf64,
 };
 use crate::Processor;
 
 use crate::executor::ExecuteSuccess;
 
-use super::fp_generic::{FloatingPointChecks, FloatingPointPublicOperations};
+use super::fp_generic::{FloatOps, FloatingPointChecks, FloatingPointPublicOperations};
 use super::ExecuteResult;
-use crate::core::register::ExtensionRegOperations;
+use crate::core::register::{DoubleReg, ExtensionReg, ExtensionRegOperations};
 use crate::executor::ExecutorHelper;
 
+/// Accumulation of a product into the destination register, shared by
+/// the chained (VMLA family) and fused (VFMA family) multiply-accumulates
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum MulAccOp {
+    /// VMLA, VFMA: d + n * m
+    Add,
+    /// VMLS, VFMS: d - n * m
+    Sub,
+    /// VNMLA, VFNMA: -d - n * m
+    NegAdd,
+    /// VNMLS, VFNMS: -d + n * m
+    NegSub,
+}
+
 pub trait IsaFloatingPointDataProcessing {
     fn exec_vabs_f32(&mut self, params: &VMovRegParamsf32) -> ExecuteResult;
     fn exec_vabs_f64(&mut self, params: &VMovRegParamsf64) -> ExecuteResult;
@@ -25,6 +41,98 @@ pub trait IsaFloatingPointDataProcessing {
     fn exec_vsub_f64(&mut self, params: &VAddSubParamsf64) -> ExecuteResult;
 
     fn exec_vcvt(&mut self, params: &VCVTParams) -> ExecuteResult;
+    fn exec_vcvt_f32_f64(&mut self, params: VCVTF32F64Params) -> ExecuteResult;
+    fn exec_vcvt_f64_f32(&mut self, params: VCVTF64F32Params) -> ExecuteResult;
+
+    fn exec_vmul_f32(&mut self, params: &VAddSubParamsf32, negate: bool) -> ExecuteResult;
+    fn exec_vmul_f64(&mut self, params: &VAddSubParamsf64, negate: bool) -> ExecuteResult;
+
+    fn exec_vdiv_f32(&mut self, params: &VAddSubParamsf32) -> ExecuteResult;
+    fn exec_vdiv_f64(&mut self, params: &VAddSubParamsf64) -> ExecuteResult;
+
+    fn exec_vmla_f32(&mut self, params: &VAddSubParamsf32, op: MulAccOp) -> ExecuteResult;
+    fn exec_vmla_f64(&mut self, params: &VAddSubParamsf64, op: MulAccOp) -> ExecuteResult;
+
+    fn exec_vfma_f32(&mut self, params: &VAddSubParamsf32, op: MulAccOp) -> ExecuteResult;
+    fn exec_vfma_f64(&mut self, params: &VAddSubParamsf64, op: MulAccOp) -> ExecuteResult;
+
+    fn exec_vneg_f32(&mut self, params: &VMovRegParamsf32) -> ExecuteResult;
+    fn exec_vneg_f64(&mut self, params: &VMovRegParamsf64) -> ExecuteResult;
+
+    fn exec_vsqrt_f32(&mut self, params: &VMovRegParamsf32) -> ExecuteResult;
+    fn exec_vsqrt_f64(&mut self, params: &VMovRegParamsf64) -> ExecuteResult;
+
+    fn exec_vmaxnm_f32(&mut self, params: &VAddSubParamsf32, minimum: bool) -> ExecuteResult;
+    fn exec_vmaxnm_f64(&mut self, params: &VAddSubParamsf64, minimum: bool) -> ExecuteResult;
+
+    fn exec_vsel_f32(&mut self, params: &VSelParamsf32) -> ExecuteResult;
+    fn exec_vsel_f64(&mut self, params: &VSelParamsf64) -> ExecuteResult;
+
+    fn exec_vrint_f32(&mut self, params: &VRintParamsf32) -> ExecuteResult;
+    fn exec_vrint_f64(&mut self, params: &VRintParamsf64) -> ExecuteResult;
+
+    fn exec_vcvt_directed(&mut self, params: &VCVTDirectedParams) -> ExecuteResult;
+}
+
+fn get_dr_bits(processor: &mut Processor, reg: DoubleReg) -> u64 {
+    let (lower, upper) = processor.get_dr(reg);
+    u64::from(upper) << 32 | u64::from(lower)
+}
+
+fn set_dr_bits(processor: &mut Processor, reg: DoubleReg, value: u64) {
+    processor.set_dr(reg, value as u32, (value >> 32) as u32);
+}
+
+/// VMLA family: the product is rounded before it is accumulated
+fn mul_accumulate<T: FloatOps>(
+    processor: &mut Processor,
+    addend: T::Bits,
+    op1: T::Bits,
+    op2: T::Bits,
+    op: MulAccOp,
+) -> T::Bits {
+    let product = processor.fp_mul::<T>(op1, op2, true);
+    match op {
+        MulAccOp::Add => processor.fp_add::<T>(addend, product, true),
+        MulAccOp::Sub => processor.fp_add::<T>(addend, T::fp_neg(product), true),
+        MulAccOp::NegAdd => processor.fp_add::<T>(T::fp_neg(addend), T::fp_neg(product), true),
+        MulAccOp::NegSub => processor.fp_add::<T>(T::fp_neg(addend), product, true),
+    }
+}
+
+/// VFMA family: a single rounding of the whole expression
+fn fused_mul_accumulate<T: FloatOps>(
+    processor: &mut Processor,
+    addend: T::Bits,
+    op1: T::Bits,
+    op2: T::Bits,
+    op: MulAccOp,
+) -> T::Bits {
+    match op {
+        MulAccOp::Add => processor.fp_mul_add::<T>(addend, op1, op2, true),
+        MulAccOp::Sub => processor.fp_mul_add::<T>(addend, T::fp_neg(op1), op2, true),
+        MulAccOp::NegAdd => processor.fp_mul_add::<T>(T::fp_neg(addend), T::fp_neg(op1), op2, true),
+        MulAccOp::NegSub => processor.fp_mul_add::<T>(T::fp_neg(addend), op1, op2, true),
+    }
+}
+
+/// Rounding mode, ties away and exactness of a rounding mnemonic suffix
+fn mode_rounding(fpscr: u32, mode: VRintMode) -> (FPSCRRounding, bool, bool) {
+    let fpscr_rounding = fpscr.get_rounding_mode();
+    match mode {
+        VRintMode::A => (FPSCRRounding::RoundToNearest, true, false),
+        VRintMode::N => (FPSCRRounding::RoundToNearest, false, false),
+        VRintMode::P => (FPSCRRounding::RoundTowardsPlusInfinity, false, false),
+        VRintMode::M => (FPSCRRounding::RoundTowardsMinusInfinity, false, false),
+        VRintMode::R => (fpscr_rounding, false, false),
+        VRintMode::X => (fpscr_rounding, false, true),
+        VRintMode::Z => (FPSCRRounding::RoundTowardsZero, false, false),
+    }
+}
+
+fn round_int<T: FloatOps>(processor: &mut Processor, op: T::Bits, mode: VRintMode) -> T::Bits {
+    let (rounding, away, exact) = mode_rounding(processor.fpscr, mode);
+    processor.fp_round_int::<T>(op, rounding, away, exact)
 }
 
 impl IsaFloatingPointDataProcessing for Processor {
@@ -195,12 +303,292 @@ impl IsaFloatingPointDataProcessing for Processor {
         }
         Ok(ExecuteSuccess::NotTaken)
     }
+
+    fn exec_vcvt_f32_f64(&mut self, params: VCVTF32F64Params) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op = get_dr_bits(self, params.dm);
+            let result = self.fp_convert::<u64, u32>(op, true);
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vcvt_f64_f32(&mut self, params: VCVTF64F32Params) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op = self.get_sr(params.sm);
+            let result = self.fp_convert::<u32, u64>(op, true);
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vmul_f32(&mut self, params: &VAddSubParamsf32, negate: bool) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op1 = self.get_sr(params.sn);
+            let op2 = self.get_sr(params.sm);
+            let product = self.fp_mul::<u32>(op1, op2, true);
+            let result = if negate {
+                self.fp_neg::<u32>(product)
+            } else {
+                product
+            };
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vmul_f64(&mut self, params: &VAddSubParamsf64, negate: bool) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op1 = get_dr_bits(self, params.dn);
+            let op2 = get_dr_bits(self, params.dm);
+            let product = self.fp_mul::<u64>(op1, op2, true);
+            let result = if negate {
+                self.fp_neg::<u64>(product)
+            } else {
+                product
+            };
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vdiv_f32(&mut self, params: &VAddSubParamsf32) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op1 = self.get_sr(params.sn);
+            let op2 = self.get_sr(params.sm);
+            let result = self.fp_div::<u32>(op1, op2, true);
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 14 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vdiv_f64(&mut self, params: &VAddSubParamsf64) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op1 = get_dr_bits(self, params.dn);
+            let op2 = get_dr_bits(self, params.dm);
+            let result = self.fp_div::<u64>(op1, op2, true);
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 14 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vmla_f32(&mut self, params: &VAddSubParamsf32, op: MulAccOp) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let addend = self.get_sr(params.sd);
+            let op1 = self.get_sr(params.sn);
+            let op2 = self.get_sr(params.sm);
+            let result = mul_accumulate::<u32>(self, addend, op1, op2, op);
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 3 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vmla_f64(&mut self, params: &VAddSubParamsf64, op: MulAccOp) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let addend = get_dr_bits(self, params.dd);
+            let op1 = get_dr_bits(self, params.dn);
+            let op2 = get_dr_bits(self, params.dm);
+            let result = mul_accumulate::<u64>(self, addend, op1, op2, op);
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 3 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vfma_f32(&mut self, params: &VAddSubParamsf32, op: MulAccOp) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let addend = self.get_sr(params.sd);
+            let op1 = self.get_sr(params.sn);
+            let op2 = self.get_sr(params.sm);
+            let result = fused_mul_accumulate::<u32>(self, addend, op1, op2, op);
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 3 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vfma_f64(&mut self, params: &VAddSubParamsf64, op: MulAccOp) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let addend = get_dr_bits(self, params.dd);
+            let op1 = get_dr_bits(self, params.dn);
+            let op2 = get_dr_bits(self, params.dm);
+            let result = fused_mul_accumulate::<u64>(self, addend, op1, op2, op);
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 3 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vneg_f32(&mut self, params: &VMovRegParamsf32) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op = self.get_sr(params.sm);
+            let result = self.fp_neg::<u32>(op);
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vneg_f64(&mut self, params: &VMovRegParamsf64) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op = get_dr_bits(self, params.dm);
+            let result = self.fp_neg::<u64>(op);
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vsqrt_f32(&mut self, params: &VMovRegParamsf32) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op = self.get_sr(params.sm);
+            let result = self.fp_sqrt::<u32>(op, true);
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 14 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vsqrt_f64(&mut self, params: &VMovRegParamsf64) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op = get_dr_bits(self, params.dm);
+            let result = self.fp_sqrt::<u64>(op, true);
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 14 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vmaxnm_f32(&mut self, params: &VAddSubParamsf32, minimum: bool) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op1 = self.get_sr(params.sn);
+            let op2 = self.get_sr(params.sm);
+            let result = if minimum {
+                self.fp_min_num::<u32>(op1, op2, true)
+            } else {
+                self.fp_max_num::<u32>(op1, op2, true)
+            };
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vmaxnm_f64(&mut self, params: &VAddSubParamsf64, minimum: bool) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op1 = get_dr_bits(self, params.dn);
+            let op2 = get_dr_bits(self, params.dm);
+            let result = if minimum {
+                self.fp_min_num::<u64>(op1, op2, true)
+            } else {
+                self.fp_max_num::<u64>(op1, op2, true)
+            };
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vsel_f32(&mut self, params: &VSelParamsf32) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let result = if self.condition_passed_b(params.cond) {
+                self.get_sr(params.sn)
+            } else {
+                self.get_sr(params.sm)
+            };
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vsel_f64(&mut self, params: &VSelParamsf64) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let result = if self.condition_passed_b(params.cond) {
+                get_dr_bits(self, params.dn)
+            } else {
+                get_dr_bits(self, params.dm)
+            };
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vrint_f32(&mut self, params: &VRintParamsf32) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op = self.get_sr(params.sm);
+            let result = round_int::<u32>(self, op, params.mode);
+            self.set_sr(params.sd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vrint_f64(&mut self, params: &VRintParamsf64) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let op = get_dr_bits(self, params.dm);
+            let result = round_int::<u64>(self, op, params.mode);
+            set_dr_bits(self, params.dd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_vcvt_directed(&mut self, params: &VCVTDirectedParams) -> ExecuteResult {
+        if self.condition_passed() {
+            self.execute_fp_check();
+            let (rounding, away, _) = mode_rounding(self.fpscr, params.mode);
+            let result = match params.m {
+                ExtensionReg::Single { reg } => {
+                    let op = self.get_sr(reg);
+                    self.fp_to_fixed_directed::<u32, u32>(op, 0, params.unsigned, rounding, away)
+                }
+                ExtensionReg::Double { reg } => {
+                    let op = get_dr_bits(self, reg);
+                    self.fp_to_fixed_directed::<u64, u32>(op, 0, params.unsigned, rounding, away)
+                }
+            };
+            self.set_sr(params.d, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::register::{DoubleReg, SingleReg};
+    use crate::core::bits::Bits;
+    use crate::core::condition::Condition;
+    use crate::core::register::{Apsr, DoubleReg, SingleReg};
 
     #[test]
     fn test_vabs_f32() {
@@ -235,4 +623,240 @@ mod tests {
         let result = processor.get_dr(DoubleReg::D1);
         assert_eq!(result, (0x00000000, 0x3FF00000));
     }
+
+    #[test]
+    fn test_vmul_f32() {
+        let mut processor = Processor::new();
+        let params = VAddSubParamsf32 {
+            sd: SingleReg::S2,
+            sn: SingleReg::S0,
+            sm: SingleReg::S1,
+        };
+
+        // 1.5 * 2.0
+        processor.set_sr(SingleReg::S0, 0x3FC00000);
+        processor.set_sr(SingleReg::S1, 0x40000000);
+        processor.exec_vmul_f32(&params, false).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0x40400000);
+
+        // vnmul: -(1.5 * 2.0)
+        processor.exec_vmul_f32(&params, true).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0xC0400000);
+    }
+
+    #[test]
+    fn test_vmla_vfma_f32() {
+        let mut processor = Processor::new();
+        let params = VAddSubParamsf32 {
+            sd: SingleReg::S2,
+            sn: SingleReg::S0,
+            sm: SingleReg::S0,
+        };
+
+        // -(1 + 2^-22) + (1 + 2^-23)^2: the chained multiply-accumulate rounds
+        // the product first and cancels to zero, the fused one does not
+        processor.set_sr(SingleReg::S0, 0x3F800001);
+        processor.set_sr(SingleReg::S2, 0xBF800002);
+        processor.exec_vmla_f32(&params, MulAccOp::Add).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0x00000000);
+
+        processor.set_sr(SingleReg::S2, 0xBF800002);
+        processor.exec_vfma_f32(&params, MulAccOp::Add).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0x28800000);
+
+        // vnmls: -d + n * m = -1.0 + 4.0
+        processor.set_sr(SingleReg::S0, 0x40000000);
+        processor.set_sr(SingleReg::S2, 0x3F800000);
+        processor.exec_vmla_f32(&params, MulAccOp::NegSub).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0x40400000);
+
+        // vfnma: -d - n * m = -3.0 - 4.0
+        processor.exec_vfma_f32(&params, MulAccOp::NegAdd).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0xC0E00000);
+    }
+
+    #[test]
+    fn test_vdiv_vsqrt_f64() {
+        let mut processor = Processor::new();
+
+        // 1.0 / 3.0
+        processor.set_dr(DoubleReg::D0, 0x00000000, 0x3FF00000);
+        processor.set_dr(DoubleReg::D1, 0x00000000, 0x40080000);
+        processor
+            .exec_vdiv_f64(&VAddSubParamsf64 {
+                dd: DoubleReg::D2,
+                dn: DoubleReg::D0,
+                dm: DoubleReg::D1,
+            })
+            .unwrap();
+        assert_eq!(processor.get_dr(DoubleReg::D2), (0x55555555, 0x3FD55555));
+
+        // sqrt(9.0)
+        processor.set_dr(DoubleReg::D0, 0x00000000, 0x40220000);
+        processor
+            .exec_vsqrt_f64(&VMovRegParamsf64 {
+                dd: DoubleReg::D2,
+                dm: DoubleReg::D0,
+            })
+            .unwrap();
+        assert_eq!(processor.get_dr(DoubleReg::D2), (0x00000000, 0x40080000));
+    }
+
+    #[test]
+    fn test_vneg_f64() {
+        let mut processor = Processor::new();
+
+        // 1.0
+        processor.set_dr(DoubleReg::D0, 0x00000000, 0x3FF00000);
+        processor
+            .exec_vneg_f64(&VMovRegParamsf64 {
+                dd: DoubleReg::D1,
+                dm: DoubleReg::D0,
+            })
+            .unwrap();
+        assert_eq!(processor.get_dr(DoubleReg::D1), (0x00000000, 0xBFF00000));
+    }
+
+    #[test]
+    fn test_vsel_f32() {
+        let mut processor = Processor::new();
+        let params = VSelParamsf32 {
+            sd: SingleReg::S2,
+            sn: SingleReg::S0,
+            sm: SingleReg::S1,
+            cond: Condition::EQ,
+        };
+        processor.set_sr(SingleReg::S0, 0x3F800000);
+        processor.set_sr(SingleReg::S1, 0x40000000);
+
+        processor.psr.set_z_bit(true);
+        processor.exec_vsel_f32(&params).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0x3F800000);
+
+        processor.psr.set_z_bit(false);
+        processor.exec_vsel_f32(&params).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0x40000000);
+    }
+
+    #[test]
+    fn test_vmaxnm_f32() {
+        let mut processor = Processor::new();
+        let params = VAddSubParamsf32 {
+            sd: SingleReg::S2,
+            sn: SingleReg::S0,
+            sm: SingleReg::S1,
+        };
+
+        // a quiet NaN operand is ignored
+        processor.set_sr(SingleReg::S0, 0x7FC00000);
+        processor.set_sr(SingleReg::S1, 0xBF800000);
+        processor.exec_vmaxnm_f32(&params, false).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0xBF800000);
+        processor.exec_vmaxnm_f32(&params, true).unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S2), 0xBF800000);
+    }
+
+    #[test]
+    fn test_vrint_f32() {
+        let mut processor = Processor::new();
+
+        // -2.5
+        processor.set_sr(SingleReg::S0, 0xC0200000);
+        let cases = [
+            (VRintMode::A, 0xC0400000),
+            (VRintMode::N, 0xC0000000),
+            (VRintMode::P, 0xC0000000),
+            (VRintMode::M, 0xC0400000),
+            (VRintMode::Z, 0xC0000000),
+            (VRintMode::R, 0xC0000000),
+        ];
+        for (mode, expected) in cases {
+            processor
+                .exec_vrint_f32(&VRintParamsf32 {
+                    sd: SingleReg::S1,
+                    sm: SingleReg::S0,
+                    mode,
+                })
+                .unwrap();
+            assert_eq!(processor.get_sr(SingleReg::S1), expected);
+        }
+        assert!(!processor.fpscr.get_bit(4));
+
+        // only vrintx signals the inexact result
+        processor
+            .exec_vrint_f32(&VRintParamsf32 {
+                sd: SingleReg::S1,
+                sm: SingleReg::S0,
+                mode: VRintMode::X,
+            })
+            .unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S1), 0xC0000000);
+        assert!(processor.fpscr.get_bit(4));
+    }
+
+    #[test]
+    fn test_vcvt_directed() {
+        let mut processor = Processor::new();
+
+        // -2.5, the rounding comes from the mnemonic, not from FPSCR
+        processor
+            .fpscr
+            .set_rounding_mode(FPSCRRounding::RoundTowardsZero);
+        processor.set_sr(SingleReg::S0, 0xC0200000);
+        let cases = [
+            (VRintMode::A, -3),
+            (VRintMode::N, -2),
+            (VRintMode::P, -2),
+            (VRintMode::M, -3),
+        ];
+        for (mode, expected) in cases {
+            processor
+                .exec_vcvt_directed(&VCVTDirectedParams {
+                    d: SingleReg::S1,
+                    m: ExtensionReg::Single { reg: SingleReg::S0 },
+                    unsigned: false,
+                    mode,
+                })
+                .unwrap();
+            assert_eq!(processor.get_sr(SingleReg::S1) as i32, expected);
+        }
+        assert!(processor.fpscr.get_bit(4));
+
+        // 2.5 from a double precision register, ties away from zero
+        set_dr_bits(&mut processor, DoubleReg::D1, 0x4004_0000_0000_0000);
+        processor
+            .exec_vcvt_directed(&VCVTDirectedParams {
+                d: SingleReg::S1,
+                m: ExtensionReg::Double { reg: DoubleReg::D1 },
+                unsigned: true,
+                mode: VRintMode::A,
+            })
+            .unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S1), 3);
+    }
+
+    #[test]
+    fn test_vcvt_f32_f64() {
+        let mut processor = Processor::new();
+
+        // 1.0 / 3.0 rounds to nearest single precision value
+        processor.set_dr(DoubleReg::D0, 0x55555555, 0x3FD55555);
+        processor
+            .exec_vcvt_f32_f64(VCVTF32F64Params {
+                sd: SingleReg::S5,
+                dm: DoubleReg::D0,
+            })
+            .unwrap();
+        assert_eq!(processor.get_sr(SingleReg::S5), 0x3EAAAAAB);
+        assert!(processor.fpscr.get_bit(4));
+
+        // and back to double precision exactly
+        processor
+            .exec_vcvt_f64_f32(VCVTF64F32Params {
+                dd: DoubleReg::D3,
+                sm: SingleReg::S5,
+            })
+            .unwrap();
+        assert_eq!(processor.get_dr(DoubleReg::D3), (0x60000000, 0x3FD55555));
+    }
 }
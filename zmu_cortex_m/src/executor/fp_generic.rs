@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::ops::{Add, AddAssign, Div, Sub};
 
 use crate::{
@@ -8,8 +9,6 @@ use crate::{
     Processor,
 };
 
-use num_bigfloat::BigFloat;
-
 #[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FPType {
@@ -31,21 +30,185 @@ pub enum FPExc {
     InputDenorm,
 }
 
-// i128 is used to represent the "integer" pseudocode type in the Architecture Reference Manual
-fn round_down(value: BigFloat) -> i128 {
-    // largest integer n so that n <= x
-    let floored = value.floor();
+/// Exact binary value (-1)^sign * mantissa * 2^exponent.
+///
+/// Used for the "real" pseudocode type where the result must round exactly
+/// like the hardware does. `sticky` stands for nonzero bits below the
+/// mantissa that only matter for rounding, as left by a division or a
+/// square root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Real {
+    sign: bool,
+    mantissa: u128,
+    exponent: i32,
+    sticky: bool,
+}
+
+// mantissa width that guarantees guard bits for every supported format
+const REAL_PRECISION: u32 = 56;
+
+impl Real {
+    fn from_integer(sign: bool, value: u128) -> Self {
+        Self {
+            sign,
+            mantissa: value,
+            exponent: 0,
+            sticky: false,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.mantissa == 0 && !self.sticky
+    }
+
+    fn bit_len(&self) -> i32 {
+        (128 - self.mantissa.leading_zeros()) as i32
+    }
+
+    /// exponent of the most significant bit
+    fn top(&self) -> i32 {
+        self.exponent + self.bit_len() - 1
+    }
+
+    fn neg(self) -> Self {
+        Self {
+            sign: !self.sign,
+            ..self
+        }
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            sign: self.sign != other.sign,
+            mantissa: self.mantissa * other.mantissa,
+            exponent: self.exponent + other.exponent,
+            sticky: false,
+        }
+    }
+
+    fn div(self, other: Self) -> Self {
+        // scale the dividend so that the quotient has REAL_PRECISION bits
+        let shift = (REAL_PRECISION as i32 + other.bit_len() - self.bit_len()).max(0);
+        let dividend = self.mantissa << shift;
+        Self {
+            sign: self.sign != other.sign,
+            mantissa: dividend / other.mantissa,
+            exponent: self.exponent - shift - other.exponent,
+            sticky: !dividend.is_multiple_of(other.mantissa),
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        // even exponent, and enough bits for a REAL_PRECISION bit root
+        let mut shift = (2 * REAL_PRECISION as i32 - self.bit_len()).max(0);
+        if (self.exponent - shift) % 2 != 0 {
+            shift += 1;
+        }
+        let radicand = self.mantissa << shift;
+        let root = radicand.isqrt();
+        Self {
+            sign: false,
+            mantissa: root,
+            exponent: (self.exponent - shift) / 2,
+            sticky: root * root != radicand,
+        }
+    }
+
+    fn add(self, other: Self) -> Self {
+        if self.is_zero() {
+            return other;
+        }
+        if other.is_zero() {
+            return self;
+        }
+        let (big, small) = if self.top() >= other.top() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        // place the larger operand at the top of the mantissa, exactly,
+        // and shift the smaller one to the same exponent
+        let exponent = big.top() - 125;
+        let big_mantissa = big.mantissa << (big.exponent - exponent);
+        let (small_mantissa, small_sticky) = if small.exponent >= exponent {
+            (small.mantissa << (small.exponent - exponent), small.sticky)
+        } else {
+            let shift = exponent - small.exponent;
+            if shift >= 128 {
+                (0, true)
+            } else {
+                let lost = small.mantissa & ((1u128 << shift) - 1);
+                (small.mantissa >> shift, small.sticky || lost != 0)
+            }
+        };
+
+        if big.sign == small.sign {
+            Self {
+                sign: big.sign,
+                mantissa: big_mantissa + small_mantissa,
+                exponent,
+                sticky: small_sticky,
+            }
+        } else if big_mantissa >= small_mantissa {
+            // a sticky fraction borrows one from the integer part
+            let borrow = u128::from(small_sticky && big_mantissa > small_mantissa);
+            Self {
+                sign: big.sign,
+                mantissa: big_mantissa - small_mantissa - borrow,
+                exponent,
+                sticky: small_sticky,
+            }
+        } else {
+            // only possible when nothing was shifted out
+            Self {
+                sign: small.sign,
+                mantissa: small_mantissa - big_mantissa,
+                exponent,
+                sticky: false,
+            }
+        }
+    }
+
+    /// true if the magnitude of self is less than the magnitude of other
+    fn abs_less(&self, other: &Self) -> bool {
+        if self.is_zero() || other.is_zero() {
+            return !other.is_zero();
+        }
+        if self.top() != other.top() {
+            return self.top() < other.top();
+        }
+        let exponent = self.exponent.min(other.exponent);
+        (self.mantissa << (self.exponent - exponent))
+            < (other.mantissa << (other.exponent - exponent))
+    }
 
-    if floored.is_nan() {
-        return 0;
+    fn less(&self, other: &Self) -> bool {
+        match (self.sign, other.sign) {
+            (false, false) => self.abs_less(other),
+            (true, true) => other.abs_less(self),
+            (true, false) => !(self.is_zero() && other.is_zero()),
+            (false, true) => false,
+        }
     }
+}
 
-    let result = floored.to_i128().unwrap_or(if value < BigFloat::default() {
-        std::i128::MIN + 1
+/// Drop `shift` low bits of a mantissa. Returns the integer part, the dropped
+/// fraction compared to one half and whether the fraction is nonzero.
+fn split_fraction(mantissa: u128, shift: i32, sticky: bool) -> (u128, Ordering, bool) {
+    if shift <= 0 {
+        (mantissa << -shift, Ordering::Less, sticky)
+    } else if shift >= 128 {
+        (0, Ordering::Less, mantissa != 0 || sticky)
     } else {
-        std::i128::MAX
-    });
-    result
+        let fraction = mantissa & ((1u128 << shift) - 1);
+        let half = 1u128 << (shift - 1);
+        let half_cmp = match fraction.cmp(&half) {
+            Ordering::Equal if sticky => Ordering::Greater,
+            other => other,
+        };
+        (mantissa >> shift, half_cmp, fraction != 0 || sticky)
+    }
 }
 
 /// Trait for floating point operations
@@ -69,13 +232,11 @@ pub trait FloatOps {
         + Sub<Output = Self::SignedBits>
         + Ord
         + Copy
-        + Into<BigFloat>;
+        + Into<i64>;
 
     /// Number of bits in the underlying integer type
     fn n() -> usize;
 
-    fn is_zero(value: Self::Bits) -> bool;
-
     /// Returns the default NaN value
     fn fp_default_nan() -> Self::Bits;
     fn fp_infinity(sign: bool) -> Self::Bits;
@@ -83,25 +244,16 @@ pub trait FloatOps {
     fn zero() -> Self::Bits;
     fn set_bit(value: Self::Bits, bit: usize, set: bool) -> Self::Bits;
 
-    fn powf2(e: BigFloat) -> BigFloat;
-
-    fn unsigned_pow2_f(e: usize) -> BigFloat;
-
     fn integer_to_bits(value: i128) -> Self::Bits;
-    fn signed_pow2(e: usize) -> Self::SignedBits;
     fn signed_value(value: i32) -> Self::SignedBits;
 
     fn fp_abs(value: Self::Bits) -> Self::Bits;
+    fn fp_neg(value: Self::Bits) -> Self::Bits;
     fn fp_max_normal(sign: bool) -> Self::Bits;
     fn from_integer(value: u64) -> Self::Bits;
 
     fn unsigned_to_signed(value: Self::Bits) -> Self::SignedBits;
-    fn bits_to_bigfloat(value: Self::Bits) -> BigFloat;
-    fn signedbits_to_bigfloat(value: Self::SignedBits) -> BigFloat;
 
-    fn fp_unpack(fpval: Self::Bits, fpscr_val: u32) -> (FPType, bool, BigFloat, Option<FPExc>);
-
-    fn normalize(mantissa: BigFloat) -> (BigFloat, Self::SignedBits);
     fn concate_bits(
         sign: bool,
         biased_exp: Self::SignedBits,
@@ -139,6 +291,66 @@ pub trait FloatingPointPublicOperations {
 
     fn fp_abs<T: FloatOps>(&mut self, op: T::Bits) -> T::Bits;
 
+    fn fp_neg<T: FloatOps>(&mut self, op: T::Bits) -> T::Bits;
+
+    fn fp_mul<T: FloatOps>(
+        &mut self,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits;
+
+    fn fp_div<T: FloatOps>(
+        &mut self,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits;
+
+    fn fp_sqrt<T: FloatOps>(&mut self, op: T::Bits, fpscr_controlled: bool) -> T::Bits;
+
+    /// Fused multiply-add: addend + op1 * op2 with a single rounding
+    fn fp_mul_add<T: FloatOps>(
+        &mut self,
+        addend: T::Bits,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits;
+
+    /// Maximum where a single quiet NaN operand is ignored (IEEE 754-2008 maxNum)
+    fn fp_max_num<T: FloatOps>(
+        &mut self,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits;
+
+    /// Minimum where a single quiet NaN operand is ignored (IEEE 754-2008 minNum)
+    fn fp_min_num<T: FloatOps>(
+        &mut self,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits;
+
+    /// Round to an integral floating point value. `away` selects ties away
+    /// from zero, `exact` raises Inexact when the value changes.
+    fn fp_round_int<T: FloatOps>(
+        &mut self,
+        op: T::Bits,
+        rounding: FPSCRRounding,
+        away: bool,
+        exact: bool,
+    ) -> T::Bits;
+
+    /// Convert between single and double precision
+    fn fp_convert<N: FloatOps, M: FloatOps>(
+        &mut self,
+        op: N::Bits,
+        fpscr_controlled: bool,
+    ) -> M::Bits;
+
     fn fp_to_fixed<N: FloatOps, M: FloatOps>(
         &mut self,
         op: N::Bits,
@@ -148,6 +360,17 @@ pub trait FloatingPointPublicOperations {
         fpscr_controlled: bool,
     ) -> M::Bits;
 
+    /// Float to integer conversion with the rounding given by the instruction
+    /// (VCVTA, VCVTN, VCVTP, VCVTM). `away` selects ties away from zero.
+    fn fp_to_fixed_directed<N: FloatOps, M: FloatOps>(
+        &mut self,
+        op: N::Bits,
+        fraction_bits: usize,
+        unsigned: bool,
+        rounding: FPSCRRounding,
+        away: bool,
+    ) -> M::Bits;
+
     fn fixed_to_fp<N: FloatOps, M: FloatOps>(
         &mut self,
         op: M::Bits,
@@ -175,13 +398,34 @@ trait FloatingPointHiddenOperations {
         fpscr_val: u32,
     ) -> (bool, T::Bits);
 
-    fn fp_unpack<T: FloatOps>(
+    #[allow(clippy::too_many_arguments)]
+    fn fp_process_nans3<T: FloatOps>(
         &mut self,
-        fpval: T::Bits,
+        type1: FPType,
+        type2: FPType,
+        type3: FPType,
+        op1: T::Bits,
+        op2: T::Bits,
+        op3: T::Bits,
         fpscr_val: u32,
-    ) -> (FPType, bool, BigFloat);
+    ) -> (bool, T::Bits);
+
+    fn fp_max<T: FloatOps>(&mut self, op1: T::Bits, op2: T::Bits, fpscr_val: u32) -> T::Bits;
 
-    fn fp_round<T: FloatOps>(&mut self, value: BigFloat, fpscr_val: u32) -> T::Bits;
+    fn fp_min<T: FloatOps>(&mut self, op1: T::Bits, op2: T::Bits, fpscr_val: u32) -> T::Bits;
+
+    fn fp_unpack<T: FloatOps>(&mut self, fpval: T::Bits, fpscr_val: u32) -> (FPType, bool, Real);
+
+    fn fp_round<T: FloatOps>(&mut self, value: Real, fpscr_val: u32) -> T::Bits;
+
+    fn fp_to_fixed_rounded<N: FloatOps, M: FloatOps>(
+        &mut self,
+        op: N::Bits,
+        fraction_bits: usize,
+        unsigned: bool,
+        away: bool,
+        fpscr_val: u32,
+    ) -> M::Bits;
 }
 
 impl FloatOps for u32 {
@@ -202,22 +446,10 @@ impl FloatOps for u32 {
         val
     }
 
-    fn powf2(e: BigFloat) -> BigFloat {
-        BigFloat::from(2).pow(&e)
-    }
-
     fn integer_to_bits(value: i128) -> Self::Bits {
         value as Self::Bits
     }
 
-    fn unsigned_pow2_f(e: usize) -> BigFloat {
-        BigFloat::from(2).pow(&((e as u32).into()))
-    }
-
-    fn signed_pow2(e: usize) -> Self::SignedBits {
-        2i32.pow(e as u32)
-    }
-
     fn signed_value(value: i32) -> Self::SignedBits {
         value
     }
@@ -226,14 +458,6 @@ impl FloatOps for u32 {
         value as Self::SignedBits
     }
 
-    fn bits_to_bigfloat(value: Self::Bits) -> BigFloat {
-        BigFloat::from(value)
-    }
-
-    fn signedbits_to_bigfloat(value: Self::SignedBits) -> BigFloat {
-        BigFloat::from(value)
-    }
-
     fn from_integer(value: u64) -> Self::Bits {
         value as Self::Bits
     }
@@ -262,59 +486,8 @@ impl FloatOps for u32 {
         value & 0x7FFFFFFF
     }
 
-    fn is_zero(value: Self::Bits) -> bool {
-        value == 0
-    }
-
-    fn fp_unpack(fpval: Self::Bits, fpscr_val: u32) -> (FPType, bool, BigFloat, Option<FPExc>) {
-        let sign = fpval.get_bit(31);
-        let exp32 = fpval.get_bits(23..31);
-        let frac32 = fpval.get_bits(0..23);
-        let mut exception = None;
-
-        let (ret_type, mut value) = if Self::is_zero(exp32) {
-            if Self::is_zero(frac32) || fpscr_val.get_bit(24) {
-                if !Self::is_zero(frac32) {
-                    // Denormalized input flushed to zero
-                    exception = Some(FPExc::InputDenorm);
-                }
-                (FPType::Zero, BigFloat::default())
-            } else {
-                (
-                    FPType::Nonzero,
-                    BigFloat::from(2.0).pow(&BigFloat::from(-126.0))
-                        * (BigFloat::from(frac32)
-                            * BigFloat::from(2.0).pow(&BigFloat::from(-23.0))),
-                )
-            }
-        } else if is_ones_32(exp32, 8) {
-            if Self::is_zero(frac32) {
-                (
-                    FPType::Infinity,
-                    BigFloat::from(2.0).pow(&BigFloat::from(1000000.0)),
-                )
-            } else {
-                (
-                    if frac32.get_bit(22) {
-                        FPType::QNaN
-                    } else {
-                        FPType::SNaN
-                    },
-                    BigFloat::default(),
-                )
-            }
-        } else {
-            (
-                FPType::Nonzero,
-                BigFloat::from(2.0).pow(&(BigFloat::from(exp32) - BigFloat::from(127.0)))
-                    * (BigFloat::from(1.0)
-                        + BigFloat::from(frac32) * BigFloat::from(2.0).pow(&BigFloat::from(-23.0))),
-            )
-        };
-        if sign {
-            value = -value;
-        }
-        (ret_type, sign, value, exception)
+    fn fp_neg(value: Self::Bits) -> Self::Bits {
+        value ^ 0x80000000
     }
 
     fn fp_max_normal(sign: bool) -> Self::Bits {
@@ -324,22 +497,6 @@ impl FloatOps for u32 {
         ((sign as u32) << 31) + (exp << 23) + frac
     }
 
-    fn normalize(mant: BigFloat) -> (BigFloat, Self::SignedBits) {
-        let mut exponent: i32 = 0;
-        let limit = BigFloat::from(1.0);
-        let multiplier = BigFloat::from(2.0);
-        let mut mantissa = mant;
-        while mantissa < limit {
-            mantissa *= multiplier;
-            exponent -= 1;
-        }
-        while mantissa >= multiplier {
-            mantissa /= multiplier;
-            exponent += 1;
-        }
-        (mantissa, exponent)
-    }
-
     fn concate_bits(
         sign: bool,
         biased_exp: Self::SignedBits,
@@ -386,18 +543,6 @@ impl FloatOps for u64 {
         value as Self::Bits
     }
 
-    fn powf2(e: BigFloat) -> BigFloat {
-        BigFloat::from(2).pow(&e)
-    }
-
-    fn unsigned_pow2_f(e: usize) -> BigFloat {
-        BigFloat::from(2).pow(&((e as u32).into()))
-    }
-
-    fn signed_pow2(e: usize) -> Self::SignedBits {
-        2i64.pow(e as u32)
-    }
-
     fn signed_value(value: i32) -> Self::SignedBits {
         value as i64
     }
@@ -406,14 +551,6 @@ impl FloatOps for u64 {
         value as Self::SignedBits
     }
 
-    fn bits_to_bigfloat(value: Self::Bits) -> BigFloat {
-        BigFloat::from(value)
-    }
-
-    fn signedbits_to_bigfloat(value: Self::SignedBits) -> BigFloat {
-        BigFloat::from(value)
-    }
-
     fn from_integer(value: u64) -> Self::Bits {
         value
     }
@@ -430,62 +567,12 @@ impl FloatOps for u64 {
         0x7FF8000000000000
     }
 
-    fn is_zero(value: Self::Bits) -> bool {
-        value == 0
-    }
-
     fn fp_abs(value: Self::Bits) -> Self::Bits {
         value & 0x7FFFFFFFFFFFFFFF
     }
 
-    fn fp_unpack(fpval: Self::Bits, fpscr_val: u32) -> (FPType, bool, BigFloat, Option<FPExc>) {
-        let sign = fpval.get_bit(63);
-        let exp64 = fpval.get_bits(52..63);
-        let frac64 = fpval.get_bits(0..52);
-        let mut exception = None;
-
-        let (ret_type, mut value) = if Self::is_zero(exp64) {
-            if Self::is_zero(frac64) || fpscr_val.get_bit(24) {
-                if !Self::is_zero(frac64) {
-                    exception = Some(FPExc::InputDenorm);
-                }
-                (FPType::Zero, BigFloat::default())
-            } else {
-                (
-                    FPType::Nonzero,
-                    BigFloat::from(2.0).pow(&BigFloat::from(-1022.0))
-                        * (BigFloat::from(frac64)
-                            * BigFloat::from(2.0).pow(&BigFloat::from(-52.0))),
-                )
-            }
-        } else if is_ones_64(exp64, 11) {
-            if Self::is_zero(frac64) {
-                (
-                    FPType::Infinity,
-                    BigFloat::from(2.0).pow(&BigFloat::from(1000000.0)),
-                )
-            } else {
-                (
-                    if frac64.get_bit(51) {
-                        FPType::QNaN
-                    } else {
-                        FPType::SNaN
-                    },
-                    BigFloat::default(),
-                )
-            }
-        } else {
-            (
-                FPType::Nonzero,
-                BigFloat::from(2.0).pow(&(BigFloat::from(exp64) - BigFloat::from(1023.0)))
-                    * (BigFloat::from(1.0)
-                        + BigFloat::from(frac64) * BigFloat::from(2.0).pow(&BigFloat::from(-52.0))),
-            )
-        };
-        if sign {
-            value = -value;
-        }
-        (ret_type, sign, value, exception)
+    fn fp_neg(value: Self::Bits) -> Self::Bits {
+        value ^ 0x8000000000000000
     }
 
     fn fp_max_normal(sign: bool) -> Self::Bits {
@@ -495,22 +582,6 @@ impl FloatOps for u64 {
         ((sign as u64) << 63) + (exp << 52) + frac
     }
 
-    fn normalize(mant: BigFloat) -> (BigFloat, Self::SignedBits) {
-        let mut exponent: i64 = 0;
-        let limit = BigFloat::from(1.0);
-        let multiplier = BigFloat::from(2.0);
-        let mut mantissa = mant;
-        while mantissa < limit {
-            mantissa *= multiplier;
-            exponent -= 1;
-        }
-        while mantissa >= multiplier {
-            mantissa /= multiplier;
-            exponent += 1;
-        }
-        (mantissa, exponent)
-    }
-
     fn concate_bits(
         sign: bool,
         biased_exp: Self::SignedBits,
@@ -532,16 +603,17 @@ fn standard_fpscr_value(fpscr: u32) -> u32 {
 /// saturate i to n bits, return the result and a boolean indicating if saturation occurred (up to 64 bits)
 fn satq(i: i128, n: usize, unsigned: bool) -> (u64, bool) {
     if unsigned {
-        let limit = 2u128.pow(n as u32) - 1;
-        let i_unsigned = i as u128;
+        let limit = 2i128.pow(n as u32) - 1;
 
-        let (result, saturated) = if i_unsigned > limit {
+        let (result, saturated) = if i > limit {
             (limit, true)
+        } else if i < 0 {
+            (0, true)
         } else {
-            (i_unsigned, false)
+            (i, false)
         };
 
-        return (result.get_bits(0..n) as u64, saturated);
+        return (result as u64, saturated);
     } else {
         let limit = 2i128.pow(n as u32 - 1) - 1;
         let neg_limit = -(2i128.pow(n as u32 - 1));
@@ -558,6 +630,25 @@ fn satq(i: i128, n: usize, unsigned: bool) -> (u64, bool) {
     }
 }
 
+/// Quiet NaN of another format, keeping the sign and the most significant
+/// bits of the payload
+fn fp_convert_nan<N: FloatOps, M: FloatOps>(op: N::Bits) -> M::Bits {
+    let fraction_len = |n: usize| if n == 32 { 23 } else { 52 };
+    let (n_frac, m_frac) = (fraction_len(N::n()), fraction_len(M::n()));
+
+    let bits: u64 = op.into();
+    let sign = u64::from(bits.get_bit(N::n() - 1));
+    let payload: u64 = bits.get_bits(0..n_frac - 1);
+    let payload = if m_frac > n_frac {
+        payload << (m_frac - n_frac)
+    } else {
+        payload >> (n_frac - m_frac)
+    };
+    let exponent = (1u64 << (M::n() - m_frac - 1)) - 1;
+
+    M::from_integer(sign << (M::n() - 1) | exponent << m_frac | 1 << (m_frac - 1) | payload)
+}
+
 impl FloatingPointHiddenOperations for Processor {
     fn fp_process_nan<T: FloatOps>(
         &mut self,
@@ -602,100 +693,237 @@ impl FloatingPointHiddenOperations for Processor {
         }
     }
 
-    fn fp_unpack<T: FloatOps>(
+    fn fp_process_nans3<T: FloatOps>(
         &mut self,
-        fpval: T::Bits,
+        type1: FPType,
+        type2: FPType,
+        type3: FPType,
+        op1: T::Bits,
+        op2: T::Bits,
+        op3: T::Bits,
         fpscr_val: u32,
-    ) -> (FPType, bool, BigFloat) {
-        let (fptype, sign, value, exception) = T::fp_unpack(fpval, fpscr_val);
-        if let Some(exc) = exception {
-            self.fp_process_exception(exc, fpscr_val);
+    ) -> (bool, T::Bits) {
+        if type1 == FPType::SNaN {
+            (true, self.fp_process_nan::<T>(type1, op1, fpscr_val))
+        } else if type2 == FPType::SNaN {
+            (true, self.fp_process_nan::<T>(type2, op2, fpscr_val))
+        } else if type3 == FPType::SNaN {
+            (true, self.fp_process_nan::<T>(type3, op3, fpscr_val))
+        } else if type1 == FPType::QNaN {
+            (true, self.fp_process_nan::<T>(type1, op1, fpscr_val))
+        } else if type2 == FPType::QNaN {
+            (true, self.fp_process_nan::<T>(type2, op2, fpscr_val))
+        } else if type3 == FPType::QNaN {
+            (true, self.fp_process_nan::<T>(type3, op3, fpscr_val))
+        } else {
+            (false, T::zero())
         }
-        (fptype, sign, value)
     }
 
-    fn fp_round<T: FloatOps>(&mut self, value: BigFloat, fpscr_val: u32) -> T::Bits {
-        assert!(value != BigFloat::default());
+    fn fp_max<T: FloatOps>(&mut self, op1: T::Bits, op2: T::Bits, fpscr_val: u32) -> T::Bits {
+        let (type1, sign1, value1) = self.fp_unpack::<T>(op1, fpscr_val);
+        let (type2, sign2, value2) = self.fp_unpack::<T>(op2, fpscr_val);
+        let (done, result) = self.fp_process_nans::<T>(type1, type2, op1, op2, fpscr_val);
+        if done {
+            return result;
+        }
+        let (fptype, sign, value) = if value2.less(&value1) {
+            (type1, sign1, value1)
+        } else {
+            (type2, sign2, value2)
+        };
+        match fptype {
+            FPType::Infinity => T::fp_infinity(sign),
+            // +0.0 is larger than -0.0
+            FPType::Zero => T::fp_zero(sign1 && sign2),
+            _ => self.fp_round::<T>(value, fpscr_val),
+        }
+    }
 
-        let e = if T::n() == 32 { 8 } else { 11 };
-        let minimum_exp: T::SignedBits = T::signed_value(2) - T::signed_pow2(e - 1);
+    fn fp_min<T: FloatOps>(&mut self, op1: T::Bits, op2: T::Bits, fpscr_val: u32) -> T::Bits {
+        let (type1, sign1, value1) = self.fp_unpack::<T>(op1, fpscr_val);
+        let (type2, sign2, value2) = self.fp_unpack::<T>(op2, fpscr_val);
+        let (done, result) = self.fp_process_nans::<T>(type1, type2, op1, op2, fpscr_val);
+        if done {
+            return result;
+        }
+        let (fptype, sign, value) = if value1.less(&value2) {
+            (type1, sign1, value1)
+        } else {
+            (type2, sign2, value2)
+        };
+        match fptype {
+            FPType::Infinity => T::fp_infinity(sign),
+            // -0.0 is smaller than +0.0
+            FPType::Zero => T::fp_zero(sign1 || sign2),
+            _ => self.fp_round::<T>(value, fpscr_val),
+        }
+    }
 
+    fn fp_unpack<T: FloatOps>(&mut self, fpval: T::Bits, fpscr_val: u32) -> (FPType, bool, Real) {
+        let e = if T::n() == 32 { 8 } else { 11 };
         let f = T::n() - e - 1;
-        let (sign, mantissa) = if value < BigFloat::default() {
-            (true, -value)
+        let bias = (1i32 << (e - 1)) - 1;
+
+        let bits: u64 = fpval.into();
+        let sign = bits.get_bit(T::n() - 1);
+        let exp = bits.get_bits(f..T::n() - 1);
+        let frac = bits.get_bits(0..f);
+
+        let (fptype, mantissa, exponent) = if exp == 0 {
+            if frac == 0 || fpscr_val.get_fz() {
+                if frac != 0 {
+                    // Denormalized input flushed to zero
+                    self.fp_process_exception(FPExc::InputDenorm, fpscr_val);
+                }
+                (FPType::Zero, 0, 0)
+            } else {
+                (FPType::Nonzero, frac, 1 - bias - f as i32)
+            }
+        } else if exp == (1 << e) - 1 {
+            if frac == 0 {
+                // larger than any finite value, for comparisons
+                (FPType::Infinity, 1, 1_000_000)
+            } else if frac.get_bit(f - 1) {
+                (FPType::QNaN, 0, 0)
+            } else {
+                (FPType::SNaN, 0, 0)
+            }
         } else {
-            (false, value)
+            (FPType::Nonzero, frac | 1 << f, exp as i32 - bias - f as i32)
+        };
+
+        let value = Real {
+            sign,
+            mantissa: u128::from(mantissa),
+            exponent,
+            sticky: false,
         };
+        (fptype, sign, value)
+    }
+
+    fn fp_round<T: FloatOps>(&mut self, value: Real, fpscr_val: u32) -> T::Bits {
+        assert!(value.mantissa != 0);
 
-        let (mut mantissa, exponent) = T::normalize(mantissa);
+        let e = if T::n() == 32 { 8 } else { 11 };
+        let f = T::n() - e - 1;
+        let minimum_exp = 2 - (1i32 << (e - 1));
+        let sign = value.sign;
+        let exponent = value.top();
 
         if fpscr_val.get_fz() && exponent < minimum_exp {
             self.fpscr.set_ufc(true);
-            T::fp_zero(sign)
+            return T::fp_zero(sign);
+        }
+
+        let mut biased_exp = (exponent - minimum_exp + 1).max(0);
+        let lsb_exp = if biased_exp == 0 {
+            minimum_exp - f as i32
         } else {
-            let mut biased_exp = T::SignedBits::max(
-                exponent - minimum_exp + T::signed_value(1),
-                T::signed_value(0),
-            );
-            if biased_exp == T::SignedBits::default() {
-                mantissa = mantissa / T::powf2((minimum_exp - exponent).into());
-            }
-            let pow2_f = T::unsigned_pow2_f(f);
-            let mut int_mantissa = round_down(mantissa * pow2_f);
-            let mut error: BigFloat = mantissa * pow2_f - BigFloat::from(int_mantissa);
-
-            if biased_exp == T::SignedBits::default()
-                && (error != BigFloat::default() || fpscr_val.get_bit(11))
-            {
-                self.fp_process_exception(FPExc::Underflow, fpscr_val);
-            }
+            exponent - f as i32
+        };
+        let (mut int_mantissa, half_cmp, mut inexact) =
+            split_fraction(value.mantissa, lsb_exp - value.exponent, value.sticky);
 
-            let (round_up, overflow_to_inf) = match fpscr_val.get_rounding_mode() {
-                FPSCRRounding::RoundToNearest => (
-                    (error > BigFloat::from(0.5)
-                        || (error == BigFloat::from(0.5) && int_mantissa.get_bit(0))),
-                    true,
-                ),
-                FPSCRRounding::RoundTowardsPlusInfinity => {
-                    (error != BigFloat::default() && !sign, !sign)
-                }
-                FPSCRRounding::RoundTowardsMinusInfinity => {
-                    (error != BigFloat::default() && sign, sign)
-                }
-                FPSCRRounding::RoundTowardsZero => (false, false),
-            };
-            if round_up {
-                int_mantissa += 1;
-                if int_mantissa == 2i128.pow(f as u32) {
-                    biased_exp = T::signed_value(1);
-                }
-                if int_mantissa == 2i128.pow(f as u32 + 1) {
-                    biased_exp += T::signed_value(1);
-                    int_mantissa = int_mantissa / 2;
-                }
+        if biased_exp == 0 && (inexact || fpscr_val.get_bit(11)) {
+            self.fp_process_exception(FPExc::Underflow, fpscr_val);
+        }
+
+        let (round_up, overflow_to_inf) = match fpscr_val.get_rounding_mode() {
+            FPSCRRounding::RoundToNearest => (
+                half_cmp == Ordering::Greater
+                    || (half_cmp == Ordering::Equal && int_mantissa.get_bit(0)),
+                true,
+            ),
+            FPSCRRounding::RoundTowardsPlusInfinity => (inexact && !sign, !sign),
+            FPSCRRounding::RoundTowardsMinusInfinity => (inexact && sign, sign),
+            FPSCRRounding::RoundTowardsZero => (false, false),
+        };
+        if round_up {
+            int_mantissa += 1;
+            if int_mantissa == 1 << f {
+                biased_exp = 1;
             }
+            if int_mantissa == 1 << (f + 1) {
+                biased_exp += 1;
+                int_mantissa /= 2;
+            }
+        }
 
-            let result = if biased_exp >= T::signed_pow2(e) - T::signed_value(1) {
-                let result = if overflow_to_inf {
-                    T::fp_infinity(sign)
-                } else {
-                    T::fp_max_normal(sign)
-                };
-                self.fp_process_exception(FPExc::Overflow, fpscr_val);
-                error = num_bigfloat::ONE;
-                result
+        let result = if biased_exp >= (1 << e) - 1 {
+            let result = if overflow_to_inf {
+                T::fp_infinity(sign)
             } else {
-                // concatenate following bits:
-                // sign: biased_exp(0..=e-1) : int_mant(0..=f-1)
-                T::concate_bits(sign, biased_exp, T::integer_to_bits(int_mantissa))
+                T::fp_max_normal(sign)
             };
-
-            if error != BigFloat::default() {
-                self.fp_process_exception(FPExc::Inexact, fpscr_val);
-            }
+            self.fp_process_exception(FPExc::Overflow, fpscr_val);
+            inexact = true;
             result
-        }
-    }
+        } else {
+            T::concate_bits(
+                sign,
+                T::signed_value(biased_exp),
+                T::integer_to_bits(int_mantissa as i128),
+            )
+        };
+
+        if inexact {
+            self.fp_process_exception(FPExc::Inexact, fpscr_val);
+        }
+        result
+    }
+
+    fn fp_to_fixed_rounded<N: FloatOps, M: FloatOps>(
+        &mut self,
+        op: N::Bits,
+        fraction_bits: usize,
+        unsigned: bool,
+        away: bool,
+        fpscr_val: u32,
+    ) -> M::Bits {
+        let (type_t, sign, value) = self.fp_unpack::<N>(op, fpscr_val);
+        if type_t == FPType::SNaN || type_t == FPType::QNaN {
+            // NaNs convert to zero
+            self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
+            return M::zero();
+        }
+
+        // Round the magnitude, the sign decides the directed roundings.
+        // Anything at or above 2^64 saturates whatever the rounding.
+        let (int_result, inexact) = if value.is_zero() {
+            (0, false)
+        } else if value.top() + fraction_bits as i32 >= 64 {
+            (if sign { i128::MIN } else { i128::MAX }, false)
+        } else {
+            let shift = -(value.exponent + fraction_bits as i32);
+            let (mut magnitude, half_cmp, inexact) = split_fraction(value.mantissa, shift, false);
+            let round_up = match fpscr_val.get_rounding_mode() {
+                FPSCRRounding::RoundToNearest => {
+                    half_cmp == Ordering::Greater
+                        || (half_cmp == Ordering::Equal && (away || magnitude.get_bit(0)))
+                }
+                FPSCRRounding::RoundTowardsPlusInfinity => inexact && !sign,
+                FPSCRRounding::RoundTowardsMinusInfinity => inexact && sign,
+                FPSCRRounding::RoundTowardsZero => false,
+            };
+            if round_up {
+                magnitude += 1;
+            }
+            let int_result = magnitude as i128;
+            (if sign { -int_result } else { int_result }, inexact)
+        };
+
+        let (result, overflow) = satq(int_result, M::n(), unsigned);
+
+        // Out of range conversions are invalid rather than overflowing
+        if overflow {
+            self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
+        } else if inexact {
+            self.fp_process_exception(FPExc::Inexact, fpscr_val);
+        }
+
+        M::from_integer(result)
+    }
 }
 
 impl FloatingPointPublicOperations for Processor {
@@ -729,10 +957,9 @@ impl FloatingPointPublicOperations for Processor {
             } else if zero1 && zero2 && sign1 == sign2 {
                 T::fp_zero(sign1)
             } else {
-                // Calculate in 'real' numbers, with unbounded size and precision.
-                // As in real life there must be some limit, we use the defined Real type limits
-                let result_value = value1 + value2;
-                if result_value == BigFloat::default() {
+                // Calculate in 'real' numbers, exact binary values with unbounded precision
+                let result_value = value1.add(value2);
+                if result_value.is_zero() {
                     // Sign of exact zero result depends on rounding mode
                     T::fp_zero(
                         fpscr_val.get_rounding_mode() == FPSCRRounding::RoundTowardsMinusInfinity,
@@ -767,19 +994,19 @@ impl FloatingPointPublicOperations for Processor {
             let inf2 = type2 == FPType::Infinity;
             let zero1 = type1 == FPType::Zero;
             let zero2 = type2 == FPType::Zero;
-            let result = if inf1 && inf2 && sign1 != sign2 {
+            let result = if inf1 && inf2 && sign1 == sign2 {
                 let res = T::fp_default_nan();
                 self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
                 res
-            } else if (inf1 && sign1 == false) || (inf2 && sign2 == false) {
+            } else if (inf1 && !sign1) || (inf2 && sign2) {
                 T::fp_infinity(false)
-            } else if (inf1 && sign1 == true) || (inf2 && sign2 == false) {
+            } else if (inf1 && sign1) || (inf2 && !sign2) {
                 T::fp_infinity(true)
-            } else if zero1 && zero2 && sign1 == sign2 {
+            } else if zero1 && zero2 && sign1 != sign2 {
                 T::fp_zero(sign1)
             } else {
-                let result_value = value1 - value2;
-                if result_value == BigFloat::default() {
+                let result_value = value1.add(value2.neg());
+                if result_value.is_zero() {
                     // Sign of exact zero result depends on rounding mode
                     T::fp_zero(
                         fpscr_val.get_rounding_mode() == FPSCRRounding::RoundTowardsMinusInfinity,
@@ -822,12 +1049,12 @@ impl FloatingPointPublicOperations for Processor {
             }
             result
         } else {
-            if value1 == value2 {
-                (false, true, true, false)
-            } else if value1 < value2 {
+            if value1.less(&value2) {
                 (true, false, false, false)
-            } else {
+            } else if value2.less(&value1) {
                 (false, false, true, false)
+            } else {
+                (false, true, true, false)
             }
         }
     }
@@ -836,6 +1063,293 @@ impl FloatingPointPublicOperations for Processor {
         T::fp_abs(op)
     }
 
+    fn fp_neg<T: FloatOps>(&mut self, op: T::Bits) -> T::Bits {
+        T::fp_neg(op)
+    }
+
+    fn fp_mul<T: FloatOps>(
+        &mut self,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits {
+        let fpscr_val = if fpscr_controlled {
+            self.fpscr
+        } else {
+            standard_fpscr_value(self.fpscr)
+        };
+        let (type1, sign1, value1) = self.fp_unpack::<T>(op1, fpscr_val);
+        let (type2, sign2, value2) = self.fp_unpack::<T>(op2, fpscr_val);
+        let (done, result) = self.fp_process_nans::<T>(type1, type2, op1, op2, fpscr_val);
+        if done {
+            return result;
+        }
+        let inf1 = type1 == FPType::Infinity;
+        let inf2 = type2 == FPType::Infinity;
+        let zero1 = type1 == FPType::Zero;
+        let zero2 = type2 == FPType::Zero;
+        if (inf1 && zero2) || (zero1 && inf2) {
+            self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
+            T::fp_default_nan()
+        } else if inf1 || inf2 {
+            T::fp_infinity(sign1 != sign2)
+        } else if zero1 || zero2 {
+            T::fp_zero(sign1 != sign2)
+        } else {
+            self.fp_round::<T>(value1.mul(value2), fpscr_val)
+        }
+    }
+
+    fn fp_div<T: FloatOps>(
+        &mut self,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits {
+        let fpscr_val = if fpscr_controlled {
+            self.fpscr
+        } else {
+            standard_fpscr_value(self.fpscr)
+        };
+        let (type1, sign1, value1) = self.fp_unpack::<T>(op1, fpscr_val);
+        let (type2, sign2, value2) = self.fp_unpack::<T>(op2, fpscr_val);
+        let (done, result) = self.fp_process_nans::<T>(type1, type2, op1, op2, fpscr_val);
+        if done {
+            return result;
+        }
+        let inf1 = type1 == FPType::Infinity;
+        let inf2 = type2 == FPType::Infinity;
+        let zero1 = type1 == FPType::Zero;
+        let zero2 = type2 == FPType::Zero;
+        if (inf1 && inf2) || (zero1 && zero2) {
+            self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
+            T::fp_default_nan()
+        } else if inf1 || zero2 {
+            if !inf1 {
+                self.fp_process_exception(FPExc::DivideByZero, fpscr_val);
+            }
+            T::fp_infinity(sign1 != sign2)
+        } else if zero1 || inf2 {
+            T::fp_zero(sign1 != sign2)
+        } else {
+            self.fp_round::<T>(value1.div(value2), fpscr_val)
+        }
+    }
+
+    fn fp_sqrt<T: FloatOps>(&mut self, op: T::Bits, fpscr_controlled: bool) -> T::Bits {
+        let fpscr_val = if fpscr_controlled {
+            self.fpscr
+        } else {
+            standard_fpscr_value(self.fpscr)
+        };
+        let (fptype, sign, value) = self.fp_unpack::<T>(op, fpscr_val);
+        match fptype {
+            FPType::SNaN | FPType::QNaN => self.fp_process_nan::<T>(fptype, op, fpscr_val),
+            FPType::Zero => T::fp_zero(sign),
+            FPType::Infinity if !sign => T::fp_infinity(false),
+            _ if sign => {
+                self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
+                T::fp_default_nan()
+            }
+            _ => self.fp_round::<T>(value.sqrt(), fpscr_val),
+        }
+    }
+
+    fn fp_mul_add<T: FloatOps>(
+        &mut self,
+        addend: T::Bits,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits {
+        let fpscr_val = if fpscr_controlled {
+            self.fpscr
+        } else {
+            standard_fpscr_value(self.fpscr)
+        };
+        let (type_a, sign_a, value_a) = self.fp_unpack::<T>(addend, fpscr_val);
+        let (type1, sign1, value1) = self.fp_unpack::<T>(op1, fpscr_val);
+        let (type2, sign2, value2) = self.fp_unpack::<T>(op2, fpscr_val);
+        let inf1 = type1 == FPType::Infinity;
+        let inf2 = type2 == FPType::Infinity;
+        let zero1 = type1 == FPType::Zero;
+        let zero2 = type2 == FPType::Zero;
+
+        let (done, result) =
+            self.fp_process_nans3::<T>(type_a, type1, type2, addend, op1, op2, fpscr_val);
+
+        // inf * 0 is invalid even when the addend is a quiet NaN
+        if type_a == FPType::QNaN && ((inf1 && zero2) || (zero1 && inf2)) {
+            self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
+            return T::fp_default_nan();
+        }
+        if done {
+            return result;
+        }
+
+        let inf_a = type_a == FPType::Infinity;
+        let zero_a = type_a == FPType::Zero;
+
+        // Determine sign and type the product will have if it does not cause an Invalid
+        // Operation.
+        let sign_p = sign1 != sign2;
+        let inf_p = inf1 || inf2;
+        let zero_p = zero1 || zero2;
+
+        if (inf1 && zero2) || (zero1 && inf2) || (inf_a && inf_p && sign_a != sign_p) {
+            self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
+            T::fp_default_nan()
+        } else if (inf_a && !sign_a) || (inf_p && !sign_p) {
+            T::fp_infinity(false)
+        } else if (inf_a && sign_a) || (inf_p && sign_p) {
+            T::fp_infinity(true)
+        } else if zero_a && zero_p && sign_a == sign_p {
+            T::fp_zero(sign_a)
+        } else {
+            let result_value = value_a.add(value1.mul(value2));
+            if result_value.is_zero() {
+                // Sign of exact zero result depends on rounding mode
+                T::fp_zero(
+                    fpscr_val.get_rounding_mode() == FPSCRRounding::RoundTowardsMinusInfinity,
+                )
+            } else {
+                self.fp_round::<T>(result_value, fpscr_val)
+            }
+        }
+    }
+
+    fn fp_max_num<T: FloatOps>(
+        &mut self,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits {
+        let fpscr_val = if fpscr_controlled {
+            self.fpscr
+        } else {
+            standard_fpscr_value(self.fpscr)
+        };
+        let (type1, _, _) = self.fp_unpack::<T>(op1, fpscr_val);
+        let (type2, _, _) = self.fp_unpack::<T>(op2, fpscr_val);
+
+        // treat a single quiet NaN as -Infinity
+        if type1 == FPType::QNaN && type2 != FPType::QNaN {
+            self.fp_max::<T>(T::fp_infinity(true), op2, fpscr_val)
+        } else if type1 != FPType::QNaN && type2 == FPType::QNaN {
+            self.fp_max::<T>(op1, T::fp_infinity(true), fpscr_val)
+        } else {
+            self.fp_max::<T>(op1, op2, fpscr_val)
+        }
+    }
+
+    fn fp_min_num<T: FloatOps>(
+        &mut self,
+        op1: T::Bits,
+        op2: T::Bits,
+        fpscr_controlled: bool,
+    ) -> T::Bits {
+        let fpscr_val = if fpscr_controlled {
+            self.fpscr
+        } else {
+            standard_fpscr_value(self.fpscr)
+        };
+        let (type1, _, _) = self.fp_unpack::<T>(op1, fpscr_val);
+        let (type2, _, _) = self.fp_unpack::<T>(op2, fpscr_val);
+
+        // treat a single quiet NaN as +Infinity
+        if type1 == FPType::QNaN && type2 != FPType::QNaN {
+            self.fp_min::<T>(T::fp_infinity(false), op2, fpscr_val)
+        } else if type1 != FPType::QNaN && type2 == FPType::QNaN {
+            self.fp_min::<T>(op1, T::fp_infinity(false), fpscr_val)
+        } else {
+            self.fp_min::<T>(op1, op2, fpscr_val)
+        }
+    }
+
+    fn fp_round_int<T: FloatOps>(
+        &mut self,
+        op: T::Bits,
+        rounding: FPSCRRounding,
+        away: bool,
+        exact: bool,
+    ) -> T::Bits {
+        let mut fpscr_val = self.fpscr;
+        fpscr_val.set_rounding_mode(rounding);
+
+        let (fptype, sign, value) = self.fp_unpack::<T>(op, fpscr_val);
+        match fptype {
+            FPType::SNaN | FPType::QNaN => self.fp_process_nan::<T>(fptype, op, fpscr_val),
+            FPType::Infinity => T::fp_infinity(sign),
+            FPType::Zero => T::fp_zero(sign),
+            FPType::Nonzero => {
+                // Values this large have no fractional bits left
+                if value.exponent >= 0 {
+                    return op;
+                }
+
+                // Round the magnitude, the sign decides the directed roundings
+                let (mut int_result, half_cmp, inexact) =
+                    split_fraction(value.mantissa, -value.exponent, false);
+                let round_up = if away {
+                    half_cmp != Ordering::Less
+                } else {
+                    match rounding {
+                        FPSCRRounding::RoundToNearest => {
+                            half_cmp == Ordering::Greater
+                                || (half_cmp == Ordering::Equal && int_result.get_bit(0))
+                        }
+                        FPSCRRounding::RoundTowardsPlusInfinity => inexact && !sign,
+                        FPSCRRounding::RoundTowardsMinusInfinity => inexact && sign,
+                        FPSCRRounding::RoundTowardsZero => false,
+                    }
+                };
+                if round_up {
+                    int_result += 1;
+                }
+
+                let result = if int_result == 0 {
+                    T::fp_zero(sign)
+                } else {
+                    self.fp_round::<T>(Real::from_integer(sign, int_result), fpscr_val)
+                };
+
+                if inexact && exact {
+                    self.fp_process_exception(FPExc::Inexact, fpscr_val);
+                }
+                result
+            }
+        }
+    }
+
+    fn fp_convert<N: FloatOps, M: FloatOps>(
+        &mut self,
+        op: N::Bits,
+        fpscr_controlled: bool,
+    ) -> M::Bits {
+        let fpscr_val = if fpscr_controlled {
+            self.fpscr
+        } else {
+            standard_fpscr_value(self.fpscr)
+        };
+        let (fptype, sign, value) = self.fp_unpack::<N>(op, fpscr_val);
+        match fptype {
+            FPType::SNaN | FPType::QNaN => {
+                let result = if fpscr_val.get_dn() {
+                    M::fp_default_nan()
+                } else {
+                    fp_convert_nan::<N, M>(op)
+                };
+                if fptype == FPType::SNaN {
+                    self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
+                }
+                result
+            }
+            FPType::Infinity => M::fp_infinity(sign),
+            FPType::Zero => M::fp_zero(sign),
+            FPType::Nonzero => self.fp_round::<M>(value, fpscr_val),
+        }
+    }
+
     fn fixed_to_fp<N: FloatOps, M: FloatOps>(
         &mut self,
         op: M::Bits,
@@ -853,24 +1367,24 @@ impl FloatingPointPublicOperations for Processor {
             fpscr_val.set_rounding_mode(FPSCRRounding::RoundToNearest);
         }
 
-        let real_operand = if unsigned {
-            let int_operand = M::Bits::from(op);
-            BigFloat::div(
-                &M::bits_to_bigfloat(int_operand),
-                &M::powf2(BigFloat::from(fraction_bits as u32)),
-            )
+        let int_operand = if unsigned {
+            let int_operand: u64 = op.into();
+            i128::from(int_operand)
         } else {
-            let int_operand = M::unsigned_to_signed(op);
-            BigFloat::div(
-                &M::signedbits_to_bigfloat(int_operand),
-                &M::powf2(BigFloat::from(fraction_bits as u32)),
-            )
+            let int_operand: i64 = M::unsigned_to_signed(op).into();
+            i128::from(int_operand)
         };
 
-        if real_operand == BigFloat::default() {
-            return N::zero();
+        if int_operand == 0 {
+            N::fp_zero(false)
         } else {
-            return self.fp_round::<N>(real_operand, fpscr_val);
+            let real_operand = Real {
+                sign: int_operand < 0,
+                mantissa: int_operand.unsigned_abs(),
+                exponent: -(fraction_bits as i32),
+                sticky: false,
+            };
+            self.fp_round::<N>(real_operand, fpscr_val)
         }
     }
 
@@ -891,46 +1405,21 @@ impl FloatingPointPublicOperations for Processor {
         if round_towards_zero {
             fpscr_val.set_rounding_mode(FPSCRRounding::RoundTowardsZero);
         }
-        let (type_t, _sign, mut value) = self.fp_unpack::<N>(op, fpscr_val);
-        if type_t == FPType::SNaN || type_t == FPType::QNaN {
-            self.fp_process_exception(FPExc::InvalidOp, fpscr_val);
-        }
-
-        value = value * N::powf2(BigFloat::from(fraction_bits as u32));
-        let mut int_result = round_down(value);
-        let error = value - BigFloat::from(int_result);
-
-        let round_up = match fpscr_val.get_rounding_mode() {
-            FPSCRRounding::RoundToNearest => {
-                error > BigFloat::from(0.5)
-                    || (error == BigFloat::from(0.5) && int_result.get_bit(0) == false)
-            }
-            FPSCRRounding::RoundTowardsPlusInfinity => error != BigFloat::default(),
-            FPSCRRounding::RoundTowardsMinusInfinity => false,
-            FPSCRRounding::RoundTowardsZero => error != BigFloat::default() && int_result < 0,
-        };
-        if round_up {
-            int_result = int_result.saturating_add(1);
-        }
-
-        let (result, overflow) = satq(int_result, M::n(), unsigned);
-
-        if overflow {
-            self.fp_process_exception(FPExc::Overflow, fpscr_val);
-        } else if error != BigFloat::default() {
-            self.fp_process_exception(FPExc::Inexact, fpscr_val);
-        }
-
-        M::from_integer(result)
+        self.fp_to_fixed_rounded::<N, M>(op, fraction_bits, unsigned, false, fpscr_val)
     }
-}
-
-fn is_ones_32(value: u32, len: usize) -> bool {
-    value.count_ones() == len as u32
-}
 
-fn is_ones_64(value: u64, len: usize) -> bool {
-    value.count_ones() == len as u32
+    fn fp_to_fixed_directed<N: FloatOps, M: FloatOps>(
+        &mut self,
+        op: N::Bits,
+        fraction_bits: usize,
+        unsigned: bool,
+        rounding: FPSCRRounding,
+        away: bool,
+    ) -> M::Bits {
+        let mut fpscr_val = self.fpscr;
+        fpscr_val.set_rounding_mode(rounding);
+        self.fp_to_fixed_rounded::<N, M>(op, fraction_bits, unsigned, away, fpscr_val)
+    }
 }
 
 impl FloatingPointChecks for Processor {
@@ -938,21 +1427,18 @@ impl FloatingPointChecks for Processor {
         // todo!()
     }
 
-    fn fp_process_exception(&mut self, exc: FPExc, fpscr_val: u32) {
-        let (enable, cumul) = match exc {
-            FPExc::InvalidOp => (8, 0),
-            FPExc::DivideByZero => (9, 1),
-            FPExc::Overflow => (10, 2),
-            FPExc::Underflow => (11, 3),
-            FPExc::Inexact => (12, 4),
-            FPExc::InputDenorm => (15, 7),
+    fn fp_process_exception(&mut self, exc: FPExc, _fpscr_val: u32) {
+        // M-profile has no floating point traps, exceptions only set the
+        // cumulative flags in FPSCR
+        let cumul = match exc {
+            FPExc::InvalidOp => 0,
+            FPExc::DivideByZero => 1,
+            FPExc::Overflow => 2,
+            FPExc::Underflow => 3,
+            FPExc::Inexact => 4,
+            FPExc::InputDenorm => 7,
         };
-        if fpscr_val.get_bit(enable) {
-            // implementation defined trap handling
-            todo!()
-        } else {
-            self.fpscr.set_bit(cumul, true);
-        }
+        self.fpscr.set_bit(cumul, true);
     }
 }
 
@@ -1045,6 +1531,15 @@ mod tests {
         );
     }
 
+    fn real(sign: bool, mantissa: u128, exponent: i32) -> Real {
+        Real {
+            sign,
+            mantissa,
+            exponent,
+            sticky: false,
+        }
+    }
+
     #[test]
     fn test_fp_unpack_f32() {
         let mut processor = Processor::new();
@@ -1054,70 +1549,74 @@ mod tests {
         // 1.0
         assert_eq!(
             processor.fp_unpack::<u32>(0x3F800000, 0x00000000),
-            (FPType::Nonzero, false, BigFloat::from(1.0))
+            (FPType::Nonzero, false, real(false, 0x80_0000, -23))
         );
 
         // -1.0
         assert_eq!(
             processor.fp_unpack::<u32>(0xBF800000, 0x00000000),
-            (FPType::Nonzero, true, BigFloat::from(-1.0))
+            (FPType::Nonzero, true, real(true, 0x80_0000, -23))
         );
 
         // 2.0
         assert_eq!(
             processor.fp_unpack::<u32>(0x40000000, 0x00000000),
-            (FPType::Nonzero, false, BigFloat::from(2.0))
+            (FPType::Nonzero, false, real(false, 0x80_0000, -22))
         );
 
         // max value
         assert_eq!(
             processor.fp_unpack::<u32>(0x7F7FFFFF, 0x00000000),
-            (
-                FPType::Nonzero,
-                false,
-                BigFloat::from(340282346638528859811704183484516925440u128)
-            )
+            (FPType::Nonzero, false, real(false, 0xFF_FFFF, 104))
         );
 
         // 0.0
         assert_eq!(
             processor.fp_unpack::<u32>(0x00000000, 0x00000000),
-            (FPType::Zero, false, BigFloat::default())
+            (FPType::Zero, false, real(false, 0, 0))
         );
 
         // minimum positive value, non zero:
         assert_eq!(
             processor.fp_unpack::<u32>(0x00800000, 0x00000000),
-            (
-                FPType::Nonzero,
-                false,
-                BigFloat::parse("1.175494350822287507968736537222245677819e-38").unwrap()
-            )
+            (FPType::Nonzero, false, real(false, 0x80_0000, -149))
         );
 
         // Infinity
         assert_eq!(
             processor.fp_unpack::<u32>(0x7F800000, 0x00000000),
-            (FPType::Infinity, false, num_bigfloat::INF_POS)
+            (FPType::Infinity, false, real(false, 1, 1_000_000))
         );
 
         // Negative infinity:
         assert_eq!(
             processor.fp_unpack::<u32>(0xFF800000, 0x00000000),
-            (FPType::Infinity, true, num_bigfloat::INF_NEG)
+            (FPType::Infinity, true, real(true, 1, 1_000_000))
         );
 
         // QNaN
         assert_eq!(
-            processor.fp_unpack::<u32>(0x7FC00000, 0x00000000),
-            (FPType::QNaN, false, BigFloat::default())
+            processor.fp_unpack::<u32>(0x7FC00000, 0x00000000).0,
+            FPType::QNaN
         );
 
         // SNaN
         assert_eq!(
-            processor.fp_unpack::<u32>(0x7F800001, 0x00000000),
-            (FPType::SNaN, false, BigFloat::default())
+            processor.fp_unpack::<u32>(0x7F800001, 0x00000000).0,
+            FPType::SNaN
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0);
+
+        // denormal, and flushed to zero with FZ
+        assert_eq!(
+            processor.fp_unpack::<u32>(0x00000001, 0x00000000),
+            (FPType::Nonzero, false, real(false, 1, -149))
         );
+        assert_eq!(
+            processor.fp_unpack::<u32>(0x80000001, 0x01000000),
+            (FPType::Zero, true, real(true, 0, 0))
+        );
+        assert!(processor.fpscr.get_bit(7));
     }
 
     #[test]
@@ -1127,65 +1626,61 @@ mod tests {
         // 1.0
         assert_eq!(
             processor.fp_unpack::<u64>(0x3FF0000000000000, 0x00000000),
-            (FPType::Nonzero, false, BigFloat::from(1.0))
+            (FPType::Nonzero, false, real(false, 1 << 52, -52))
         );
 
         // -1.0
         assert_eq!(
             processor.fp_unpack::<u64>(0xBFF0000000000000, 0x00000000),
-            (FPType::Nonzero, true, BigFloat::from(-1.0))
+            (FPType::Nonzero, true, real(true, 1 << 52, -52))
         );
 
         // 2.0
         assert_eq!(
             processor.fp_unpack::<u64>(0x4000000000000000, 0x00000000),
-            (FPType::Nonzero, false, BigFloat::from(2.0))
+            (FPType::Nonzero, false, real(false, 1 << 52, -51))
         );
 
         // max value:
         assert_eq!(
             processor.fp_unpack::<u64>(0x7FEFFFFFFFFFFFFF, 0x00000000),
-            (FPType::Nonzero, false, BigFloat::from(std::f64::MAX))
+            (FPType::Nonzero, false, real(false, (1 << 53) - 1, 971))
         );
 
         // 0.0
         assert_eq!(
             processor.fp_unpack::<u64>(0x0000000000000000, 0x00000000),
-            (FPType::Zero, false, BigFloat::default())
+            (FPType::Zero, false, real(false, 0, 0))
         );
 
         // minimum positive value, non zero:
         assert_eq!(
             processor.fp_unpack::<u64>(0x0010000000000000, 0x00000000),
-            (
-                FPType::Nonzero,
-                false,
-                BigFloat::from(std::f64::MIN_POSITIVE)
-            )
+            (FPType::Nonzero, false, real(false, 1 << 52, -1074))
         );
 
         // Infinity
         assert_eq!(
             processor.fp_unpack::<u64>(0x7FF0000000000000, 0x00000000),
-            (FPType::Infinity, false, num_bigfloat::INF_POS)
+            (FPType::Infinity, false, real(false, 1, 1_000_000))
         );
 
         // Negative infinity:
         assert_eq!(
             processor.fp_unpack::<u64>(0xFFF0000000000000, 0x00000000),
-            (FPType::Infinity, true, num_bigfloat::INF_NEG)
+            (FPType::Infinity, true, real(true, 1, 1_000_000))
         );
 
         // QNaN
         assert_eq!(
-            processor.fp_unpack::<u64>(0x7FF8000000000000, 0x00000000),
-            (FPType::QNaN, false, BigFloat::default())
+            processor.fp_unpack::<u64>(0x7FF8000000000000, 0x00000000).0,
+            FPType::QNaN
         );
 
         // SNaN
         assert_eq!(
-            processor.fp_unpack::<u64>(0x7FF0000000000001, 0x00000000),
-            (FPType::SNaN, false, BigFloat::default())
+            processor.fp_unpack::<u64>(0x7FF0000000000001, 0x00000000).0,
+            FPType::SNaN
         );
     }
     #[test]
@@ -1205,15 +1700,30 @@ mod tests {
 
         // 1.0 -> 0x3F800000 exact
         assert_eq!(
-            processor.fp_round::<u32>(BigFloat::from(1.0), 0),
+            processor.fp_round::<u32>(real(false, 1, 0), processor.fpscr),
             0x3F800000
         );
+        assert_eq!(processor.fpscr & 0x1f, 0);
 
-        // 0.33333333333333333333 -> 3eaaaaab
+        // 1/3 -> 3eaaaaab
+        let third = real(false, 1, 0).div(real(false, 3, 0));
         assert_eq!(
-            processor.fp_round::<u32>(BigFloat::from(0.33333333333333333333), 0),
+            processor.fp_round::<u32>(third, processor.fpscr),
             0x3eaaaaab
         );
+        assert!(processor.fpscr.get_bit(4));
+
+        // -1/3 in the directed rounding modes
+        let cases = [
+            (FPSCRRounding::RoundTowardsPlusInfinity, 0xbeaaaaaa),
+            (FPSCRRounding::RoundTowardsMinusInfinity, 0xbeaaaaab),
+            (FPSCRRounding::RoundTowardsZero, 0xbeaaaaaa),
+        ];
+        for (rounding, expected) in cases {
+            let mut fpscr_val = 0;
+            fpscr_val.set_rounding_mode(rounding);
+            assert_eq!(processor.fp_round::<u32>(third.neg(), fpscr_val), expected);
+        }
     }
 
     #[test]
@@ -1263,29 +1773,297 @@ mod tests {
     }
 
     #[test]
-    fn test_fp_round_f64() {
+    fn test_fp_mul_f32() {
         let mut processor = Processor::new();
 
-        // 1.0 -> 0x3FF0000000000000 exact
+        // 1.5 * 2.0 = 3.0, exact
         assert_eq!(
-            processor.fp_round::<u64>(BigFloat::from(1.0f64), 0),
-            0x3FF0000000000000
+            processor.fp_mul::<u32>(0x3FC00000, 0x40000000, true),
+            0x40400000
         );
-    }
+        assert_eq!(processor.fpscr & 0x1f, 0);
 
-    #[test]
-    fn test_satq() {
-        // 1.0 -> 1 (unsigned, 32 bit)
-        assert_eq!(satq(1, 32, true), (1, false));
+        // 0.1 * 3.0 = 0.3 rounds to nearest and is inexact
+        assert_eq!(
+            processor.fp_mul::<u32>(0x3DCCCCCD, 0x40400000, true),
+            0x3E99999A
+        );
+        assert!(processor.fpscr.get_bit(4));
 
-        // 1.0 -> 1 (signed, 32 bit)
-        assert_eq!(satq(1, 32, false), (1, false));
+        // -0.0 * 2.0 = -0.0
+        assert_eq!(
+            processor.fp_mul::<u32>(0x80000000, 0x40000000, true),
+            0x80000000
+        );
 
-        // 1.0 -> 1 (unsigned, 64 bit)
-        assert_eq!(satq(1, 64, true), (1, false));
+        // inf * 0.0 is invalid
+        assert_eq!(
+            processor.fp_mul::<u32>(0x7F800000, 0x00000000, true),
+            0x7FC00000
+        );
+        assert!(processor.fpscr.get_bit(0));
 
-        // 1.0 -> 1 (signed, 64 bit)
-        assert_eq!(satq(1, 64, false), (1, false));
+        // max * 2.0 overflows to infinity
+        assert_eq!(
+            processor.fp_mul::<u32>(0x7F7FFFFF, 0x40000000, true),
+            0x7F800000
+        );
+        assert!(processor.fpscr.get_bit(2));
+    }
+
+    #[test]
+    fn test_fp_mul_f64() {
+        let mut processor = Processor::new();
+
+        // 1.5 * -2.0 = -3.0
+        assert_eq!(
+            processor.fp_mul::<u64>(0x3FF8000000000000, 0xC000000000000000, true),
+            0xC008000000000000
+        );
+
+        // 0.1 * 3.0 = 0.30000000000000004
+        assert_eq!(
+            processor.fp_mul::<u64>(0x3FB999999999999A, 0x4008000000000000, true),
+            0x3FD3333333333334
+        );
+    }
+
+    #[test]
+    fn test_fp_div_f32() {
+        let mut processor = Processor::new();
+
+        // 1.0 / 3.0
+        assert_eq!(
+            processor.fp_div::<u32>(0x3F800000, 0x40400000, true),
+            0x3EAAAAAB
+        );
+
+        // 1.0 / -0.0 = -inf, divide by zero
+        assert_eq!(processor.fpscr & 0x1f, 0x10);
+        assert_eq!(
+            processor.fp_div::<u32>(0x3F800000, 0x80000000, true),
+            0xFF800000
+        );
+        assert!(processor.fpscr.get_bit(1));
+
+        // 0.0 / 0.0 is invalid
+        assert_eq!(
+            processor.fp_div::<u32>(0x00000000, 0x00000000, true),
+            0x7FC00000
+        );
+        assert!(processor.fpscr.get_bit(0));
+
+        // quiet NaN operand propagates
+        assert_eq!(
+            processor.fp_div::<u32>(0x7FC00001, 0x3F800000, true),
+            0x7FC00001
+        );
+    }
+
+    #[test]
+    fn test_fp_div_f64() {
+        let mut processor = Processor::new();
+
+        // 1.0 / 3.0
+        assert_eq!(
+            processor.fp_div::<u64>(0x3FF0000000000000, 0x4008000000000000, true),
+            0x3FD5555555555555
+        );
+    }
+
+    #[test]
+    fn test_fp_sqrt_f32() {
+        let mut processor = Processor::new();
+
+        // sqrt(4.0) = 2.0, exact
+        assert_eq!(processor.fp_sqrt::<u32>(0x40800000, true), 0x40000000);
+        assert_eq!(processor.fpscr & 0x1f, 0);
+
+        // sqrt(2.0)
+        assert_eq!(processor.fp_sqrt::<u32>(0x40000000, true), 0x3FB504F3);
+
+        // sqrt(-0.0) = -0.0
+        assert_eq!(processor.fp_sqrt::<u32>(0x80000000, true), 0x80000000);
+
+        // sqrt(-1.0) is invalid
+        assert_eq!(processor.fp_sqrt::<u32>(0xBF800000, true), 0x7FC00000);
+        assert!(processor.fpscr.get_bit(0));
+    }
+
+    #[test]
+    fn test_fp_sqrt_f64() {
+        let mut processor = Processor::new();
+
+        // sqrt(2.0)
+        assert_eq!(
+            processor.fp_sqrt::<u64>(0x4000000000000000, true),
+            0x3FF6A09E667F3BCD
+        );
+    }
+
+    #[test]
+    fn test_fp_mul_add_f32() {
+        let mut processor = Processor::new();
+
+        // 2.0 + 3.0 * 4.0 = 14.0
+        assert_eq!(
+            processor.fp_mul_add::<u32>(0x40000000, 0x40400000, 0x40800000, true),
+            0x41600000
+        );
+
+        // -(1 + 2^-22) + (1 + 2^-23)^2 = 2^-46, only with a single rounding
+        assert_eq!(
+            processor.fp_mul_add::<u32>(0xBF800002, 0x3F800001, 0x3F800001, true),
+            0x28800000
+        );
+
+        // quiet NaN addend with inf * 0 is invalid
+        assert_eq!(
+            processor.fp_mul_add::<u32>(0x7FC00001, 0x7F800000, 0x00000000, true),
+            0x7FC00000
+        );
+        assert!(processor.fpscr.get_bit(0));
+
+        // +inf + -inf product is invalid
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_mul_add::<u32>(0x7F800000, 0xFF800000, 0x3F800000, true),
+            0x7FC00000
+        );
+        assert!(processor.fpscr.get_bit(0));
+    }
+
+    #[test]
+    fn test_fp_max_min_num_f32() {
+        let mut processor = Processor::new();
+
+        // maxnum(1.0, 2.0) = 2.0, minnum(1.0, 2.0) = 1.0
+        assert_eq!(
+            processor.fp_max_num::<u32>(0x3F800000, 0x40000000, true),
+            0x40000000
+        );
+        assert_eq!(
+            processor.fp_min_num::<u32>(0x3F800000, 0x40000000, true),
+            0x3F800000
+        );
+
+        // a single quiet NaN is ignored
+        assert_eq!(
+            processor.fp_max_num::<u32>(0x7FC00000, 0xBF800000, true),
+            0xBF800000
+        );
+        assert_eq!(
+            processor.fp_min_num::<u32>(0x3F800000, 0x7FC00000, true),
+            0x3F800000
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0);
+
+        // signaling NaN is invalid
+        assert_eq!(
+            processor.fp_max_num::<u32>(0x7F800001, 0x3F800000, true),
+            0x7FC00001
+        );
+        assert!(processor.fpscr.get_bit(0));
+
+        // +0.0 is greater than -0.0
+        assert_eq!(
+            processor.fp_max_num::<u32>(0x80000000, 0x00000000, true),
+            0x00000000
+        );
+        assert_eq!(
+            processor.fp_min_num::<u32>(0x00000000, 0x80000000, true),
+            0x80000000
+        );
+    }
+
+    #[test]
+    fn test_fp_round_int_f32() {
+        let mut processor = Processor::new();
+
+        // 2.5 and -2.5 in each rounding mode
+        use FPSCRRounding::*;
+        let cases = [
+            (RoundToNearest, true, 0x40400000, 0xC0400000),
+            (RoundToNearest, false, 0x40000000, 0xC0000000),
+            (RoundTowardsPlusInfinity, false, 0x40400000, 0xC0000000),
+            (RoundTowardsMinusInfinity, false, 0x40000000, 0xC0400000),
+            (RoundTowardsZero, false, 0x40000000, 0xC0000000),
+        ];
+        for (rounding, away, positive, negative) in cases {
+            assert_eq!(
+                processor.fp_round_int::<u32>(0x40200000, rounding, away, false),
+                positive
+            );
+            assert_eq!(
+                processor.fp_round_int::<u32>(0xC0200000, rounding, away, false),
+                negative
+            );
+        }
+        assert_eq!(processor.fpscr & 0x1f, 0);
+
+        // -0.4 rounds to -0.0, inexact only when requested
+        assert_eq!(
+            processor.fp_round_int::<u32>(0xBECCCCCD, FPSCRRounding::RoundToNearest, false, true),
+            0x80000000
+        );
+        assert!(processor.fpscr.get_bit(4));
+
+        // large values are already integral
+        assert_eq!(
+            processor.fp_round_int::<u32>(0x7F7FFFFF, FPSCRRounding::RoundToNearest, false, true),
+            0x7F7FFFFF
+        );
+    }
+
+    #[test]
+    fn test_fp_round_int_f64() {
+        let mut processor = Processor::new();
+
+        // 1.5 -> 2.0, 0.5 -> 0.0 with ties to even
+        assert_eq!(
+            processor.fp_round_int::<u64>(
+                0x3FF8000000000000,
+                FPSCRRounding::RoundToNearest,
+                false,
+                false
+            ),
+            0x4000000000000000
+        );
+        assert_eq!(
+            processor.fp_round_int::<u64>(
+                0x3FE0000000000000,
+                FPSCRRounding::RoundToNearest,
+                false,
+                false
+            ),
+            0x0000000000000000
+        );
+    }
+
+    #[test]
+    fn test_fp_round_f64() {
+        let mut processor = Processor::new();
+
+        // 1.0 -> 0x3FF0000000000000 exact
+        assert_eq!(
+            processor.fp_round::<u64>(real(false, 1, 0), 0),
+            0x3FF0000000000000
+        );
+    }
+
+    #[test]
+    fn test_satq() {
+        // 1.0 -> 1 (unsigned, 32 bit)
+        assert_eq!(satq(1, 32, true), (1, false));
+
+        // 1.0 -> 1 (signed, 32 bit)
+        assert_eq!(satq(1, 32, false), (1, false));
+
+        // 1.0 -> 1 (unsigned, 64 bit)
+        assert_eq!(satq(1, 64, true), (1, false));
+
+        // 1.0 -> 1 (signed, 64 bit)
+        assert_eq!(satq(1, 64, false), (1, false));
 
         // -1.0 -> 0 (signed, 32 bit)
         assert_eq!(satq(-1, 32, false), (0xffff_ffff, false));
@@ -1318,7 +2096,7 @@ mod tests {
         );
 
         // value smaller than min value (unsigned, 64 bit)
-        assert_eq!(satq(-1, 64, true), (0xffff_ffff_ffff_ffff, true));
+        assert_eq!(satq(-1, 64, true), (0, true));
     }
 
     #[test]
@@ -1403,10 +2181,10 @@ mod tests {
             0xFFFFFFFF
         );
 
-        // negative infinity -> max value
+        // negative infinity -> min value
         assert_eq!(
             processor.fp_to_fixed::<u32, u32>(0xFF800000, 0, true, false, false),
-            0xFFFFFFFF
+            0x00000000
         );
 
         // QNAN -> 0
@@ -1497,10 +2275,10 @@ mod tests {
             0xFFFFFFFF
         );
 
-        // negative infinity -> max value
+        // negative infinity -> min value
         assert_eq!(
             processor.fp_to_fixed::<u64, u32>(0xFFF0000000000000, 0, true, false, false),
-            0xFFFFFFFF
+            0x00000000
         );
 
         // QNAN -> 0
@@ -1544,4 +2322,414 @@ mod tests {
 
     }
 
+    #[test]
+    fn test_fixed_to_fp_u32_f32() {
+        let mut processor = Processor::new();
+
+        // 0xffff_ffff -> 4294967296.0 (unsigned), inexact
+        assert_eq!(
+            processor.fixed_to_fp::<u32, u32>(0xffff_ffff, 0, true, false, true),
+            0x4F800000
+        );
+        assert!(processor.fpscr.get_bit(4));
+
+        // 2^24 + 1 -> 2^24 ties to even, 2^24 + 3 -> 2^24 + 4
+        assert_eq!(
+            processor.fixed_to_fp::<u32, u32>(0x0100_0001, 0, true, false, true),
+            0x4B800000
+        );
+        assert_eq!(
+            processor.fixed_to_fp::<u32, u32>(0x0100_0003, 0, true, false, true),
+            0x4B800002
+        );
+
+        // 0x8000 with 16 fraction bits -> 0.5
+        assert_eq!(
+            processor.fixed_to_fp::<u32, u32>(0x8000, 16, true, false, true),
+            0x3F000000
+        );
+    }
+
+    #[test]
+    fn test_fp_to_fixed_rounding() {
+        let mut processor = Processor::new();
+
+        // 2.5, 3.5 and -2.5 in each rounding mode
+        let cases = [
+            (FPSCRRounding::RoundToNearest, 2, 4, -2),
+            (FPSCRRounding::RoundTowardsPlusInfinity, 3, 4, -2),
+            (FPSCRRounding::RoundTowardsMinusInfinity, 2, 3, -3),
+            (FPSCRRounding::RoundTowardsZero, 2, 3, -2),
+        ];
+        for (rounding, a, b, c) in cases {
+            processor.fpscr = 0;
+            processor.fpscr.set_rounding_mode(rounding);
+            assert_eq!(
+                processor.fp_to_fixed::<u32, u32>(0x40200000, 0, false, false, true),
+                a
+            );
+            assert_eq!(
+                processor.fp_to_fixed::<u32, u32>(0x40600000, 0, false, false, true),
+                b
+            );
+            assert_eq!(
+                processor.fp_to_fixed::<u32, u32>(0xC0200000, 0, false, false, true),
+                c as u32
+            );
+            assert_eq!(processor.fpscr & 0x1f, 0x10);
+        }
+
+        // 1.5 with one fraction bit is exact
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_to_fixed::<u32, u32>(0x3FC00000, 1, false, false, true),
+            3
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0);
+    }
+
+    #[test]
+    fn test_fp_to_fixed_invalid() {
+        let mut processor = Processor::new();
+
+        // out of range is an invalid operation, not an overflow
+        assert_eq!(
+            processor.fp_to_fixed::<u64, u32>(0x7E37E43C8800759C, 0, false, true, true),
+            0x7FFFFFFF
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0x01);
+
+        // negative values saturate to zero when unsigned
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_to_fixed::<u32, u32>(0xBF800000, 0, true, true, true),
+            0
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0x01);
+
+        // -0.5 rounds to zero and is only inexact
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_to_fixed::<u32, u32>(0xBF000000, 0, true, true, true),
+            0
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0x10);
+
+        // NaN converts to zero
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_to_fixed::<u32, u32>(0x7FC00000, 0, false, true, true),
+            0
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0x01);
+    }
+
+    #[test]
+    fn test_fp_compare_corner_cases() {
+        let mut processor = Processor::new();
+
+        // 1e200 < 1e201
+        assert_eq!(
+            processor.fp_compare::<u64>(0x6974E718D7D7625A, 0x69AA20DF0DCD3AF1, false, true),
+            (true, false, false, false)
+        );
+
+        // -0.0 == +0.0
+        assert_eq!(
+            processor.fp_compare::<u32>(0x80000000, 0x00000000, false, true),
+            (false, true, true, false)
+        );
+
+        // -inf < max
+        assert_eq!(
+            processor.fp_compare::<u32>(0xFF800000, 0x7F7FFFFF, false, true),
+            (true, false, false, false)
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0);
+
+        // quiet NaN is unordered, invalid only for VCMPE
+        assert_eq!(
+            processor.fp_compare::<u32>(0x7FC00000, 0x00000000, false, true),
+            (false, false, true, true)
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0);
+        assert_eq!(
+            processor.fp_compare::<u32>(0x7FC00000, 0x00000000, true, true),
+            (false, false, true, true)
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0x01);
+    }
+
+    #[test]
+    fn test_fp_sub_special_values() {
+        let mut processor = Processor::new();
+
+        // inf - inf is invalid
+        assert_eq!(
+            processor.fp_sub::<u32>(0x7F800000, 0x7F800000, true),
+            0x7FC00000
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0x01);
+
+        // inf - -inf = inf, -inf - inf = -inf
+        assert_eq!(
+            processor.fp_sub::<u32>(0x7F800000, 0xFF800000, true),
+            0x7F800000
+        );
+        assert_eq!(
+            processor.fp_sub::<u32>(0xFF800000, 0x7F800000, true),
+            0xFF800000
+        );
+
+        // 1.0 - inf = -inf
+        assert_eq!(
+            processor.fp_sub::<u32>(0x3F800000, 0x7F800000, true),
+            0xFF800000
+        );
+
+        // -0.0 - -0.0 = +0.0, -0.0 - +0.0 = -0.0
+        assert_eq!(
+            processor.fp_sub::<u32>(0x80000000, 0x80000000, true),
+            0x00000000
+        );
+        assert_eq!(
+            processor.fp_sub::<u32>(0x80000000, 0x00000000, true),
+            0x80000000
+        );
+
+        // x - x = -0.0 when rounding towards minus infinity
+        processor
+            .fpscr
+            .set_rounding_mode(FPSCRRounding::RoundTowardsMinusInfinity);
+        assert_eq!(
+            processor.fp_sub::<u32>(0x3F800000, 0x3F800000, true),
+            0x80000000
+        );
+    }
+
+    #[test]
+    fn test_fpscr_rounding_modes() {
+        let mut processor = Processor::new();
+
+        // 1.0 + 2^-24 and -1.0 - 2^-24 are halfway between two values
+        let cases = [
+            (FPSCRRounding::RoundToNearest, 0x3F800000, 0xBF800000),
+            (
+                FPSCRRounding::RoundTowardsPlusInfinity,
+                0x3F800001,
+                0xBF800000,
+            ),
+            (
+                FPSCRRounding::RoundTowardsMinusInfinity,
+                0x3F800000,
+                0xBF800001,
+            ),
+            (FPSCRRounding::RoundTowardsZero, 0x3F800000, 0xBF800000),
+        ];
+        for (rounding, positive, negative) in cases {
+            processor.fpscr = 0;
+            processor.fpscr.set_rounding_mode(rounding);
+            assert_eq!(
+                processor.fp_add::<u32>(0x3F800000, 0x33800000, true),
+                positive
+            );
+            assert_eq!(
+                processor.fp_sub::<u32>(0xBF800000, 0x33800000, true),
+                negative
+            );
+            assert_eq!(processor.fpscr & 0x1f, 0x10);
+
+            // not controlled by FPSCR: always round to nearest
+            assert_eq!(
+                processor.fp_add::<u32>(0x3F800000, 0x33800000, false),
+                0x3F800000
+            );
+        }
+
+        // overflow only reaches infinity in the direction of rounding
+        let cases = [
+            (FPSCRRounding::RoundToNearest, 0x7F800000, 0xFF800000),
+            (
+                FPSCRRounding::RoundTowardsPlusInfinity,
+                0x7F800000,
+                0xFF7FFFFF,
+            ),
+            (
+                FPSCRRounding::RoundTowardsMinusInfinity,
+                0x7F7FFFFF,
+                0xFF800000,
+            ),
+            (FPSCRRounding::RoundTowardsZero, 0x7F7FFFFF, 0xFF7FFFFF),
+        ];
+        for (rounding, positive, negative) in cases {
+            processor.fpscr = 0;
+            processor.fpscr.set_rounding_mode(rounding);
+            assert_eq!(
+                processor.fp_mul::<u32>(0x7F7FFFFF, 0x40000000, true),
+                positive
+            );
+            assert_eq!(
+                processor.fp_mul::<u32>(0xFF7FFFFF, 0x40000000, true),
+                negative
+            );
+            assert_eq!(processor.fpscr & 0x1f, 0x14);
+        }
+    }
+
+    #[test]
+    fn test_fpscr_underflow_and_flush_to_zero() {
+        let mut processor = Processor::new();
+
+        // exact denormal result does not underflow
+        assert_eq!(
+            processor.fp_mul::<u32>(0x00800000, 0x3F000000, true),
+            0x00400000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0);
+
+        // 1e-30 * 1e-30 underflows to zero and is inexact
+        assert_eq!(
+            processor.fp_mul::<u32>(0x0DA24260, 0x0DA24260, true),
+            0x00000000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0x18);
+
+        // with FZ, a denormal result is flushed to zero and only underflows
+        processor.fpscr = 1 << 24;
+        assert_eq!(
+            processor.fp_mul::<u32>(0x80800000, 0x3F000000, true),
+            0x80000000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0x08);
+
+        // with FZ, a denormal input is treated as zero
+        processor.fpscr = 1 << 24;
+        assert_eq!(
+            processor.fp_add::<u32>(0x00000001, 0x3F800000, true),
+            0x3F800000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0x80);
+
+        // operations not controlled by FPSCR always flush to zero
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_add::<u32>(0x00000001, 0x00000001, false),
+            0x00000000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0x80);
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_add::<u32>(0x00000001, 0x00000001, true),
+            0x00000002
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0);
+    }
+
+    #[test]
+    fn test_fpscr_default_nan() {
+        let mut processor = Processor::new();
+
+        // NaN operands propagate, signaling NaNs are quieted
+        assert_eq!(
+            processor.fp_add::<u32>(0x3F800000, 0x7FC00123, true),
+            0x7FC00123
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0);
+        assert_eq!(
+            processor.fp_add::<u32>(0x7FC00123, 0x7F800456, true),
+            0x7FC00456
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0x01);
+
+        // with DN, any NaN result is the default NaN
+        processor.fpscr = 1 << 25;
+        assert_eq!(
+            processor.fp_add::<u32>(0x3F800000, 0xFFC00123, true),
+            0x7FC00000
+        );
+        assert_eq!(
+            processor.fp_sqrt::<u64>(0x7FF0000000000001, true),
+            0x7FF8000000000000
+        );
+        assert_eq!(processor.fpscr & 0x1f, 0x01);
+    }
+
+    #[test]
+    fn test_fp_process_exception() {
+        let mut processor = Processor::new();
+
+        // exceptions only accumulate, the trap enable bits do not exist
+        processor.fp_process_exception(FPExc::DivideByZero, 0x0000_9f00);
+        processor.fp_process_exception(FPExc::InputDenorm, 0x0000_9f00);
+        assert_eq!(processor.fpscr, 0x82);
+        processor.fp_process_exception(FPExc::DivideByZero, 0);
+        assert_eq!(processor.fpscr, 0x82);
+    }
+
+    #[test]
+    fn test_fp_convert() {
+        let mut processor = Processor::new();
+
+        // 1.0 converts exactly both ways
+        assert_eq!(
+            processor.fp_convert::<u32, u64>(0x3F800000, true),
+            0x3FF0000000000000
+        );
+        assert_eq!(
+            processor.fp_convert::<u64, u32>(0x3FF0000000000000, true),
+            0x3F800000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0);
+
+        // 0.1 is rounded to single precision
+        assert_eq!(
+            processor.fp_convert::<u64, u32>(0x3FB999999999999A, true),
+            0x3DCCCCCD
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0x10);
+
+        // 1e300 overflows
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_convert::<u64, u32>(0x7E37E43C8800759C, true),
+            0x7F800000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0x14);
+
+        // single precision denormals are normal in double precision
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_convert::<u32, u64>(0x80000001, true),
+            0xB6A0000000000000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0);
+
+        // ..unless flushed to zero
+        processor.fpscr = 1 << 24;
+        assert_eq!(
+            processor.fp_convert::<u32, u64>(0x80000001, true),
+            0x8000000000000000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0x80);
+
+        // signaling NaN is quieted and keeps the top of its payload
+        processor.fpscr = 0;
+        assert_eq!(
+            processor.fp_convert::<u32, u64>(0xFF800123, true),
+            0xFFF8002460000000
+        );
+        assert_eq!(
+            processor.fp_convert::<u64, u32>(0x7FF0000123456789, true),
+            0x7FC00009
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0x01);
+
+        // with DN, the result is the default NaN
+        processor.fpscr = 1 << 25;
+        assert_eq!(
+            processor.fp_convert::<u32, u64>(0x7FC00123, true),
+            0x7FF8000000000000
+        );
+        assert_eq!(processor.fpscr & 0x9f, 0);
+    }
 }
@@ -90,6 +90,7 @@ impl IsaFloatingPointLoadAndStore for Processor {
 
             let sp = self.get_r(Reg::SP);
             let mut address = sp - params.imm32;
+            self.check_sp_limit(address)?;
             self.set_r(Reg::SP, address);
 
             if params.single_regs {
@@ -1,6 +1,6 @@
 use crate::core::instruction::{
-    VMRSTarget, VMovCr2DpParams, VMovCrSpParams, VMovImmParams32, VMovImmParams64,
-    VMovRegParamsf32, VMovRegParamsf64,
+    VMRSTarget, VMovCr2DpParams, VMovCr2Sp2Params, VMovCrScalarParams, VMovCrSpParams,
+    VMovImmParams32, VMovImmParams64, VMovRegParamsf32, VMovRegParamsf64,
 };
 
 use crate::core::fpregister::Fpscr;
@@ -9,13 +9,16 @@ use crate::Processor;
 use crate::executor::ExecuteSuccess;
 
 use super::ExecuteResult;
-use crate::core::register::{Apsr, BaseReg, ExtensionRegOperations};
+use crate::core::register::{Apsr, BaseReg, ExtensionRegOperations, Reg};
 
 use crate::executor::ExecutorHelper;
 
 pub trait IsaFloatingPointRegisterTransfer {
     fn exec_vmov_cr_sp(&mut self, params: &VMovCrSpParams) -> ExecuteResult;
+    fn exec_vmov_cr2_sp2(&mut self, params: &VMovCr2Sp2Params) -> ExecuteResult;
     fn exec_vmov_cr2_dp(&mut self, params: &VMovCr2DpParams) -> ExecuteResult;
+    fn exec_vmov_cr_scalar(&mut self, params: &VMovCrScalarParams) -> ExecuteResult;
+    fn exec_vmov_scalar_cr(&mut self, params: &VMovCrScalarParams) -> ExecuteResult;
 
     fn exec_vmov_reg_f32(&mut self, params: VMovRegParamsf32) -> ExecuteResult;
     fn exec_vmov_reg_f64(&mut self, params: VMovRegParamsf64) -> ExecuteResult;
@@ -24,6 +27,7 @@ pub trait IsaFloatingPointRegisterTransfer {
     fn exec_vmov_imm_64(&mut self, params: VMovImmParams64) -> ExecuteResult;
 
     fn exec_vmrs(&mut self, params: VMRSTarget) -> ExecuteResult;
+    fn exec_vmsr(&mut self, rt: Reg) -> ExecuteResult;
 }
 
 impl IsaFloatingPointRegisterTransfer for Processor {
@@ -38,6 +42,22 @@ impl IsaFloatingPointRegisterTransfer for Processor {
         Ok(ExecuteSuccess::Taken { cycles: 1 })
     }
 
+    fn exec_vmov_cr2_sp2(&mut self, params: &VMovCr2Sp2Params) -> ExecuteResult {
+        if params.to_arm_registers {
+            let low = self.get_sr(params.sm);
+            let high = self.get_sr(params.sm1);
+            self.set_r(params.rt, low);
+            self.set_r(params.rt2, high);
+        } else {
+            let low = self.get_r(params.rt);
+            let high = self.get_r(params.rt2);
+            self.set_sr(params.sm, low);
+            self.set_sr(params.sm1, high);
+        }
+
+        Ok(ExecuteSuccess::Taken { cycles: 1 })
+    }
+
     fn exec_vmov_cr2_dp(&mut self, params: &VMovCr2DpParams) -> ExecuteResult {
         if params.to_arm_registers {
             let (low, high) = self.get_dr(params.dm);
@@ -52,6 +72,25 @@ impl IsaFloatingPointRegisterTransfer for Processor {
         Ok(ExecuteSuccess::Taken { cycles: 1 })
     }
 
+    fn exec_vmov_cr_scalar(&mut self, params: &VMovCrScalarParams) -> ExecuteResult {
+        let (low, high) = self.get_dr(params.dd);
+        let value = self.get_r(params.rt);
+        if params.x {
+            self.set_dr(params.dd, low, value);
+        } else {
+            self.set_dr(params.dd, value, high);
+        }
+
+        Ok(ExecuteSuccess::Taken { cycles: 1 })
+    }
+
+    fn exec_vmov_scalar_cr(&mut self, params: &VMovCrScalarParams) -> ExecuteResult {
+        let (low, high) = self.get_dr(params.dd);
+        self.set_r(params.rt, if params.x { high } else { low });
+
+        Ok(ExecuteSuccess::Taken { cycles: 1 })
+    }
+
     fn exec_vmov_reg_f32(&mut self, params: VMovRegParamsf32) -> ExecuteResult {
         let value = self.get_sr(params.sm);
         self.set_sr(params.sd, value);
@@ -101,4 +140,115 @@ impl IsaFloatingPointRegisterTransfer for Processor {
         }
         Ok(ExecuteSuccess::Taken { cycles: 1 })
     }
+    fn exec_vmsr(&mut self, rt: Reg) -> ExecuteResult {
+        if self.condition_passed() {
+            // N, Z, C, V, AHP, DN, FZ, RMode, IDC and the cumulative exception
+            // flags, the rest of the bits are reserved
+            self.fpscr = self.get_r(rt) & 0xf7c0_009f;
+        }
+        Ok(ExecuteSuccess::Taken { cycles: 1 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fpregister::FPSCRRounding;
+    use crate::core::register::{DoubleReg, SingleReg};
+
+    #[test]
+    fn test_vmsr_vmrs() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.set_r(Reg::R0, 0xffff_ffff);
+
+        // Act
+        processor.exec_vmsr(Reg::R0).unwrap();
+        processor.exec_vmrs(VMRSTarget::Register(Reg::R1)).unwrap();
+
+        // Assert: reserved bits, including the trap enables, read as zero
+        assert_eq!(processor.fpscr, 0xf7c0_009f);
+        assert_eq!(processor.get_r(Reg::R1), 0xf7c0_009f);
+        assert_eq!(
+            processor.fpscr.get_rounding_mode(),
+            FPSCRRounding::RoundTowardsZero
+        );
+
+        // Act: flags to APSR
+        processor.set_r(Reg::R0, 0x6000_0000);
+        processor.exec_vmsr(Reg::R0).unwrap();
+        processor.exec_vmrs(VMRSTarget::APSRNZCV).unwrap();
+
+        // Assert
+        assert!(!processor.psr.get_n());
+        assert!(processor.psr.get_z());
+        assert!(processor.psr.get_c());
+        assert!(!processor.psr.get_v());
+    }
+
+    #[test]
+    fn test_vmov_double_registers() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.set_r(Reg::R0, 0x5555_5555);
+        processor.set_r(Reg::R1, 0x3fd5_5555);
+
+        // Act: vmov d15, r0, r1 and vmov.f64 d1, d15
+        processor
+            .exec_vmov_cr2_dp(&VMovCr2DpParams {
+                to_arm_registers: false,
+                rt: Reg::R0,
+                rt2: Reg::R1,
+                dm: DoubleReg::D15,
+            })
+            .unwrap();
+        processor
+            .exec_vmov_reg_f64(VMovRegParamsf64 {
+                dd: DoubleReg::D1,
+                dm: DoubleReg::D15,
+            })
+            .unwrap();
+
+        // Assert: d1 is backed by s2 and s3
+        assert_eq!(processor.get_dr(DoubleReg::D1), (0x5555_5555, 0x3fd5_5555));
+        assert_eq!(processor.get_sr(SingleReg::S2), 0x5555_5555);
+        assert_eq!(processor.get_sr(SingleReg::S3), 0x3fd5_5555);
+
+        // Act: vmov.32 d1[1], r2 and vmov.32 r3, d1[0]
+        processor.set_r(Reg::R2, 0x4000_0000);
+        processor
+            .exec_vmov_cr_scalar(&VMovCrScalarParams {
+                rt: Reg::R2,
+                dd: DoubleReg::D1,
+                x: true,
+            })
+            .unwrap();
+        processor
+            .exec_vmov_scalar_cr(&VMovCrScalarParams {
+                rt: Reg::R3,
+                dd: DoubleReg::D1,
+                x: false,
+            })
+            .unwrap();
+
+        // Assert
+        assert_eq!(processor.get_dr(DoubleReg::D1), (0x5555_5555, 0x4000_0000));
+        assert_eq!(processor.get_r(Reg::R3), 0x5555_5555);
+
+        // Act: vmov r4, r5, s3, s4
+        processor.set_sr(SingleReg::S4, 0x3f80_0000);
+        processor
+            .exec_vmov_cr2_sp2(&VMovCr2Sp2Params {
+                to_arm_registers: true,
+                rt: Reg::R4,
+                rt2: Reg::R5,
+                sm: SingleReg::S3,
+                sm1: SingleReg::S4,
+            })
+            .unwrap();
+
+        // Assert
+        assert_eq!(processor.get_r(Reg::R4), 0x4000_0000);
+        assert_eq!(processor.get_r(Reg::R5), 0x3f80_0000);
+    }
 }
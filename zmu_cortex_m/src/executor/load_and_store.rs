@@ -48,6 +48,9 @@ pub trait IsaLoadAndStore {
     fn exec_strexb(&mut self, params: Reg3RdRtRnParams) -> ExecuteResult;
     fn exec_strexh(&mut self, params: Reg3RdRtRnParams) -> ExecuteResult;
 
+    fn exec_lda(&mut self, params: Reg2RtRnParams, size: usize) -> ExecuteResult;
+    fn exec_stl(&mut self, params: Reg2RtRnParams, size: usize) -> ExecuteResult;
+
     fn exec_ldrd_imm(&mut self, params: &Reg2DoubleParams) -> ExecuteResult;
     fn exec_strd_imm(&mut self, params: &Reg2DoubleParams) -> ExecuteResult;
 
@@ -330,6 +333,9 @@ impl IsaLoadAndStore for Processor {
 
             let value = self.get_r(params.rt);
             if params.wback {
+                if params.rn == Reg::SP {
+                    self.check_sp_limit(offset_address)?;
+                }
                 self.set_r(params.rn, offset_address);
             }
 
@@ -443,6 +449,37 @@ impl IsaLoadAndStore for Processor {
         Ok(ExecuteSuccess::NotTaken)
     }
 
+    fn exec_lda(&mut self, params: Reg2RtRnParams, size: usize) -> ExecuteResult {
+        if self.condition_passed() {
+            // memory accesses are in program order, acquire needs no extra ordering
+            let address = self.get_r(params.rn);
+            let data = match size {
+                1 => u32::from(self.read8(address)?),
+                2 => u32::from(self.read16(address)?),
+                _ => self.read32(address)?,
+            };
+            self.set_r(params.rt, data);
+
+            return Ok(ExecuteSuccess::Taken { cycles: 2 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_stl(&mut self, params: Reg2RtRnParams, size: usize) -> ExecuteResult {
+        if self.condition_passed() {
+            let address = self.get_r(params.rn);
+            let value = self.get_r(params.rt);
+            match size {
+                1 => self.write8(address, value as u8)?,
+                2 => self.write16(address, value as u16)?,
+                _ => self.write32(address, value)?,
+            }
+
+            return Ok(ExecuteSuccess::Taken { cycles: 2 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
     fn exec_strexb(&mut self, params: Reg3RdRtRnParams) -> ExecuteResult {
         if self.condition_passed() {
             let address = self.get_r(params.rn);
@@ -505,6 +542,9 @@ impl IsaLoadAndStore for Processor {
                 params.add,
                 params.index,
             );
+            if params.rn == Reg::SP && params.wback {
+                self.check_sp_limit(offset_address)?;
+            }
 
             let value1 = self.get_r(params.rt);
             self.write32(address, value1)?;
@@ -48,6 +48,9 @@ impl IsaLoadAndStoreMultiple for Processor {
             let regs_size = 4 * (params.registers.len() as u32);
 
             let mut address = self.get_r(params.rn) - regs_size;
+            if params.rn == Reg::SP && params.wback {
+                self.check_sp_limit(address)?;
+            }
 
             for reg in &params.registers {
                 let r = self.get_r(reg);
@@ -100,6 +103,7 @@ impl IsaLoadAndStoreMultiple for Processor {
             let regs_size = 4 * (registers.len() as u32);
             let sp = self.get_r(Reg::SP);
             let mut address = sp - regs_size;
+            self.check_sp_limit(address)?;
 
             for reg in &registers {
                 let value = self.get_r(reg);
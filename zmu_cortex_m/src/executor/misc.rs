@@ -1,7 +1,7 @@
 use crate::Processor;
 
 use crate::{
-    core::{condition::Condition, exception::ExceptionHandling},
+    core::{condition::Condition, exception::ExceptionHandling, monitor::Monitor},
     executor::{ExecuteSuccess, ExecutorHelper},
 };
 
@@ -9,6 +9,7 @@ use super::ExecuteResult;
 
 /// Branching operations
 pub trait IsaMisc {
+    fn exec_clrex(&mut self) -> ExecuteResult;
     fn exec_dmb(&self) -> ExecuteResult;
     fn exec_dsb(&self) -> ExecuteResult;
     fn exec_isb(&self) -> ExecuteResult;
@@ -23,6 +24,13 @@ pub trait IsaMisc {
 }
 
 impl IsaMisc for Processor {
+    fn exec_clrex(&mut self) -> ExecuteResult {
+        if self.condition_passed() {
+            self.clear_exclusive_local();
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
     fn exec_dmb(&self) -> ExecuteResult {
         if self.condition_passed() {
             return Ok(ExecuteSuccess::Taken { cycles: 4 });
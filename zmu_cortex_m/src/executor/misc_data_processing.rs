@@ -7,7 +7,8 @@ use super::ExecuteResult;
 use crate::core::{
     bits::Bits,
     instruction::{
-        BfcParams, BfiParams, MovtParams, Reg2RdRmParams, Reg3NoSetFlagsParams, BfxParams,
+        BfcParams, BfiParams, BfxParams, MovtParams, Reg2RdRmParams, Reg3NoSetFlagsParams,
+        Reg4NoSetFlagsParams,
     },
     operation::sign_extend,
     register::{Apsr, BaseReg},
@@ -27,6 +28,18 @@ pub trait IsaMiscDataProcessing {
     fn exec_sel(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult;
     fn exec_ubfx(&mut self, params: &BfxParams) -> ExecuteResult;
     fn exec_sbfx(&mut self, params: &BfxParams) -> ExecuteResult;
+    fn exec_usad8(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult;
+    fn exec_usada8(&mut self, params: &Reg4NoSetFlagsParams) -> ExecuteResult;
+}
+
+/// Sum of the absolute differences of the unsigned bytes of the operands
+fn sum_absolute_differences(rn: u32, rm: u32) -> u32 {
+    (0..4)
+        .map(|byte| {
+            let range = byte * 8..(byte + 1) * 8;
+            rn.get_bits(range.clone()).abs_diff(rm.get_bits(range))
+        })
+        .sum()
 }
 
 impl IsaMiscDataProcessing for Processor {
@@ -203,6 +216,24 @@ impl IsaMiscDataProcessing for Processor {
         }
         Ok(ExecuteSuccess::NotTaken)
     }
+
+    fn exec_usad8(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let result = sum_absolute_differences(self.get_r(params.rn), self.get_r(params.rm));
+            self.set_r(params.rd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_usada8(&mut self, params: &Reg4NoSetFlagsParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let result = sum_absolute_differences(self.get_r(params.rn), self.get_r(params.rm));
+            self.set_r(params.rd, self.get_r(params.ra).wrapping_add(result));
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
 }
 
 #[cfg(test)]
@@ -257,4 +288,28 @@ mod tests {
         assert_eq!(core.get_r(Reg::R0), 0xe000_e400);
         assert_eq!(core.get_r(Reg::R1), 0x00e0_00e4);
     }
+
+    #[test]
+    fn test_usada8() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R1, 0x01ff_1020);
+        core.set_r(Reg::R2, 0xff01_2010);
+        core.set_r(Reg::R3, 0x0000_1000);
+
+        let instruction = Instruction::USADA8 {
+            params: Reg4NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                ra: Reg::R3,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert: 0xfe + 0xfe + 0x10 + 0x10 + 0x1000
+        assert_eq!(core.get_r(Reg::R0), 0x0000_121c);
+    }
 }
@@ -3,17 +3,29 @@
 //!
 
 use crate::core::bits::Bits;
+use crate::core::block_cache::{ends_block, MAX_BLOCK_INSTRUCTIONS};
 use crate::core::condition::Condition;
-use crate::core::exception::{Exception, ExceptionHandling};
+use crate::core::exception::ExceptionHandling;
 use crate::core::fault::Fault;
-use crate::core::instruction::{Imm32Carry, Instruction, SetFlags};
+use crate::core::fetch::Fetch;
+use crate::core::instruction::{
+    instruction_size, Imm32Carry, Instruction, Reg2RtRnImm32Params, Reg3RdRtRnImm32Params, SetFlags,
+};
+use crate::core::instruction_cache::CachedInstruction;
 
 use crate::core::operation::condition_test;
-use crate::core::register::{Apsr, BaseReg};
+use crate::core::register::{Apsr, BaseReg, Reg};
+use crate::decoder::Decoder;
 use crate::memory::map::MapMemory;
-use crate::peripheral::{dwt::Dwt, systick::SysTick};
+use crate::peripheral::{
+    dwt::Dwt,
+    fpu::FloatingPointUnit,
+    mpu::{MemoryAccess, MemoryProtectionUnit},
+    systick::SysTick,
+};
 
 use crate::Processor;
+use std::rc::Rc;
 
 mod branch;
 mod coproc;
@@ -26,6 +38,9 @@ mod misc_data_processing;
 mod multiply;
 mod packing;
 mod parallel_add;
+mod saturate;
+mod saturating_add_sub;
+mod security;
 mod shift;
 mod signed_multiply;
 mod status_register;
@@ -46,7 +61,10 @@ use misc::IsaMisc;
 use misc_data_processing::IsaMiscDataProcessing;
 use multiply::IsaMultiply;
 use packing::IsaPacking;
-use parallel_add::IsaParallelAddSub;
+use parallel_add::{IsaParallelAddSub, ParallelArith, ParallelOp};
+use saturate::IsaSaturate;
+use saturating_add_sub::IsaSaturatingAddSub;
+use security::IsaSecurity;
 use shift::IsaShift;
 use signed_multiply::IsaSignedMultiply;
 use status_register::IsaStatusRegister;
@@ -54,7 +72,7 @@ use std_data_processing::IsaStandardDataProcessing;
 
 use fp_load_and_store::IsaFloatingPointLoadAndStore;
 use fp_register_transfer::IsaFloatingPointRegisterTransfer;
-use fp_data_processing::IsaFloatingPointDataProcessing;
+use fp_data_processing::{IsaFloatingPointDataProcessing, MulAccOp};
 
 ///
 /// Stepping processor with instructions
@@ -65,6 +83,13 @@ pub trait Executor {
     ///
     fn step(&mut self);
 
+    ///
+    /// Run processor forward by a block of straight-line instructions, at most
+    /// `max_instructions`. Peripherals are ticked and exceptions checked at the
+    /// end of the block.
+    ///
+    fn step_block(&mut self, max_instructions: u64);
+
     ///
     /// Run processor forward with core sleeping (peripherals only)
     ///
@@ -133,6 +158,78 @@ fn expand_conditional_carry(imm32: &Imm32Carry, carry: bool) -> (u32, bool) {
     }
 }
 
+///
+/// True for instructions that use the floating point context
+///
+fn is_fp_instruction(instruction: &Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::VLDR { .. }
+            | Instruction::VSTR { .. }
+            | Instruction::VSTM_T1 { .. }
+            | Instruction::VSTM_T2 { .. }
+            | Instruction::VPUSH { .. }
+            | Instruction::VPOP { .. }
+            | Instruction::VMOV_imm_32 { .. }
+            | Instruction::VMOV_imm_64 { .. }
+            | Instruction::VMOV_reg_f32 { .. }
+            | Instruction::VMOV_reg_f64 { .. }
+            | Instruction::VMOV_cr_scalar { .. }
+            | Instruction::VMOV_scalar_cr { .. }
+            | Instruction::VMOV_cr_sp { .. }
+            | Instruction::VMOV_cr2_sp2 { .. }
+            | Instruction::VMOV_cr2_dp { .. }
+            | Instruction::VMRS { .. }
+            | Instruction::VMSR { .. }
+            | Instruction::VABS_f32 { .. }
+            | Instruction::VABS_f64 { .. }
+            | Instruction::VCMP_f32 { .. }
+            | Instruction::VCMP_f64 { .. }
+            | Instruction::VADD_f32 { .. }
+            | Instruction::VADD_f64 { .. }
+            | Instruction::VSUB_f32 { .. }
+            | Instruction::VSUB_f64 { .. }
+            | Instruction::VCVT { .. }
+            | Instruction::VCVT_directed { .. }
+            | Instruction::VCVT_f32_f64 { .. }
+            | Instruction::VCVT_f64_f32 { .. }
+            | Instruction::VMUL_f32 { .. }
+            | Instruction::VMUL_f64 { .. }
+            | Instruction::VNMUL_f32 { .. }
+            | Instruction::VNMUL_f64 { .. }
+            | Instruction::VDIV_f32 { .. }
+            | Instruction::VDIV_f64 { .. }
+            | Instruction::VMLA_f32 { .. }
+            | Instruction::VMLA_f64 { .. }
+            | Instruction::VMLS_f32 { .. }
+            | Instruction::VMLS_f64 { .. }
+            | Instruction::VNMLA_f32 { .. }
+            | Instruction::VNMLA_f64 { .. }
+            | Instruction::VNMLS_f32 { .. }
+            | Instruction::VNMLS_f64 { .. }
+            | Instruction::VFMA_f32 { .. }
+            | Instruction::VFMA_f64 { .. }
+            | Instruction::VFMS_f32 { .. }
+            | Instruction::VFMS_f64 { .. }
+            | Instruction::VFNMA_f32 { .. }
+            | Instruction::VFNMA_f64 { .. }
+            | Instruction::VFNMS_f32 { .. }
+            | Instruction::VFNMS_f64 { .. }
+            | Instruction::VNEG_f32 { .. }
+            | Instruction::VNEG_f64 { .. }
+            | Instruction::VSQRT_f32 { .. }
+            | Instruction::VSQRT_f64 { .. }
+            | Instruction::VMAXNM_f32 { .. }
+            | Instruction::VMAXNM_f64 { .. }
+            | Instruction::VMINNM_f32 { .. }
+            | Instruction::VMINNM_f64 { .. }
+            | Instruction::VSEL_f32 { .. }
+            | Instruction::VSEL_f64 { .. }
+            | Instruction::VRINT_f32 { .. }
+            | Instruction::VRINT_f64 { .. }
+    )
+}
+
 fn conditional_setflags(setflags: SetFlags, in_it_block: bool) -> bool {
     match setflags {
         SetFlags::True => true,
@@ -204,6 +301,9 @@ impl ExecutorHelper for Processor {
 
     #[allow(clippy::too_many_lines)]
     fn execute_internal(&mut self, instruction: &Instruction) -> ExecuteResult {
+        if is_fp_instruction(instruction) && self.condition_passed() {
+            self.execute_fp_check()?;
+        }
         match instruction {
             // --------------------------------------------
             //
@@ -215,6 +315,8 @@ impl ExecutorHelper for Processor {
 
             Instruction::BLX { rm } => self.exec_blx(*rm),
             Instruction::BX { rm } => self.exec_bx(*rm),
+            Instruction::BLXNS { rm } => self.exec_blxns(*rm),
+            Instruction::BXNS { rm } => self.exec_bxns(*rm),
             Instruction::BL { imm32 } => self.exec_bl(*imm32),
 
             Instruction::CBZ { params } => self.exec_cbz(*params),
@@ -309,7 +411,7 @@ impl ExecutorHelper for Processor {
             // Group: Signed multiply instructions (ArmV7-m)
             //
             // --------------------------------------------
-            Instruction::SMLAL { params: _ } => unimplemented!(),
+            Instruction::SMLAL { params } => self.exec_smlal(params),
             Instruction::SMULL { params } => self.exec_smull(params),
 
             // --------------------------------------------
@@ -320,6 +422,13 @@ impl ExecutorHelper for Processor {
             Instruction::UMLAL { params } => self.exec_umlal(params),
             Instruction::UMULL { params } => self.exec_umull(params),
 
+            // --------------------------------------------
+            //
+            // Group: Unsigned Multiply instructions (ARMv7-M DSP extension)
+            //
+            // --------------------------------------------
+            Instruction::UMAAL { params } => self.exec_umaal(params),
+
             // --------------------------------------------
             //
             // Group: Signed Multiply instructions (ARMv7-M DSP extension)
@@ -327,24 +436,44 @@ impl ExecutorHelper for Processor {
             // --------------------------------------------
             Instruction::SMUL { params } => self.exec_smul(params),
             Instruction::SMLA { params } => self.exec_smla(params),
+            Instruction::SMLAD { params } => self.exec_smlad(params),
+            Instruction::SMLSD { params } => self.exec_smlsd(params),
+            Instruction::SMLALxy { params } => self.exec_smlalxy(params),
+            Instruction::SMLALD { params } => self.exec_smlald(params),
+            Instruction::SMLSLD { params } => self.exec_smlsld(params),
+            Instruction::SMUAD { params } => self.exec_smuad(params),
+            Instruction::SMUSD { params } => self.exec_smusd(params),
+            Instruction::SMLAW { params } => self.exec_smlaw(params),
+            Instruction::SMULW { params } => self.exec_smulw(params),
+            Instruction::SMMLA { params } => self.exec_smmla(params),
+            Instruction::SMMLS { params } => self.exec_smmls(params),
+            Instruction::SMMUL { params } => self.exec_smmul(params),
 
             // --------------------------------------------
             //
             // Group: Saturating instructions (ARMv7-M base arch)
             //
             // --------------------------------------------
+            Instruction::SSAT { params } => self.exec_ssat(params),
+            Instruction::USAT { params } => self.exec_usat(params),
 
             // --------------------------------------------
             //
             // Group: Saturating instructions (ARMv7-M DSP extensions)
             //
             // --------------------------------------------
+            Instruction::SSAT16 { params } => self.exec_ssat16(params),
+            Instruction::USAT16 { params } => self.exec_usat16(params),
 
             // --------------------------------------------
             //
             // Group: Saturating add/sub (ARMv7-M DSP extensions)
             //
             // --------------------------------------------
+            Instruction::QADD { params } => self.exec_qadd(params),
+            Instruction::QSUB { params } => self.exec_qsub(params),
+            Instruction::QDADD { params } => self.exec_qdadd(params),
+            Instruction::QDSUB { params } => self.exec_qdsub(params),
 
             // --------------------------------------------
             //
@@ -363,6 +492,15 @@ impl ExecutorHelper for Processor {
             //
             // --------------------------------------------
             Instruction::UXTAB { params } => self.exec_uxtab(params),
+            Instruction::UXTAH { params } => self.exec_uxtah(params),
+            Instruction::SXTAB { params } => self.exec_sxtab(params),
+            Instruction::SXTAH { params } => self.exec_sxtah(params),
+            Instruction::SXTB16 { params } => self.exec_sxtb16(params),
+            Instruction::UXTB16 { params } => self.exec_uxtb16(params),
+            Instruction::SXTAB16 { params } => self.exec_sxtab16(params),
+            Instruction::UXTAB16 { params } => self.exec_uxtab16(params),
+            Instruction::PKHBT { params } => self.exec_pkhbt(params),
+            Instruction::PKHTB { params } => self.exec_pkhtb(params),
 
             // --------------------------------------------
             //
@@ -378,7 +516,138 @@ impl ExecutorHelper for Processor {
             // Group: Parallel add / sub (DSP extension)
             //
             // --------------------------------------------
-            Instruction::UADD8 { params } => self.exec_uadd8(params),
+            Instruction::SADD16 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Add16, ParallelArith::Signed)
+            }
+            Instruction::QADD16 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Add16,
+                ParallelArith::SignedSaturating,
+            ),
+            Instruction::SHADD16 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Add16, ParallelArith::SignedHalving)
+            }
+            Instruction::UADD16 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Add16, ParallelArith::Unsigned)
+            }
+            Instruction::UQADD16 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Add16,
+                ParallelArith::UnsignedSaturating,
+            ),
+            Instruction::UHADD16 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Add16,
+                ParallelArith::UnsignedHalving,
+            ),
+            Instruction::SASX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Asx, ParallelArith::Signed)
+            }
+            Instruction::QASX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Asx, ParallelArith::SignedSaturating)
+            }
+            Instruction::SHASX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Asx, ParallelArith::SignedHalving)
+            }
+            Instruction::UASX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Asx, ParallelArith::Unsigned)
+            }
+            Instruction::UQASX { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Asx,
+                ParallelArith::UnsignedSaturating,
+            ),
+            Instruction::UHASX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Asx, ParallelArith::UnsignedHalving)
+            }
+            Instruction::SSAX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sax, ParallelArith::Signed)
+            }
+            Instruction::QSAX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sax, ParallelArith::SignedSaturating)
+            }
+            Instruction::SHSAX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sax, ParallelArith::SignedHalving)
+            }
+            Instruction::USAX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sax, ParallelArith::Unsigned)
+            }
+            Instruction::UQSAX { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Sax,
+                ParallelArith::UnsignedSaturating,
+            ),
+            Instruction::UHSAX { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sax, ParallelArith::UnsignedHalving)
+            }
+            Instruction::SSUB16 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sub16, ParallelArith::Signed)
+            }
+            Instruction::QSUB16 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Sub16,
+                ParallelArith::SignedSaturating,
+            ),
+            Instruction::SHSUB16 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sub16, ParallelArith::SignedHalving)
+            }
+            Instruction::USUB16 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sub16, ParallelArith::Unsigned)
+            }
+            Instruction::UQSUB16 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Sub16,
+                ParallelArith::UnsignedSaturating,
+            ),
+            Instruction::UHSUB16 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Sub16,
+                ParallelArith::UnsignedHalving,
+            ),
+            Instruction::SADD8 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Add8, ParallelArith::Signed)
+            }
+            Instruction::QADD8 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Add8,
+                ParallelArith::SignedSaturating,
+            ),
+            Instruction::SHADD8 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Add8, ParallelArith::SignedHalving)
+            }
+            Instruction::UADD8 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Add8, ParallelArith::Unsigned)
+            }
+            Instruction::UQADD8 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Add8,
+                ParallelArith::UnsignedSaturating,
+            ),
+            Instruction::UHADD8 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Add8, ParallelArith::UnsignedHalving)
+            }
+            Instruction::SSUB8 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sub8, ParallelArith::Signed)
+            }
+            Instruction::QSUB8 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Sub8,
+                ParallelArith::SignedSaturating,
+            ),
+            Instruction::SHSUB8 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sub8, ParallelArith::SignedHalving)
+            }
+            Instruction::USUB8 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sub8, ParallelArith::Unsigned)
+            }
+            Instruction::UQSUB8 { params } => self.exec_parallel_add_sub(
+                params,
+                ParallelOp::Sub8,
+                ParallelArith::UnsignedSaturating,
+            ),
+            Instruction::UHSUB8 { params } => {
+                self.exec_parallel_add_sub(params, ParallelOp::Sub8, ParallelArith::UnsignedHalving)
+            }
 
             // --------------------------------------------
             //
@@ -405,6 +674,8 @@ impl ExecutorHelper for Processor {
             //
             // --------------------------------------------
             Instruction::SEL { params } => self.exec_sel(params),
+            Instruction::USAD8 { params } => self.exec_usad8(params),
+            Instruction::USADA8 { params } => self.exec_usada8(params),
             // --------------------------------------------
             //
             // Group: Status register access instructions
@@ -413,10 +684,6 @@ impl ExecutorHelper for Processor {
             Instruction::MRS { params } => self.exec_mrs(*params),
             Instruction::MSR_reg { params } => self.exec_msr(*params),
 
-            #[cfg(feature = "armv6m")]
-            Instruction::CPS { im } => self.exec_cps(*im),
-
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             Instruction::CPS {
                 im,
                 affect_pri,
@@ -431,6 +698,16 @@ impl ExecutorHelper for Processor {
             Instruction::LDREX { params } => self.exec_ldrex(*params),
             Instruction::LDREXB { params } => self.exec_ldrexb(*params),
             Instruction::LDREXH { params } => self.exec_ldrexh(*params),
+            Instruction::LDA { params } => self.exec_lda(*params, 4),
+            Instruction::LDAB { params } => self.exec_lda(*params, 1),
+            Instruction::LDAH { params } => self.exec_lda(*params, 2),
+            Instruction::LDAEX { params } => self.exec_ldrex(Reg2RtRnImm32Params {
+                rt: params.rt,
+                rn: params.rn,
+                imm32: 0,
+            }),
+            Instruction::LDAEXB { params } => self.exec_ldrexb(*params),
+            Instruction::LDAEXH { params } => self.exec_ldrexh(*params),
 
             Instruction::LDR_imm { params, .. } => self.exec_ldr_imm(params),
             Instruction::LDRB_imm { params, .. } => self.exec_ldrb_imm(params),
@@ -455,6 +732,17 @@ impl ExecutorHelper for Processor {
             Instruction::STREX { params } => self.exec_strex(*params),
             Instruction::STREXB { params } => self.exec_strexb(*params),
             Instruction::STREXH { params } => self.exec_strexh(*params),
+            Instruction::STL { params } => self.exec_stl(*params, 4),
+            Instruction::STLB { params } => self.exec_stl(*params, 1),
+            Instruction::STLH { params } => self.exec_stl(*params, 2),
+            Instruction::STLEX { params } => self.exec_strex(Reg3RdRtRnImm32Params {
+                rd: params.rd,
+                rt: params.rt,
+                rn: params.rn,
+                imm32: 0,
+            }),
+            Instruction::STLEXB { params } => self.exec_strexb(*params),
+            Instruction::STLEXH { params } => self.exec_strexh(*params),
 
             Instruction::STRD_imm { params } => self.exec_strd_imm(params),
             Instruction::LDRD_imm { params } => self.exec_ldrd_imm(params),
@@ -478,6 +766,7 @@ impl ExecutorHelper for Processor {
             // Group: Miscellaneous
             //
             // --------------------------------------------
+            Instruction::CLREX => self.exec_clrex(),
             Instruction::DMB => self.exec_dmb(),
             Instruction::DSB => self.exec_dsb(),
             Instruction::ISB => self.exec_isb(),
@@ -504,6 +793,8 @@ impl ExecutorHelper for Processor {
             // --------------------------------------------
             Instruction::SVC { .. } => self.exec_svc(),
             Instruction::BKPT { imm32 } => self.exec_bkpt(*imm32),
+            Instruction::SG => self.exec_sg(),
+            Instruction::TT { params } => self.exec_tt(params),
 
             // --------------------------------------------
             //
@@ -529,10 +820,10 @@ impl ExecutorHelper for Processor {
             Instruction::VMOV_imm_64 { params } => self.exec_vmov_imm_64(*params),
             Instruction::VMOV_reg_f32 { params } => self.exec_vmov_reg_f32(*params),
             Instruction::VMOV_reg_f64 { params } => self.exec_vmov_reg_f64(*params),
-            Instruction::VMOV_cr_scalar { .. } => unimplemented!(),
-            Instruction::VMOV_scalar_cr { .. } => unimplemented!(),
+            Instruction::VMOV_cr_scalar { params } => self.exec_vmov_cr_scalar(params),
+            Instruction::VMOV_scalar_cr { params } => self.exec_vmov_scalar_cr(params),
             Instruction::VMOV_cr_sp { params } => self.exec_vmov_cr_sp(params),
-            Instruction::VMOV_cr2_sp2 { .. } => unimplemented!(),
+            Instruction::VMOV_cr2_sp2 { params } => self.exec_vmov_cr2_sp2(params),
             Instruction::VMOV_cr2_dp { params } => self.exec_vmov_cr2_dp(params),
 
             // --------------------------------------------
@@ -542,6 +833,7 @@ impl ExecutorHelper for Processor {
             // --------------------------------------------
 
             Instruction::VMRS { rt } => self.exec_vmrs(*rt),
+            Instruction::VMSR { rt } => self.exec_vmsr(*rt),
 
             // --------------------------------------------
             //
@@ -557,24 +849,114 @@ impl ExecutorHelper for Processor {
             Instruction::VSUB_f32 { params } => self.exec_vsub_f32(params),
             Instruction::VSUB_f64 { params } => self.exec_vsub_f64(params),
             Instruction::VCVT { params } => self.exec_vcvt(params),
+            Instruction::VCVT_directed { params } => self.exec_vcvt_directed(params),
+            Instruction::VCVT_f32_f64 { params } => self.exec_vcvt_f32_f64(*params),
+            Instruction::VCVT_f64_f32 { params } => self.exec_vcvt_f64_f32(*params),
+            Instruction::VMUL_f32 { params } => self.exec_vmul_f32(params, false),
+            Instruction::VNMUL_f32 { params } => self.exec_vmul_f32(params, true),
+            Instruction::VDIV_f32 { params } => self.exec_vdiv_f32(params),
+            Instruction::VMLA_f32 { params } => self.exec_vmla_f32(params, MulAccOp::Add),
+            Instruction::VMLS_f32 { params } => self.exec_vmla_f32(params, MulAccOp::Sub),
+            Instruction::VNMLA_f32 { params } => self.exec_vmla_f32(params, MulAccOp::NegAdd),
+            Instruction::VNMLS_f32 { params } => self.exec_vmla_f32(params, MulAccOp::NegSub),
+            Instruction::VFMA_f32 { params } => self.exec_vfma_f32(params, MulAccOp::Add),
+            Instruction::VFMS_f32 { params } => self.exec_vfma_f32(params, MulAccOp::Sub),
+            Instruction::VFNMA_f32 { params } => self.exec_vfma_f32(params, MulAccOp::NegAdd),
+            Instruction::VFNMS_f32 { params } => self.exec_vfma_f32(params, MulAccOp::NegSub),
+            Instruction::VNEG_f32 { params } => self.exec_vneg_f32(params),
+            Instruction::VSQRT_f32 { params } => self.exec_vsqrt_f32(params),
+            Instruction::VMAXNM_f32 { params } => self.exec_vmaxnm_f32(params, false),
+            Instruction::VMINNM_f32 { params } => self.exec_vmaxnm_f32(params, true),
+            Instruction::VSEL_f32 { params } => self.exec_vsel_f32(params),
+            Instruction::VRINT_f32 { params } => self.exec_vrint_f32(params),
+            Instruction::VMUL_f64 { params } => self.exec_vmul_f64(params, false),
+            Instruction::VNMUL_f64 { params } => self.exec_vmul_f64(params, true),
+            Instruction::VDIV_f64 { params } => self.exec_vdiv_f64(params),
+            Instruction::VMLA_f64 { params } => self.exec_vmla_f64(params, MulAccOp::Add),
+            Instruction::VMLS_f64 { params } => self.exec_vmla_f64(params, MulAccOp::Sub),
+            Instruction::VNMLA_f64 { params } => self.exec_vmla_f64(params, MulAccOp::NegAdd),
+            Instruction::VNMLS_f64 { params } => self.exec_vmla_f64(params, MulAccOp::NegSub),
+            Instruction::VFMA_f64 { params } => self.exec_vfma_f64(params, MulAccOp::Add),
+            Instruction::VFMS_f64 { params } => self.exec_vfma_f64(params, MulAccOp::Sub),
+            Instruction::VFNMA_f64 { params } => self.exec_vfma_f64(params, MulAccOp::NegAdd),
+            Instruction::VFNMS_f64 { params } => self.exec_vfma_f64(params, MulAccOp::NegSub),
+            Instruction::VNEG_f64 { params } => self.exec_vneg_f64(params),
+            Instruction::VSQRT_f64 { params } => self.exec_vsqrt_f64(params),
+            Instruction::VMAXNM_f64 { params } => self.exec_vmaxnm_f64(params, false),
+            Instruction::VMINNM_f64 { params } => self.exec_vmaxnm_f64(params, true),
+            Instruction::VSEL_f64 { params } => self.exec_vsel_f64(params),
+            Instruction::VRINT_f64 { params } => self.exec_vrint_f64(params),
 
             // --------------------------------------------
             //
             // Fallback: unknown instruction
             //
             // --------------------------------------------
-            Instruction::UDF { imm32, opcode, .. } => {
-                println!("unsupported instruction, opcode {opcode}, imm32 {imm32}");
-                todo!("should give undefined instruction fault")
-                //Err(Fault::UndefInstr)
+            Instruction::UDF { .. } => Err(Fault::UndefInstr),
+        }
+    }
+}
+
+impl Processor {
+    ///
+    /// Decode the straight-line instructions starting at `pc` to a block.
+    /// Only a failed fetch of the first instruction is a fault.
+    ///
+    fn translate_block(&mut self, pc: u32) -> Result<Rc<[CachedInstruction]>, Fault> {
+        let mut instructions = Vec::new();
+        let mut address = pc;
+        while instructions.len() < MAX_BLOCK_INSTRUCTIONS {
+            let thumb = match self.fetch(address) {
+                Ok(thumb) => thumb,
+                Err(fault) if instructions.is_empty() => return Err(fault),
+                Err(_) => break,
+            };
+            let instruction = self.decode(thumb);
+            let instruction_size = instruction_size(&instruction);
+            instructions.push((instruction, instruction_size));
+            address += instruction_size as u32;
+            if ends_block(&instruction) {
+                break;
             }
         }
+        let mapped_pc = self.map_address(pc);
+        Ok(self.block_cache.insert(pc, mapped_pc, instructions))
+    }
+
+    ///
+    /// Count the cycles of the current block to the cycle counters, DWT and `SysTick`
+    ///
+    pub(crate) fn tick_block_cycles(&mut self) {
+        let cycles = self.block_cycles;
+        self.block_cycles = 0;
+        self.cycle_count += u64::from(cycles);
+        self.dwt_tick(cycles);
+        self.syst_step(cycles);
+    }
+
+    ///
+    /// Take the fault of an instruction fetch or execution, or stop the
+    /// processor in fault trap mode. Returns the number of cycles burnt.
+    ///
+    fn instruction_fault(&mut self, fault: Fault, instruction: Option<&Instruction>) -> u32 {
+        if self.fault_trap {
+            self.trapped_fault = Some((fault, instruction.copied()));
+            self.running = false;
+            return 0;
+        }
+        let new_pc = self.get_pc();
+
+        //TODO: cycles not correctly accumulated yet for exception entry
+        self.fault_entry(fault, new_pc);
+        //TODO: proper amount of cycles calculation
+        12
     }
 }
 
 impl Executor for Processor {
     #[inline(always)]
     fn step_sleep(&mut self) {
+        self.sleep_cycle_count += 1;
         self.syst_step(1);
         self.check_exceptions();
         self.dwt_tick(1);
@@ -583,9 +965,22 @@ impl Executor for Processor {
     #[inline(always)]
     fn step(&mut self) {
         let pc = self.get_pc();
-        let mapped_pc = (self.map_address(pc) >> 1) as usize;
-        let (instruction, instruction_size) = self.instruction_cache[mapped_pc];
-        let count = self.execute(&instruction, instruction_size);
+        let mapped_pc = self.map_address(pc);
+        let count =
+            if let Some(&(instruction, instruction_size)) = self.instruction_cache.get(mapped_pc) {
+                self.execute(&instruction, instruction_size)
+            } else {
+                match self.fetch(pc) {
+                    Ok(thumb) => {
+                        let instruction = self.decode(thumb);
+                        let instruction_size = instruction_size(&instruction);
+                        self.instruction_cache
+                            .insert(mapped_pc, (instruction, instruction_size));
+                        self.execute(&instruction, instruction_size)
+                    }
+                    Err(fault) => self.instruction_fault(fault, None),
+                }
+            };
         self.cycle_count += u64::from(count);
         self.dwt_tick(count);
         self.syst_step(count);
@@ -594,24 +989,94 @@ impl Executor for Processor {
         //DWT and SYST ticking
     }
 
+    fn step_block(&mut self, max_instructions: u64) {
+        let pc = self.get_pc();
+        let instructions = match self.block_cache.lookup(pc) {
+            Some(instructions) => Ok(instructions),
+            None => self.translate_block(pc),
+        };
+
+        match instructions {
+            Ok(instructions) => {
+                let pending_exception_count = self.pending_exception_count;
+                let execution_priority = self.execution_priority;
+                #[cfg(all(feature = "jit", target_arch = "x86_64", unix))]
+                let native = self.execute_native(pc, &instructions, max_instructions);
+                #[cfg(not(all(feature = "jit", target_arch = "x86_64", unix)))]
+                let native = 0;
+
+                // the interpreter continues from the first instruction not compiled
+                let mut next_pc = self.get_pc();
+                for (instruction, instruction_size) in instructions
+                    .iter()
+                    .take(max_instructions as usize)
+                    .skip(native)
+                {
+                    self.block_cycles += self.execute(instruction, *instruction_size);
+                    next_pc = next_pc.wrapping_add(*instruction_size as u32);
+
+                    // leave the block on a branch, fault or a change in the exception
+                    // state, the exception is taken as with single steps
+                    if self.get_pc() != next_pc
+                        || !self.running
+                        || self.sleeping
+                        || self.pending_exception_count != pending_exception_count
+                        || self.execution_priority != execution_priority
+                    {
+                        break;
+                    }
+                }
+            }
+            Err(fault) => {
+                self.block_cache.unlink();
+                self.block_cycles += self.instruction_fault(fault, None);
+            }
+        }
+
+        self.tick_block_cycles();
+        self.check_exceptions();
+    }
+
     #[inline(always)]
     fn execute(&mut self, instruction: &Instruction, instruction_size: usize) -> u32 {
         self.instruction_count += 1;
 
         let in_it_block = self.in_it_block();
 
-        match self.execute_internal(instruction) {
-            Err(_fault) => {
-                // all faults are mapped to hardfaults on armv6m
-                let new_pc = self.get_pc();
+        // both halfwords of the instruction are fetched
+        let pc = self.get_pc();
+        let last_halfword = pc + instruction_size as u32 - 2;
+        let fetch = self
+            .mpu_check(pc, MemoryAccess::InstructionFetch)
+            .and_then(|()| self.mpu_check(last_halfword, MemoryAccess::InstructionFetch))
+            .and_then(|()| self.security_fetch_check(pc, instruction));
+
+        // Armv8-M stack limit: an instruction moving the active stack pointer
+        // below its limit is rolled back. MSR writes are not checked.
+        let check_stack =
+            self.profile.is_armv8m() && !matches!(instruction, Instruction::MSR_reg { .. });
+        let stack = check_stack.then(|| {
+            let state = (self.secure, self.control.sp_sel, self.mode);
+            (self.get_r(Reg::SP), state)
+        });
+
+        let result = fetch
+            .and_then(|()| self.execute_internal(instruction))
+            .and_then(|success| match stack {
+                Some((sp, state))
+                    if (self.secure, self.control.sp_sel, self.mode) == state
+                        && self.get_r(Reg::SP) != sp =>
+                {
+                    let new_sp = self.get_r(Reg::SP);
+                    self.check_sp_limit(new_sp)
+                        .map(|()| success)
+                        .inspect_err(|_| self.set_r(Reg::SP, sp))
+                }
+                _ => Ok(success),
+            });
 
-                //TODO: map to correct exception
-                //TODO: cycles not correctly accumulated yet for exception entry
-                self.exception_entry(Exception::HardFault, new_pc)
-                    .expect("error handling on exception entry not implemented");
-                //TODO: proper amount of cycles calculation
-                12
-            }
+        match result {
+            Err(fault) => self.instruction_fault(fault, Some(instruction)),
             Ok(ExecuteSuccess::NotTaken) => {
                 self.add_pc(instruction_size as u32);
                 if in_it_block {
@@ -635,13 +1100,17 @@ impl Executor for Processor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bus::Bus;
     use crate::core::condition::Condition;
-    use crate::core::instruction::instruction_size;
+    use crate::core::exception::Exception;
     use crate::core::{
         instruction::{
-            ITCondition, Reg2ShiftNoSetFlagsParams, RegImmCarryParams, SRType, SetFlags,
+            ITCondition, Reg2ImmParams, Reg2ShiftNoSetFlagsParams, RegImmCarryParams, SRType,
+            SetFlags,
         },
+        profile::ArchProfile,
         register::Reg,
+        reset::Reset,
     };
 
     #[test]
@@ -686,4 +1155,272 @@ mod tests {
         assert_eq!(core.get_r(Reg::R4), 0x01);
         assert!(!core.in_it_block());
     }
+
+    #[test]
+    fn test_push_stack_limit() {
+        // arrange
+        let mut core = Processor::new();
+        core.profile(ArchProfile::CORTEX_M33);
+        core.reset().unwrap();
+        core.fault_trap(true);
+        core.msp = 0x2000_0408;
+        core.msplim = 0x2000_0400;
+        let push = Instruction::PUSH {
+            registers: [Reg::R4, Reg::R5, Reg::LR].into_iter().collect(),
+            thumb32: false,
+        };
+        let sub = Instruction::SUB_imm {
+            params: Reg2ImmParams {
+                rd: Reg::SP,
+                rn: Reg::SP,
+                imm32: 0x10,
+                setflags: SetFlags::False,
+            },
+            thumb32: false,
+        };
+
+        // act
+        core.execute(&push, instruction_size(&push));
+
+        // assert: nothing is written below the limit
+        assert_eq!(
+            core.trapped_fault.map(|(fault, _)| fault),
+            Some(Fault::Stkof)
+        );
+        assert_eq!(core.msp, 0x2000_0408);
+
+        // act
+        core.trapped_fault = None;
+        core.execute(&sub, instruction_size(&sub));
+
+        // assert: the stack pointer is restored
+        assert_eq!(
+            core.trapped_fault.map(|(fault, _)| fault),
+            Some(Fault::Stkof)
+        );
+        assert_eq!(core.msp, 0x2000_0408);
+    }
+
+    #[test]
+    fn test_store_exclusive_fails_without_reservation() {
+        // arrange
+        let mut core = Processor::new();
+        core.profile(ArchProfile::CORTEX_M3);
+        core.reset().unwrap();
+        core.msp = 0x2000_1000;
+        core.set_r(Reg::R0, 0x2000_0100);
+        core.set_r(Reg::R2, 42);
+        core.write32(0x2000_0100, 7).unwrap();
+        let ldrex = Instruction::LDREX {
+            params: Reg2RtRnImm32Params {
+                rt: Reg::R1,
+                rn: Reg::R0,
+                imm32: 0,
+            },
+        };
+        let strex = Instruction::STREX {
+            params: Reg3RdRtRnImm32Params {
+                rd: Reg::R3,
+                rt: Reg::R2,
+                rn: Reg::R0,
+                imm32: 0,
+            },
+        };
+
+        // act: store without a load exclusive
+        core.execute(&strex, instruction_size(&strex));
+
+        // assert
+        assert_eq!(core.get_r(Reg::R3), 1);
+        assert_eq!(core.read32(0x2000_0100).unwrap(), 7);
+
+        // act: load exclusive, store exclusive
+        core.execute(&ldrex, instruction_size(&ldrex));
+        core.execute(&strex, instruction_size(&strex));
+
+        // assert
+        assert_eq!(core.get_r(Reg::R1), 7);
+        assert_eq!(core.get_r(Reg::R3), 0);
+        assert_eq!(core.read32(0x2000_0100).unwrap(), 42);
+
+        // act: CLREX clears the reservation
+        core.execute(&ldrex, instruction_size(&ldrex));
+        core.execute(&Instruction::CLREX, 4);
+        core.set_r(Reg::R2, 43);
+        core.execute(&strex, instruction_size(&strex));
+
+        // assert
+        assert_eq!(core.get_r(Reg::R3), 1);
+        assert_eq!(core.read32(0x2000_0100).unwrap(), 42);
+
+        // act: exception entry clears the reservation
+        core.execute(&ldrex, instruction_size(&ldrex));
+        core.set_exception_pending(Exception::PendSV);
+        core.check_exceptions();
+        core.execute(&strex, instruction_size(&strex));
+
+        // assert
+        assert_eq!(core.get_r(Reg::R3), 1);
+        assert_eq!(core.read32(0x2000_0100).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_execute_from_ram() {
+        // arrange
+        let mut core = Processor::new();
+        core.cache_instructions();
+        core.fault_trap(true);
+        // movs r0, #42; bl <next instruction>
+        core.write16(0x2000_0000, 0x202a).unwrap();
+        core.write16(0x2000_0002, 0xf000).unwrap();
+        core.write16(0x2000_0004, 0xf800).unwrap();
+        core.set_pc(0x2000_0000);
+
+        // act
+        core.step();
+        core.step();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 42);
+        assert_eq!(core.get_r(Reg::LR), 0x2000_0007);
+        assert_eq!(core.get_pc(), 0x2000_0006);
+        assert_eq!(core.trapped_fault, None);
+    }
+
+    #[test]
+    fn test_signed_overflow_conditions() {
+        // arrange
+        let mut core = Processor::new();
+        core.fault_trap(true);
+        // adds r2, r0, r1; bvs 1f; movs r3, #1
+        // 1: cmp r2, r1; blt 2f; movs r4, #1
+        // 2: bge 3f; movs r5, #1; 3: b .
+        for (i, opcode) in [
+            0x1842, 0xd600, 0x2301, 0x428a, 0xdb00, 0x2401, 0xda00, 0x2501, 0xe7fe,
+        ]
+        .iter()
+        .enumerate()
+        {
+            core.write16(0x2000_0000 + 2 * i as u32, *opcode).unwrap();
+        }
+        core.set_r(Reg::R0, 0x7fff_ffff);
+        core.set_r(Reg::R1, 1);
+        core.set_pc(0x2000_0000);
+
+        // act
+        while core.get_pc() != 0x2000_0010 {
+            core.step();
+        }
+
+        // assert: 0x7fff_ffff + 1 and 0x8000_0000 - 1 overflow
+        assert_eq!(core.get_r(Reg::R2), 0x8000_0000);
+        assert_eq!(core.get_r(Reg::R3), 0);
+        assert_eq!(core.get_r(Reg::R4), 0);
+        assert_eq!(core.get_r(Reg::R5), 1);
+        assert!(core.psr.get_v());
+        assert!(!core.psr.get_n());
+    }
+
+    #[test]
+    fn test_instruction_fetch_faults() {
+        // arrange
+        let mut core = Processor::new();
+        core.cache_instructions();
+        core.fault_trap(true);
+
+        // act: nothing on the bus
+        core.set_pc(0x1000_0000);
+        core.step();
+
+        // assert
+        assert_eq!(core.trapped_fault, Some((Fault::IBusErr, None)));
+
+        // act: the System region is never executable
+        core.trapped_fault = None;
+        core.set_pc(0xE000_0000);
+        core.step();
+
+        // assert
+        assert_eq!(core.trapped_fault, Some((Fault::IAccViol, None)));
+    }
+
+    #[test]
+    fn test_patched_code_is_decoded_again() {
+        // arrange
+        let mut core = Processor::new();
+        core.cache_instructions();
+        core.fault_trap(true);
+
+        // act: reprogram the pre-decoded flash with movs r0, #42
+        core.program_flash(0x0000_0100, &[0x2a, 0x20]).unwrap();
+        core.set_pc(0x0000_0100);
+        core.step();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 42);
+        assert_eq!(core.program_flash(0x0000_0100, &[]), Ok(()));
+
+        // act: self-modifying code in RAM, movs r0, #1 patched to movs r0, #2
+        core.write16(0x2000_0000, 0x2001).unwrap();
+        core.set_pc(0x2000_0000);
+        core.step();
+        core.write16(0x2000_0000, 0x2002).unwrap();
+        core.set_pc(0x2000_0000);
+        core.step();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 2);
+        assert_eq!(core.program_flash(0x2000_0000, &[0]), Err(Fault::Preciserr));
+    }
+
+    #[test]
+    fn test_block_execution_matches_single_steps() {
+        let run = |block: bool, load: u16, compare: u16| {
+            let mut core = Processor::new();
+            core.fault_trap(true);
+            // movs r0, #0; loop: adds r0, #1; ldr r1, [r2]; cmp r0, #n; bne loop; b .
+            for (i, opcode) in [0x2000, 0x3001, load, compare, 0xd1fb, 0xe7fe]
+                .iter()
+                .enumerate()
+            {
+                core.write16(0x2000_0000 + 2 * i as u32, *opcode).unwrap();
+            }
+            // SysTick running, read by the loop
+            core.write32(0xE000_E014, 1000).unwrap();
+            core.write32(0xE000_E010, 1).unwrap();
+            core.set_r(Reg::R2, 0xE000_E018);
+            core.set_pc(0x2000_0000);
+            while core.get_pc() != 0x2000_000a {
+                if block {
+                    core.step_block(u64::MAX);
+                } else {
+                    core.step();
+                }
+            }
+            core
+        };
+
+        // act & assert: word, halfword and byte reads of the counter
+        for load in [0x6811, 0x8811, 0x7811] {
+            let single = run(false, load, 0x280a);
+            let blocks = run(true, load, 0x280a);
+
+            assert_eq!(blocks.get_r(Reg::R0), 10);
+            assert_eq!(blocks.get_r(Reg::R1), single.get_r(Reg::R1));
+            assert_eq!(blocks.instruction_count, single.instruction_count);
+            assert_eq!(blocks.cycle_count, single.cycle_count);
+            assert_eq!(blocks.syst_cvr, single.syst_cvr);
+        }
+        let mut blocks = run(true, 0x6811, 0x280a);
+
+        // act: patch the loop count to 5
+        blocks.write16(0x2000_0006, 0x2805).unwrap();
+        blocks.set_pc(0x2000_0000);
+        while blocks.get_pc() != 0x2000_000a {
+            blocks.step_block(u64::MAX);
+        }
+
+        // assert
+        assert_eq!(blocks.get_r(Reg::R0), 5);
+    }
 }
@@ -14,6 +14,7 @@ pub trait IsaMultiply {
     fn exec_mla(&mut self, params: &Reg4NoSetFlagsParams) -> ExecuteResult;
     fn exec_mls(&mut self, params: &Reg4NoSetFlagsParams) -> ExecuteResult;
     fn exec_mul(&mut self, params: &Reg3Params) -> ExecuteResult;
+    fn exec_umaal(&mut self, params: &Reg643232Params) -> ExecuteResult;
     fn exec_umlal(&mut self, params: &Reg643232Params) -> ExecuteResult;
     fn exec_umull(&mut self, params: &Reg643232Params) -> ExecuteResult;
 }
@@ -63,6 +64,23 @@ impl IsaMultiply for Processor {
         Ok(ExecuteSuccess::NotTaken)
     }
 
+    fn exec_umaal(&mut self, params: &Reg643232Params) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = u64::from(self.get_r(params.rn));
+            let rm = u64::from(self.get_r(params.rm));
+            let rdlo = u64::from(self.get_r(params.rdlo));
+            let rdhi = u64::from(self.get_r(params.rdhi));
+
+            // cannot overflow: (2^32 - 1)^2 + 2 * (2^32 - 1) == 2^64 - 1
+            let result = rn * rm + rdhi + rdlo;
+
+            self.set_r(params.rdlo, result.get_bits(0..32) as u32);
+            self.set_r(params.rdhi, result.get_bits(32..64) as u32);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
     fn exec_umlal(&mut self, params: &Reg643232Params) -> ExecuteResult {
         if self.condition_passed() {
             let rn = u64::from(self.get_r(params.rn));
@@ -124,4 +142,30 @@ mod tests {
 
         assert_eq!(core.get_r(Reg::R1), 0x2000_0DD0);
     }
+
+    #[test]
+    fn test_umaal() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R0, 0xffff_ffff);
+        core.set_r(Reg::R1, 0xffff_ffff);
+        core.set_r(Reg::R2, 0xffff_ffff);
+        core.set_r(Reg::R3, 0xffff_ffff);
+
+        let instruction = Instruction::UMAAL {
+            params: Reg643232Params {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R3,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0xffff_ffff);
+        assert_eq!(core.get_r(Reg::R1), 0xffff_ffff);
+    }
 }
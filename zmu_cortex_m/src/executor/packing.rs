@@ -3,11 +3,11 @@ use crate::Processor;
 use crate::executor::{ExecuteSuccess, ExecutorHelper};
 
 use super::ExecuteResult;
-use crate::core::instruction::{Reg2UsizeParams, Reg3UsizeParams};
+use crate::core::instruction::{Reg2UsizeParams, Reg3ShiftNoSetFlagsParams, Reg3UsizeParams};
 use crate::core::{
     bits::Bits,
-    operation::{ror, sign_extend},
-    register::BaseReg,
+    operation::{ror, shift, sign_extend},
+    register::{Apsr, BaseReg},
 };
 
 /// Multiply operations
@@ -17,6 +17,34 @@ pub trait IsaPacking {
     fn exec_uxtb(&mut self, params: &Reg2UsizeParams) -> ExecuteResult;
     fn exec_uxth(&mut self, params: &Reg2UsizeParams) -> ExecuteResult;
     fn exec_uxtab(&mut self, params: &Reg3UsizeParams) -> ExecuteResult;
+    fn exec_uxtah(&mut self, params: &Reg3UsizeParams) -> ExecuteResult;
+    fn exec_sxtab(&mut self, params: &Reg3UsizeParams) -> ExecuteResult;
+    fn exec_sxtah(&mut self, params: &Reg3UsizeParams) -> ExecuteResult;
+    fn exec_sxtb16(&mut self, params: &Reg2UsizeParams) -> ExecuteResult;
+    fn exec_uxtb16(&mut self, params: &Reg2UsizeParams) -> ExecuteResult;
+    fn exec_sxtab16(&mut self, params: &Reg3UsizeParams) -> ExecuteResult;
+    fn exec_uxtab16(&mut self, params: &Reg3UsizeParams) -> ExecuteResult;
+    fn exec_pkhbt(&mut self, params: &Reg3ShiftNoSetFlagsParams) -> ExecuteResult;
+    fn exec_pkhtb(&mut self, params: &Reg3ShiftNoSetFlagsParams) -> ExecuteResult;
+}
+
+/// Extend bytes 0 and 2 of `value` to halfwords and add them to the halfwords of `addend`
+fn extend_add_byte16(value: u32, addend: u32, signed: bool) -> u32 {
+    let extend = |byte: u32| {
+        if signed {
+            sign_extend(byte, 7, 32) as u32
+        } else {
+            byte
+        }
+    };
+    let low = addend.wrapping_add(extend(value.get_bits(0..8)));
+    let high = addend
+        .get_bits(16..32)
+        .wrapping_add(extend(value.get_bits(16..24)));
+    let mut result: u32 = 0;
+    result.set_bits(0..16, low.get_bits(0..16));
+    result.set_bits(16..32, high.get_bits(0..16));
+    result
 }
 
 impl IsaPacking for Processor {
@@ -73,4 +101,199 @@ impl IsaPacking for Processor {
         }
         Ok(ExecuteSuccess::NotTaken)
     }
+
+    fn exec_uxtah(&mut self, params: &Reg3UsizeParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rotated = ror(self.get_r(params.rm), params.rotation);
+            let result = self.get_r(params.rn).wrapping_add(rotated.get_bits(0..16));
+            self.set_r(params.rd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_sxtab(&mut self, params: &Reg3UsizeParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rotated = ror(self.get_r(params.rm), params.rotation);
+            let extended = sign_extend(rotated.get_bits(0..8), 7, 32) as u32;
+            let result = self.get_r(params.rn).wrapping_add(extended);
+            self.set_r(params.rd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_sxtah(&mut self, params: &Reg3UsizeParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rotated = ror(self.get_r(params.rm), params.rotation);
+            let extended = sign_extend(rotated.get_bits(0..16), 15, 32) as u32;
+            let result = self.get_r(params.rn).wrapping_add(extended);
+            self.set_r(params.rd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_sxtb16(&mut self, params: &Reg2UsizeParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rotated = ror(self.get_r(params.rm), params.rotation);
+            self.set_r(params.rd, extend_add_byte16(rotated, 0, true));
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_uxtb16(&mut self, params: &Reg2UsizeParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rotated = ror(self.get_r(params.rm), params.rotation);
+            self.set_r(params.rd, extend_add_byte16(rotated, 0, false));
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_sxtab16(&mut self, params: &Reg3UsizeParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rotated = ror(self.get_r(params.rm), params.rotation);
+            let rn = self.get_r(params.rn);
+            self.set_r(params.rd, extend_add_byte16(rotated, rn, true));
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_uxtab16(&mut self, params: &Reg3UsizeParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rotated = ror(self.get_r(params.rm), params.rotation);
+            let rn = self.get_r(params.rn);
+            self.set_r(params.rd, extend_add_byte16(rotated, rn, false));
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_pkhbt(&mut self, params: &Reg3ShiftNoSetFlagsParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let operand2 = shift(
+                self.get_r(params.rm),
+                params.shift_t,
+                params.shift_n as usize,
+                self.psr.get_c(),
+            );
+            let mut result = self.get_r(params.rn);
+            result.set_bits(16..32, operand2.get_bits(16..32));
+            self.set_r(params.rd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_pkhtb(&mut self, params: &Reg3ShiftNoSetFlagsParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let operand2 = shift(
+                self.get_r(params.rm),
+                params.shift_t,
+                params.shift_n as usize,
+                self.psr.get_c(),
+            );
+            let mut result = self.get_r(params.rn);
+            result.set_bits(0..16, operand2.get_bits(0..16));
+            self.set_r(params.rd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        instruction::{Instruction, SRType},
+        register::Reg,
+    };
+
+    #[test]
+    fn test_sxtab16() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R1, 0x0010_0010);
+        core.set_r(Reg::R2, 0x80ff_7f00);
+
+        let instruction = Instruction::SXTAB16 {
+            params: Reg3UsizeParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                rotation: 8,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert: bytes 0x7f and 0x80 after rotation
+        assert_eq!(core.get_r(Reg::R0), 0xff90_008f);
+    }
+
+    #[test]
+    fn test_pkhtb() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R1, 0x1234_5678);
+        core.set_r(Reg::R2, 0x8765_4321);
+
+        let instruction = Instruction::PKHTB {
+            params: Reg3ShiftNoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                shift_t: SRType::ASR,
+                shift_n: 16,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0x1234_8765);
+    }
+
+    #[test]
+    fn test_sxtab_sxtah_uxtah() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R1, 0x0001_0000);
+        core.set_r(Reg::R2, 0xffff_8001);
+        let params = Reg3UsizeParams {
+            rd: Reg::R0,
+            rn: Reg::R1,
+            rm: Reg::R2,
+            rotation: 8,
+        };
+
+        // act + assert: byte 0x80 after rotation
+        core.execute_internal(&Instruction::SXTAB { params })
+            .unwrap();
+        assert_eq!(core.get_r(Reg::R0), 0x0000_ff80);
+
+        // act + assert: halfword 0x8001 without rotation
+        let params = Reg3UsizeParams {
+            rotation: 0,
+            ..params
+        };
+        core.execute_internal(&Instruction::SXTAH { params })
+            .unwrap();
+        assert_eq!(core.get_r(Reg::R0), 0x0000_8001);
+
+        // act + assert: halfword 0xffff after rotation
+        let params = Reg3UsizeParams {
+            rotation: 16,
+            ..params
+        };
+        core.execute_internal(&Instruction::UXTAH { params })
+            .unwrap();
+        assert_eq!(core.get_r(Reg::R0), 0x0001_ffff);
+    }
 }
@@ -6,38 +6,232 @@ use super::ExecuteResult;
 use crate::core::instruction::Reg3NoSetFlagsParams;
 use crate::core::{
     bits::Bits,
+    operation::{signed_sat_q, unsigned_sat_q},
     register::{Apsr, BaseReg},
 };
 
-/// Divide operations
+/// Lane layout of a parallel add / sub operation
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ParallelOp {
+    /// ADD16: add both halfwords
+    Add16,
+    /// ASX: subtract top of Rm from bottom of Rn, add bottom of Rm to top of Rn
+    Asx,
+    /// SAX: add top of Rm to bottom of Rn, subtract bottom of Rm from top of Rn
+    Sax,
+    /// SUB16: subtract both halfwords
+    Sub16,
+    /// ADD8: add all bytes
+    Add8,
+    /// SUB8: subtract all bytes
+    Sub8,
+}
+
+/// Arithmetic of a parallel add / sub operation, selected by the mnemonic prefix
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum ParallelArith {
+    /// S: signed modulo, sets GE flags
+    Signed,
+    /// Q: signed saturating
+    SignedSaturating,
+    /// SH: signed halving
+    SignedHalving,
+    /// U: unsigned modulo, sets GE flags
+    Unsigned,
+    /// UQ: unsigned saturating
+    UnsignedSaturating,
+    /// UH: unsigned halving
+    UnsignedHalving,
+}
+
+impl ParallelArith {
+    fn is_signed(self) -> bool {
+        matches!(
+            self,
+            Self::Signed | Self::SignedSaturating | Self::SignedHalving
+        )
+    }
+}
+
+/// Parallel addition and subtraction operations
 pub trait IsaParallelAddSub {
-    fn exec_uadd8(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult;
+    fn exec_parallel_add_sub(
+        &mut self,
+        params: &Reg3NoSetFlagsParams,
+        op: ParallelOp,
+        arith: ParallelArith,
+    ) -> ExecuteResult;
 }
 
 impl IsaParallelAddSub for Processor {
-    fn exec_uadd8(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult {
+    fn exec_parallel_add_sub(
+        &mut self,
+        params: &Reg3NoSetFlagsParams,
+        op: ParallelOp,
+        arith: ParallelArith,
+    ) -> ExecuteResult {
         if self.condition_passed() {
-            let rm: u32 = self.get_r(params.rm);
             let rn: u32 = self.get_r(params.rn);
+            let rm: u32 = self.get_r(params.rm);
+
+            // (lane of rn, lane of rm, subtract) for each lane of the result
+            let (width, lanes): (usize, &[(usize, usize, bool)]) = match op {
+                ParallelOp::Add16 => (16, &[(0, 0, false), (1, 1, false)]),
+                ParallelOp::Asx => (16, &[(0, 1, true), (1, 0, false)]),
+                ParallelOp::Sax => (16, &[(0, 1, false), (1, 0, true)]),
+                ParallelOp::Sub16 => (16, &[(0, 0, true), (1, 1, true)]),
+                ParallelOp::Add8 => (
+                    8,
+                    &[(0, 0, false), (1, 1, false), (2, 2, false), (3, 3, false)],
+                ),
+                ParallelOp::Sub8 => (8, &[(0, 0, true), (1, 1, true), (2, 2, true), (3, 3, true)]),
+            };
+
+            let lane_value = |value: u32, lane: usize| -> i64 {
+                let bits = value.get_bits(lane * width..(lane + 1) * width);
+                if arith.is_signed() {
+                    (i64::from(bits) << (64 - width)) >> (64 - width)
+                } else {
+                    i64::from(bits)
+                }
+            };
+
+            let mut result: u32 = 0;
+            let mut ge: u32 = 0;
+            for (lane, &(n, m, subtract)) in lanes.iter().enumerate() {
+                let operand1 = lane_value(rn, n);
+                let operand2 = lane_value(rm, m);
+                let value = if subtract {
+                    operand1 - operand2
+                } else {
+                    operand1 + operand2
+                };
 
-            let sum1: u32 = rn.get_bits(0..8) + rm.get_bits(0..8);
-            let sum2: u32 = rn.get_bits(8..16) + rm.get_bits(8..16);
-            let sum3: u32 = rn.get_bits(16..24) + rm.get_bits(16..24);
-            let sum4: u32 = rn.get_bits(24..32) + rm.get_bits(24..32);
+                let ge_lane = match arith {
+                    ParallelArith::Unsigned if !subtract => value >= 1 << width,
+                    _ => value >= 0,
+                };
+                let lane_result = match arith {
+                    ParallelArith::Signed | ParallelArith::Unsigned => value,
+                    ParallelArith::SignedSaturating => signed_sat_q(value, width).0,
+                    ParallelArith::UnsignedSaturating => unsigned_sat_q(value, width).0,
+                    ParallelArith::SignedHalving | ParallelArith::UnsignedHalving => value >> 1,
+                };
 
-            let mut result: u32 = sum1.get_bits(0..8);
-            result.set_bits(8..16, sum2.get_bits(0..8));
-            result.set_bits(16..24, sum3.get_bits(0..8));
-            result.set_bits(24..32, sum4.get_bits(0..8));
+                let range = lane * width..(lane + 1) * width;
+                result.set_bits(range, (lane_result as u32).get_bits(0..width));
+                // each GE bit covers a byte of the result
+                let ge_bits = width / 8;
+                if ge_lane {
+                    ge.set_bits(lane * ge_bits..(lane + 1) * ge_bits, (1 << ge_bits) - 1);
+                }
+            }
             self.set_r(params.rd, result);
 
-            self.psr.set_ge0(sum1 >= 0x100);
-            self.psr.set_ge1(sum2 >= 0x100);
-            self.psr.set_ge2(sum3 >= 0x100);
-            self.psr.set_ge3(sum4 >= 0x100);
+            if matches!(arith, ParallelArith::Signed | ParallelArith::Unsigned) {
+                self.psr.set_ge0(ge.get_bit(0));
+                self.psr.set_ge1(ge.get_bit(1));
+                self.psr.set_ge2(ge.get_bit(2));
+                self.psr.set_ge3(ge.get_bit(3));
+            }
 
             return Ok(ExecuteSuccess::Taken { cycles: 1 });
         }
         Ok(ExecuteSuccess::NotTaken)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{instruction::Instruction, register::Reg};
+
+    fn execute(instruction: &Instruction, rn: u32, rm: u32) -> (u32, [bool; 4]) {
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R1, rn);
+        core.set_r(Reg::R2, rm);
+        core.execute_internal(instruction).unwrap();
+        (
+            core.get_r(Reg::R0),
+            [
+                core.psr.get_ge0(),
+                core.psr.get_ge1(),
+                core.psr.get_ge2(),
+                core.psr.get_ge3(),
+            ],
+        )
+    }
+
+    const PARAMS: Reg3NoSetFlagsParams = Reg3NoSetFlagsParams {
+        rd: Reg::R0,
+        rn: Reg::R1,
+        rm: Reg::R2,
+    };
+
+    #[test]
+    fn test_uadd8() {
+        let (result, ge) = execute(
+            &Instruction::UADD8 { params: PARAMS },
+            0x80ff_0102,
+            0x8001_0304,
+        );
+        assert_eq!(result, 0x0000_0406);
+        assert_eq!(ge, [false, false, true, true]);
+    }
+
+    #[test]
+    fn test_sadd16() {
+        let (result, ge) = execute(
+            &Instruction::SADD16 { params: PARAMS },
+            0x8000_0001,
+            0xffff_0002,
+        );
+        assert_eq!(result, 0x7fff_0003);
+        assert_eq!(ge, [true, true, false, false]);
+    }
+
+    #[test]
+    fn test_usub8() {
+        let (result, ge) = execute(
+            &Instruction::USUB8 { params: PARAMS },
+            0x0102_0304,
+            0x0201_0304,
+        );
+        assert_eq!(result, 0xff01_0000);
+        assert_eq!(ge, [true, true, true, false]);
+    }
+
+    #[test]
+    fn test_qasx() {
+        // bottom: 0x8000 - 0x0001 saturates, top: 0x7fff + 0x0001 saturates
+        let (result, ge) = execute(
+            &Instruction::QASX { params: PARAMS },
+            0x7fff_8000,
+            0x0001_0001,
+        );
+        assert_eq!(result, 0x7fff_8000);
+        assert_eq!(ge, [false, false, false, false]);
+    }
+
+    #[test]
+    fn test_uhadd16() {
+        let (result, _) = execute(
+            &Instruction::UHADD16 { params: PARAMS },
+            0xffff_0003,
+            0xffff_0004,
+        );
+        assert_eq!(result, 0xffff_0003);
+    }
+
+    #[test]
+    fn test_shsax() {
+        // bottom: (-4 + 2) / 2, top: (4 - (-2)) / 2
+        let (result, _) = execute(
+            &Instruction::SHSAX { params: PARAMS },
+            0x0004_fffc,
+            0x0002_fffe,
+        );
+        assert_eq!(result, 0x0003_ffff);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::Processor;
+
+use crate::executor::{ExecuteSuccess, ExecutorHelper};
+
+use super::ExecuteResult;
+use crate::core::instruction::{Sat16Params, SatParams};
+use crate::core::{
+    bits::Bits,
+    operation::{shift, signed_sat_q, unsigned_sat_q},
+    register::{Apsr, BaseReg},
+};
+
+/// Saturating operations
+pub trait IsaSaturate {
+    fn exec_ssat(&mut self, params: &SatParams) -> ExecuteResult;
+    fn exec_usat(&mut self, params: &SatParams) -> ExecuteResult;
+    fn exec_ssat16(&mut self, params: &Sat16Params) -> ExecuteResult;
+    fn exec_usat16(&mut self, params: &Sat16Params) -> ExecuteResult;
+}
+
+impl IsaSaturate for Processor {
+    fn exec_ssat(&mut self, params: &SatParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = self.get_r(params.rn);
+            let operand = shift(
+                rn,
+                params.shift_t,
+                params.shift_n as usize,
+                self.psr.get_c(),
+            );
+            let (result, sat) =
+                signed_sat_q(i64::from(operand as i32), params.saturate_to as usize);
+            self.set_r(params.rd, result as u32);
+            if sat {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_usat(&mut self, params: &SatParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = self.get_r(params.rn);
+            let operand = shift(
+                rn,
+                params.shift_t,
+                params.shift_n as usize,
+                self.psr.get_c(),
+            );
+            let (result, sat) =
+                unsigned_sat_q(i64::from(operand as i32), params.saturate_to as usize);
+            self.set_r(params.rd, result as u32);
+            if sat {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_ssat16(&mut self, params: &Sat16Params) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = self.get_r(params.rn);
+            let (result1, sat1) = signed_sat_q(
+                i64::from(rn.get_bits(0..16) as i16),
+                params.saturate_to as usize,
+            );
+            let (result2, sat2) = signed_sat_q(
+                i64::from(rn.get_bits(16..32) as i16),
+                params.saturate_to as usize,
+            );
+            let mut result: u32 = 0;
+            result.set_bits(0..16, (result1 as u32).get_bits(0..16));
+            result.set_bits(16..32, (result2 as u32).get_bits(0..16));
+            self.set_r(params.rd, result);
+            if sat1 || sat2 {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_usat16(&mut self, params: &Sat16Params) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = self.get_r(params.rn);
+            let (result1, sat1) = unsigned_sat_q(
+                i64::from(rn.get_bits(0..16) as i16),
+                params.saturate_to as usize,
+            );
+            let (result2, sat2) = unsigned_sat_q(
+                i64::from(rn.get_bits(16..32) as i16),
+                params.saturate_to as usize,
+            );
+            let mut result: u32 = 0;
+            result.set_bits(0..16, result1 as u32);
+            result.set_bits(16..32, result2 as u32);
+            self.set_r(params.rd, result);
+            if sat1 || sat2 {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        instruction::{Instruction, SRType},
+        register::Reg,
+    };
+
+    #[test]
+    fn test_ssat() {
+        // arrange
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R1, 0x0000_0100);
+
+        let instruction = Instruction::SSAT {
+            params: SatParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                saturate_to: 8,
+                shift_t: SRType::LSL,
+                shift_n: 0,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0x7f);
+        assert!(core.psr.get_q());
+    }
+
+    #[test]
+    fn test_usat16() {
+        // arrange
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R1, 0xfff0_0020);
+
+        let instruction = Instruction::USAT16 {
+            params: Sat16Params {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                saturate_to: 4,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0x0000_000f);
+        assert!(core.psr.get_q());
+    }
+}
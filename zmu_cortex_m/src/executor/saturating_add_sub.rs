@@ -0,0 +1,132 @@
+use crate::Processor;
+
+use crate::executor::{ExecuteSuccess, ExecutorHelper};
+
+use super::ExecuteResult;
+use crate::core::instruction::Reg3NoSetFlagsParams;
+use crate::core::{
+    operation::signed_sat_q,
+    register::{Apsr, BaseReg},
+};
+
+/// Saturating addition and subtraction operations
+pub trait IsaSaturatingAddSub {
+    fn exec_qadd(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult;
+    fn exec_qsub(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult;
+    fn exec_qdadd(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult;
+    fn exec_qdsub(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult;
+}
+
+impl IsaSaturatingAddSub for Processor {
+    fn exec_qadd(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rm = i64::from(self.get_r(params.rm) as i32);
+            let rn = i64::from(self.get_r(params.rn) as i32);
+            let (result, sat) = signed_sat_q(rm + rn, 32);
+            self.set_r(params.rd, result as u32);
+            if sat {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_qsub(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rm = i64::from(self.get_r(params.rm) as i32);
+            let rn = i64::from(self.get_r(params.rn) as i32);
+            let (result, sat) = signed_sat_q(rm - rn, 32);
+            self.set_r(params.rd, result as u32);
+            if sat {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_qdadd(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rm = i64::from(self.get_r(params.rm) as i32);
+            let rn = i64::from(self.get_r(params.rn) as i32);
+            let (doubled, sat1) = signed_sat_q(2 * rn, 32);
+            let (result, sat2) = signed_sat_q(rm + doubled, 32);
+            self.set_r(params.rd, result as u32);
+            if sat1 || sat2 {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_qdsub(&mut self, params: &Reg3NoSetFlagsParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rm = i64::from(self.get_r(params.rm) as i32);
+            let rn = i64::from(self.get_r(params.rn) as i32);
+            let (doubled, sat1) = signed_sat_q(2 * rn, 32);
+            let (result, sat2) = signed_sat_q(rm - doubled, 32);
+            self.set_r(params.rd, result as u32);
+            if sat1 || sat2 {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{instruction::Instruction, register::Reg};
+
+    #[test]
+    fn test_qadd() {
+        // arrange
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R1, 0x7fff_fff0);
+        core.set_r(Reg::R2, 0x0000_0100);
+
+        let instruction = Instruction::QADD {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0x7fff_ffff);
+        assert!(core.psr.get_q());
+    }
+
+    #[test]
+    fn test_qdsub() {
+        // arrange
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R1, 0x0000_0010);
+        core.set_r(Reg::R2, 0x0000_0100);
+
+        let instruction = Instruction::QDSUB {
+            params: Reg3NoSetFlagsParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0x0000_00e0);
+        assert!(!core.psr.get_q());
+    }
+}
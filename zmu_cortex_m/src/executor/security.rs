@@ -0,0 +1,285 @@
+use crate::Processor;
+
+use crate::executor::{ExecuteSuccess, ExecutorHelper};
+
+use super::ExecuteResult;
+
+use crate::core::{
+    bits::Bits,
+    fault::Fault,
+    instruction::{Instruction, TtParams},
+    register::{BaseReg, Reg},
+    security::SecurityExtension,
+};
+use crate::peripheral::sau::{MemorySecurity, SecurityAttributionUnit, SAU_CTRL_ENABLE};
+
+/// Security extension operations
+pub trait IsaSecurity {
+    fn exec_sg(&mut self) -> ExecuteResult;
+    fn exec_bxns(&mut self, rm: Reg) -> ExecuteResult;
+    fn exec_blxns(&mut self, rm: Reg) -> ExecuteResult;
+    fn exec_tt(&mut self, params: &TtParams) -> ExecuteResult;
+}
+
+impl Processor {
+    ///
+    /// Check the security state transition of an instruction fetch. Non-secure
+    /// code can enter Secure state only at an SG in Non-secure callable memory.
+    ///
+    pub(super) fn security_fetch_check(
+        &self,
+        pc: u32,
+        instruction: &Instruction,
+    ) -> Result<(), Fault> {
+        if !self.profile.has_security() {
+            return Ok(());
+        }
+        let attribution = self.security_attribution(pc);
+        if attribution.exempt {
+            return Ok(());
+        }
+        match (self.secure, attribution.security) {
+            (true, MemorySecurity::NonSecure) => Err(Fault::Invtran),
+            (true, _) | (false, MemorySecurity::NonSecure) => Ok(()),
+            (false, MemorySecurity::NonSecureCallable) if *instruction == Instruction::SG => Ok(()),
+            (false, _) => Err(Fault::Invep),
+        }
+    }
+}
+
+impl IsaSecurity for Processor {
+    fn exec_sg(&mut self) -> ExecuteResult {
+        let pc = self.get_pc();
+        if !self.secure
+            && self.security_attribution(pc).security == MemorySecurity::NonSecureCallable
+        {
+            // LR bit 0 cleared tells the Secure function it was called from Non-secure state
+            let lr = self.get_r(Reg::LR);
+            self.set_r(Reg::LR, lr & !1);
+            self.set_security_state(true);
+        }
+        Ok(ExecuteSuccess::Taken { cycles: 1 })
+    }
+
+    fn exec_bxns(&mut self, rm: Reg) -> ExecuteResult {
+        if self.condition_passed() {
+            if !self.secure {
+                return Err(Fault::UndefInstr);
+            }
+            let target = self.get_r(rm);
+            if target.get_bit(0) {
+                self.bx_write_pc(target)?;
+            } else {
+                self.set_security_state(false);
+                self.branch_write_pc(target);
+            }
+            return Ok(ExecuteSuccess::Branched { cycles: 3 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_blxns(&mut self, rm: Reg) -> ExecuteResult {
+        if self.condition_passed() {
+            if !self.secure {
+                return Err(Fault::UndefInstr);
+            }
+            let pc = self.get_r(Reg::PC);
+            let target = self.get_r(rm);
+            let return_address = (((pc - 2) >> 1) << 1) | 1;
+            if target.get_bit(0) {
+                self.set_r(Reg::LR, return_address);
+                self.blx_write_pc(target);
+            } else {
+                self.non_secure_call(target, return_address)?;
+            }
+            return Ok(ExecuteSuccess::Branched { cycles: 3 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_tt(&mut self, params: &TtParams) -> ExecuteResult {
+        if self.condition_passed() {
+            if params.a && !self.secure {
+                return Err(Fault::UndefInstr);
+            }
+            let address = self.get_r(params.rn);
+
+            // no MPU regions, the default memory map permits reads and writes
+            let mut result = 0;
+            result.set_bit(18, true);
+            result.set_bit(19, true);
+
+            if self.secure {
+                let attribution = self.security_attribution(address);
+                let secure = attribution.security != MemorySecurity::NonSecure;
+                result.set_bit(22, secure);
+                result.set_bit(20, !secure);
+                result.set_bit(21, !secure);
+                if let Some(region) = attribution.sau_region {
+                    if self.sau_ctrl.get_bit(SAU_CTRL_ENABLE) {
+                        result.set_bits(8..16, u32::from(region));
+                        result.set_bit(17, true);
+                    }
+                }
+                if let Some(region) = attribution.idau_region {
+                    result.set_bits(24..32, u32::from(region));
+                    result.set_bit(23, true);
+                }
+            }
+
+            self.set_r(params.rd, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::profile::ArchProfile;
+    use crate::core::reset::Reset;
+    use crate::peripheral::sau::IdauRegion;
+
+    fn processor_m33() -> Processor {
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+        processor.reset().unwrap();
+        processor
+    }
+
+    #[test]
+    fn test_sg_enters_secure_state() {
+        // Arrange: Non-secure callable veneer at 0x0000_8000
+        let mut processor = processor_m33();
+        processor.write_sau_rbar(0x0000_8000);
+        processor.write_sau_rlar(0x0000_8FE0 | 0b11);
+        processor.write_sau_ctrl(1);
+        processor.set_security_state(false);
+        processor.set_r(Reg::LR, 0x2000_0101);
+        processor.set_pc(0x0000_8000);
+
+        // Act
+        let result = processor.exec_sg();
+
+        // Assert
+        assert_eq!(result, Ok(ExecuteSuccess::Taken { cycles: 1 }));
+        assert!(processor.secure);
+        assert_eq!(processor.get_r(Reg::LR), 0x2000_0100);
+    }
+
+    #[test]
+    fn test_fetch_from_secure_memory_in_non_secure_state() {
+        // Arrange
+        let mut processor = processor_m33();
+        processor.write_sau_rbar(0x0000_8000);
+        processor.write_sau_rlar(0x0000_8FE0 | 0b11);
+        processor.write_sau_ctrl(1);
+        processor.set_security_state(false);
+
+        // Assert: only SG is allowed in Non-secure callable memory
+        assert_eq!(
+            processor.security_fetch_check(0x0000_8000, &Instruction::SG),
+            Ok(())
+        );
+        assert_eq!(
+            processor.security_fetch_check(0x0000_8000, &Instruction::DSB),
+            Err(Fault::Invep)
+        );
+        assert_eq!(
+            processor.security_fetch_check(0x0000_0000, &Instruction::SG),
+            Err(Fault::Invep)
+        );
+
+        // Assert: Secure code cannot fall through to Non-secure memory
+        processor.set_security_state(true);
+        processor.write_sau_rbar(0x2000_0000);
+        processor.write_sau_rlar(0x2000_0FE0 | 1);
+        assert_eq!(
+            processor.security_fetch_check(0x2000_0000, &Instruction::DSB),
+            Err(Fault::Invtran)
+        );
+    }
+
+    #[test]
+    fn test_bxns_in_non_secure_state() {
+        // Arrange
+        let mut processor = processor_m33();
+        processor.set_security_state(false);
+
+        // Act
+        let result = processor.exec_bxns(Reg::LR);
+
+        // Assert
+        assert_eq!(result, Err(Fault::UndefInstr));
+    }
+
+    #[test]
+    fn test_bxns_to_non_secure() {
+        // Arrange
+        let mut processor = processor_m33();
+        processor.set_r(Reg::R3, 0x0002_0100);
+
+        // Act
+        let result = processor.exec_bxns(Reg::R3);
+
+        // Assert
+        assert_eq!(result, Ok(ExecuteSuccess::Branched { cycles: 3 }));
+        assert!(!processor.secure);
+        assert_eq!(processor.get_pc(), 0x0002_0100);
+    }
+
+    #[test]
+    fn test_tt() {
+        // Arrange
+        let mut processor = processor_m33();
+        processor.idau(&[IdauRegion {
+            start: 0x1000_0000,
+            end: 0x1FFF_FFFF,
+            number: 5,
+            security: MemorySecurity::Secure,
+        }]);
+        processor.write_sau_rnr(2);
+        processor.write_sau_rbar(0x2000_0000);
+        processor.write_sau_rlar(0x2000_0FE0 | 1);
+        processor.write_sau_ctrl(1);
+        let params = TtParams {
+            rd: Reg::R1,
+            rn: Reg::R2,
+            a: false,
+            t: false,
+        };
+
+        // Act
+        processor.set_r(Reg::R2, 0x2000_0010);
+        processor.exec_tt(&params).unwrap();
+
+        // Assert: Non-secure, SAU region 2
+        assert_eq!(
+            processor.get_r(Reg::R1),
+            (1 << 21) | (1 << 20) | (1 << 19) | (1 << 18) | (1 << 17) | (2 << 8)
+        );
+
+        // Act
+        processor.set_r(Reg::R2, 0x1000_0000);
+        processor.exec_tt(&params).unwrap();
+
+        // Assert: Secure, IDAU region 5
+        assert_eq!(
+            processor.get_r(Reg::R1),
+            (5 << 24) | (1 << 23) | (1 << 22) | (1 << 19) | (1 << 18)
+        );
+
+        // Act: Non-secure code sees only its own permissions
+        processor.set_security_state(false);
+        processor.set_r(Reg::R2, 0x2000_0010);
+        processor.exec_tt(&params).unwrap();
+
+        // Assert
+        assert_eq!(processor.get_r(Reg::R1), (1 << 19) | (1 << 18));
+        assert_eq!(
+            processor.exec_tt(&TtParams { a: true, ..params }),
+            Err(Fault::UndefInstr)
+        );
+    }
+}
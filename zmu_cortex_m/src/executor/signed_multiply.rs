@@ -3,17 +3,76 @@ use crate::Processor;
 use crate::executor::{ExecuteSuccess, ExecutorHelper};
 
 use super::ExecuteResult;
-use crate::core::instruction::{Reg3HighParams, Reg4HighParams, Reg643232Params};
+use crate::core::instruction::{
+    Reg3DualParams, Reg3HighParams, Reg3MHighParams, Reg3RoundParams, Reg4DualParams,
+    Reg4HighParams, Reg4MHighParams, Reg4RoundParams, Reg643232DualParams, Reg643232HighParams,
+    Reg643232Params,
+};
 use crate::core::{
     bits::Bits,
-    register::{Apsr, BaseReg},
+    operation::ror,
+    register::{Apsr, BaseReg, Reg},
 };
 
 /// Multiply operations
 pub trait IsaSignedMultiply {
     fn exec_smull(&mut self, params: &Reg643232Params) -> ExecuteResult;
+    fn exec_smlal(&mut self, params: &Reg643232Params) -> ExecuteResult;
+    fn exec_smlalxy(&mut self, params: &Reg643232HighParams) -> ExecuteResult;
+    fn exec_smlald(&mut self, params: &Reg643232DualParams) -> ExecuteResult;
+    fn exec_smlsld(&mut self, params: &Reg643232DualParams) -> ExecuteResult;
     fn exec_smul(&mut self, params: &Reg3HighParams) -> ExecuteResult;
     fn exec_smla(&mut self, params: &Reg4HighParams) -> ExecuteResult;
+    fn exec_smlad(&mut self, params: &Reg4DualParams) -> ExecuteResult;
+    fn exec_smlsd(&mut self, params: &Reg4DualParams) -> ExecuteResult;
+    fn exec_smuad(&mut self, params: &Reg3DualParams) -> ExecuteResult;
+    fn exec_smusd(&mut self, params: &Reg3DualParams) -> ExecuteResult;
+    fn exec_smlaw(&mut self, params: &Reg4MHighParams) -> ExecuteResult;
+    fn exec_smulw(&mut self, params: &Reg3MHighParams) -> ExecuteResult;
+    fn exec_smmla(&mut self, params: &Reg4RoundParams) -> ExecuteResult;
+    fn exec_smmls(&mut self, params: &Reg4RoundParams) -> ExecuteResult;
+    fn exec_smmul(&mut self, params: &Reg3RoundParams) -> ExecuteResult;
+}
+
+/// Signed products of the bottom and top halfwords of the operands,
+/// with the halfwords of `rm` optionally swapped
+fn dual_products(rn: u32, rm: u32, m_swap: bool) -> (i64, i64) {
+    let operand2 = if m_swap { ror(rm, 16) } else { rm };
+    let product1 =
+        i64::from(rn.get_bits(0..16) as i16) * i64::from(operand2.get_bits(0..16) as i16);
+    let product2 =
+        i64::from(rn.get_bits(16..32) as i16) * i64::from(operand2.get_bits(16..32) as i16);
+    (product1, product2)
+}
+
+/// Signed 64-bit accumulator held in `rdhi:rdlo`
+fn get_rdhilo(processor: &Processor, rdlo: Reg, rdhi: Reg) -> i64 {
+    (u64::from(processor.get_r(rdhi)) << 32 | u64::from(processor.get_r(rdlo))) as i64
+}
+
+fn set_rdhilo(processor: &mut Processor, rdlo: Reg, rdhi: Reg, value: i64) {
+    processor.set_r(rdlo, value as u32);
+    processor.set_r(rdhi, (value >> 32) as u32);
+}
+
+/// Halfword of `value` as a signed number
+fn halfword(value: u32, high: bool) -> i64 {
+    let half = if high {
+        value.get_bits(16..32)
+    } else {
+        value.get_bits(0..16)
+    };
+    i64::from(half as i16)
+}
+
+/// Most significant word of a 64-bit result, optionally rounded
+fn most_significant_word(result: i64, round: bool) -> u32 {
+    let result = if round {
+        result.wrapping_add(0x8000_0000)
+    } else {
+        result
+    };
+    (result >> 32) as u32
 }
 
 impl IsaSignedMultiply for Processor {
@@ -30,6 +89,32 @@ impl IsaSignedMultiply for Processor {
         Ok(ExecuteSuccess::NotTaken)
     }
 
+    fn exec_smlal(&mut self, params: &Reg643232Params) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = i64::from(self.get_r(params.rn) as i32);
+            let rm = i64::from(self.get_r(params.rm) as i32);
+            let accumulate = get_rdhilo(self, params.rdlo, params.rdhi);
+
+            let result = accumulate.wrapping_add(rn * rm);
+            set_rdhilo(self, params.rdlo, params.rdhi, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smlalxy(&mut self, params: &Reg643232HighParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let product = halfword(self.get_r(params.rn), params.n_high)
+                * halfword(self.get_r(params.rm), params.m_high);
+            let accumulate = get_rdhilo(self, params.rdlo, params.rdhi);
+
+            let result = accumulate.wrapping_add(product);
+            set_rdhilo(self, params.rdlo, params.rdhi, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
     fn exec_smul(&mut self, params: &Reg3HighParams) -> ExecuteResult {
         if self.condition_passed() {
             let operand1 = i32::from(if params.n_high {
@@ -73,16 +158,170 @@ impl IsaSignedMultiply for Processor {
                 op as i16
             });
 
-            let result = operand1
-                .wrapping_mul(operand2)
-                .wrapping_add(self.get_r(params.ra) as i32);
+            let result = i64::from(operand1 * operand2) + i64::from(self.get_r(params.ra) as i32);
+
+            self.set_r(params.rd, result as u32);
+            if result != i64::from(result as i32) {
+                self.psr.set_q(true);
+            }
+
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smlad(&mut self, params: &Reg4DualParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let (product1, product2) =
+                dual_products(self.get_r(params.rn), self.get_r(params.rm), params.m_swap);
+            let result = product1 + product2 + i64::from(self.get_r(params.ra) as i32);
 
             self.set_r(params.rd, result as u32);
-            // FIXME: this is likely wrong .. make a test and investigate.
-            if result != result as i32 {
+            if result != i64::from(result as i32) {
                 self.psr.set_q(true);
             }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smlsd(&mut self, params: &Reg4DualParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let (product1, product2) =
+                dual_products(self.get_r(params.rn), self.get_r(params.rm), params.m_swap);
+            let result = product1 - product2 + i64::from(self.get_r(params.ra) as i32);
+
+            self.set_r(params.rd, result as u32);
+            if result != i64::from(result as i32) {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smlald(&mut self, params: &Reg643232DualParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let (product1, product2) =
+                dual_products(self.get_r(params.rn), self.get_r(params.rm), params.m_swap);
+            let accumulate = get_rdhilo(self, params.rdlo, params.rdhi);
+
+            let result = accumulate.wrapping_add(product1 + product2);
+            set_rdhilo(self, params.rdlo, params.rdhi, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smlsld(&mut self, params: &Reg643232DualParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let (product1, product2) =
+                dual_products(self.get_r(params.rn), self.get_r(params.rm), params.m_swap);
+            let accumulate = get_rdhilo(self, params.rdlo, params.rdhi);
+
+            let result = accumulate.wrapping_add(product1 - product2);
+            set_rdhilo(self, params.rdlo, params.rdhi, result);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smuad(&mut self, params: &Reg3DualParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let (product1, product2) =
+                dual_products(self.get_r(params.rn), self.get_r(params.rm), params.m_swap);
+            let result = product1 + product2;
+
+            self.set_r(params.rd, result as u32);
+            if result != i64::from(result as i32) {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smusd(&mut self, params: &Reg3DualParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let (product1, product2) =
+                dual_products(self.get_r(params.rn), self.get_r(params.rm), params.m_swap);
+            let result = product1 - product2;
+
+            self.set_r(params.rd, result as u32);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smlaw(&mut self, params: &Reg4MHighParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rm = self.get_r(params.rm);
+            let operand2 = if params.m_high {
+                rm.get_bits(16..32)
+            } else {
+                rm.get_bits(0..16)
+            };
+            let result = i64::from(self.get_r(params.rn) as i32) * i64::from(operand2 as i16)
+                + (i64::from(self.get_r(params.ra) as i32) << 16);
+            let result = result >> 16;
+
+            self.set_r(params.rd, result as u32);
+            if result != i64::from(result as i32) {
+                self.psr.set_q(true);
+            }
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smulw(&mut self, params: &Reg3MHighParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rm = self.get_r(params.rm);
+            let operand2 = if params.m_high {
+                rm.get_bits(16..32)
+            } else {
+                rm.get_bits(0..16)
+            };
+            let product = i64::from(self.get_r(params.rn) as i32) * i64::from(operand2 as i16);
+
+            self.set_r(params.rd, (product >> 16) as u32);
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smmla(&mut self, params: &Reg4RoundParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = i64::from(self.get_r(params.rn) as i32);
+            let rm = i64::from(self.get_r(params.rm) as i32);
+            let ra = i64::from(self.get_r(params.ra) as i32);
+            let result = (ra << 32).wrapping_add(rn * rm);
+
+            self.set_r(params.rd, most_significant_word(result, params.round));
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
+
+    fn exec_smmls(&mut self, params: &Reg4RoundParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = i64::from(self.get_r(params.rn) as i32);
+            let rm = i64::from(self.get_r(params.rm) as i32);
+            let ra = i64::from(self.get_r(params.ra) as i32);
+            let result = (ra << 32).wrapping_sub(rn * rm);
+
+            self.set_r(params.rd, most_significant_word(result, params.round));
+            return Ok(ExecuteSuccess::Taken { cycles: 1 });
+        }
+        Ok(ExecuteSuccess::NotTaken)
+    }
 
+    fn exec_smmul(&mut self, params: &Reg3RoundParams) -> ExecuteResult {
+        if self.condition_passed() {
+            let rn = i64::from(self.get_r(params.rn) as i32);
+            let rm = i64::from(self.get_r(params.rm) as i32);
+
+            self.set_r(params.rd, most_significant_word(rn * rm, params.round));
             return Ok(ExecuteSuccess::Taken { cycles: 1 });
         }
         Ok(ExecuteSuccess::NotTaken)
@@ -121,5 +360,240 @@ mod tests {
         core.execute_internal(&instruction).unwrap();
 
         assert_eq!(core.get_r(Reg::R12), 0xFFD4_F24B);
+        assert!(!core.psr.get_q());
+    }
+
+    #[test]
+    fn test_smlabb_overflow() {
+        // arrange
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R1, 0x0000_7fff);
+        core.set_r(Reg::R2, 0x7fff_ffff);
+
+        let instruction = Instruction::SMLA {
+            params: Reg4HighParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R1,
+                ra: Reg::R2,
+                n_high: false,
+                m_high: false,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0xbfff_0000);
+        assert!(core.psr.get_q());
+    }
+
+    #[test]
+    fn test_smladx() {
+        // arrange
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R1, 0x0002_0003);
+        core.set_r(Reg::R2, 0xffff_0005);
+        core.set_r(Reg::R3, 0x0000_0100);
+
+        let instruction = Instruction::SMLAD {
+            params: Reg4DualParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                ra: Reg::R3,
+                m_swap: true,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert: 3 * -1 + 2 * 5 + 0x100
+        assert_eq!(core.get_r(Reg::R0), 0x0000_0107);
+        assert!(!core.psr.get_q());
+    }
+
+    #[test]
+    fn test_smuad_overflow() {
+        // arrange
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R1, 0x8000_8000);
+
+        let instruction = Instruction::SMUAD {
+            params: Reg3DualParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R1,
+                m_swap: false,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0x8000_0000);
+        assert!(core.psr.get_q());
+    }
+
+    #[test]
+    fn test_smulwt() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R1, 0x0001_0000);
+        core.set_r(Reg::R2, 0xfffe_0000);
+
+        let instruction = Instruction::SMULW {
+            params: Reg3MHighParams {
+                rd: Reg::R0,
+                rn: Reg::R1,
+                rm: Reg::R2,
+                m_high: true,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert
+        assert_eq!(core.get_r(Reg::R0), 0xffff_fffe);
+    }
+
+    #[test]
+    fn test_smmulr() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R1, 0x4000_0000);
+        core.set_r(Reg::R2, 0x0000_0003);
+
+        let mut params = Reg3RoundParams {
+            rd: Reg::R0,
+            rn: Reg::R1,
+            rm: Reg::R2,
+            round: false,
+        };
+
+        // act & assert: 0xc000_0000 / 2^32
+        core.execute_internal(&Instruction::SMMUL { params })
+            .unwrap();
+        assert_eq!(core.get_r(Reg::R0), 0);
+        params.round = true;
+        core.execute_internal(&Instruction::SMMUL { params })
+            .unwrap();
+        assert_eq!(core.get_r(Reg::R0), 1);
+    }
+
+    #[test]
+    fn test_smlal() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R0, 0);
+        core.set_r(Reg::R1, 1);
+        core.set_r(Reg::R2, 0xffff_fffe);
+        core.set_r(Reg::R3, 3);
+
+        let instruction = Instruction::SMLAL {
+            params: Reg643232Params {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R3,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert: 0x1_0000_0000 + -2 * 3
+        assert_eq!(core.get_r(Reg::R0), 0xffff_fffa);
+        assert_eq!(core.get_r(Reg::R1), 0);
+    }
+
+    #[test]
+    fn test_smlaltb() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R0, 0x1000);
+        core.set_r(Reg::R1, 0);
+        core.set_r(Reg::R2, 0x8000_0001);
+        core.set_r(Reg::R3, 0x0000_7fff);
+
+        let instruction = Instruction::SMLALxy {
+            params: Reg643232HighParams {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R3,
+                n_high: true,
+                m_high: false,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert: 0x1000 + -0x8000 * 0x7fff
+        assert_eq!(core.get_r(Reg::R0), 0xc000_9000);
+        assert_eq!(core.get_r(Reg::R1), 0xffff_ffff);
+    }
+
+    #[test]
+    fn test_smlald() {
+        // arrange
+        let mut core = Processor::new();
+        core.psr.value = 0;
+        core.set_r(Reg::R0, 0xffff_ffff);
+        core.set_r(Reg::R1, 0);
+        core.set_r(Reg::R2, 0x8000_8000);
+
+        let instruction = Instruction::SMLALD {
+            params: Reg643232DualParams {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R2,
+                m_swap: false,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert: the 64-bit accumulation neither wraps at 32 bits nor sets Q
+        assert_eq!(core.get_r(Reg::R0), 0x7fff_ffff);
+        assert_eq!(core.get_r(Reg::R1), 1);
+        assert!(!core.psr.get_q());
+    }
+
+    #[test]
+    fn test_smlsldx() {
+        // arrange
+        let mut core = Processor::new();
+        core.set_r(Reg::R0, 0);
+        core.set_r(Reg::R1, 0);
+        core.set_r(Reg::R2, 0x0002_0003);
+        core.set_r(Reg::R3, 0xffff_0005);
+
+        let instruction = Instruction::SMLSLD {
+            params: Reg643232DualParams {
+                rdlo: Reg::R0,
+                rdhi: Reg::R1,
+                rn: Reg::R2,
+                rm: Reg::R3,
+                m_swap: true,
+            },
+        };
+
+        // act
+        core.execute_internal(&instruction).unwrap();
+
+        // assert: 3 * -1 - 2 * 5
+        assert_eq!(core.get_r(Reg::R0), 0xffff_fff3);
+        assert_eq!(core.get_r(Reg::R1), 0xffff_ffff);
     }
 }
@@ -9,7 +9,8 @@ use crate::{
         bits::Bits,
         exception::ExceptionHandling,
         instruction::{MrsParams, MsrParams},
-        register::BaseReg,
+        register::{BaseReg, Reg},
+        security::SecurityExtension,
     },
     ProcessorMode,
 };
@@ -19,18 +20,35 @@ pub trait IsaStatusRegister {
     fn exec_mrs(&mut self, params: MrsParams) -> ExecuteResult;
     fn exec_msr(&mut self, params: MsrParams) -> ExecuteResult;
 
-    #[cfg(feature = "armv6m")]
-    fn exec_cps(&mut self, im: bool) -> ExecuteResult;
-
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn exec_cps(&mut self, im: bool, affect_pri: bool, affect_fault: bool) -> ExecuteResult;
 }
 
+impl Processor {
+    ///
+    /// Enter the Non-secure state for an access to a Non-secure register alias.
+    /// Returns false if the alias is not accessible: the alias is RAZ/WI outside Secure state.
+    ///
+    fn enter_non_secure_alias(&mut self, sysm: u8) -> bool {
+        if !sysm.get_bit(7) {
+            return true;
+        }
+        if !self.profile.has_security() || !self.secure {
+            return false;
+        }
+        self.set_security_state(false);
+        true
+    }
+}
+
 impl IsaStatusRegister for Processor {
     fn exec_mrs(&mut self, params: MrsParams) -> ExecuteResult {
         if self.condition_passed() {
             let mut value: u32 = 0;
-            match params.sysm.get_bits(3..8) {
+            if !self.enter_non_secure_alias(params.sysm) {
+                self.set_r(params.rd, value);
+                return Ok(ExecuteSuccess::Taken { cycles: 4 });
+            }
+            match params.sysm.get_bits(3..7) {
                 0b00000 => {
                     if params.sysm.get_bit(0) {
                         value.set_bits(0..9, self.psr.value.get_bits(0..9));
@@ -50,31 +68,38 @@ impl IsaStatusRegister for Processor {
                     1 => {
                         value = self.psp;
                     }
+                    0b010 if self.profile.is_armv8m() => {
+                        value = self.msplim;
+                    }
+                    0b011 if self.profile.is_armv8m() => {
+                        value = self.psplim;
+                    }
                     _ => (),
                 },
                 0b00010 => match params.sysm.get_bits(0..3) {
                     0b000 => {
                         value.set_bit(0, self.primask);
                     }
-                    0b001 => {
-                        value.set_bits(0..8, u32::from(self.basepri));
-                    }
-                    0b010 => {
+                    0b001 | 0b010 if self.profile.is_mainline() => {
                         value.set_bits(0..8, u32::from(self.basepri));
                     }
-                    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-                    0b011 => {
+                    0b011 if self.profile.is_mainline() => {
                         value.set_bit(0, self.faultmask);
                     }
                     0b100 => {
-                        //let ctrl = u8::from(self.control) as u32;
-                        //value.set_bits(0..2, ctrl);
-                        todo!("unimplemented CONTROL");
+                        value.set_bits(0..3, u32::from(u8::from(self.control)));
                     }
                     _ => (),
                 },
+                // SP_NS, the Non-secure stack pointer in use
+                0b00011 if params.sysm == 0x98 => {
+                    value = self.get_r(Reg::SP);
+                }
                 _ => (),
             }
+            if params.sysm.get_bit(7) {
+                self.set_security_state(true);
+            }
             self.set_r(params.rd, value);
             return Ok(ExecuteSuccess::Taken { cycles: 4 });
         }
@@ -85,7 +110,10 @@ impl IsaStatusRegister for Processor {
     fn exec_msr(&mut self, params: MsrParams) -> ExecuteResult {
         if self.condition_passed() {
             let r_n = self.get_r(params.rn);
-            match params.sysm.get_bits(3..8) {
+            if !self.enter_non_secure_alias(params.sysm) {
+                return Ok(ExecuteSuccess::Taken { cycles: 4 });
+            }
+            match params.sysm.get_bits(3..7) {
                 0b00000 => {
                     if !params.sysm.get_bit(2) {
                         if params.mask.get_bit(0) {
@@ -101,6 +129,8 @@ impl IsaStatusRegister for Processor {
                 0b00001 => match params.sysm.get_bits(0..3) {
                     0 => self.msp = r_n,
                     1 => self.psp = r_n,
+                    0b010 if self.profile.is_armv8m() => self.msplim = r_n & !7,
+                    0b011 if self.profile.is_armv8m() => self.psplim = r_n & !7,
                     _ => (),
                 },
                 0b00010 => match params.sysm.get_bits(0..3) {
@@ -108,53 +138,48 @@ impl IsaStatusRegister for Processor {
                         self.primask = r_n.get_bit(0);
                         self.execution_priority = self.get_execution_priority();
                     }
-                    0b001 => {
+                    0b001 if self.profile.is_mainline() => {
                         self.basepri = r_n.get_bits(0..8) as u8;
                         self.execution_priority = self.get_execution_priority();
                     }
-                    0b010 => {
+                    0b010 if self.profile.is_mainline() => {
                         let low_rn = r_n.get_bits(0..8) as u8;
                         if low_rn != 0 && low_rn < self.basepri || self.basepri == 0 {
                             self.basepri = low_rn;
                             self.execution_priority = self.get_execution_priority();
                         }
                     }
-                    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
-                    0b011 => {
-                        if self.execution_priority > -1 {
-                            self.faultmask = r_n.get_bit(0);
-                            self.execution_priority = self.get_execution_priority();
-                        }
+                    0b011 if self.profile.is_mainline() && self.execution_priority > -1 => {
+                        self.faultmask = r_n.get_bit(0);
+                        self.execution_priority = self.get_execution_priority();
                     }
                     0b100 => {
                         self.control.n_priv = r_n.get_bit(0);
                         if self.mode == ProcessorMode::ThreadMode {
                             self.control.sp_sel = r_n.get_bit(1);
                         }
+                        if self.profile.has_fpu() {
+                            self.control.fpca = r_n.get_bit(2);
+                        }
                     }
                     _ => (),
                 },
+                0b00011 if params.sysm == 0x98 => self.set_r(Reg::SP, r_n),
                 _ => (),
             }
+            if params.sysm.get_bit(7) {
+                self.set_security_state(true);
+                self.execution_priority = self.get_execution_priority();
+            }
 
             return Ok(ExecuteSuccess::Taken { cycles: 4 });
         }
         Ok(ExecuteSuccess::NotTaken)
     }
 
-    #[cfg(feature = "armv6m")]
-    fn exec_cps(&mut self, im: bool) -> ExecuteResult {
-        if im {
-            self.primask = true;
-        } else {
-            self.primask = false;
-        }
-        self.execution_priority = self.get_execution_priority();
-        Ok(ExecuteSuccess::Taken { cycles: 1 })
-    }
-
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn exec_cps(&mut self, im: bool, affect_pri: bool, affect_fault: bool) -> ExecuteResult {
+        // Armv6-M has no FAULTMASK
+        let affect_fault = affect_fault && self.profile.is_mainline();
         if im {
             if affect_pri {
                 self.primask = true;
@@ -12,7 +12,7 @@ use gdbstub::common::Signal;
 use gdbstub::stub::GdbStub;
 use gdbstub::stub::DisconnectReason;
 
-use crate::{MemoryMapConfig};
+use crate::system::simulation::SimulationConfig;
 use crate::gdb::conn;
 use conn::TcpConnection;
 use crate::gdb::simulation::SimulationEvent;
@@ -36,18 +36,14 @@ impl GdbServer {
     /// 
     /// # Arguments
     /// 
-    /// * `code` - The binary code to run in the emulator
+    /// * `config` - The configuration of the simulated system, including the image to run
     /// * `semihost_func` - A function that will be called when a semihosting command is issued
-    /// * `map` - The memory map configuration
-    /// * `flash_size` - The size of the flash memory
     pub fn new(
-        code: &[u8],
+        config: &SimulationConfig,
         semihost_func: Box<dyn FnMut(&SemihostingCommand) -> SemihostingResponse + 'static>,
-        map: Option<MemoryMapConfig>,
-        flash_size: usize,
     ) -> Result<GdbServer, &'static str> {
 
-        let target = ZmuTarget::new(code, semihost_func, map, flash_size);
+        let target = ZmuTarget::new(config, semihost_func);
 
         Ok(GdbServer {target})
     }
@@ -3,9 +3,10 @@
 //!
 
 
+use crate::system::simulation::configure_processor;
+use crate::system::simulation::SimulationConfig;
 use crate::system::simulation::SimulationError;
 use crate::Processor;
-use crate::MemoryMapConfig;
 use crate::executor::Executor;
 use crate::core::reset::Reset;
 use crate::core::register::BaseReg;
@@ -34,7 +35,6 @@ pub enum SimulationEvent {
     /// Step done
     DoneStep,
     /// Processor is halted
-    #[allow(dead_code)]
     Halted,
     /// A breakpoint was hit
     Break,
@@ -63,16 +63,11 @@ impl Simulation {
     ///
     /// Prepare a new simulation instance
     /// 
-    pub fn new(code: &[u8],
+    pub fn new(config: &SimulationConfig,
         semihost_func: Box<dyn FnMut(&SemihostingCommand) -> SemihostingResponse + 'static>,
-        map: Option<MemoryMapConfig>,
-        flash_size: usize,    
     ) -> Result<Simulation, &'static str> {
-        let mut processor = Processor::new();
+        let mut processor = configure_processor(config);
         processor.semihost(Some(semihost_func));
-        processor.memory_map(map);
-        processor.flash_memory(flash_size, code);
-        processor.cache_instructions();
         processor.running = true; 
         match processor.reset() {
             Ok(_) => {},
@@ -150,7 +145,7 @@ impl Simulation {
     pub fn reset(&mut self) -> Result<(), SimulationError> {
         match self.processor.reset() {
             Ok(_) => Ok(()),
-            Err(_) => Err(SimulationError::FaultTrap),
+            Err(fault) => Err(SimulationError::reset_fault(fault, &self.processor)),
         }
     }
 
@@ -166,6 +161,8 @@ impl Simulation {
             return SimulationEvent::Break;
         } if self.watchpoints.contains(&self.processor.get_pc()) {
             return SimulationEvent::WatchRead(self.processor.get_pc());
+        } else if self.processor.lockup.is_some() {
+            return SimulationEvent::Halted;
         } if !self.processor.running && !self.processor.sleeping {
             return SimulationEvent::Finalized(self.processor.exit_code);
         } else {
@@ -7,8 +7,7 @@ use gdbstub::outputln;
 
 use log::debug;
 
-use crate::bus::Bus;
-use crate::MemoryMapConfig;
+use crate::system::simulation::SimulationConfig;
 use crate::gdb::simulation;
 
 use gdbstub::target::ext::base::singlethread::SingleThreadBase;
@@ -34,12 +33,10 @@ pub struct ZmuTarget {
 
 impl ZmuTarget {
     pub fn new(
-        code: &[u8],
+        config: &SimulationConfig,
         semihost_func: Box<dyn FnMut(&SemihostingCommand) -> SemihostingResponse + 'static>,
-        map: Option<MemoryMapConfig>,
-        flash_size: usize,
     ) -> ZmuTarget {
-        let simulation = simulation::Simulation::new(code, semihost_func, map, flash_size);
+        let simulation = simulation::Simulation::new(config, semihost_func);
         ZmuTarget {
             simulation: simulation.unwrap(),
         }
@@ -121,7 +118,7 @@ impl SingleThreadBase for ZmuTarget {
         data: &mut [u8],
     ) -> TargetResult<usize, Self> {
         for i in 0..data.len() {
-            match self.simulation.processor.read8(start_addr + i as u32) {
+            match self.simulation.processor.bus_read8(start_addr + i as u32) {
                 Ok(b) => data[i] = b,
                 Err(_) => {
                     return Ok(i);
@@ -138,7 +135,13 @@ impl SingleThreadBase for ZmuTarget {
         data: &[u8],
     ) -> TargetResult<(), Self> {
         for i in 0..data.len() {
-            match self.simulation.processor.write8(start_addr + i as u32, data[i]) {
+            let address = start_addr + i as u32;
+            let processor = &mut self.simulation.processor;
+            // flash is programmed, like debug probes do
+            match processor
+                .bus_write8(address, data[i])
+                .or_else(|_| processor.program_flash(address, &data[i..=i]))
+            {
                 Ok(_) => (),
                 Err(_) => {
                     return Err(target::TargetError::NonFatal);
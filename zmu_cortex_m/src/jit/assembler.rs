@@ -0,0 +1,285 @@
+//!
+//! Minimal x86-64 machine code emitter for the JIT
+//!
+
+/// x86-64 general purpose register, by its encoding number
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Host(pub u8);
+
+pub const EAX: Host = Host(0);
+pub const ECX: Host = Host(1);
+pub const EDX: Host = Host(2);
+pub const EBX: Host = Host(3);
+pub const ESI: Host = Host(6);
+pub const EDI: Host = Host(7);
+pub const R8: Host = Host(8);
+pub const R9: Host = Host(9);
+pub const R10: Host = Host(10);
+pub const R11: Host = Host(11);
+pub const R12: Host = Host(12);
+pub const R13: Host = Host(13);
+pub const R15: Host = Host(15);
+
+/// Arithmetic and logic operations sharing the same encodings
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Alu {
+    Add = 0,
+    Or = 1,
+    Adc = 2,
+    Sbb = 3,
+    And = 4,
+    Sub = 5,
+    Xor = 6,
+    Cmp = 7,
+}
+
+/// Shift operations by an immediate
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Shift {
+    Shl = 4,
+    Shr = 5,
+    Sar = 7,
+}
+
+/// Condition codes of the conditional jumps
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Jcc {
+    O = 0x0,
+    NO = 0x1,
+    B = 0x2,
+    AE = 0x3,
+    E = 0x4,
+    NE = 0x5,
+    S = 0x8,
+    NS = 0x9,
+    L = 0xc,
+    GE = 0xd,
+    LE = 0xe,
+    G = 0xf,
+}
+
+/// Position of a jump whose target is patched later
+pub struct Label(usize);
+
+///
+/// Emits the instructions into a byte buffer. All register operations are
+/// 32-bit unless named otherwise, memory operands are relative to R15.
+///
+#[derive(Default)]
+pub struct Assembler {
+    pub code: Vec<u8>,
+}
+
+impl Assembler {
+    fn rex(&mut self, w: bool, reg: Host, rm: Host) {
+        let rex = 0x40 | (u8::from(w) << 3) | ((reg.0 >> 3) << 2) | (rm.0 >> 3);
+        if rex != 0x40 {
+            self.code.push(rex);
+        }
+    }
+
+    fn modrm_reg(&mut self, reg: u8, rm: Host) {
+        self.code.push(0xc0 | ((reg & 7) << 3) | (rm.0 & 7));
+    }
+
+    fn modrm_state(&mut self, reg: u8, offset: usize) {
+        // [r15 + disp8], r15 needs no SIB byte
+        self.code.push(0x40 | ((reg & 7) << 3) | (R15.0 & 7));
+        self.code.push(u8::try_from(offset).unwrap());
+    }
+
+    fn imm32(&mut self, imm: u32) {
+        self.code.extend_from_slice(&imm.to_le_bytes());
+    }
+
+    /// `mov dst, src`
+    pub fn mov(&mut self, dst: Host, src: Host) {
+        self.rex(false, src, dst);
+        self.code.push(0x89);
+        self.modrm_reg(src.0, dst);
+    }
+
+    /// `mov dst, src` on the 64-bit registers
+    pub fn mov64(&mut self, dst: Host, src: Host) {
+        self.rex(true, src, dst);
+        self.code.push(0x89);
+        self.modrm_reg(src.0, dst);
+    }
+
+    /// `mov dst, imm`
+    pub fn mov_imm(&mut self, dst: Host, imm: u32) {
+        self.rex(false, Host(0), dst);
+        self.code.push(0xb8 + (dst.0 & 7));
+        self.imm32(imm);
+    }
+
+    /// `mov dst, dword [r15 + offset]`
+    pub fn load(&mut self, dst: Host, offset: usize) {
+        self.rex(false, dst, R15);
+        self.code.push(0x8b);
+        self.modrm_state(dst.0, offset);
+    }
+
+    /// `mov dword [r15 + offset], src`
+    pub fn store(&mut self, offset: usize, src: Host) {
+        self.rex(false, src, R15);
+        self.code.push(0x89);
+        self.modrm_state(src.0, offset);
+    }
+
+    /// `mov dword [r15 + offset], imm`
+    pub fn store_imm(&mut self, offset: usize, imm: u32) {
+        self.rex(false, Host(0), R15);
+        self.code.push(0xc7);
+        self.modrm_state(0, offset);
+        self.imm32(imm);
+    }
+
+    /// `or dst, dword [r15 + offset]`
+    pub fn or_load(&mut self, dst: Host, offset: usize) {
+        self.rex(false, dst, R15);
+        self.code.push(0x0b);
+        self.modrm_state(dst.0, offset);
+    }
+
+    /// `op dst, src`
+    pub fn alu(&mut self, op: Alu, dst: Host, src: Host) {
+        self.rex(false, src, dst);
+        self.code.push(((op as u8) << 3) | 0x01);
+        self.modrm_reg(src.0, dst);
+    }
+
+    /// `op dst, imm`
+    pub fn alu_imm(&mut self, op: Alu, dst: Host, imm: u32) {
+        self.rex(false, Host(0), dst);
+        self.code.push(0x81);
+        self.modrm_reg(op as u8, dst);
+        self.imm32(imm);
+    }
+
+    /// `test dst, src`
+    pub fn test(&mut self, dst: Host, src: Host) {
+        self.rex(false, src, dst);
+        self.code.push(0x85);
+        self.modrm_reg(src.0, dst);
+    }
+
+    /// `not dst`
+    pub fn not(&mut self, dst: Host) {
+        self.rex(false, Host(0), dst);
+        self.code.push(0xf7);
+        self.modrm_reg(2, dst);
+    }
+
+    /// `imul dst, src`
+    pub fn imul(&mut self, dst: Host, src: Host) {
+        self.rex(false, dst, src);
+        self.code.extend_from_slice(&[0x0f, 0xaf]);
+        self.modrm_reg(dst.0, src);
+    }
+
+    /// `op dst, amount`
+    pub fn shift(&mut self, op: Shift, dst: Host, amount: u8) {
+        self.rex(false, Host(0), dst);
+        self.code.push(0xc1);
+        self.modrm_reg(op as u8, dst);
+        self.code.push(amount);
+    }
+
+    /// `cmc`, complement the carry flag
+    pub fn cmc(&mut self) {
+        self.code.push(0xf5);
+    }
+
+    /// `pushfq`
+    pub fn pushf(&mut self) {
+        self.code.push(0x9c);
+    }
+
+    /// `popfq`
+    pub fn popf(&mut self) {
+        self.code.push(0x9d);
+    }
+
+    /// `push reg`
+    pub fn push(&mut self, reg: Host) {
+        self.rex(false, Host(0), reg);
+        self.code.push(0x50 + (reg.0 & 7));
+    }
+
+    /// `pop reg`
+    pub fn pop(&mut self, reg: Host) {
+        self.rex(false, Host(0), reg);
+        self.code.push(0x58 + (reg.0 & 7));
+    }
+
+    /// `pop qword [r15 + offset]`
+    pub fn pop_store(&mut self, offset: usize) {
+        self.rex(false, Host(0), R15);
+        self.code.push(0x8f);
+        self.modrm_state(0, offset);
+    }
+
+    /// `ret`
+    pub fn ret(&mut self) {
+        self.code.push(0xc3);
+    }
+
+    /// `jcc` to a label bound later
+    pub fn jcc(&mut self, cc: Jcc) -> Label {
+        self.code.extend_from_slice(&[0x0f, 0x80 | cc as u8]);
+        self.imm32(0);
+        Label(self.code.len())
+    }
+
+    /// `jmp` to a label bound later
+    pub fn jmp(&mut self) -> Label {
+        self.code.push(0xe9);
+        self.imm32(0);
+        Label(self.code.len())
+    }
+
+    /// Point the jump of the label to the current position
+    pub fn bind(&mut self, label: &Label) {
+        let rel = (self.code.len() - label.0) as u32;
+        self.code[label.0 - 4..label.0].copy_from_slice(&rel.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encodings() {
+        // Arrange
+        let mut asm = Assembler::default();
+
+        // Act
+        asm.alu(Alu::Add, EBX, EDX);
+        asm.mov(ECX, EBX);
+        asm.mov(R11, R9);
+        asm.alu_imm(Alu::Sub, R11, 1);
+        asm.load(R12, 48);
+        asm.mov64(R15, EDI);
+        asm.imul(R11, ESI);
+        asm.shift(Shift::Shl, R11, 3);
+        asm.push(R10);
+
+        // Assert
+        assert_eq!(
+            asm.code,
+            [
+                0x01, 0xd3, // add ebx, edx
+                0x89, 0xd9, // mov ecx, ebx
+                0x45, 0x89, 0xcb, // mov r11d, r9d
+                0x41, 0x81, 0xeb, 0x01, 0x00, 0x00, 0x00, // sub r11d, 1
+                0x45, 0x8b, 0x67, 0x30, // mov r12d, [r15 + 48]
+                0x49, 0x89, 0xff, // mov r15, rdi
+                0x44, 0x0f, 0xaf, 0xde, // imul r11d, esi
+                0x41, 0xc1, 0xe3, 0x03, // shl r11d, 3
+                0x41, 0x52, // push r10
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//!
+//! Executable memory for the generated code
+//!
+
+use std::ptr;
+
+/// Size of a code arena, new arenas are mapped as the previous ones fill up
+const ARENA_SIZE: usize = 1024 * 1024;
+
+struct Arena {
+    base: *mut u8,
+    used: usize,
+}
+
+///
+/// Code memory, writable only while new code is copied in and executable
+/// otherwise
+///
+#[derive(Default)]
+pub struct ExecutableMemory {
+    arenas: Vec<Arena>,
+}
+
+impl ExecutableMemory {
+    ///
+    /// Copy the code into executable memory, returns the address of its
+    /// first byte
+    ///
+    pub fn push(&mut self, code: &[u8]) -> *const u8 {
+        assert!(code.len() <= ARENA_SIZE);
+        let full = self
+            .arenas
+            .last()
+            .is_none_or(|arena| arena.used + code.len() > ARENA_SIZE);
+        if full {
+            self.arenas.push(Arena::map());
+        }
+        let arena = self.arenas.last_mut().unwrap();
+
+        // SAFETY: the arena is a private mapping of ARENA_SIZE bytes and the
+        // code fits in its unused part
+        unsafe {
+            arena.protect(libc::PROT_READ | libc::PROT_WRITE);
+            let start = arena.base.add(arena.used);
+            ptr::copy_nonoverlapping(code.as_ptr(), start, code.len());
+            arena.protect(libc::PROT_READ | libc::PROT_EXEC);
+            arena.used += code.len();
+            start
+        }
+    }
+
+    /// Number of bytes of code in the memory
+    #[cfg(test)]
+    pub fn used(&self) -> usize {
+        self.arenas.iter().map(|arena| arena.used).sum()
+    }
+}
+
+impl Arena {
+    fn map() -> Self {
+        // SAFETY: anonymous private mapping, no file or existing memory involved
+        let base = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                ARENA_SIZE,
+                libc::PROT_READ | libc::PROT_EXEC,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        assert!(base != libc::MAP_FAILED, "mapping JIT code memory failed");
+        Self {
+            base: base.cast(),
+            used: 0,
+        }
+    }
+
+    unsafe fn protect(&self, protection: libc::c_int) {
+        let result = libc::mprotect(self.base.cast(), ARENA_SIZE, protection);
+        assert!(result == 0, "changing JIT code memory protection failed");
+    }
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        // SAFETY: unmaps the mapping made in map(), no code of it runs any more
+        unsafe {
+            libc::munmap(self.base.cast(), ARENA_SIZE);
+        }
+    }
+}
@@ -0,0 +1,684 @@
+//!
+//! Native x86-64 execution of translated blocks
+//!
+//! The data processing instructions and branches at the start of a
+//! translated block are compiled to host code, the rest of the block runs in
+//! the interpreter. See `doc/jit.md` for the register and flag mapping.
+//!
+//! The module is built only on x86-64 unix hosts, elsewhere the `jit`
+//! feature has no effect and all blocks are interpreted.
+//!
+
+mod assembler;
+mod memory;
+
+use crate::core::bits::Bits;
+use crate::core::condition::Condition;
+use crate::core::instruction::{Imm32Carry, Instruction, SetFlags};
+use crate::core::instruction_cache::CachedInstruction;
+use crate::core::register::{BaseReg, Reg};
+use crate::peripheral::mpu::{MemoryAccess, MemoryProtectionUnit, MPU_CTRL_ENABLE};
+use crate::Processor;
+use assembler::{
+    Alu, Assembler, Host, Jcc, Label, Shift, EAX, EBX, ECX, EDI, EDX, ESI, R10, R11, R12, R13, R15,
+    R8, R9,
+};
+use memory::ExecutableMemory;
+use std::collections::HashMap;
+use std::mem::offset_of;
+use std::rc::Rc;
+
+/// Host registers of the Thumb registers. r8-r11, SP and LR stay in the
+/// processor state, as the compiled instructions do not use them.
+const REGISTERS: [(Reg, Host); 9] = [
+    (Reg::R0, EAX),
+    (Reg::R1, EBX),
+    (Reg::R2, ECX),
+    (Reg::R3, EDX),
+    (Reg::R4, ESI),
+    (Reg::R5, EDI),
+    (Reg::R6, R8),
+    (Reg::R7, R9),
+    (Reg::R12, R12),
+];
+
+/// Scratch register for the operation results
+const RESULT: Host = R11;
+/// Scratch registers for the flags merging
+const SAVED_FLAGS: Host = R10;
+const NEW_FLAGS: Host = R13;
+/// Pointer to the `JitState`
+const STATE: Host = R15;
+
+const CF: u32 = 1 << 0;
+const ZF: u32 = 1 << 6;
+const SF: u32 = 1 << 7;
+const OF: u32 = 1 << 11;
+const NZ: u32 = SF | ZF;
+const NZCV: u32 = SF | ZF | CF | OF;
+
+///
+/// Registers exchanged with the generated code. PC and the cycle count are
+/// written at the exit of the code.
+///
+#[repr(C)]
+struct JitState {
+    r0_12: [u32; 13],
+    pc: u32,
+    flags: u64,
+    cycles: u32,
+}
+
+type NativeCode = unsafe extern "C" fn(*mut JitState);
+
+#[derive(Copy, Clone)]
+struct NativeBlock {
+    code: NativeCode,
+    /// Number of compiled instructions from the start of the block
+    instructions: usize,
+}
+
+///
+/// Compiled blocks by their entry address. A block is compiled again when
+/// the translated block it was compiled from has been replaced. All code is
+/// freed when translated blocks are dropped, the blocks still in use are
+/// compiled again.
+///
+#[derive(Default)]
+pub struct Jit {
+    memory: ExecutableMemory,
+    blocks: HashMap<u32, (Rc<[CachedInstruction]>, Option<NativeBlock>)>,
+}
+
+impl Jit {
+    /// Free all the compiled code
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn lookup(&mut self, pc: u32, instructions: &Rc<[CachedInstruction]>) -> Option<NativeBlock> {
+        match self.blocks.get(&pc) {
+            Some((source, native)) if Rc::ptr_eq(source, instructions) => *native,
+            _ => {
+                let native = compile(pc, instructions).map(|(code, count)| {
+                    let code = self.memory.push(&code);
+                    NativeBlock {
+                        // SAFETY: the code follows the C calling convention,
+                        // taking the state pointer as the only argument
+                        code: unsafe { std::mem::transmute::<*const u8, NativeCode>(code) },
+                        instructions: count,
+                    }
+                });
+                self.blocks.insert(pc, (Rc::clone(instructions), native));
+                native
+            }
+        }
+    }
+}
+
+/// x86-64 flags (SF, ZF, CF, OF) from the APSR N, Z, C and V bits
+fn host_flags(psr: u32) -> u64 {
+    let flag = |bit: usize, host: u32| if psr.get_bit(bit) { host } else { 0 };
+    u64::from(flag(31, SF) | flag(30, ZF) | flag(29, CF) | flag(28, OF))
+}
+
+/// APSR with N, Z, C and V bits from the x86-64 flags
+fn apsr_flags(psr: u32, flags: u64) -> u32 {
+    let flag = |host: u32, bit: u32| {
+        if flags & u64::from(host) == 0 {
+            0
+        } else {
+            1 << bit
+        }
+    };
+    (psr & 0x0fff_ffff) | flag(SF, 31) | flag(ZF, 30) | flag(CF, 29) | flag(OF, 28)
+}
+
+fn host(reg: Reg) -> Option<Host> {
+    REGISTERS
+        .iter()
+        .find(|&&(thumb, _)| thumb == reg)
+        .map(|&(_, host)| host)
+}
+
+fn sets(setflags: SetFlags) -> bool {
+    // the compiled code never runs in an IT block
+    setflags != SetFlags::False
+}
+
+/// Second operand of an operation
+#[derive(Copy, Clone)]
+enum Operand {
+    Reg(Host),
+    Imm(u32),
+}
+
+/// Register operand with no shift
+fn unshifted(reg: Reg, shift_n: u8) -> Option<Operand> {
+    (shift_n == 0).then_some(())?;
+    host(reg).map(Operand::Reg)
+}
+
+/// Immediate operand, the carry out of the immediate is not supported
+fn immediate(imm32: &Imm32Carry, setflags: bool) -> Option<Operand> {
+    match imm32 {
+        Imm32Carry::NoCarry { imm32 } => Some(Operand::Imm(*imm32)),
+        Imm32Carry::Carry { imm32_c0, .. } if !setflags => Some(Operand::Imm(imm32_c0.0)),
+        Imm32Carry::Carry { .. } => None,
+    }
+}
+
+#[derive(Default)]
+struct Compiler {
+    asm: Assembler,
+    exits: Vec<Label>,
+}
+
+impl Compiler {
+    fn prologue(&mut self) {
+        for reg in [EBX, R12, R13, STATE] {
+            self.asm.push(reg);
+        }
+        // rdi, the first argument
+        self.asm.mov64(STATE, EDI);
+        for (reg, host) in REGISTERS {
+            self.asm
+                .load(host, offset_of!(JitState, r0_12) + 4 * reg as usize);
+        }
+        self.asm.pushf();
+        self.asm.pop(SAVED_FLAGS);
+        self.asm.alu_imm(Alu::And, SAVED_FLAGS, !NZCV);
+        self.asm.or_load(SAVED_FLAGS, offset_of!(JitState, flags));
+        self.asm.push(SAVED_FLAGS);
+        self.asm.popf();
+    }
+
+    fn epilogue(&mut self) {
+        for exit in std::mem::take(&mut self.exits) {
+            self.asm.bind(&exit);
+        }
+        self.asm.pushf();
+        self.asm.pop_store(offset_of!(JitState, flags));
+        for (reg, host) in REGISTERS {
+            self.asm
+                .store(offset_of!(JitState, r0_12) + 4 * reg as usize, host);
+        }
+        for reg in [STATE, R13, R12, EBX] {
+            self.asm.pop(reg);
+        }
+        self.asm.ret();
+    }
+
+    fn exit(&mut self, pc: u32, cycles: u32) {
+        self.asm.store_imm(offset_of!(JitState, pc), pc);
+        self.asm.store_imm(offset_of!(JitState, cycles), cycles);
+        let exit = self.asm.jmp();
+        self.exits.push(exit);
+    }
+
+    ///
+    /// Emit an operation updating the flags in `mask`, the other flags are
+    /// preserved
+    ///
+    fn with_flags(&mut self, mask: u32, operation: impl FnOnce(&mut Assembler)) {
+        if mask == NZCV {
+            operation(&mut self.asm);
+            return;
+        }
+        self.asm.pushf();
+        self.asm.pop(SAVED_FLAGS);
+        operation(&mut self.asm);
+        if mask != 0 {
+            self.asm.pushf();
+            self.asm.pop(NEW_FLAGS);
+            self.asm.alu_imm(Alu::And, NEW_FLAGS, mask);
+            self.asm.alu_imm(Alu::And, SAVED_FLAGS, !mask);
+            self.asm.alu(Alu::Or, SAVED_FLAGS, NEW_FLAGS);
+        }
+        self.asm.push(SAVED_FLAGS);
+        self.asm.popf();
+    }
+
+    ///
+    /// `rd = rn op operand`. The carry flag holds the Arm carry, which is the
+    /// inverse of the x86 borrow of the subtractions.
+    ///
+    fn binary(&mut self, op: Alu, rd: Option<Host>, rn: Host, operand: Operand, mask: u32) {
+        self.with_flags(mask, |asm| {
+            asm.mov(RESULT, rn);
+            if op == Alu::Sbb {
+                asm.cmc();
+            }
+            match operand {
+                Operand::Reg(rm) => asm.alu(op, RESULT, rm),
+                Operand::Imm(imm) => asm.alu_imm(op, RESULT, imm),
+            }
+            if matches!(op, Alu::Sub | Alu::Sbb | Alu::Cmp) {
+                asm.cmc();
+            }
+            if let Some(rd) = rd {
+                asm.mov(rd, RESULT);
+            }
+        });
+    }
+
+    /// `rd = operand`, with N and Z set from the result when `mask` is set
+    fn unary(&mut self, rd: Host, operand: Operand, not: bool, mask: u32) {
+        self.with_flags(mask, |asm| {
+            match operand {
+                Operand::Reg(rm) => asm.mov(RESULT, rm),
+                Operand::Imm(imm) => asm.mov_imm(RESULT, imm),
+            }
+            if not {
+                asm.not(RESULT);
+            }
+            asm.test(RESULT, RESULT);
+            asm.mov(rd, RESULT);
+        });
+    }
+
+    /// `rd = rn & !operand`
+    fn bit_clear(&mut self, rd: Host, rn: Host, operand: Operand, mask: u32) {
+        self.with_flags(mask, |asm| {
+            match operand {
+                Operand::Reg(rm) => {
+                    asm.mov(RESULT, rm);
+                    asm.not(RESULT);
+                    asm.alu(Alu::And, RESULT, rn);
+                }
+                Operand::Imm(imm) => {
+                    asm.mov(RESULT, rn);
+                    asm.alu_imm(Alu::And, RESULT, !imm);
+                }
+            }
+            asm.mov(rd, RESULT);
+        });
+    }
+
+    /// `rd = operand - rn`
+    fn reverse_subtract(&mut self, rd: Host, rn: Host, imm: u32, mask: u32) {
+        self.with_flags(mask, |asm| {
+            asm.mov_imm(RESULT, imm);
+            asm.alu(Alu::Sub, RESULT, rn);
+            asm.cmc();
+            asm.mov(rd, RESULT);
+        });
+    }
+
+    fn multiply(&mut self, rd: Host, rn: Host, rm: Host, mask: u32) {
+        self.with_flags(mask, |asm| {
+            asm.mov(RESULT, rn);
+            asm.imul(RESULT, rm);
+            asm.test(RESULT, RESULT);
+            asm.mov(rd, RESULT);
+        });
+    }
+
+    /// Shift by 1 to 31, the carry is the last bit shifted out
+    fn shift(&mut self, op: Shift, rd: Host, rm: Host, shift_n: u8, mask: u32) {
+        self.with_flags(mask, |asm| {
+            asm.mov(RESULT, rm);
+            asm.shift(op, RESULT, shift_n);
+            asm.mov(rd, RESULT);
+        });
+    }
+
+    ///
+    /// Emit a data processing instruction, `None` if it is not supported. No
+    /// code is emitted for an unsupported instruction.
+    ///
+    fn data_processing(&mut self, instruction: &Instruction) -> Option<()> {
+        let arith = |setflags| if setflags { NZCV } else { 0 };
+        let logical = |setflags| if setflags { NZ } else { 0 };
+
+        match instruction {
+            Instruction::NOP { .. } => {}
+            Instruction::MOV_imm { params, .. } => {
+                let operand = immediate(&params.imm32, sets(params.setflags))?;
+                let mask = logical(sets(params.setflags));
+                self.unary(host(params.rd)?, operand, false, mask);
+            }
+            Instruction::MOV_reg { params, .. } => {
+                let (rd, rm) = (host(params.rd)?, host(params.rm)?);
+                self.unary(rd, Operand::Reg(rm), false, logical(params.setflags));
+            }
+            Instruction::MVN_reg { params, .. } => {
+                let (rd, rm) = (host(params.rd)?, unshifted(params.rm, params.shift_n)?);
+                self.unary(rd, rm, true, logical(sets(params.setflags)));
+            }
+            Instruction::ADD_reg { params, .. }
+            | Instruction::ADC_reg { params, .. }
+            | Instruction::SUB_reg { params, .. }
+            | Instruction::SBC_reg { params, .. }
+            | Instruction::AND_reg { params, .. }
+            | Instruction::ORR_reg { params, .. }
+            | Instruction::EOR_reg { params, .. }
+            | Instruction::BIC_reg { params, .. } => {
+                let (rd, rn) = (host(params.rd)?, host(params.rn)?);
+                let rm = unshifted(params.rm, params.shift_n)?;
+                let setflags = sets(params.setflags);
+                match instruction {
+                    Instruction::ADD_reg { .. } => {
+                        self.binary(Alu::Add, Some(rd), rn, rm, arith(setflags));
+                    }
+                    Instruction::ADC_reg { .. } => {
+                        self.binary(Alu::Adc, Some(rd), rn, rm, arith(setflags));
+                    }
+                    Instruction::SUB_reg { .. } => {
+                        self.binary(Alu::Sub, Some(rd), rn, rm, arith(setflags));
+                    }
+                    Instruction::SBC_reg { .. } => {
+                        self.binary(Alu::Sbb, Some(rd), rn, rm, arith(setflags));
+                    }
+                    Instruction::AND_reg { .. } => {
+                        self.binary(Alu::And, Some(rd), rn, rm, logical(setflags));
+                    }
+                    Instruction::ORR_reg { .. } => {
+                        self.binary(Alu::Or, Some(rd), rn, rm, logical(setflags));
+                    }
+                    Instruction::EOR_reg { .. } => {
+                        self.binary(Alu::Xor, Some(rd), rn, rm, logical(setflags));
+                    }
+                    _ => self.bit_clear(rd, rn, rm, logical(setflags)),
+                }
+            }
+            Instruction::ADD_imm { params, .. } | Instruction::SUB_imm { params, .. } => {
+                let (rd, rn) = (host(params.rd)?, host(params.rn)?);
+                let op = if matches!(instruction, Instruction::ADD_imm { .. }) {
+                    Alu::Add
+                } else {
+                    Alu::Sub
+                };
+                let mask = arith(sets(params.setflags));
+                self.binary(op, Some(rd), rn, Operand::Imm(params.imm32), mask);
+            }
+            Instruction::RSB_imm { params, .. } => {
+                let (rd, rn) = (host(params.rd)?, host(params.rn)?);
+                self.reverse_subtract(rd, rn, params.imm32, arith(sets(params.setflags)));
+            }
+            Instruction::AND_imm { params }
+            | Instruction::ORR_imm { params }
+            | Instruction::EOR_imm { params }
+            | Instruction::BIC_imm { params } => {
+                let (rd, rn) = (host(params.rd)?, host(params.rn)?);
+                let imm = immediate(&params.imm32, params.setflags)?;
+                let mask = logical(params.setflags);
+                match instruction {
+                    Instruction::AND_imm { .. } => self.binary(Alu::And, Some(rd), rn, imm, mask),
+                    Instruction::ORR_imm { .. } => self.binary(Alu::Or, Some(rd), rn, imm, mask),
+                    Instruction::EOR_imm { .. } => self.binary(Alu::Xor, Some(rd), rn, imm, mask),
+                    _ => self.bit_clear(rd, rn, imm, mask),
+                }
+            }
+            Instruction::CMP_imm { params, .. } => {
+                self.binary(
+                    Alu::Cmp,
+                    None,
+                    host(params.r)?,
+                    Operand::Imm(params.imm32),
+                    NZCV,
+                );
+            }
+            Instruction::CMN_imm { params } => {
+                self.binary(
+                    Alu::Add,
+                    None,
+                    host(params.r)?,
+                    Operand::Imm(params.imm32),
+                    NZCV,
+                );
+            }
+            Instruction::CMP_reg { params, .. }
+            | Instruction::CMN_reg { params, .. }
+            | Instruction::TST_reg { params, .. } => {
+                let rn = host(params.rn)?;
+                let rm = unshifted(params.rm, params.shift_n)?;
+                match instruction {
+                    Instruction::CMP_reg { .. } => self.binary(Alu::Cmp, None, rn, rm, NZCV),
+                    Instruction::CMN_reg { .. } => self.binary(Alu::Add, None, rn, rm, NZCV),
+                    _ => self.binary(Alu::And, None, rn, rm, NZ),
+                }
+            }
+            Instruction::MUL { params, .. } => {
+                let (rd, rn, rm) = (host(params.rd)?, host(params.rn)?, host(params.rm)?);
+                self.multiply(rd, rn, rm, logical(sets(params.setflags)));
+            }
+            Instruction::LSL_imm { params, .. }
+            | Instruction::LSR_imm { params, .. }
+            | Instruction::ASR_imm { params, .. } => {
+                let (rd, rm) = (host(params.rd)?, host(params.rm)?);
+                let setflags = sets(params.setflags);
+                let op = match instruction {
+                    Instruction::LSL_imm { .. } if params.shift_n == 0 => {
+                        self.unary(rd, Operand::Reg(rm), false, logical(setflags));
+                        return Some(());
+                    }
+                    Instruction::LSL_imm { .. } => Shift::Shl,
+                    Instruction::LSR_imm { .. } => Shift::Shr,
+                    _ => Shift::Sar,
+                };
+                // x86 shifts are limited to 31 bits
+                (1..32).contains(&params.shift_n).then_some(())?;
+                let mask = if setflags { NZ | CF } else { 0 };
+                self.shift(op, rd, rm, params.shift_n, mask);
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    /// Emit jumps to the returned labels when the condition passes
+    fn condition(&mut self, cond: Condition) -> Vec<Label> {
+        let jcc = match cond {
+            Condition::EQ => Jcc::E,
+            Condition::NE => Jcc::NE,
+            Condition::CS => Jcc::B,
+            Condition::CC => Jcc::AE,
+            Condition::MI => Jcc::S,
+            Condition::PL => Jcc::NS,
+            Condition::VS => Jcc::O,
+            Condition::VC => Jcc::NO,
+            Condition::GE => Jcc::GE,
+            Condition::LT => Jcc::L,
+            Condition::GT => Jcc::G,
+            Condition::LE => Jcc::LE,
+            Condition::HI => {
+                let not_taken = self.asm.jcc(Jcc::AE);
+                let taken = self.asm.jcc(Jcc::NE);
+                self.asm.bind(&not_taken);
+                return vec![taken];
+            }
+            Condition::LS => return vec![self.asm.jcc(Jcc::AE), self.asm.jcc(Jcc::E)],
+            Condition::AL => return vec![self.asm.jmp()],
+        };
+        vec![self.asm.jcc(jcc)]
+    }
+}
+
+///
+/// Compile the instructions at the start of the block at `pc`. Returns the
+/// code and the number of compiled instructions, `None` if the first
+/// instruction is not supported.
+///
+fn compile(pc: u32, instructions: &[CachedInstruction]) -> Option<(Vec<u8>, usize)> {
+    let mut compiler = Compiler::default();
+    compiler.prologue();
+
+    let mut address = pc;
+    let mut cycles = 0;
+    for (count, (instruction, instruction_size)) in instructions.iter().enumerate() {
+        let next = address.wrapping_add(*instruction_size as u32);
+        let branch = match instruction {
+            Instruction::B_t13 { params, .. } => Some((params.cond, params.imm32)),
+            Instruction::B_t24 { imm32, .. } => Some((Condition::AL, *imm32)),
+            _ => None,
+        };
+
+        if let Some((cond, imm32)) = branch {
+            let target = address.wrapping_add(4).wrapping_add(imm32 as u32);
+            let taken = compiler.condition(cond);
+            compiler.exit(next, cycles + 1);
+            for label in taken {
+                compiler.asm.bind(&label);
+            }
+            compiler.exit(target, cycles + 3);
+            compiler.epilogue();
+            return Some((compiler.asm.code, count + 1));
+        }
+
+        if compiler.data_processing(instruction).is_none() {
+            if count == 0 {
+                return None;
+            }
+            compiler.exit(address, cycles);
+            compiler.epilogue();
+            return Some((compiler.asm.code, count));
+        }
+        cycles += 1;
+        address = next;
+    }
+
+    compiler.exit(address, cycles);
+    compiler.epilogue();
+    Some((compiler.asm.code, instructions.len()))
+}
+
+impl Processor {
+    ///
+    /// Run the compiled start of the block at `pc` natively, if at most
+    /// `max_instructions` long. Returns the number of instructions executed.
+    ///
+    pub(crate) fn execute_native(
+        &mut self,
+        pc: u32,
+        instructions: &Rc<[CachedInstruction]>,
+        max_instructions: u64,
+    ) -> usize {
+        // the compiled code does no fetch checks and has no IT state
+        if self.itstate != 0
+            || self.mpu_ctrl.get_bit(MPU_CTRL_ENABLE)
+            || self.profile.has_security()
+        {
+            return 0;
+        }
+        let native = match self.jit.lookup(pc, instructions) {
+            Some(native) if native.instructions as u64 <= max_instructions => native,
+            _ => return 0,
+        };
+        if self.mpu_check(pc, MemoryAccess::InstructionFetch).is_err() {
+            return 0;
+        }
+
+        let mut state = JitState {
+            r0_12: self.r0_12,
+            pc,
+            flags: host_flags(self.psr.value),
+            cycles: 0,
+        };
+        // SAFETY: the code only accesses the state and the host registers it saves
+        unsafe { (native.code)(&raw mut state) };
+
+        self.r0_12 = state.r0_12;
+        self.set_pc(state.pc);
+        self.psr.value = apsr_flags(self.psr.value, state.flags);
+        self.instruction_count += native.instructions as u64;
+        self.block_cycles += state.cycles;
+        native.instructions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::executor::Executor;
+
+    #[test]
+    fn test_flags_conversion() {
+        // Arrange
+        let psr = 0b1010 << 28 | 0x0100_0000;
+
+        // Act
+        let flags = host_flags(psr);
+
+        // Assert
+        assert_eq!(flags, u64::from(SF | CF));
+        assert_eq!(apsr_flags(0x0100_0000, flags), psr);
+        assert_eq!(
+            apsr_flags(psr, u64::from(ZF | OF)),
+            0b0101 << 28 | 0x0100_0000
+        );
+    }
+
+    #[test]
+    fn test_native_execution_matches_interpreter() {
+        let run = |block: bool| {
+            let mut core = Processor::new();
+            core.fault_trap(true);
+            // movs r0, #0; movs r1, #10; str r1, [r6]; movs r2, #3
+            // loop: adds r0, r0, r1; eors r2, r0; lsls r3, r2, #3; sbcs r3, r1;
+            // muls r3, r2; asrs r4, r3, #1; subs r1, #1; bhi loop; b .
+            for (i, opcode) in [
+                0x2000, 0x210a, 0x6031, 0x2203, 0x1840, 0x4042, 0x00d3, 0x418b, 0x4353, 0x105c,
+                0x3901, 0xd8f7, 0xe7fe,
+            ]
+            .iter()
+            .enumerate()
+            {
+                core.write16(0x2000_0000 + 2 * i as u32, *opcode).unwrap();
+            }
+            core.set_r(Reg::R6, 0x2000_1000);
+            core.set_pc(0x2000_0000);
+            while core.get_pc() != 0x2000_0018 {
+                if block {
+                    core.step_block(u64::MAX);
+                } else {
+                    core.step();
+                }
+            }
+            core
+        };
+
+        // Act
+        let single = run(false);
+        let native = run(true);
+
+        // Assert
+        assert_eq!(
+            native
+                .jit
+                .blocks
+                .values()
+                .filter(|(_, n)| n.is_some())
+                .count(),
+            2
+        );
+        assert_eq!(native.r0_12, single.r0_12);
+        assert_eq!(native.psr.value, single.psr.value);
+        assert_eq!(native.instruction_count, single.instruction_count);
+        assert_eq!(native.cycle_count, single.cycle_count);
+        assert_eq!(native.get_r(Reg::R0), 55);
+    }
+
+    #[test]
+    fn test_patched_code_memory_is_bounded() {
+        // Arrange: movs r0, #0; b .
+        let mut core = Processor::new();
+        core.fault_trap(true);
+        core.write16(0x2000_0002, 0xe7fe).unwrap();
+        let mut used = None;
+
+        for value in 0..1000_u16 {
+            // Act: patch the immediate and run the block again
+            core.write16(0x2000_0000, 0x2000 | (value & 0xff)).unwrap();
+            core.set_pc(0x2000_0000);
+            core.step_block(u64::MAX);
+
+            // Assert: the code of the replaced block has been freed
+            assert_eq!(core.get_r(Reg::R0), u32::from(value & 0xff));
+            assert_eq!(core.jit.blocks.len(), 1);
+            let size = *used.get_or_insert(core.jit.memory.used());
+            assert_eq!(core.jit.memory.used(), size);
+        }
+    }
+}
@@ -38,21 +38,31 @@ pub mod peripheral;
 pub mod semihosting;
 pub mod system;
 pub mod gdb;
+#[cfg(all(feature = "jit", target_arch = "x86_64", unix))]
+pub mod jit;
 
-use crate::core::instruction::instruction_size;
+use crate::bus::Bus;
 
-use crate::core::exception::Exception;
-use crate::core::fetch::Fetch;
+use crate::core::block_cache::BlockCache;
+use crate::core::exception::{Exception, LockupReason};
+use crate::core::fault::Fault;
 use crate::core::instruction::Instruction;
+use crate::core::instruction_cache::InstructionCache;
+use crate::core::profile::ArchProfile;
 use crate::core::register::{Apsr, BaseReg, Control, Reg, PSR};
+use crate::core::security::{BankedPending, BankedRegisters};
 
 use crate::memory::flash::FlashMemory;
-use crate::memory::map::MemoryMapConfig;
+use crate::memory::image::{merge_segments, Segment};
+use crate::memory::map::{MapMemory, MemoryMapConfig};
 use crate::memory::ram::RAM;
+use crate::peripheral::mpu::MPU_REGIONS;
+use crate::peripheral::sau::{IdauRegion, SAU_REGIONS};
 use crate::semihosting::SemihostingCommand;
 use crate::semihosting::SemihostingResponse;
 
 use crate::core::exception::ExceptionState;
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::fmt;
 use std::io;
@@ -60,9 +70,8 @@ use std::io;
 #[cfg(feature = "stm32f103")]
 use crate::device::stm32f1xx::Device;
 
-#[cfg(feature = "generic-device")]
+#[cfg(not(feature = "stm32f103"))]
 use crate::device::generic::Device;
-use decoder::Decoder;
 
 #[derive(PartialEq, Debug, Copy, Clone)]
 /// Main execution mode of the processor
@@ -89,6 +98,10 @@ pub struct Processor {
     pub msp: u32,
     /// PSP, virtual reg r[13]
     pub psp: u32,
+    /// Armv8-M main stack limit
+    pub msplim: u32,
+    /// Armv8-M process stack limit
+    pub psplim: u32,
     pub lr: u32,
     pc: u32,
 
@@ -96,6 +109,12 @@ pub struct Processor {
     pub cycle_count: u64,
     pub instruction_count: u64,
 
+    /// Number of clock cycles the processor has been sleeping
+    pub sleep_cycle_count: u64,
+
+    /// Number of semihosting calls made
+    pub semihost_call_count: u64,
+
     /// Processor state register, status flags.
     pub psr: PSR,
 
@@ -107,9 +126,8 @@ pub struct Processor {
 
     ///
     /// interrupt fault mask, a 1 bit mask register for
-    /// global interrupt masking
+    /// global interrupt masking. Always false on Armv6-M.
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     faultmask: bool,
 
     ///
@@ -127,6 +145,21 @@ pub struct Processor {
     ///
     mode: ProcessorMode,
 
+    ///
+    /// Security state, Secure on reset when the security extension is present
+    ///
+    secure: bool,
+
+    ///
+    /// stack pointers, masks, CONTROL and VTOR of the other security state
+    ///
+    banked: BankedRegisters,
+
+    ///
+    /// `PendSV` and `SysTick` pending for each security state
+    ///
+    banked_pending: BankedPending,
+
     ///
     /// processor simulation state
     ///
@@ -153,14 +186,29 @@ pub struct Processor {
 
     itstate: u8,
 
+    ///
+    /// local exclusive monitor: address and size of the LDREX reservation,
+    /// `None` in the open access state
+    ///
+    exclusive_monitor: Option<(u32, usize)>,
+
+    ///
+    /// architecture profile: available instructions, registers and exceptions
+    ///
+    profile: ArchProfile,
+
     ///
     /// flash memory data
     ///
     pub code: FlashMemory,
     ///
-    /// ram data
+    /// additional flash regions outside of the boot flash
+    ///
+    pub flash_regions: Vec<FlashMemory>,
     ///
-    pub sram: RAM,
+    /// ram banks
+    ///
+    pub sram: Vec<RAM>,
 
     pub cpuid: u32,
     pub icsr: u32,
@@ -186,11 +234,29 @@ pub struct Processor {
     pub mvfr1: u32,
     pub mvfr2: u32,
 
+    pub mpu_ctrl: u32,
+    pub mpu_rnr: u32,
+    pub mpu_rbar: [u32; MPU_REGIONS],
+    pub mpu_rasr: [u32; MPU_REGIONS],
+
+    pub sau_ctrl: u32,
+    pub sau_rnr: u32,
+    pub sau_rbar: [u32; SAU_REGIONS],
+    pub sau_rlar: [u32; SAU_REGIONS],
+    pub sfsr: u32,
+    pub sfar: u32,
+
+    ///
+    /// fixed security attribution of the device
+    ///
+    idau: Vec<IdauRegion>,
+
     pub ictr: u32,
     pub actlr: u32,
 
     pub nvic_interrupt_enabled: [u32; 16],
     pub nvic_interrupt_pending: [u32; 16],
+    pub nvic_interrupt_target_ns: [u32; 16],
 
     pub dwt_ctrl: u32,
     pub dwt_cyccnt: u32,
@@ -198,6 +264,8 @@ pub struct Processor {
     pub syst_rvr: u32,
     pub syst_cvr: u32,
     pub syst_csr: u32,
+    /// `SysTick` belongs to the Secure state and pends the Secure `SysTick`
+    syst_secure: bool,
 
     ///
     /// file handle to which to write ITM data
@@ -209,16 +277,62 @@ pub struct Processor {
     ///
     semihost_func: SemihostingCall,
 
-    instruction_cache: Vec<(Instruction, usize)>,
+    instruction_cache: InstructionCache,
+
+    ///
+    /// translated blocks of the block execution engine
+    ///
+    block_cache: BlockCache,
+
+    ///
+    /// cycles of the instructions executed in the current block, not yet
+    /// counted to the cycle counters
+    ///
+    block_cycles: u32,
+
+    ///
+    /// native code compiled from the translated blocks
+    ///
+    #[cfg(all(feature = "jit", target_arch = "x86_64", unix))]
+    jit: crate::jit::Jit,
 
     pub last_pc: u32,
 
+    ///
+    /// stop the processor on the first fault instead of taking the fault exception
+    ///
+    fault_trap: bool,
+
+    ///
+    /// fault and the faulting instruction that stopped the processor in fault trap mode,
+    /// no instruction for faults on the instruction fetch
+    ///
+    pub trapped_fault: Option<(Fault, Option<Instruction>)>,
+
+    ///
+    /// reason and PC of the Lockup state that stopped the processor
+    ///
+    pub lockup: Option<(LockupReason, u32)>,
+
+    ///
+    /// address of the last failed bus access, source for MMFAR and BFAR
+    ///
+    fault_address: Cell<u32>,
+
     mem_map: Option<MemoryMapConfig>,
 
-    pub device: Device,
+    ///
+    /// additional address aliases, resolved before the flash mapping
+    ///
+    aliases: Vec<MemoryMapConfig>,
+
+    ///
+    /// peripheral device models on the bus
+    ///
+    pub devices: Vec<Box<dyn Bus>>,
 }
 
-fn make_default_exception_priorities() -> HashMap<usize, ExceptionState> {
+fn make_exception_priorities(irq_count: usize) -> HashMap<usize, ExceptionState> {
     let mut priorities = HashMap::new();
 
     priorities.insert(
@@ -249,6 +363,11 @@ fn make_default_exception_priorities() -> HashMap<usize, ExceptionState> {
         ExceptionState::new(Exception::UsageFault, 0),
     );
 
+    priorities.insert(
+        Exception::SecureFault.into(),
+        ExceptionState::new(Exception::SecureFault, 0),
+    );
+
     priorities.insert(
         Exception::DebugMonitor.into(),
         ExceptionState::new(Exception::DebugMonitor, 0),
@@ -269,7 +388,7 @@ fn make_default_exception_priorities() -> HashMap<usize, ExceptionState> {
         ExceptionState::new(Exception::SysTick, 0),
     );
 
-    for irqn in 0..32 {
+    for irqn in 0..irq_count {
         let irq = Exception::Interrupt { n: irqn };
         priorities.insert(irq.into(), ExceptionState::new(irq, 0));
     }
@@ -277,6 +396,11 @@ fn make_default_exception_priorities() -> HashMap<usize, ExceptionState> {
     priorities
 }
 
+///
+/// Segments closer to each other than this are placed to the same flash region
+///
+const MAX_FLASH_SEGMENT_GAP: u32 = 0x1_0000;
+
 type SemihostingStaticCall =
     Option<Box<dyn FnMut(&SemihostingCommand) -> SemihostingResponse + 'static>>;
 
@@ -285,39 +409,50 @@ impl Processor {
     /// Create processor with default data
     ///
     pub fn new() -> Self {
+        let profile = ArchProfile::default();
+        let (mvfr0, mvfr1, mvfr2) = profile.mvfr();
         Self {
             mode: ProcessorMode::ThreadMode,
+            secure: profile.has_security(),
+            banked: BankedRegisters::new(),
+            banked_pending: BankedPending::default(),
             vtor: 0,
             psr: PSR { value: 0 },
             primask: false,
-            #[cfg(any(feature = "armv7m", feature = "armv7em"))]
             faultmask: false,
             basepri: 0,
             control: Control {
                 n_priv: false,
                 sp_sel: false,
+                fpca: false,
             },
             r0_12: [0; 13],
             fp_regs: [0; 32],
             pc: 0,
             msp: 0,
             psp: 0,
+            msplim: 0,
+            psplim: 0,
             lr: 0,
             code: FlashMemory::new(65536, &[0; 65536]),
-            // TODO make RAM size configurable
-            sram: RAM::new_with_fill(0x2000_0000, 128 * 1024, 0xcd),
+            flash_regions: Vec::new(),
+            sram: vec![RAM::new_with_fill(0x2000_0000, 128 * 1024, 0xcd)],
             itm_file: None,
             sleeping: false,
             running: true,
             exit_code: 0,
             cycle_count: 0,
             instruction_count: 0,
-            exceptions: make_default_exception_priorities(),
+            sleep_cycle_count: 0,
+            semihost_call_count: 0,
+            exceptions: make_exception_priorities(32),
             execution_priority: 0,
             pending_exception_count: 0,
             itstate: 0,
+            exclusive_monitor: None,
+            profile,
             semihost_func: None,
-            cpuid: 0,
+            cpuid: profile.cpuid(),
             icsr: 0,
             aircr: 0,
             scr: 0,
@@ -335,9 +470,22 @@ impl Processor {
             fpcar: 0,
             fpdscr: 0,
             fpscr: 0,
-            mvfr0: 0,
-            mvfr1: 0,
-            mvfr2: 0,
+            mvfr0,
+            mvfr1,
+            mvfr2,
+
+            mpu_ctrl: 0,
+            mpu_rnr: 0,
+            mpu_rbar: [0; MPU_REGIONS],
+            mpu_rasr: [0; MPU_REGIONS],
+
+            sau_ctrl: 0,
+            sau_rnr: 0,
+            sau_rbar: [0; SAU_REGIONS],
+            sau_rlar: [0; SAU_REGIONS],
+            sfsr: 0,
+            sfar: 0,
+            idau: Vec::new(),
 
             ictr: 0,
             actlr: 0,
@@ -347,28 +495,148 @@ impl Processor {
 
             nvic_interrupt_enabled: [0; 16],
             nvic_interrupt_pending: [0; 16],
+            nvic_interrupt_target_ns: [0; 16],
             syst_rvr: 0,
             syst_cvr: 0,
             syst_csr: 0,
-            instruction_cache: Vec::new(),
+            syst_secure: false,
+            instruction_cache: InstructionCache::default(),
+            block_cache: BlockCache::default(),
+            block_cycles: 0,
+            #[cfg(all(feature = "jit", target_arch = "x86_64", unix))]
+            jit: crate::jit::Jit::default(),
             last_pc: 0,
+            fault_trap: false,
+            trapped_fault: None,
+            lockup: None,
+            fault_address: Cell::new(0),
             mem_map: None,
-            device: Device::new(),
+            aliases: Vec::new(),
+            devices: vec![Box::new(Device::new())],
         }
     }
 
+    ///
+    /// Select the architecture profile. Also sets the CPUID and the
+    /// floating point feature registers to match the profile.
+    ///
+    pub fn profile(&mut self, profile: ArchProfile) -> &mut Self {
+        self.profile = profile;
+        self.secure = profile.has_security();
+        self.cpuid = profile.cpuid();
+        (self.mvfr0, self.mvfr1, self.mvfr2) = profile.mvfr();
+        self
+    }
+
+    /// Architecture profile of the processor
+    pub fn arch_profile(&self) -> ArchProfile {
+        self.profile
+    }
+
     /// Configure flash memory
     pub fn flash_memory<'a>(&'a mut self, flash_size: usize, code: &[u8]) -> &'a mut Self {
         self.code = FlashMemory::new(flash_size, code);
         self
     }
 
+    ///
+    /// Configure RAM banks as (start address, size) pairs. Replaces the
+    /// default 128 KiB of RAM at `0x2000_0000`.
+    ///
+    pub fn ram_memory(&mut self, banks: &[(u32, usize)]) -> &mut Self {
+        self.sram = banks
+            .iter()
+            .map(|&(start_address, size)| RAM::new_with_fill(start_address, size, 0xcd))
+            .collect();
+        self
+    }
+
+    /// Add a flash region with given start address and content
+    pub fn flash_region(&mut self, start_address: u32, code: &[u8]) -> &mut Self {
+        self.flash_regions.push(FlashMemory::new_with_start_address(
+            start_address,
+            code.len(),
+            code,
+        ));
+        self
+    }
+
+    ///
+    /// Load the content of an executable image. Segments that fit to RAM are
+    /// copied there, the rest are placed to flash regions. The lowest flash
+    /// region is used as the boot flash, aliased to address 0.
+    ///
+    pub fn load_image(&mut self, segments: &[Segment]) -> &mut Self {
+        let mut flash_segments = Vec::new();
+
+        for segment in segments.iter().filter(|s| !s.data.is_empty()) {
+            let last_address = (segment.end_address() - 1) as u32;
+            if let Some(bank) = self
+                .sram
+                .iter_mut()
+                .find(|b| b.in_range(segment.address) && b.in_range(last_address))
+            {
+                bank.load(segment.address, &segment.data);
+            } else {
+                flash_segments.push(segment);
+            }
+        }
+
+        let mut regions = merge_segments(&flash_segments, MAX_FLASH_SEGMENT_GAP).into_iter();
+
+        if let Some(boot) = regions.next() {
+            self.memory_map(if boot.address == 0 {
+                None
+            } else {
+                Some(MemoryMapConfig::new(boot.address, 0, boot.data.len()))
+            });
+            self.flash_memory(boot.data.len(), &boot.data);
+        }
+
+        for region in regions {
+            self.flash_region(region.address, &region.data);
+        }
+        self
+    }
+
     /// Configure memory mapping
     pub fn memory_map(&mut self, map: Option<MemoryMapConfig>) -> &mut Self {
         self.mem_map = map;
         self
     }
 
+    /// Add an address alias: accesses to the source range are redirected to the target
+    pub fn memory_alias(&mut self, alias: MemoryMapConfig) -> &mut Self {
+        self.aliases.push(alias);
+        self
+    }
+
+    ///
+    /// Configure the number of external interrupts supported by the NVIC.
+    /// Interrupts are implemented in groups of 32, with at least 32 interrupts.
+    ///
+    pub fn nvic_irqs(&mut self, irq_count: usize) -> &mut Self {
+        let groups = irq_count.div_ceil(32).clamp(1, self.nvic_interrupt_enabled.len());
+        self.exceptions = make_exception_priorities(groups * 32);
+        self.ictr = (groups - 1) as u32;
+        self
+    }
+
+    ///
+    /// Configure the IDAU regions of an Armv8-M device with the security
+    /// extension. Memory outside of the regions is attributed by the SAU alone.
+    ///
+    pub fn idau(&mut self, regions: &[IdauRegion]) -> &mut Self {
+        self.idau = regions.to_vec();
+        self
+    }
+
+    /// Replace the peripheral device models
+    pub fn devices(&mut self, devices: Vec<Box<dyn Bus>>) -> &mut Self {
+        self.devices = devices;
+        self
+    }
+
     /// Configure itm output file
     pub fn itm<'a>(&'a mut self, file: Option<Box<dyn io::Write + 'static>>) -> &'a mut Self {
         self.itm_file = file;
@@ -382,20 +650,54 @@ impl Processor {
     }
 
     ///
-    /// Pre cache (decode) instructions to speed up simulation
+    /// Stop the processor on the first fault, leaving PC at the faulting
+    /// instruction, instead of taking the fault exception
+    ///
+    pub fn fault_trap(&mut self, enable: bool) -> &mut Self {
+        self.fault_trap = enable;
+        self
+    }
+
+    ///
+    /// Cache the decoded instructions of the boot flash to speed up simulation.
+    /// Instructions are decoded on their first execution, code in other
+    /// regions on every execution.
     ///
     pub fn cache_instructions(&mut self) {
-        // pre-cache the decoded instructions
-        {
-            let mut pc = 0;
-
-            while pc < (self.code.len() as u32) {
-                let thumb = self.fetch_non_fail(pc);
-                let instruction = self.decode(thumb);
-                self.instruction_cache
-                    .push((instruction, instruction_size(&instruction)));
-                pc += 2;
-            }
+        self.instruction_cache = InstructionCache::new(self.code.len());
+    }
+
+    ///
+    /// Program the content of flash memory, like a debugger or a flash
+    /// loader does. Flash is not writable with bus accesses.
+    ///
+    pub fn program_flash(&mut self, address: u32, data: &[u8]) -> Result<(), Fault> {
+        let addr = self.map_address(address);
+        let last_address = addr + data.len().saturating_sub(1) as u32;
+        let flash = if self.code.in_range(addr) && self.code.in_range(last_address) {
+            &mut self.code
+        } else {
+            self.flash_regions
+                .iter_mut()
+                .find(|r| r.in_range(addr) && r.in_range(last_address))
+                .ok_or(Fault::Preciserr)?
+        };
+        flash.program(addr, data);
+        self.invalidate_code(addr, data.len() as u32);
+        Ok(())
+    }
+
+    ///
+    /// Drop the decoded instructions and translated blocks overlapping
+    /// a memory write of `size` bytes
+    ///
+    #[inline(always)]
+    pub(crate) fn invalidate_code(&mut self, address: u32, size: u32) {
+        self.instruction_cache.invalidate(address, size);
+        if self.block_cache.invalidate(address, size) {
+            // the code compiled from the dropped blocks is freed with the rest
+            #[cfg(all(feature = "jit", target_arch = "x86_64", unix))]
+            self.jit.clear();
         }
     }
 
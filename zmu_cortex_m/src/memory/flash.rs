@@ -10,16 +10,30 @@ use byteorder::{ByteOrder, LittleEndian};
 #[derive(Debug)]
 /// Flash memory with configurable start address and data content
 pub struct FlashMemory {
+    start_address: u32,
     data: Box<[u8]>,
 }
 
 impl FlashMemory {
-    /// make a flash data instance with given start address, size and data content
+    /// make a flash data instance starting from address 0 with given size and data content
     pub fn new(size: usize, new_data: &[u8]) -> Self {
+        Self::new_with_start_address(0, size, new_data)
+    }
+
+    /// make a flash data instance with given start address, size and data content
+    pub fn new_with_start_address(start_address: u32, size: usize, new_data: &[u8]) -> Self {
         let mut data = vec![0_u8; size].into_boxed_slice();
         data.copy_from_slice(new_data);
 
-        Self { data }
+        Self {
+            start_address,
+            data,
+        }
+    }
+
+    /// Get the start address
+    pub fn start_address(&self) -> u32 {
+        self.start_address
     }
 
     /// Get the length
@@ -31,37 +45,44 @@ impl FlashMemory {
     pub fn is_empty(&self) -> bool {
         self.data.len() == 0
     }
+
+    /// Program data to given address, like a debugger or flash loader does.
+    /// Flash is not writable with bus accesses.
+    pub fn program(&mut self, addr: u32, new_data: &[u8]) {
+        let a = (addr - self.start_address) as usize;
+        self.data[a..a + new_data.len()].copy_from_slice(new_data);
+    }
 }
 
 impl Bus for FlashMemory {
-    fn read8(&self, addr: u32) -> Result<u8, Fault> {
-        let a = addr as usize;
+    fn read8(&mut self, addr: u32) -> Result<u8, Fault> {
+        let a = (addr - self.start_address) as usize;
         Ok(self.data[a])
     }
-    fn read16(&self, addr: u32) -> Result<u16, Fault> {
-        let a = addr as usize;
+    fn read16(&mut self, addr: u32) -> Result<u16, Fault> {
+        let a = (addr - self.start_address) as usize;
 
         Ok(LittleEndian::read_u16(&self.data[a..a + 2]))
     }
 
     fn read32(&mut self, addr: u32) -> Result<u32, Fault> {
-        let a = addr as usize;
+        let a = (addr - self.start_address) as usize;
         Ok(LittleEndian::read_u32(&self.data[a..a + 4]))
     }
 
     fn write32(&mut self, _addr: u32, _value: u32) -> Result<(), Fault> {
-        Err(Fault::DAccViol)
+        Err(Fault::Preciserr)
     }
 
     fn write16(&mut self, _addr: u32, _value: u16) -> Result<(), Fault> {
-        Err(Fault::DAccViol)
+        Err(Fault::Preciserr)
     }
     fn write8(&mut self, _addr: u32, _value: u8) -> Result<(), Fault> {
-        Err(Fault::DAccViol)
+        Err(Fault::Preciserr)
     }
 
     fn in_range(&self, addr: u32) -> bool {
-        addr < (self.data.len() as u32)
+        addr >= self.start_address && ((addr - self.start_address) as usize) < self.data.len()
     }
 }
 
@@ -93,4 +114,21 @@ fn test_in_range() {
         assert!(!mem.in_range(1024));
         assert!(!mem.in_range(0xFFFF_FFFF));
     }
+
+    {
+        /* offset of 0x9000_0000 */
+        let mem = FlashMemory::new_with_start_address(0x9000_0000, 1024, &vec![0u8; 1024]);
+        assert!(!mem.in_range(0));
+        assert!(mem.in_range(0x9000_0000));
+        assert!(mem.in_range(0x9000_0000 + 1023));
+        assert!(!mem.in_range(0x9000_0000 + 1024));
+    }
+}
+
+#[test]
+fn test_read_with_start_address() {
+    let mut mem = FlashMemory::new_with_start_address(0x9000_0000, 4, &[1, 2, 3, 4]);
+    assert_eq!(mem.read8(0x9000_0001).unwrap(), 2);
+    assert_eq!(mem.read16(0x9000_0002).unwrap(), 0x0403);
+    assert_eq!(mem.read32(0x9000_0000).unwrap(), 0x0403_0201);
 }
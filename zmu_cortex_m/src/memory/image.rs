@@ -0,0 +1,74 @@
+//!
+//! Executable image content
+//!
+//!
+
+///
+/// Block of initialized data to be placed at a given address
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// start address of the data
+    pub address: u32,
+    /// content
+    pub data: Vec<u8>,
+}
+
+impl Segment {
+    /// Address one past the last byte of the segment
+    pub fn end_address(&self) -> u64 {
+        u64::from(self.address) + self.data.len() as u64
+    }
+}
+
+///
+/// Combine segments into contiguous regions. Segments that are at most
+/// `max_gap` bytes apart are placed into the same region, the gaps are
+/// zero filled. Returned regions are ordered by address.
+///
+pub fn merge_segments(segments: &[&Segment], max_gap: u32) -> Vec<Segment> {
+    let mut sorted: Vec<&Segment> = segments.to_vec();
+    sorted.sort_by_key(|s| s.address);
+
+    let mut regions: Vec<Segment> = Vec::new();
+    for segment in sorted {
+        if let Some(region) = regions.last_mut() {
+            if u64::from(segment.address) <= region.end_address() + u64::from(max_gap) {
+                let offset = (segment.address - region.address) as usize;
+                let end = offset + segment.data.len();
+                if end > region.data.len() {
+                    region.data.resize(end, 0);
+                }
+                region.data[offset..end].copy_from_slice(&segment.data);
+                continue;
+            }
+        }
+        regions.push(segment.clone());
+    }
+    regions
+}
+
+#[test]
+fn test_merge_segments() {
+    let text = Segment {
+        address: 0x0800_0000,
+        data: vec![1, 2, 3, 4],
+    };
+    let data = Segment {
+        address: 0x0800_0008,
+        data: vec![5, 6],
+    };
+    let qspi = Segment {
+        address: 0x9000_0000,
+        data: vec![7],
+    };
+
+    let regions = merge_segments(&[&qspi, &data, &text], 0x100);
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].address, 0x0800_0000);
+    assert_eq!(regions[0].data, vec![1, 2, 3, 4, 0, 0, 0, 0, 5, 6]);
+    assert_eq!(regions[1], qspi);
+
+    let regions = merge_segments(&[&text, &data], 0);
+    assert_eq!(regions.len(), 2);
+}
@@ -16,6 +16,7 @@ pub trait MapMemory {
 
 ///
 /// Mapping of memory range to another range
+#[derive(Debug, Clone, Copy)]
 pub struct MemoryMapConfig {
     /// source of mapping
     source_start: u32,
@@ -53,6 +54,10 @@ impl MapMemory for MemoryMapConfig {
 
 impl MapMemory for Processor {
     fn map_address(&self, address: u32) -> u32 {
+        let address = match self.aliases.iter().find(|alias| alias.contains(address)) {
+            Some(alias) => alias.map_address(address),
+            None => address,
+        };
         if let Some(map) = &self.mem_map {
             map.map_address(address)
         } else {
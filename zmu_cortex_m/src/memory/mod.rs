@@ -4,5 +4,6 @@
 //!
 
 pub mod flash;
+pub mod image;
 pub mod map;
 pub mod ram;
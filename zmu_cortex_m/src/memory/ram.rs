@@ -33,15 +33,21 @@ impl RAM {
             data,
         }
     }
+
+    /// Copy initial content to the memory starting from given address
+    pub fn load(&mut self, address: u32, content: &[u8]) {
+        let a = (address - self.start_address) as usize;
+        self.data[a..a + content.len()].copy_from_slice(content);
+    }
 }
 
 impl Bus for RAM {
-    fn read8(&self, addr: u32) -> Result<u8, Fault> {
+    fn read8(&mut self, addr: u32) -> Result<u8, Fault> {
         let a = addr - self.start_address;
         Ok(self.data[a as usize])
     }
 
-    fn read16(&self, addr: u32) -> Result<u16, Fault> {
+    fn read16(&mut self, addr: u32) -> Result<u16, Fault> {
         let a = (addr - self.start_address) as usize;
 
         Ok(LittleEndian::read_u16(&self.data[a..a + 2]))
@@ -72,10 +78,7 @@ impl Bus for RAM {
     }
 
     fn in_range(&self, addr: u32) -> bool {
-        if (addr >= self.start_address) && (addr < (self.start_address + self.data.len() as u32)) {
-            return true;
-        }
-        false
+        addr >= self.start_address && ((addr - self.start_address) as usize) < self.data.len()
     }
 }
 
@@ -104,6 +107,13 @@ fn test_in_range() {
         assert!(!mem.in_range(0x8000_0000 + 1024));
         assert!(!mem.in_range(0x8000_0000 + 0xffff));
     }
+
+    {
+        /* bank at the top of the address space */
+        let mem = RAM::new(0xFFFF_FC00, 1024);
+        assert!(mem.in_range(0xFFFF_FFFF));
+        assert!(!mem.in_range(0xFFFF_FBFF));
+    }
 }
 
 #[test]
@@ -132,3 +142,12 @@ fn test_write_read() {
         assert_eq!(mem.read8(1022).unwrap(), 0xCC);
     }
 }
+
+#[test]
+fn test_load() {
+    let mut mem = RAM::new_with_fill(0x2000_0000, 1024, 0xcd);
+    mem.load(0x2000_0010, &[0xAA, 0xBB]);
+    assert_eq!(mem.read8(0x2000_000F).unwrap(), 0xcd);
+    assert_eq!(mem.read16(0x2000_0010).unwrap(), 0xBBAA);
+    assert_eq!(mem.read8(0x2000_0012).unwrap(), 0xcd);
+}
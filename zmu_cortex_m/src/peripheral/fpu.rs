@@ -0,0 +1,160 @@
+//!
+//! Cortex Floating Point extension: context control and lazy state preservation
+//!
+
+use crate::bus::Bus;
+use crate::core::bits::Bits;
+use crate::core::fault::Fault;
+use crate::Processor;
+use crate::ProcessorMode;
+
+/// FPCCR.LSPACT: lazy state preservation is pending
+pub const FPCCR_LSPACT: usize = 0;
+/// FPCCR.LSPEN: lazy state preservation enabled
+pub const FPCCR_LSPEN: usize = 30;
+/// FPCCR.ASPEN: automatic FP context activation and stacking enabled
+pub const FPCCR_ASPEN: usize = 31;
+
+/// Register API and context handling of the Floating Point extension
+pub trait FloatingPointUnit {
+    ///
+    /// Write Coprocessor Access Control Register
+    ///
+    fn write_cpacr(&mut self, value: u32);
+
+    ///
+    /// Write Floating Point Context Control Register
+    ///
+    fn write_fpccr(&mut self, value: u32);
+
+    ///
+    /// Write Floating Point Context Address Register
+    ///
+    fn write_fpcar(&mut self, value: u32);
+
+    ///
+    /// Write Floating Point Default Status Control Register
+    ///
+    fn write_fpdscr(&mut self, value: u32);
+
+    ///
+    /// Check done before a floating point instruction is executed: faults
+    /// with NOCP if CPACR denies the access, saves the lazily stacked FP
+    /// context and activates a new FP context with the default FPSCR.
+    ///
+    fn execute_fp_check(&mut self) -> Result<(), Fault>;
+
+    ///
+    /// Save S0-S15 and FPSCR to the space reserved in the extended exception
+    /// frame at FPCAR, and clear the pending lazy state preservation.
+    ///
+    fn preserve_fp_state(&mut self) -> Result<(), Fault>;
+}
+
+impl FloatingPointUnit for Processor {
+    fn write_cpacr(&mut self, value: u32) {
+        // CP10 and CP11 access fields
+        self.cpacr.set_bits(20..24, value.get_bits(20..24));
+    }
+
+    fn write_fpccr(&mut self, value: u32) {
+        self.fpccr = value & 0xc000_017b;
+    }
+
+    fn write_fpcar(&mut self, value: u32) {
+        self.fpcar = value & 0xffff_fff8;
+    }
+
+    fn write_fpdscr(&mut self, value: u32) {
+        // AHP, DN, FZ and RMode
+        self.fpdscr = value & 0x07c0_0000;
+    }
+
+    fn execute_fp_check(&mut self) -> Result<(), Fault> {
+        // CPACR.CP10: 0b01 privileged access only, 0b11 full access
+        let privileged = self.mode == ProcessorMode::HandlerMode || !self.control.n_priv;
+        match self.cpacr.get_bits(20..22) {
+            0b11 => (),
+            0b01 if privileged => (),
+            _ => return Err(Fault::Nocp),
+        }
+        if self.fpccr.get_bit(FPCCR_LSPACT) {
+            self.preserve_fp_state()?;
+        }
+        if self.fpccr.get_bit(FPCCR_ASPEN) && !self.control.fpca {
+            self.fpscr.set_bits(22..27, self.fpdscr.get_bits(22..27));
+            self.control.fpca = true;
+        }
+        Ok(())
+    }
+
+    fn preserve_fp_state(&mut self) -> Result<(), Fault> {
+        let address = self.fpcar;
+        for (offset, index) in (0..0x40).step_by(4).zip(0..16) {
+            let value = self.fp_regs[index];
+            self.write32(address + offset, value)
+                .map_err(Fault::lazy_preservation)?;
+        }
+        let fpscr = self.fpscr;
+        self.write32(address + 0x40, fpscr)
+            .map_err(Fault::lazy_preservation)?;
+        self.fpccr.set_bit(FPCCR_LSPACT, false);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::profile::ArchProfile;
+    use crate::core::reset::Reset;
+
+    #[test]
+    fn test_execute_fp_check() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M4F);
+        processor.reset().unwrap();
+        processor.cpacr = 0x00f0_0000;
+        processor.fpdscr = 0x0300_0000;
+        processor.fpscr = 0x8000_0000;
+        processor.fp_regs[15] = 0x3f80_0000;
+        processor.fpcar = 0x2000_0100;
+        processor.fpccr.set_bit(FPCCR_LSPACT, true);
+
+        // Act
+        processor.execute_fp_check().unwrap();
+
+        // Assert: lazy state saved before the new context is activated
+        assert_eq!(processor.read32(0x2000_0100 + 0x3c).unwrap(), 0x3f80_0000);
+        assert_eq!(processor.read32(0x2000_0140).unwrap(), 0x8000_0000);
+        assert!(!processor.fpccr.get_bit(FPCCR_LSPACT));
+        assert!(processor.control.fpca);
+        assert_eq!(processor.fpscr, 0x8300_0000);
+    }
+
+    #[test]
+    fn test_execute_fp_check_cpacr() {
+        // Arrange: CP10 privileged access only
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M4F);
+        processor.reset().unwrap();
+        processor.fpccr.set_bit(FPCCR_ASPEN, true);
+
+        // Act & Assert: no access after reset
+        assert_eq!(processor.execute_fp_check(), Err(Fault::Nocp));
+        assert!(!processor.control.fpca);
+
+        // Act & Assert: privileged thread mode
+        processor.write_cpacr(0x0050_0000);
+        assert_eq!(processor.execute_fp_check(), Ok(()));
+
+        // Act & Assert: unprivileged thread mode
+        processor.control.n_priv = true;
+        assert_eq!(processor.execute_fp_check(), Err(Fault::Nocp));
+
+        // Act & Assert: full access
+        processor.write_cpacr(0x00f0_0000);
+        assert_eq!(processor.execute_fp_check(), Ok(()));
+    }
+}
@@ -3,7 +3,10 @@
 //!
 
 pub mod dwt;
+pub mod fpu;
 pub mod itm;
+pub mod mpu;
 pub mod nvic;
+pub mod sau;
 pub mod scb;
 pub mod systick;
@@ -0,0 +1,385 @@
+//!
+//! Cortex Memory Protection Unit (`PMSAv7`)
+//!
+
+use crate::core::bits::Bits;
+use crate::core::fault::Fault;
+use crate::Processor;
+use crate::ProcessorMode;
+
+/// Number of MPU regions
+pub const MPU_REGIONS: usize = 8;
+
+/// `MPU_CTRL.ENABLE`: MPU enabled
+pub const MPU_CTRL_ENABLE: usize = 0;
+/// `MPU_CTRL.HFNMIENA`: MPU enabled also for `HardFault`, NMI and FAULTMASK handlers
+pub const MPU_CTRL_HFNMIENA: usize = 1;
+/// `MPU_CTRL.PRIVDEFENA`: default memory map as background region for privileged accesses
+pub const MPU_CTRL_PRIVDEFENA: usize = 2;
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Kind of memory access checked by the MPU
+///
+pub enum MemoryAccess {
+    /// Data read
+    Read,
+    /// Data write
+    Write,
+    /// Instruction fetch
+    InstructionFetch,
+}
+
+/// Register API and access checks of the Memory Protection Unit
+pub trait MemoryProtectionUnit {
+    ///
+    /// Read MPU Type Register
+    ///
+    fn read_mpu_type(&self) -> u32;
+
+    ///
+    /// Write MPU Control Register
+    ///
+    fn write_mpu_ctrl(&mut self, value: u32);
+
+    ///
+    /// Write MPU Region Number Register
+    ///
+    fn write_mpu_rnr(&mut self, value: u32);
+
+    ///
+    /// Read MPU Region Base Address Register of the selected region
+    ///
+    fn read_mpu_rbar(&self) -> u32;
+
+    ///
+    /// Write MPU Region Base Address Register. With the VALID bit set,
+    /// also selects the region.
+    ///
+    fn write_mpu_rbar(&mut self, value: u32);
+
+    ///
+    /// Read MPU Region Attribute and Size Register of the selected region
+    ///
+    fn read_mpu_rasr(&self) -> u32;
+
+    ///
+    /// Write MPU Region Attribute and Size Register of the selected region
+    ///
+    fn write_mpu_rasr(&mut self, value: u32);
+
+    ///
+    /// Check that the current privilege level is allowed to access the address.
+    /// Denied accesses are `DAccViol` faults, or `IAccViol` for instruction fetches.
+    /// Without the MPU, instruction fetches from the execute never regions of
+    /// the default memory map are denied.
+    ///
+    fn mpu_check(&self, address: u32, access: MemoryAccess) -> Result<(), Fault>;
+}
+
+/// Address ranges that are never executable in the default memory map:
+/// Peripheral, Device and System regions
+fn default_map_xn(address: u32) -> bool {
+    matches!(address, 0x4000_0000..=0x5FFF_FFFF | 0xA000_0000..=0xFFFF_FFFF)
+}
+
+/// Access permission field (AP) of RASR
+fn ap_allows(ap: u32, privileged: bool, write: bool) -> bool {
+    match (ap, privileged) {
+        (0b011, _) | (0b001 | 0b010, true) => true,
+        (0b010, false) | (0b101, true) | (0b110 | 0b111, _) => !write,
+        _ => false,
+    }
+}
+
+impl Processor {
+    /// Attributes (RASR) of the highest numbered enabled region containing the address
+    fn mpu_region(&self, address: u32) -> Option<u32> {
+        (0..MPU_REGIONS).rev().find_map(|region| {
+            let rasr = self.mpu_rasr[region];
+            if !rasr.get_bit(0) {
+                return None;
+            }
+            let size_log2 = (rasr.get_bits(1..6) + 1).max(5);
+            let base = u64::from(self.mpu_rbar[region]) & !((1u64 << size_log2) - 1);
+            let offset = u64::from(address).checked_sub(base)?;
+            if offset >= 1 << size_log2 {
+                return None;
+            }
+            // regions of 256 bytes or more are split to 8 subregions
+            if size_log2 >= 8 && rasr.get_bit(8 + (offset >> (size_log2 - 3)) as usize) {
+                return None;
+            }
+            Some(rasr)
+        })
+    }
+}
+
+impl MemoryProtectionUnit for Processor {
+    fn read_mpu_type(&self) -> u32 {
+        // DREGION: unified regions
+        if self.profile.has_mpu() {
+            (MPU_REGIONS as u32) << 8
+        } else {
+            0
+        }
+    }
+
+    fn write_mpu_ctrl(&mut self, value: u32) {
+        self.mpu_ctrl = value & 0b111;
+    }
+
+    fn write_mpu_rnr(&mut self, value: u32) {
+        self.mpu_rnr = value & (MPU_REGIONS as u32 - 1);
+    }
+
+    fn read_mpu_rbar(&self) -> u32 {
+        self.mpu_rbar[self.mpu_rnr as usize] | self.mpu_rnr
+    }
+
+    fn write_mpu_rbar(&mut self, value: u32) {
+        if value.get_bit(4) {
+            self.write_mpu_rnr(value.get_bits(0..4));
+        }
+        self.mpu_rbar[self.mpu_rnr as usize] = value & 0xffff_ffe0;
+    }
+
+    fn read_mpu_rasr(&self) -> u32 {
+        self.mpu_rasr[self.mpu_rnr as usize]
+    }
+
+    fn write_mpu_rasr(&mut self, value: u32) {
+        // XN, AP, TEX, S, C, B, SRD, SIZE and ENABLE
+        self.mpu_rasr[self.mpu_rnr as usize] = value & 0x173f_ff3f;
+    }
+
+    fn mpu_check(&self, address: u32, access: MemoryAccess) -> Result<(), Fault> {
+        let fault = if access == MemoryAccess::InstructionFetch {
+            Fault::IAccViol
+        } else {
+            Fault::DAccViol
+        };
+
+        if !self.mpu_ctrl.get_bit(MPU_CTRL_ENABLE)
+            || (self.execution_priority < 0 && !self.mpu_ctrl.get_bit(MPU_CTRL_HFNMIENA))
+        {
+            // the default memory map applies
+            return if access == MemoryAccess::InstructionFetch && default_map_xn(address) {
+                Err(fault)
+            } else {
+                Ok(())
+            };
+        }
+
+        // Private Peripheral Bus always uses the default memory map
+        if (0xE000_0000..=0xE00F_FFFF).contains(&address) {
+            return if access == MemoryAccess::InstructionFetch {
+                Err(fault)
+            } else {
+                Ok(())
+            };
+        }
+
+        let privileged = self.mode == ProcessorMode::HandlerMode || !self.control.n_priv;
+        let allowed = match self.mpu_region(address) {
+            Some(rasr) => {
+                ap_allows(
+                    rasr.get_bits(24..27),
+                    privileged,
+                    access == MemoryAccess::Write,
+                ) && !(access == MemoryAccess::InstructionFetch && rasr.get_bit(28))
+            }
+            None => {
+                privileged
+                    && self.mpu_ctrl.get_bit(MPU_CTRL_PRIVDEFENA)
+                    && !(access == MemoryAccess::InstructionFetch && default_map_xn(address))
+            }
+        };
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(fault)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::core::exception::{Exception, ExceptionHandling};
+    use crate::core::instruction::Instruction;
+    use crate::core::profile::ArchProfile;
+    use crate::core::register::BaseReg;
+    use crate::core::reset::Reset;
+    use crate::executor::Executor;
+
+    // RASR: 1 KiB region with given access permissions
+    fn rasr_1k(ap: u32) -> u32 {
+        ap << 24 | 9 << 1 | 1
+    }
+
+    #[test]
+    fn test_mpu_registers() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M3);
+
+        // Act
+        processor.write32(0xE000_ED98, 2).unwrap();
+        processor.write32(0xE000_ED9C, 0x2000_0000).unwrap();
+        processor.write32(0xE000_EDA0, 0xffff_ffff).unwrap();
+        // region number in RBAR with VALID, through the alias registers
+        processor
+            .write32(0xE000_EDA4, 0x2000_1000 | 1 << 4 | 5)
+            .unwrap();
+        processor.write32(0xE000_EDA8, rasr_1k(0b011)).unwrap();
+
+        // Assert
+        assert_eq!(processor.read32(0xE000_ED90).unwrap(), 0x0000_0800);
+        assert_eq!(processor.read32(0xE000_ED98).unwrap(), 5);
+        assert_eq!(processor.read32(0xE000_ED9C).unwrap(), 0x2000_1005);
+        assert_eq!(processor.read32(0xE000_EDB8).unwrap(), rasr_1k(0b011));
+        processor.write32(0xE000_ED98, 2).unwrap();
+        assert_eq!(processor.read32(0xE000_EDB4).unwrap(), 0x2000_0002);
+        assert_eq!(processor.read32(0xE000_EDA0).unwrap(), 0x173f_ff3f);
+
+        // Act: no MPU on Armv6-M
+        processor.profile(ArchProfile::CORTEX_M0);
+
+        // Assert
+        assert_eq!(processor.read32(0xE000_ED94), Err(Fault::Preciserr));
+    }
+
+    #[test]
+    fn test_armv8m_mpu_probe() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+
+        // Act
+        processor.write32(0xE000_ED94, 1).unwrap();
+
+        // Assert: MPU_TYPE reports no regions, the MPU stays disabled
+        assert_eq!(processor.read32(0xE000_ED90).unwrap(), 0);
+        assert_eq!(processor.read32(0xE000_ED94).unwrap(), 0);
+        assert_eq!(processor.read32(0xE000_EDC0).unwrap(), 0);
+        assert_eq!(processor.mpu_ctrl, 0);
+    }
+
+    #[test]
+    fn test_mpu_privilege_separation() {
+        // Arrange: privileged read-write, unprivileged read-only region
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M3);
+        processor.write_mpu_rbar(0x2000_0000 | 1 << 4);
+        processor.write_mpu_rasr(rasr_1k(0b010));
+        processor.write_mpu_ctrl(1);
+
+        // Act & Assert: privileged thread
+        processor.write32(0x2000_0000, 0x1234_5678).unwrap();
+        assert_eq!(processor.read32(0x2000_0000).unwrap(), 0x1234_5678);
+
+        // Act & Assert: unprivileged thread
+        processor.control.n_priv = true;
+        assert_eq!(processor.read32(0x2000_0000).unwrap(), 0x1234_5678);
+        assert_eq!(processor.write8(0x2000_03FF, 0), Err(Fault::DAccViol));
+        assert_eq!(processor.fault_address.get(), 0x2000_03FF);
+
+        // Act & Assert: outside of the region, without PRIVDEFENA
+        assert_eq!(processor.read16(0x2000_0400), Err(Fault::DAccViol));
+        processor.control.n_priv = false;
+        assert_eq!(processor.read16(0x2000_0400), Err(Fault::DAccViol));
+
+        // Act & Assert: default memory map as background for privileged accesses
+        processor.write_mpu_ctrl(0b101);
+        assert!(processor.read16(0x2000_0400).is_ok());
+        processor.control.n_priv = true;
+        assert_eq!(processor.read16(0x2000_0400), Err(Fault::DAccViol));
+
+        // Act & Assert: System Control Space is not affected by the regions
+        assert!(processor.read32(0xE000_ED94).is_ok());
+    }
+
+    #[test]
+    fn test_mpu_region_priority_and_subregions() {
+        // Arrange: full access background region, a 4 KiB no access region
+        // with the first 512 byte subregion disabled and a read-only region on top
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M3);
+        processor.write_mpu_rbar(0x2000_0000 | 1 << 4);
+        processor.write_mpu_rasr(0b011 << 24 | 31 << 1 | 1);
+        processor.write_mpu_rbar(0x2000_1000 | 1 << 4 | 1);
+        processor.write_mpu_rasr(0b0000_0001 << 8 | 11 << 1 | 1);
+        processor.write_mpu_rbar(0x2000_1c00 | 1 << 4 | 2);
+        processor.write_mpu_rasr(rasr_1k(0b110));
+        processor.write_mpu_ctrl(1);
+        processor.control.n_priv = true;
+
+        // Act & Assert: disabled subregion falls through to region 0
+        processor.write32(0x2000_11FC, 0).unwrap();
+        assert_eq!(processor.write32(0x2000_1200, 0), Err(Fault::DAccViol));
+
+        // Act & Assert: region 2 overrides region 1
+        assert!(processor.read32(0x2000_1c00).is_ok());
+        assert_eq!(processor.write32(0x2000_1c00, 0), Err(Fault::DAccViol));
+    }
+
+    #[test]
+    fn test_mpu_hfnmiena() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M3);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+        processor.set_exception_pending(Exception::HardFault);
+        processor.check_exceptions();
+        processor.write_mpu_ctrl(1);
+
+        // Act & Assert: MPU is ignored at negative priority, unless HFNMIENA
+        assert!(processor.read32(0x2000_0000).is_ok());
+        processor.write_mpu_ctrl(0b011);
+        assert_eq!(processor.read32(0x2000_0000), Err(Fault::DAccViol));
+    }
+
+    #[test]
+    fn test_mpu_memmanage_fault() {
+        // Arrange
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M3);
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+        processor.write32(0xE000_ED24, 1 << 16).unwrap();
+        processor.write_mpu_rbar(0x2000_0000 | 1 << 4);
+        processor.write_mpu_rasr(rasr_1k(0b011) | 1 << 28);
+        processor.write_mpu_ctrl(0b101);
+        processor.control.n_priv = true;
+
+        // Act: unprivileged data access outside of the regions
+        assert_eq!(processor.read32(0x2000_0800), Err(Fault::DAccViol));
+        processor.fault_entry(Fault::DAccViol, 0x100);
+
+        // Assert: MMFAR holds the faulting address
+        assert!(processor.exception_active(Exception::MemoryManagementFault));
+        assert_eq!(processor.cfsr, (1 << 7) | (1 << 1));
+        assert_eq!(processor.read32(0xE000_ED34).unwrap(), 0x2000_0800);
+
+        // Act: execute from an execute never region
+        processor.reset().unwrap();
+        processor.msp = 0x2000_0400;
+        processor.write32(0xE000_ED24, 1 << 16).unwrap();
+        processor.write_mpu_rbar(0x2000_0000 | 1 << 4);
+        processor.write_mpu_rasr(rasr_1k(0b011) | 1 << 28);
+        processor.write_mpu_ctrl(0b101);
+        processor.mmfar = 0;
+        processor.set_pc(0x2000_0200);
+        processor.execute(&Instruction::NOP { thumb32: false }, 2);
+
+        // Assert: no fault address for instruction access violations
+        assert!(processor.exception_active(Exception::MemoryManagementFault));
+        assert_eq!(processor.cfsr, 1);
+        assert_eq!(processor.mmfar, 0);
+        assert_eq!(processor.read32(0x2000_0400 - 8).unwrap(), 0x2000_0200);
+    }
+}
@@ -238,6 +238,25 @@ mod tests {
     use crate::core::reset::Reset;
     use crate::executor::Executor;
 
+    #[test]
+    fn test_nvic_irqs() {
+        // Arrange
+        let mut processor = Processor::new();
+        assert_eq!(processor.ictr, 0);
+
+        // Act
+        processor.nvic_irqs(68);
+        processor.nvic_write_ipr_u8(95, 0x40);
+
+        // Assert
+        assert_eq!(processor.ictr, 2);
+        assert_eq!(
+            processor.get_exception_priority(Exception::Interrupt { n: 95 }),
+            0x40
+        );
+        assert!(!processor.exceptions.contains_key(&(16 + 96)));
+    }
+
     #[test]
     fn test_nvic_iser_icer() {
         // Arrange
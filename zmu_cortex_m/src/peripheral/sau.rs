@@ -0,0 +1,349 @@
+//!
+//! Cortex Security Attribution Unit (SAU) and the Implementation Defined
+//! Attribution Unit (IDAU) of the Armv8-M security extension
+//!
+
+use crate::core::bits::Bits;
+use crate::core::fault::Fault;
+use crate::Processor;
+
+/// Number of SAU regions
+pub const SAU_REGIONS: usize = 8;
+
+/// `SAU_CTRL.ENABLE`: SAU enabled
+pub const SAU_CTRL_ENABLE: usize = 0;
+/// `SAU_CTRL.ALLNS`: all memory is Non-secure while the SAU is disabled
+pub const SAU_CTRL_ALLNS: usize = 1;
+/// `SFSR.SFARVALID`: SFAR holds the address of the violating access
+pub const SFSR_SFARVALID: usize = 6;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
+///
+/// Security attribute of a memory address, ordered from the least secure
+///
+pub enum MemorySecurity {
+    /// Accessible and executable from both security states
+    NonSecure,
+    /// Secure, but Non-secure code can enter Secure state at SG instructions
+    NonSecureCallable,
+    /// Accessible and executable only in Secure state
+    Secure,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Region of the IDAU, the fixed security map of the device
+///
+pub struct IdauRegion {
+    /// First address of the region
+    pub start: u32,
+    /// Last address of the region
+    pub end: u32,
+    /// Region number, reported by the TT instructions
+    pub number: u8,
+    /// Security attribute of the region
+    pub security: MemorySecurity,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Security attribution of an address, combined from the SAU and the IDAU
+///
+pub struct SecurityAttribution {
+    /// The more secure of the SAU and the IDAU attributes
+    pub security: MemorySecurity,
+    /// Exempt from the attribution, accessed in the current security state
+    pub exempt: bool,
+    /// SAU region of the address, when it matches exactly one region
+    pub sau_region: Option<u8>,
+    /// IDAU region of the address
+    pub idau_region: Option<u8>,
+}
+
+/// Register API and access checks of the Security Attribution Unit
+pub trait SecurityAttributionUnit {
+    ///
+    /// Read SAU Type Register
+    ///
+    fn read_sau_type(&self) -> u32;
+
+    ///
+    /// Write SAU Control Register
+    ///
+    fn write_sau_ctrl(&mut self, value: u32);
+
+    ///
+    /// Write SAU Region Number Register
+    ///
+    fn write_sau_rnr(&mut self, value: u32);
+
+    ///
+    /// Read SAU Region Base Address Register of the selected region
+    ///
+    fn read_sau_rbar(&self) -> u32;
+
+    ///
+    /// Write SAU Region Base Address Register of the selected region
+    ///
+    fn write_sau_rbar(&mut self, value: u32);
+
+    ///
+    /// Read SAU Region Limit Address Register of the selected region
+    ///
+    fn read_sau_rlar(&self) -> u32;
+
+    ///
+    /// Write SAU Region Limit Address Register of the selected region
+    ///
+    fn write_sau_rlar(&mut self, value: u32);
+
+    ///
+    /// Write Secure Fault Status Register, set bits clear the status
+    ///
+    fn write_sfsr(&mut self, value: u32);
+
+    ///
+    /// Security attribution of an address
+    ///
+    fn security_attribution(&self, address: u32) -> SecurityAttribution;
+
+    ///
+    /// Check that a data access is allowed in the current security state.
+    /// Non-secure accesses to Secure memory are `Auviol` faults.
+    ///
+    fn sau_check(&self, address: u32) -> Result<(), Fault>;
+}
+
+impl Processor {
+    /// SAU attribute and region of an address
+    fn sau_attribution(&self, address: u32) -> (MemorySecurity, Option<u8>) {
+        if !self.sau_ctrl.get_bit(SAU_CTRL_ENABLE) {
+            return if self.sau_ctrl.get_bit(SAU_CTRL_ALLNS) {
+                (MemorySecurity::NonSecure, None)
+            } else {
+                (MemorySecurity::Secure, None)
+            };
+        }
+
+        let mut regions = (0..SAU_REGIONS).filter(|&region| {
+            let rlar = self.sau_rlar[region];
+            rlar.get_bit(0) && (self.sau_rbar[region]..=(rlar | 0x1f)).contains(&address)
+        });
+        match (regions.next(), regions.next()) {
+            (Some(region), None) => {
+                let security = if self.sau_rlar[region].get_bit(1) {
+                    MemorySecurity::NonSecureCallable
+                } else {
+                    MemorySecurity::NonSecure
+                };
+                (security, Some(region as u8))
+            }
+            // outside of the regions, or in overlapping regions
+            _ => (MemorySecurity::Secure, None),
+        }
+    }
+}
+
+impl SecurityAttributionUnit for Processor {
+    fn read_sau_type(&self) -> u32 {
+        SAU_REGIONS as u32
+    }
+
+    fn write_sau_ctrl(&mut self, value: u32) {
+        self.sau_ctrl = value & 0b11;
+    }
+
+    fn write_sau_rnr(&mut self, value: u32) {
+        self.sau_rnr = value & (SAU_REGIONS as u32 - 1);
+    }
+
+    fn read_sau_rbar(&self) -> u32 {
+        self.sau_rbar[self.sau_rnr as usize]
+    }
+
+    fn write_sau_rbar(&mut self, value: u32) {
+        self.sau_rbar[self.sau_rnr as usize] = value & 0xffff_ffe0;
+    }
+
+    fn read_sau_rlar(&self) -> u32 {
+        self.sau_rlar[self.sau_rnr as usize]
+    }
+
+    fn write_sau_rlar(&mut self, value: u32) {
+        // LADDR, NSC and ENABLE
+        self.sau_rlar[self.sau_rnr as usize] = value & 0xffff_ffe3;
+    }
+
+    fn write_sfsr(&mut self, value: u32) {
+        self.sfsr &= !value;
+    }
+
+    fn security_attribution(&self, address: u32) -> SecurityAttribution {
+        // Private Peripheral Bus has banked registers instead of an attribution
+        if (0xE000_0000..=0xE00F_FFFF).contains(&address) {
+            return SecurityAttribution {
+                security: if self.secure {
+                    MemorySecurity::Secure
+                } else {
+                    MemorySecurity::NonSecure
+                },
+                exempt: true,
+                sau_region: None,
+                idau_region: None,
+            };
+        }
+
+        let (sau_security, sau_region) = self.sau_attribution(address);
+        let idau = self
+            .idau
+            .iter()
+            .find(|region| (region.start..=region.end).contains(&address));
+        let idau_security = idau.map_or(MemorySecurity::NonSecure, |region| region.security);
+
+        SecurityAttribution {
+            security: sau_security.max(idau_security),
+            exempt: false,
+            sau_region,
+            idau_region: idau.map(|region| region.number),
+        }
+    }
+
+    fn sau_check(&self, address: u32) -> Result<(), Fault> {
+        if !self.profile.has_security() || self.secure {
+            return Ok(());
+        }
+        let attribution = self.security_attribution(address);
+        if attribution.exempt || attribution.security == MemorySecurity::NonSecure {
+            Ok(())
+        } else {
+            Err(Fault::Auviol)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::Bus;
+    use crate::core::exception::{Exception, ExceptionHandling};
+    use crate::core::profile::ArchProfile;
+    use crate::core::reset::Reset;
+    use crate::core::security::SecurityExtension;
+
+    fn processor_m33() -> Processor {
+        let mut processor = Processor::new();
+        processor.profile(ArchProfile::CORTEX_M33);
+        processor.reset().unwrap();
+        processor
+    }
+
+    #[test]
+    fn test_sau_registers() {
+        // Arrange
+        let mut processor = processor_m33();
+
+        // Act
+        processor.write32(0xE000_EDD8, 3).unwrap();
+        processor.write32(0xE000_EDDC, 0x2000_0010).unwrap();
+        processor.write32(0xE000_EDE0, 0x2000_0FFF).unwrap();
+
+        // Assert
+        assert_eq!(processor.read32(0xE000_EDD4).unwrap(), 8);
+        assert_eq!(processor.read32(0xE000_EDDC).unwrap(), 0x2000_0000);
+        assert_eq!(processor.read32(0xE000_EDE0).unwrap(), 0x2000_0FE3);
+
+        // Act: the SAU is not visible to Non-secure code
+        processor.set_security_state(false);
+
+        // Assert
+        assert_eq!(processor.read32(0xE000_EDDC).unwrap(), 0);
+        processor.write32(0xE000_EDD0, 1).unwrap();
+        assert_eq!(processor.sau_ctrl, 0);
+    }
+
+    #[test]
+    fn test_security_attribution() {
+        // Arrange
+        let mut processor = processor_m33();
+        processor.idau(&[IdauRegion {
+            start: 0x1000_0000,
+            end: 0x1FFF_FFFF,
+            number: 1,
+            security: MemorySecurity::Secure,
+        }]);
+        let security =
+            |processor: &Processor, address| processor.security_attribution(address).security;
+
+        // Assert: all Secure after reset, all Non-secure with ALLNS
+        assert_eq!(security(&processor, 0x2000_0000), MemorySecurity::Secure);
+        processor.write_sau_ctrl(0b10);
+        assert_eq!(security(&processor, 0x2000_0000), MemorySecurity::NonSecure);
+        assert_eq!(security(&processor, 0x1000_0000), MemorySecurity::Secure);
+
+        // Act: Non-secure RAM with a Non-secure callable veneer region inside the IDAU range
+        processor.write_sau_rnr(0);
+        processor.write_sau_rbar(0x2000_0000);
+        processor.write_sau_rlar(0x2000_FFE0 | 1);
+        processor.write_sau_rnr(1);
+        processor.write_sau_rbar(0x1000_8000);
+        processor.write_sau_rlar(0x1000_8FE0 | 0b11);
+        processor.write_sau_rnr(2);
+        processor.write_sau_rbar(0x0000_8000);
+        processor.write_sau_rlar(0x0000_8FE0 | 0b11);
+        processor.write_sau_ctrl(0b01);
+
+        // Assert
+        let attribution = processor.security_attribution(0x2000_FFFF);
+        assert_eq!(attribution.security, MemorySecurity::NonSecure);
+        assert_eq!(attribution.sau_region, Some(0));
+        assert_eq!(attribution.idau_region, None);
+        assert_eq!(security(&processor, 0x2001_0000), MemorySecurity::Secure);
+        assert_eq!(
+            security(&processor, 0x0000_8100),
+            MemorySecurity::NonSecureCallable
+        );
+        // the IDAU makes the region Secure
+        let attribution = processor.security_attribution(0x1000_8100);
+        assert_eq!(attribution.security, MemorySecurity::Secure);
+        assert_eq!(attribution.sau_region, Some(1));
+        assert_eq!(attribution.idau_region, Some(1));
+
+        // Act: overlapping regions
+        processor.write_sau_rnr(3);
+        processor.write_sau_rbar(0x2000_F000);
+        processor.write_sau_rlar(0x2001_0FE0 | 1);
+
+        // Assert
+        let attribution = processor.security_attribution(0x2000_F000);
+        assert_eq!(attribution.security, MemorySecurity::Secure);
+        assert_eq!(attribution.sau_region, None);
+        assert!(processor.security_attribution(0xE000_ED00).exempt);
+    }
+
+    #[test]
+    fn test_secure_fault_on_non_secure_access() {
+        // Arrange: Non-secure RAM from 0x2000_8000
+        let mut processor = processor_m33();
+        processor.msp = 0x2000_1000;
+        processor.write32(0xE000_ED24, 1 << 19).unwrap();
+        processor.write_sau_rbar(0x2000_8000);
+        processor.write_sau_rlar(0x2001_FFE0 | 1);
+        processor.write_sau_ctrl(1);
+        processor.write32(0x2000_8000, 42).unwrap();
+        processor.write32(0x2000_0000, 43).unwrap();
+        processor.set_security_state(false);
+        processor.msp = 0x2000_9000;
+
+        // Act
+        assert_eq!(processor.read32(0x2000_8000), Ok(42));
+        assert_eq!(processor.read32(0x2000_0000), Err(Fault::Auviol));
+        processor.fault_entry(Fault::Auviol, 0x100);
+
+        // Assert: taken in Secure state, SFAR holds the address
+        assert!(processor.exception_active(Exception::SecureFault));
+        assert!(processor.secure);
+        assert_eq!(processor.sfsr, (1 << SFSR_SFARVALID) | (1 << 3));
+        assert_eq!(processor.sfar, 0x2000_0000);
+        assert_eq!(processor.read32(0xE000_EDE8).unwrap(), 0x2000_0000);
+    }
+}
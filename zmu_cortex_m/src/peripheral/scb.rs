@@ -5,6 +5,7 @@
 use crate::core::bits::Bits;
 use crate::core::exception::Exception;
 use crate::core::exception::ExceptionHandling;
+use crate::core::security::SecurityExtension;
 use crate::Processor;
 
 use crate::core::register::Ipsr;
@@ -31,109 +32,91 @@ pub trait SystemControlBlock {
     ///
     /// Write System Handler Priority Register 1
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr1(&mut self, value: u32);
 
     ///
     /// Write System Handler Priority Register 2
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr2(&mut self, value: u32);
 
     ///
     /// Write System Handler Priority Register 3
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr3(&mut self, value: u32);
 
     ///
     /// Write System Handler Priority Register 1, 8-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr1_u8(&mut self, offset: usize, value: u8);
 
     ///
     /// Write System Handler Priority Register 2, 8-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr2_u8(&mut self, offset: usize, value: u8);
 
     ///
     /// Write System Handler Priority Register 3, 8-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr3_u8(&mut self, offset: usize, value: u8);
 
     ///
     /// Write System Handler Priority Register 1, 16-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr1_u16(&mut self, offset: usize, value: u16);
 
     ///
     /// Write System Handler Priority Register 2, 16-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr2_u16(&mut self, offset: usize, value: u16);
 
     ///
     /// Write System Handler Priority Register 3, 16-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr3_u16(&mut self, offset: usize, value: u16);
 
     ///
     /// Read System Handler Priority Register 1
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr1(&self) -> u32;
 
     ///
     /// Read System Handler Priority Register 2
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr2(&self) -> u32;
 
     ///
     /// Read System Handler Priority Register 3
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr3(&self) -> u32;
 
     ///
     /// Read System Handler Priority Register 1, 8-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr1_u8(&self, offset: usize) -> u8;
 
     ///
     /// Read System Handler Priority Register 2, 8-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr2_u8(&self, offset: usize) -> u8;
 
     ///
     /// Read System Handler Priority Register 3, 8-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr3_u8(&self, offset: usize) -> u8;
 
     ///
     /// Read System Handler Priority Register 1, 16-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr1_u16(&self, offset: usize) -> u16;
 
     ///
     /// Read System Handler Priority Register 2, 16-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr2_u16(&self, offset: usize) -> u16;
 
     ///
     /// Read System Handler Priority Register 3, 16-bit access
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr3_u16(&self, offset: usize) -> u16;
 
     ///
@@ -164,8 +147,37 @@ pub trait SystemControlBlock {
     ///
     /// Write "Software Triggered Interrupt Register"
     ///
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_stir(&mut self, value: u32);
+
+    ///
+    /// Write Configuration and Control Register
+    ///
+    fn write_ccr(&mut self, value: u32);
+
+    ///
+    /// Read System Handler Control and State Register
+    ///
+    fn read_shcsr(&self) -> u32;
+
+    ///
+    /// Write System Handler Control and State Register
+    ///
+    fn write_shcsr(&mut self, value: u32);
+
+    ///
+    /// Write Configurable Fault Status Register, bits written as one are cleared
+    ///
+    fn write_cfsr(&mut self, value: u32);
+
+    ///
+    /// Write `HardFault` Status Register, bits written as one are cleared
+    ///
+    fn write_hfsr(&mut self, value: u32);
+
+    ///
+    /// Write Application Interrupt and Reset Control Register, ignored without the `VECTKEY`
+    ///
+    fn write_aircr(&mut self, value: u32);
 }
 
 impl SystemControlBlock for Processor {
@@ -185,15 +197,17 @@ impl SystemControlBlock for Processor {
         if value.get_bit(31) {
             self.set_exception_pending(Exception::NMI);
         }
+        // PendSV and SysTick of the current security state
+        let secure = self.secure;
         if value.get_bit(28) {
-            self.set_exception_pending(Exception::PendSV);
+            self.set_banked_exception_pending(Exception::PendSV, secure);
         } else if value.get_bit(27) {
-            self.clear_pending_exception(Exception::PendSV);
+            self.clear_banked_exception_pending(Exception::PendSV, secure);
         }
         if value.get_bit(26) {
-            self.set_exception_pending(Exception::SysTick);
+            self.set_banked_exception_pending(Exception::SysTick, secure);
         } else if value.get_bit(25) {
-            self.clear_pending_exception(Exception::SysTick);
+            self.clear_banked_exception_pending(Exception::SysTick, secure);
         }
     }
 
@@ -201,7 +215,6 @@ impl SystemControlBlock for Processor {
         self.vtor = value;
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr1(&mut self, value: u32) {
         self.write_shpr1_u8(0, value.get_bits(0..8) as u8);
         self.write_shpr1_u8(1, value.get_bits(8..16) as u8);
@@ -209,7 +222,6 @@ impl SystemControlBlock for Processor {
         self.write_shpr1_u8(3, value.get_bits(24..32) as u8);
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr2(&mut self, value: u32) {
         self.write_shpr2_u8(0, value.get_bits(0..8) as u8);
         self.write_shpr2_u8(1, value.get_bits(8..16) as u8);
@@ -217,7 +229,6 @@ impl SystemControlBlock for Processor {
         self.write_shpr2_u8(3, value.get_bits(24..32) as u8);
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr3(&mut self, value: u32) {
         self.write_shpr3_u8(0, value.get_bits(0..8) as u8);
         self.write_shpr3_u8(1, value.get_bits(8..16) as u8);
@@ -225,7 +236,6 @@ impl SystemControlBlock for Processor {
         self.write_shpr3_u8(3, value.get_bits(24..32) as u8);
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr1_u16(&mut self, offset: usize, value: u16) {
         match offset {
             0 | 1 => {
@@ -237,7 +247,6 @@ impl SystemControlBlock for Processor {
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr2_u16(&mut self, offset: usize, value: u16) {
         match offset {
             0 | 1 => {
@@ -249,7 +258,6 @@ impl SystemControlBlock for Processor {
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr3_u16(&mut self, offset: usize, value: u16) {
         match offset {
             0 | 1 => {
@@ -261,24 +269,24 @@ impl SystemControlBlock for Processor {
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr1_u8(&mut self, offset: usize, value: u8) {
         match offset {
             0 => self.set_exception_priority(Exception::MemoryManagementFault, value),
             1 => self.set_exception_priority(Exception::BusFault, value),
             2 => self.set_exception_priority(Exception::UsageFault, value),
+            3 if self.profile.has_security() => {
+                self.set_exception_priority(Exception::SecureFault, value);
+            }
             _ => (),
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr2_u8(&mut self, offset: usize, value: u8) {
         if 3 == offset {
             self.set_exception_priority(Exception::SVCall, value);
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_shpr3_u8(&mut self, offset: usize, value: u8) {
         match offset {
             0 => self.set_exception_priority(Exception::DebugMonitor, value),
@@ -294,7 +302,6 @@ impl SystemControlBlock for Processor {
 
     fn write_demcr(&mut self, _value: u32) {}
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr1(&self) -> u32 {
         (u32::from(self.read_shpr1_u8(3)) << 24)
             + (u32::from(self.read_shpr1_u8(2)) << 16)
@@ -302,7 +309,6 @@ impl SystemControlBlock for Processor {
             + u32::from(self.read_shpr1_u8(0))
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr2(&self) -> u32 {
         (u32::from(self.read_shpr2_u8(3)) << 24)
             + (u32::from(self.read_shpr2_u8(2)) << 16)
@@ -310,7 +316,6 @@ impl SystemControlBlock for Processor {
             + u32::from(self.read_shpr2_u8(0))
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr3(&self) -> u32 {
         (u32::from(self.read_shpr3_u8(3)) << 24)
             + (u32::from(self.read_shpr3_u8(2)) << 16)
@@ -318,17 +323,18 @@ impl SystemControlBlock for Processor {
             + u32::from(self.read_shpr3_u8(0))
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr1_u8(&self, offset: usize) -> u8 {
         match offset {
             0 => self.get_exception_priority(Exception::MemoryManagementFault) as u8,
             1 => self.get_exception_priority(Exception::BusFault) as u8,
             2 => self.get_exception_priority(Exception::UsageFault) as u8,
+            3 if self.profile.has_security() => {
+                self.get_exception_priority(Exception::SecureFault) as u8
+            }
             _ => 0,
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr2_u8(&self, offset: usize) -> u8 {
         match offset {
             3 => self.get_exception_priority(Exception::SVCall) as u8,
@@ -336,7 +342,6 @@ impl SystemControlBlock for Processor {
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr3_u8(&self, offset: usize) -> u8 {
         match offset {
             0 => self.get_exception_priority(Exception::DebugMonitor) as u8,
@@ -346,7 +351,6 @@ impl SystemControlBlock for Processor {
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr1_u16(&self, offset: usize) -> u16 {
         match offset {
             0 | 1 => {
@@ -357,7 +361,6 @@ impl SystemControlBlock for Processor {
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr2_u16(&self, offset: usize) -> u16 {
         match offset {
             0 | 1 => {
@@ -368,7 +371,6 @@ impl SystemControlBlock for Processor {
         }
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn read_shpr3_u16(&self, offset: usize) -> u16 {
         match offset {
             0 | 1 => {
@@ -390,23 +392,71 @@ impl SystemControlBlock for Processor {
         0
     }
 
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn write_stir(&mut self, value: u32) {
         self.set_exception_pending(Exception::Interrupt {
             n: value.get_bits(0..9) as usize,
         });
     }
+
+    fn write_ccr(&mut self, value: u32) {
+        // NONBASETHRDENA, USERSETMPEND, UNALIGN_TRP, DIV_0_TRP, BFHFNMIGN, STKALIGN
+        self.ccr = value & 0x31b;
+    }
+
+    fn read_shcsr(&self) -> u32 {
+        let mut value = self.shcsr & 0xf_0000;
+        value.set_bit(0, self.exception_active(Exception::MemoryManagementFault));
+        value.set_bit(1, self.exception_active(Exception::BusFault));
+        value.set_bit(3, self.exception_active(Exception::UsageFault));
+        value.set_bit(4, self.exception_active(Exception::SecureFault));
+        value.set_bit(7, self.exception_active(Exception::SVCall));
+        value.set_bit(8, self.exception_active(Exception::DebugMonitor));
+        value.set_bit(10, self.exception_active(Exception::PendSV));
+        value.set_bit(11, self.exception_active(Exception::SysTick));
+        value
+    }
+
+    fn write_shcsr(&mut self, value: u32) {
+        // MEMFAULTENA, BUSFAULTENA, USGFAULTENA and SECUREFAULTENA
+        self.shcsr = if self.profile.has_security() {
+            value & 0xf_0000
+        } else {
+            value & 0x7_0000
+        };
+    }
+
+    fn write_cfsr(&mut self, value: u32) {
+        self.cfsr &= !value;
+    }
+
+    fn write_hfsr(&mut self, value: u32) {
+        self.hfsr &= !value;
+    }
+
+    fn write_aircr(&mut self, value: u32) {
+        if value.get_bits(16..32) != 0x05fa {
+            return;
+        }
+        if self.profile.is_mainline() {
+            // PRIGROUP
+            self.aircr.set_bits(8..11, value.get_bits(8..11));
+        }
+        if self.profile.has_security() && self.secure {
+            // BFHFNMINS, PRIS and SYSRESETREQS are writable from Secure state only
+            self.aircr.set_bits(13..15, value.get_bits(13..15));
+            self.aircr.set_bit(3, value.get_bit(3));
+        }
+        self.execution_priority = self.get_execution_priority();
+    }
 }
 
 #[cfg(test)]
-#[cfg(any(feature = "armv7m", feature = "armv7em"))]
 mod tests {
     use super::*;
     use crate::core::exception::Exception;
     use crate::core::exception::ExceptionHandling;
 
     #[test]
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn test_shpr_read_write_32() {
         // Arrange
         let mut processor = Processor::new();
@@ -443,7 +493,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(any(feature = "armv7m", feature = "armv7em"))]
     fn test_shpr_read_write_16() {
         // Arrange
         let mut processor = Processor::new();
@@ -4,7 +4,7 @@
 
 use crate::core::bits::Bits;
 use crate::core::exception::Exception;
-use crate::core::exception::ExceptionHandling;
+use crate::core::security::SecurityExtension;
 use crate::Processor;
 
 ///
@@ -70,6 +70,8 @@ impl SysTick for Processor {
 
     fn syst_write_csr(&mut self, value: u32) {
         self.syst_csr.set_bits(0..3, value.get_bits(0..3));
+        // a single timer is modelled, it belongs to the state that configured it
+        self.syst_secure = self.secure;
     }
 
     fn syst_read_csr(&mut self) -> u32 {
@@ -92,28 +94,45 @@ impl SysTick for Processor {
 
     #[inline(always)]
     fn syst_step(&mut self, cycles: u32) {
-        for _ in 0..cycles {
-            if (self.syst_csr & SYST_CSR_ENABLE) == SYST_CSR_ENABLE {
-                if self.syst_cvr > 0 {
-                    self.syst_cvr -= 1;
-
-                    if self.syst_cvr == 0 {
-                        self.syst_csr |= SYST_CSR_COUNTFLAG;
-                        if (self.syst_csr & SYST_CSR_TICKINT) == SYST_CSR_TICKINT {
-                            self.set_exception_pending(Exception::SysTick);
-                        }
-                    }
-                } else {
-                    self.syst_cvr = self.syst_rvr & 0x00ff_ffff;
-                }
+        if (self.syst_csr & SYST_CSR_ENABLE) != SYST_CSR_ENABLE || cycles == 0 {
+            return;
+        }
+        let reload = self.syst_rvr & 0x00ff_ffff;
+        let mut cycles = cycles;
+        if self.syst_cvr == 0 {
+            // the counter is reloaded on the cycle after reaching zero
+            self.syst_cvr = reload;
+            cycles -= 1;
+            if reload == 0 {
+                return;
             }
         }
+        if cycles < self.syst_cvr {
+            self.syst_cvr -= cycles;
+            return;
+        }
+
+        cycles -= self.syst_cvr;
+        self.syst_cvr = 0;
+        self.syst_csr |= SYST_CSR_COUNTFLAG;
+        if (self.syst_csr & SYST_CSR_TICKINT) == SYST_CSR_TICKINT {
+            self.set_banked_exception_pending(Exception::SysTick, self.syst_secure);
+        }
+
+        // each further period is a reload and `reload` decrements
+        if reload > 0 {
+            self.syst_cvr = match cycles % (reload + 1) {
+                0 => 0,
+                remaining => reload - (remaining - 1),
+            };
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::exception::ExceptionHandling;
     use crate::core::reset::Reset;
 
     #[test]
@@ -236,4 +255,33 @@ mod tests {
             SYST_CSR_COUNTFLAG | SYST_CSR_ENABLE | SYST_CSR_TICKINT
         );
     }
+
+    #[test]
+    fn test_batched_steps_match_single_cycles() {
+        for reload in [0, 1, 2, 5] {
+            for cycles in 0..14 {
+                // Arrange
+                let mut batched = Processor::new();
+                batched.reset().unwrap();
+                batched.syst_write_rvr(reload);
+                batched.syst_write_csr(SYST_CSR_ENABLE);
+                let mut single = Processor::new();
+                single.reset().unwrap();
+                single.syst_write_rvr(reload);
+                single.syst_write_csr(SYST_CSR_ENABLE);
+                single.syst_step(1);
+                batched.syst_step(1);
+
+                // Act
+                batched.syst_step(cycles);
+                for _ in 0..cycles {
+                    single.syst_step(1);
+                }
+
+                // Assert
+                assert_eq!(batched.syst_cvr, single.syst_cvr);
+                assert_eq!(batched.syst_read_csr(), single.syst_read_csr());
+            }
+        }
+    }
 }
@@ -159,6 +159,107 @@ pub enum SemihostingCommand {
         subcode: u32,
     },
     ///
+    /// Write null terminated string to the debug console
+    ///
+    SysWrite0 {
+        /// string to write, without the terminator
+        data: Vec<u8>,
+    },
+    ///
+    /// Read a byte from the debug console
+    ///
+    SysReadC,
+    ///
+    /// Check if a return code from other semihosting call is an error
+    ///
+    SysIsError {
+        /// status code to check
+        status: i32,
+    },
+    ///
+    /// Create a name for a temporary file
+    ///
+    SysTmpnam {
+        ///
+        /// location in memory to which the name is written
+        ///
+        memoryptr: u32,
+        ///
+        /// target identifier of the temporary file, 0..255
+        ///
+        id: u32,
+        ///
+        /// size of the buffer in bytes
+        ///
+        len: u32,
+    },
+    ///
+    /// Delete a file
+    ///
+    SysRemove {
+        /// name of the file
+        name: String,
+    },
+    ///
+    /// Rename a file
+    ///
+    SysRename {
+        /// current name of the file
+        from: String,
+        /// new name of the file
+        to: String,
+    },
+    ///
+    /// Get the number of seconds since 00:00 January 1, 1970
+    ///
+    SysTime,
+    ///
+    /// Pass a command to the host command line interpreter
+    ///
+    SysSystem {
+        /// command to execute
+        command: String,
+    },
+    ///
+    /// Get the system stack and heap parameters
+    ///
+    SysHeapinfo {
+        ///
+        /// location of the four word block to which the parameters are written
+        ///
+        memoryptr: u32,
+    },
+    ///
+    /// Get the number of elapsed target ticks since the execution started
+    ///
+    SysElapsed {
+        ///
+        /// location of the two word block to which the tick count is written
+        ///
+        memoryptr: u32,
+    },
+    ///
+    /// Get the frequency of the ticks of `SysElapsed`
+    ///
+    SysTickFreq,
+    ///
+    /// Get the command line of the application
+    ///
+    SysGetCmdline {
+        ///
+        /// location of the argument block, updated with the length of the command line
+        ///
+        params_ptr: u32,
+        ///
+        /// location in memory to which the command line is written
+        ///
+        memoryptr: u32,
+        ///
+        /// size of the buffer in bytes
+        ///
+        len: u32,
+    },
+    ///
     /// Get the value of sysclock
     ///
     SysClock,
@@ -166,6 +267,13 @@ pub enum SemihostingCommand {
     /// Get the value of errno
     ///
     SysErrno,
+    ///
+    /// Operation number not supported by the host
+    ///
+    SysUnsupported {
+        /// the operation number
+        operation: u32,
+    },
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -224,6 +332,61 @@ pub enum SemihostingResponse {
         /// subcode of the exit, dependant of the reason
         exit_code: Option<u32>,
     },
+    /// sysreadc command response
+    SysReadC {
+        /// result Ok = byte read, Err = error code
+        result: Result<u8, i32>,
+    },
+    /// sysiserror command response
+    SysIsError {
+        /// status code was an error
+        result: bool,
+    },
+    /// systmpnam command response
+    SysTmpnam {
+        /// result Ok = (buffer, name), Err = error code
+        result: Result<(u32, Vec<u8>), i32>,
+    },
+    /// sysremove command response
+    SysRemove {
+        /// result
+        success: bool,
+    },
+    /// sysrename command response
+    SysRename {
+        /// result
+        success: bool,
+    },
+    /// systime command response
+    SysTime {
+        /// result Ok = seconds since the epoch, Err = error code
+        result: Result<u32, i32>,
+    },
+    /// syssystem command response
+    SysSystem {
+        /// result Ok = exit status of the command, Err = error code
+        result: Result<u32, i32>,
+    },
+    /// sysheapinfo command response
+    SysHeapinfo {
+        /// location of the block and heap base, heap limit, stack base, stack limit
+        result: (u32, [u32; 4]),
+    },
+    /// syselapsed command response
+    SysElapsed {
+        /// result Ok = (block, tick count), Err = error code
+        result: Result<(u32, u64), i32>,
+    },
+    /// systickfreq command response
+    SysTickFreq {
+        /// result Ok = ticks per second, Err = error code
+        result: Result<u32, i32>,
+    },
+    /// sysgetcmdline command response
+    SysGetCmdline {
+        /// result Ok = (argument block, buffer, command line), Err = error code
+        result: Result<(u32, u32, Vec<u8>), i32>,
+    },
     /// sysclock command response
     SysClock {
         /// result Ok = value, Err = error code
@@ -234,20 +397,45 @@ pub enum SemihostingResponse {
         /// result
         result: u32,
     },
+    /// unsupported operation response, the host sets errno
+    SysUnsupported,
 }
 
 const SYS_OPEN: u32 = 0x01;
 const SYS_CLOSE: u32 = 0x02;
 const SYS_WRITEC: u32 = 0x03;
+const SYS_WRITE0: u32 = 0x04;
 const SYS_WRITE: u32 = 0x05;
 const SYS_READ: u32 = 0x06;
+const SYS_READC: u32 = 0x07;
+const SYS_ISERROR: u32 = 0x08;
 const SYS_ISTTY: u32 = 0x09;
 const SYS_SEEK: u32 = 0x0a;
 const SYS_FLEN: u32 = 0x0c;
+const SYS_TMPNAM: u32 = 0x0d;
+const SYS_REMOVE: u32 = 0x0e;
+const SYS_RENAME: u32 = 0x0f;
 const SYS_CLOCK: u32 = 0x10;
+const SYS_TIME: u32 = 0x11;
+const SYS_SYSTEM: u32 = 0x12;
 const SYS_ERRNO: u32 = 0x13;
+const SYS_GET_CMDLINE: u32 = 0x15;
+const SYS_HEAPINFO: u32 = 0x16;
 const SYS_EXIT: u32 = 0x18;
 const SYS_EXIT_EXTENDED: u32 = 0x20;
+const SYS_ELAPSED: u32 = 0x30;
+const SYS_TICKFREQ: u32 = 0x31;
+
+///
+/// Read a string of given length from memory
+///
+fn read_string(processor: &mut Processor, ptr: u32, len: u32) -> Result<String, Fault> {
+    let mut bytes = Vec::with_capacity(len as usize);
+    for addr in ptr..ptr.wrapping_add(len) {
+        bytes.push(processor.read8(addr)?);
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
 
 ///
 /// Decode semihosting command based on register values
@@ -334,8 +522,87 @@ pub fn decode_semihostcmd(
 
             SemihostingCommand::SysSeek { handle, position }
         }
+        SYS_WRITE0 => {
+            let mut string_ptr = r1;
+            let mut data: Vec<u8> = Vec::new();
+
+            loop {
+                let ch = processor.read8(string_ptr)?;
+                if ch == 0 {
+                    break;
+                }
+                data.push(ch);
+                string_ptr += 1;
+            }
+            SemihostingCommand::SysWrite0 { data }
+        }
+        SYS_READC => SemihostingCommand::SysReadC,
+        SYS_ISERROR => {
+            let params_ptr = r1;
+            let status = processor.read32(params_ptr)? as i32;
+
+            SemihostingCommand::SysIsError { status }
+        }
+        SYS_TMPNAM => {
+            let params_ptr = r1;
+            let memoryptr = processor.read32(params_ptr)?;
+            let id = processor.read32(params_ptr + 4)?;
+            let len = processor.read32(params_ptr + 8)?;
+
+            SemihostingCommand::SysTmpnam { memoryptr, id, len }
+        }
+        SYS_REMOVE => {
+            let params_ptr = r1;
+            let string_ptr = processor.read32(params_ptr)?;
+            let len = processor.read32(params_ptr + 4)?;
+
+            SemihostingCommand::SysRemove {
+                name: read_string(processor, string_ptr, len)?,
+            }
+        }
+        SYS_RENAME => {
+            let params_ptr = r1;
+            let from_ptr = processor.read32(params_ptr)?;
+            let from_len = processor.read32(params_ptr + 4)?;
+            let to_ptr = processor.read32(params_ptr + 8)?;
+            let to_len = processor.read32(params_ptr + 12)?;
+
+            SemihostingCommand::SysRename {
+                from: read_string(processor, from_ptr, from_len)?,
+                to: read_string(processor, to_ptr, to_len)?,
+            }
+        }
+        SYS_SYSTEM => {
+            let params_ptr = r1;
+            let string_ptr = processor.read32(params_ptr)?;
+            let len = processor.read32(params_ptr + 4)?;
+
+            SemihostingCommand::SysSystem {
+                command: read_string(processor, string_ptr, len)?,
+            }
+        }
+        SYS_HEAPINFO => {
+            // the argument block holds a pointer to the parameter block
+            let memoryptr = processor.read32(r1)?;
+
+            SemihostingCommand::SysHeapinfo { memoryptr }
+        }
         SYS_CLOCK => SemihostingCommand::SysClock,
+        SYS_TIME => SemihostingCommand::SysTime,
+        SYS_ELAPSED => SemihostingCommand::SysElapsed { memoryptr: r1 },
+        SYS_TICKFREQ => SemihostingCommand::SysTickFreq,
         SYS_ERRNO => SemihostingCommand::SysErrno,
+        SYS_GET_CMDLINE => {
+            let params_ptr = r1;
+            let memoryptr = processor.read32(params_ptr)?;
+            let len = processor.read32(params_ptr + 4)?;
+
+            SemihostingCommand::SysGetCmdline {
+                params_ptr,
+                memoryptr,
+                len,
+            }
+        }
         SYS_EXIT_EXTENDED => {
             let params_ptr = r1;
 
@@ -350,9 +617,7 @@ pub fn decode_semihostcmd(
         SYS_EXIT => SemihostingCommand::SysException {
             reason: SysExceptionReason::from_u32(r1),
         },
-        _ => {
-            todo!("unknown semihosting command {}", r0);
-        }
+        operation => SemihostingCommand::SysUnsupported { operation },
     };
     Ok(result)
 }
@@ -414,6 +679,59 @@ pub fn semihost_return(processor: &mut Processor, response: &SemihostingResponse
             }
             Err(error_code) => processor.set_r(Reg::R0, *error_code as u32),
         },
+        SemihostingResponse::SysReadC { result } => match result {
+            Ok(byte) => processor.set_r(Reg::R0, u32::from(byte)),
+            Err(error_code) => processor.set_r(Reg::R0, error_code as u32),
+        },
+        SemihostingResponse::SysIsError { result } => {
+            processor.set_r(Reg::R0, u32::from(result));
+        }
+        SemihostingResponse::SysTmpnam { ref result } => match result {
+            Ok((memoryptr, name)) => {
+                for (addr, x) in (*memoryptr..).zip(name.iter().chain(&[0])) {
+                    processor.write8(addr, *x);
+                }
+                processor.set_r(Reg::R0, 0);
+            }
+            Err(error_code) => processor.set_r(Reg::R0, *error_code as u32),
+        },
+        SemihostingResponse::SysRemove { success }
+        | SemihostingResponse::SysRename { success } => {
+            // host specific error code on failure
+            processor.set_r(Reg::R0, u32::from(!success));
+        }
+        SemihostingResponse::SysTime { result }
+        | SemihostingResponse::SysSystem { result }
+        | SemihostingResponse::SysTickFreq { result } => match result {
+            Ok(value) => processor.set_r(Reg::R0, value),
+            Err(error_code) => processor.set_r(Reg::R0, error_code as u32),
+        },
+        SemihostingResponse::SysHeapinfo {
+            result: (memoryptr, ref values),
+        } => {
+            for (addr, value) in (memoryptr..).step_by(4).zip(values) {
+                processor.write32(addr, *value);
+            }
+        }
+        SemihostingResponse::SysElapsed { result } => match result {
+            Ok((memoryptr, ticks)) => {
+                processor.write32(memoryptr, ticks as u32);
+                processor.write32(memoryptr + 4, (ticks >> 32) as u32);
+                processor.set_r(Reg::R0, 0);
+            }
+            Err(error_code) => processor.set_r(Reg::R0, error_code as u32),
+        },
+        SemihostingResponse::SysGetCmdline { ref result } => match result {
+            Ok((params_ptr, memoryptr, cmdline)) => {
+                // null terminated string, length excludes the terminator
+                for (addr, x) in (*memoryptr..).zip(cmdline.iter().chain(&[0])) {
+                    processor.write8(addr, *x);
+                }
+                processor.write32(params_ptr + 4, cmdline.len() as u32);
+                processor.set_r(Reg::R0, 0);
+            }
+            Err(error_code) => processor.set_r(Reg::R0, *error_code as u32),
+        },
         SemihostingResponse::SysClock { result } => match result {
             Ok(centiseconds) => processor.set_r(Reg::R0, centiseconds),
             Err(error_code) => processor.set_r(Reg::R0, error_code as u32),
@@ -421,5 +739,107 @@ pub fn semihost_return(processor: &mut Processor, response: &SemihostingResponse
         SemihostingResponse::SysErrno { result } => {
             processor.set_r(Reg::R0, result);
         }
+        SemihostingResponse::SysUnsupported => {
+            processor.set_r(Reg::R0, (-1_i32) as u32);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_cmdline() {
+        // Arrange
+        let mut processor = Processor::new();
+        let params_ptr = 0x2000_0000;
+        let buffer = 0x2000_0100;
+        processor.write32(params_ptr, buffer).unwrap();
+        processor.write32(params_ptr + 4, 80).unwrap();
+
+        // Act
+        let command = decode_semihostcmd(SYS_GET_CMDLINE, params_ptr, &mut processor).unwrap();
+        semihost_return(
+            &mut processor,
+            &SemihostingResponse::SysGetCmdline {
+                result: Ok((params_ptr, buffer, b"app 1".to_vec())),
+            },
+        );
+
+        // Assert
+        assert_eq!(
+            command,
+            SemihostingCommand::SysGetCmdline {
+                params_ptr,
+                memoryptr: buffer,
+                len: 80
+            }
+        );
+        assert_eq!(processor.read32(params_ptr + 4).unwrap(), 5);
+        assert_eq!(processor.read32(buffer).unwrap(), 0x2070_7061);
+        assert_eq!(processor.read16(buffer + 4).unwrap(), 0x0031);
+        assert_eq!(processor.get_r(Reg::R0), 0);
+    }
+
+    #[test]
+    fn test_heapinfo() {
+        // Arrange
+        let mut processor = Processor::new();
+        let params_ptr = 0x2000_0000;
+        let block = 0x2000_0100;
+        processor.write32(params_ptr, block).unwrap();
+
+        // Act
+        let command = decode_semihostcmd(SYS_HEAPINFO, params_ptr, &mut processor).unwrap();
+        semihost_return(
+            &mut processor,
+            &SemihostingResponse::SysHeapinfo {
+                result: (block, [0x2000_1000, 0x2000_2000, 0x2000_8000, 0x2000_7000]),
+            },
+        );
+
+        // Assert
+        assert_eq!(command, SemihostingCommand::SysHeapinfo { memoryptr: block });
+        assert_eq!(processor.read32(block).unwrap(), 0x2000_1000);
+        assert_eq!(processor.read32(block + 12).unwrap(), 0x2000_7000);
+    }
+
+    #[test]
+    fn test_rename() {
+        // Arrange
+        let mut processor = Processor::new();
+        let params_ptr = 0x2000_0000;
+        for (i, value) in [0x2000_0100, 3, 0x2000_0104, 2].iter().enumerate() {
+            processor.write32(params_ptr + 4 * i as u32, *value).unwrap();
+        }
+        processor.write32(0x2000_0100, 0x6f6f_6261).unwrap();
+        processor.write32(0x2000_0104, 0x0000_7978).unwrap();
+
+        // Act
+        let command = decode_semihostcmd(SYS_RENAME, params_ptr, &mut processor).unwrap();
+
+        // Assert
+        assert_eq!(
+            command,
+            SemihostingCommand::SysRename {
+                from: "abo".to_string(),
+                to: "xy".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_unsupported_operation() {
+        // Arrange
+        let mut processor = Processor::new();
+
+        // Act
+        let command = decode_semihostcmd(0x40, 0x2000_0000, &mut processor).unwrap();
+        semihost_return(&mut processor, &SemihostingResponse::SysUnsupported);
+
+        // Assert
+        assert_eq!(command, SemihostingCommand::SysUnsupported { operation: 0x40 });
+        assert_eq!(processor.get_r(Reg::R0), 0xffff_ffff);
     }
 }
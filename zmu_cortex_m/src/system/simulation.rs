@@ -2,14 +2,20 @@
 //! Cortex system simulation framework
 //!
 
+use crate::core::exception::{Exception, LockupReason};
 use crate::core::fault::Fault;
-use crate::core::register::BaseReg;
+use crate::core::instruction::Instruction;
+use crate::core::profile::ArchProfile;
+use crate::core::register::{BaseReg, Reg};
 use crate::core::reset::Reset;
 use crate::executor::Executor;
 use crate::semihosting::SemihostingCommand;
 use crate::semihosting::SemihostingResponse;
-use crate::MemoryMapConfig;
+use crate::device::create_device;
+use crate::memory::image::Segment;
+use crate::memory::map::MemoryMapConfig;
 use crate::Processor;
+use std::collections::BTreeMap;
 use std::io;
 use std::time::Duration;
 use std::time::Instant;
@@ -21,7 +27,264 @@ pub enum SimulationError {
     ///
     /// A fault was triggered and escalated to stop the simulation
     ///
-    FaultTrap,
+    FaultTrap {
+        ///
+        /// The fault
+        ///
+        fault: Fault,
+        ///
+        /// The faulting instruction, if the fault was caused by one
+        ///
+        instruction: Option<Instruction>,
+        ///
+        /// Processor state at the time of the fault, PC at the faulting instruction
+        ///
+        state: Box<ProcessorState>,
+    },
+
+    ///
+    /// An execution limit of the configuration was reached
+    ///
+    LimitReached {
+        ///
+        /// The limit that stopped the simulation
+        ///
+        limit: Limit,
+        ///
+        /// Processor state at the time the limit was reached
+        ///
+        state: Box<ProcessorState>,
+    },
+
+    ///
+    /// The processor entered the Lockup state on a fault it could not handle
+    ///
+    Lockup {
+        ///
+        /// Address of the faulting instruction, the return address of the
+        /// exception that could not be entered or the vector table address
+        /// for a fault during reset
+        ///
+        pc: u32,
+        ///
+        /// The fault that locked up the processor
+        ///
+        reason: LockupReason,
+        ///
+        /// Processor state at the time of the lockup
+        ///
+        state: Box<ProcessorState>,
+    },
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+///
+/// Execution limits of the simulation run
+///
+pub enum Limit {
+    ///
+    /// `SimulationConfig::max_instructions` instructions were executed
+    ///
+    Instructions,
+    ///
+    /// `SimulationConfig::max_cycles` clock cycles were simulated
+    ///
+    Cycles,
+    ///
+    /// The simulation ran longer than `SimulationConfig::timeout`
+    ///
+    Timeout,
+}
+
+#[derive(Debug, Clone)]
+///
+/// Snapshot of the core registers, for diagnostics
+///
+pub struct ProcessorState {
+    ///
+    /// r0-r12, SP, LR and PC
+    ///
+    pub registers: [u32; 16],
+
+    ///
+    /// Program status register
+    ///
+    pub psr: u32,
+
+    ///
+    /// Main stack pointer
+    ///
+    pub msp: u32,
+
+    ///
+    /// Process stack pointer
+    ///
+    pub psp: u32,
+
+    ///
+    /// Number of instructions executed so far
+    ///
+    pub instruction_count: u64,
+
+    ///
+    /// Number of clock cycles simulated so far
+    ///
+    pub cycle_count: u64,
+
+    ///
+    /// Number of clock cycles the processor has been sleeping
+    ///
+    pub sleep_cycle_count: u64,
+
+    ///
+    /// Number of times each exception was taken, by exception number
+    ///
+    pub exceptions: BTreeMap<usize, u64>,
+
+    ///
+    /// Number of semihosting calls made so far
+    ///
+    pub semihost_call_count: u64,
+}
+
+impl ProcessorState {
+    ///
+    /// Current program counter
+    ///
+    pub fn pc(&self) -> u32 {
+        self.registers[15]
+    }
+
+    ///
+    /// Statistics of a run that was stopped in this state
+    ///
+    pub fn statistics(&self, duration: Duration, exit_code: u32) -> SimulationStatistics {
+        SimulationStatistics {
+            instruction_count: self.instruction_count,
+            cycle_count: self.cycle_count,
+            sleep_cycle_count: self.sleep_cycle_count,
+            exceptions: self.exceptions.clone(),
+            semihost_call_count: self.semihost_call_count,
+            duration,
+            exit_code,
+        }
+    }
+}
+
+///
+/// Number of times each exception was taken. Exceptions that were never
+/// taken are left out.
+///
+fn taken_exceptions(processor: &Processor) -> BTreeMap<usize, u64> {
+    processor
+        .exceptions
+        .iter()
+        .filter(|(_, state)| state.taken_count() > 0)
+        .map(|(&number, state)| (number, state.taken_count()))
+        .collect()
+}
+
+impl From<&Processor> for ProcessorState {
+    fn from(processor: &Processor) -> Self {
+        let mut registers = [0; 16];
+        registers[..13].copy_from_slice(&processor.r0_12);
+        registers[13] = processor.get_r(Reg::SP);
+        registers[14] = processor.lr;
+        registers[15] = processor.pc;
+        Self {
+            registers,
+            psr: processor.psr.value,
+            msp: processor.msp,
+            psp: processor.psp,
+            instruction_count: processor.instruction_count,
+            cycle_count: processor.cycle_count,
+            sleep_cycle_count: processor.sleep_cycle_count,
+            exceptions: taken_exceptions(processor),
+            semihost_call_count: processor.semihost_call_count,
+        }
+    }
+}
+
+///
+/// Configuration of the simulated system
+///
+#[derive(Default)]
+pub struct SimulationConfig {
+    ///
+    /// Architecture profile of the core, default profile of the build if not given
+    ///
+    pub profile: Option<ArchProfile>,
+
+    ///
+    /// Initialized memory content from the executable image
+    ///
+    pub image: Vec<Segment>,
+
+    ///
+    /// RAM banks as (start address, size) pairs, default RAM is used if empty
+    ///
+    pub ram: Vec<(u32, usize)>,
+
+    ///
+    /// Flash regions as (start address, size) pairs. Erased flash is
+    /// read as 0xff. If empty, flash regions are sized after the image.
+    ///
+    pub flash: Vec<(u32, usize)>,
+
+    ///
+    /// Address aliases, such as boot memory mirrored to address 0
+    ///
+    pub aliases: Vec<MemoryMapConfig>,
+
+    ///
+    /// Value of the CPUID register
+    ///
+    pub cpuid: Option<u32>,
+
+    ///
+    /// Number of external interrupts supported by the NVIC
+    ///
+    pub nvic_irqs: Option<usize>,
+
+    ///
+    /// Peripheral device models, replacing the default device if not empty
+    ///
+    pub devices: Vec<DeviceConfig>,
+
+    ///
+    /// Stop the simulation on the first fault instead of taking the fault exception
+    ///
+    pub fault_trap: bool,
+
+    ///
+    /// Stop the simulation after this many instructions
+    ///
+    pub max_instructions: Option<u64>,
+
+    ///
+    /// Stop the simulation after this many clock cycles
+    ///
+    pub max_cycles: Option<u64>,
+
+    ///
+    /// Stop the simulation after this much wallclock time
+    ///
+    pub timeout: Option<Duration>,
+}
+
+///
+/// Peripheral device model placed on the bus
+///
+pub struct DeviceConfig {
+    ///
+    /// Name of the model, one of `device::DEVICE_MODELS`
+    ///
+    pub model: String,
+
+    ///
+    /// Base address of the device, model default if not given
+    ///
+    pub base_address: Option<u32>,
 }
 
 ///
@@ -39,6 +302,22 @@ pub struct SimulationStatistics {
     ///
     pub cycle_count: u64,
 
+    ///
+    /// Number of system clock cycles the processor was sleeping.
+    ///
+    pub sleep_cycle_count: u64,
+
+    ///
+    /// Number of times each exception was taken, by exception number.
+    /// Exceptions that were never taken are left out.
+    ///
+    pub exceptions: BTreeMap<usize, u64>,
+
+    ///
+    /// Number of semihosting calls made by the firmware.
+    ///
+    pub semihost_call_count: u64,
+
     ///
     /// Wallclock time spent for the simulation
     ///
@@ -50,103 +329,434 @@ pub struct SimulationStatistics {
     pub exit_code: u32
 }
 
-impl From<Fault> for SimulationError {
-    fn from(_fault: Fault) -> Self {
-        Self::FaultTrap
+impl SimulationStatistics {
+    fn new(processor: &Processor, duration: Duration) -> Self {
+        Self {
+            instruction_count: processor.instruction_count,
+            cycle_count: processor.cycle_count,
+            sleep_cycle_count: processor.sleep_cycle_count,
+            exceptions: taken_exceptions(processor),
+            semihost_call_count: processor.semihost_call_count,
+            duration,
+            exit_code: processor.exit_code,
+        }
     }
 }
 
+impl SimulationError {
+    ///
+    /// Error for the fault that stopped the processor in fault trap mode, if any
+    ///
+    fn fault_trap(processor: &Processor) -> Option<Self> {
+        processor
+            .trapped_fault
+            .map(|(fault, instruction)| Self::FaultTrap {
+                fault,
+                instruction,
+                state: Box::new(ProcessorState::from(processor)),
+            })
+    }
+
+    ///
+    /// Error for the Lockup state that stopped the processor, if any
+    ///
+    fn lockup(processor: &Processor) -> Option<Self> {
+        processor.lockup.map(|(reason, pc)| Self::Lockup {
+            pc,
+            reason,
+            state: Box::new(ProcessorState::from(processor)),
+        })
+    }
+
+    ///
+    /// Error for a fault during the processor reset, reading the vector table.
+    /// The processor locks up as on a fault on any other exception entry.
+    ///
+    pub(crate) fn reset_fault(fault: Fault, processor: &Processor) -> Self {
+        Self::Lockup {
+            pc: processor.vtor,
+            reason: LockupReason::ExceptionEntry {
+                exception: Exception::Reset,
+                fault,
+            },
+            state: Box::new(ProcessorState::from(processor)),
+        }
+    }
+}
+
+const TIMEOUT_CHECK_INTERVAL: u32 = 0x1_0000;
+
+///
+/// Execution limits of a run, checked between instructions or blocks
+///
+struct RunLimits {
+    max_instructions: u64,
+    max_cycles: u64,
+    deadline: Option<Instant>,
+    steps: u32,
+}
+
+impl RunLimits {
+    fn new(config: &SimulationConfig, start: Instant) -> Self {
+        Self {
+            max_instructions: config.max_instructions.unwrap_or(u64::MAX),
+            max_cycles: config.max_cycles.unwrap_or(u64::MAX),
+            deadline: config.timeout.map(|timeout| start + timeout),
+            steps: TIMEOUT_CHECK_INTERVAL,
+        }
+    }
+
+    ///
+    /// Number of instructions that can be executed before the instruction limit
+    ///
+    #[inline(always)]
+    fn remaining_instructions(&self, processor: &Processor) -> u64 {
+        self.max_instructions - processor.instruction_count
+    }
+
+    #[inline(always)]
+    fn check(&mut self, processor: &Processor) -> Result<(), SimulationError> {
+        let limit = if processor.instruction_count >= self.max_instructions {
+            Some(Limit::Instructions)
+        } else if processor.cycle_count >= self.max_cycles {
+            Some(Limit::Cycles)
+        } else {
+            // reading the clock is slow, only do it once in a while
+            self.steps -= 1;
+            if self.steps == 0 {
+                self.steps = TIMEOUT_CHECK_INTERVAL;
+                match self.deadline {
+                    Some(deadline) if Instant::now() >= deadline => Some(Limit::Timeout),
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        };
+
+        match limit {
+            Some(limit) => Err(SimulationError::LimitReached {
+                limit,
+                state: Box::new(ProcessorState::from(processor)),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+///
+/// Create a processor with memories set up according to the configuration
+///
+pub(crate) fn configure_processor(config: &SimulationConfig) -> Processor {
+    let mut processor = Processor::new();
+    if let Some(profile) = config.profile {
+        processor.profile(profile);
+    }
+    processor.fault_trap(config.fault_trap);
+    if !config.ram.is_empty() {
+        processor.ram_memory(&config.ram);
+    }
+
+    // erased flash regions, overlaid with the image content
+    let mut image: Vec<Segment> = config
+        .flash
+        .iter()
+        .map(|&(address, size)| Segment {
+            address,
+            data: vec![0xff; size],
+        })
+        .collect();
+    image.extend(config.image.iter().cloned());
+    processor.load_image(&image);
+
+    for alias in &config.aliases {
+        processor.memory_alias(*alias);
+    }
+    if let Some(cpuid) = config.cpuid {
+        processor.cpuid = cpuid;
+    }
+    if let Some(irq_count) = config.nvic_irqs {
+        processor.nvic_irqs(irq_count);
+    }
+    if !config.devices.is_empty() {
+        processor.devices(
+            config
+                .devices
+                .iter()
+                .filter_map(|d| create_device(&d.model, d.base_address))
+                .collect(),
+        );
+    }
+    processor.cache_instructions();
+    processor
+}
+
 ///
 /// Run simulation until processing gets terminated
 ///
 pub fn simulate(
-    code: &[u8],
+    config: &SimulationConfig,
     semihost_func: Box<dyn FnMut(&SemihostingCommand) -> SemihostingResponse + 'static>,
     itm_file: Option<Box<dyn io::Write + 'static>>,
-    map: Option<MemoryMapConfig>,
-    flash_size: usize,
 ) -> Result<SimulationStatistics, SimulationError> {
-    let mut processor = Processor::new();
+    let mut processor = configure_processor(config);
 
     processor.itm(itm_file);
     processor.semihost(Some(semihost_func));
-    processor.memory_map(map);
-    processor.flash_memory(flash_size, code);
-    //processor.ram_memory(ram_size);
-
-    processor.cache_instructions();
 
     let start = Instant::now();
-    processor.reset()?;
+    let mut limits = RunLimits::new(config, start);
+    processor
+        .reset()
+        .map_err(|fault| SimulationError::reset_fault(fault, &processor))?;
     processor.running = true;
 
     while processor.running {
         while !processor.sleeping && processor.running {
             //running, !sleeping
-            processor.step();
+            limits.check(&processor)?;
+            processor.step_block(limits.remaining_instructions(&processor));
         }
 
         while processor.sleeping && processor.running{
             //running, sleeping
+            limits.check(&processor)?;
             processor.step_sleep();
         }
     }
     let end = Instant::now();
 
-    Ok(SimulationStatistics {
-        instruction_count: processor.instruction_count,
-        cycle_count: processor.cycle_count,
-        duration: end.duration_since(start),
-        exit_code: processor.exit_code,
-    })
+    if let Some(error) =
+        SimulationError::fault_trap(&processor).or_else(|| SimulationError::lockup(&processor))
+    {
+        return Err(error);
+    }
+    Ok(SimulationStatistics::new(
+        &processor,
+        end.duration_since(start),
+    ))
 }
 
 ///
 /// Run System simulation with tracing support
 ///
 pub fn simulate_trace<F>(
-    code: &[u8],
+    config: &SimulationConfig,
     mut trace_func: F,
     semihost_func: Box<dyn FnMut(&SemihostingCommand) -> SemihostingResponse + 'static>,
     itm_file: Option<Box<dyn io::Write + 'static>>,
-    map: Option<MemoryMapConfig>,
-    flash_size: usize,
 ) -> Result<SimulationStatistics, SimulationError>
 where
-    F: FnMut(&Processor),
+    F: FnMut(&mut Processor),
 {
-    let mut processor = Processor::new();
+    let mut processor = configure_processor(config);
     processor.itm(itm_file);
     processor.semihost(Some(semihost_func));
-    processor.memory_map(map);
-    processor.flash_memory(flash_size, code);
-    processor.cache_instructions();
 
     let start = Instant::now();
+    let mut limits = RunLimits::new(config, start);
 
-    processor.reset().unwrap();
+    processor
+        .reset()
+        .map_err(|fault| SimulationError::reset_fault(fault, &processor))?;
     processor.running = true;
 
     while processor.running {
         while !processor.sleeping && processor.running {
             //running, !sleeping
+            limits.check(&processor)?;
             processor.last_pc = processor.get_pc();
             processor.step();
-            trace_func(&processor);
+            trace_func(&mut processor);
         }
         processor.last_pc = processor.get_pc();
         while processor.sleeping && processor.running {
             //running, sleeping
+            limits.check(&processor)?;
             processor.step_sleep();
         }
     }
 
     let end = Instant::now();
 
-    Ok(SimulationStatistics {
-        instruction_count: processor.instruction_count,
-        cycle_count: processor.cycle_count,
-        duration: end.duration_since(start),
-        exit_code: processor.exit_code
-    })
+    if let Some(error) =
+        SimulationError::fault_trap(&processor).or_else(|| SimulationError::lockup(&processor))
+    {
+        return Err(error);
+    }
+    Ok(SimulationStatistics::new(
+        &processor,
+        end.duration_since(start),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endless_loop_config() -> SimulationConfig {
+        // vector table with the reset handler at 0x8: "b ."
+        let data = [
+            0x00, 0x10, 0x00, 0x20, 0x09, 0x00, 0x00, 0x00, 0xfe, 0xe7, 0x00, 0x00,
+        ];
+        SimulationConfig {
+            image: vec![Segment {
+                address: 0,
+                data: data.to_vec(),
+            }],
+            ..SimulationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut data = vec![0; 0x54];
+        data[0..4].copy_from_slice(&0x2000_1000_u32.to_le_bytes());
+        data[4..8].copy_from_slice(&0x41_u32.to_le_bytes());
+        // PendSV handler: "bx lr"
+        data[0x38..0x3c].copy_from_slice(&0x4b_u32.to_le_bytes());
+        // set ICSR.PENDSVSET, then exit via semihosting
+        // "ldr r1, =ICSR", "ldr r2, =PENDSVSET", "str r2, [r1]", "movs r0, #0x18", "bkpt 0xab"
+        for (i, opcode) in [0x4902_u16, 0x4a03, 0x600a, 0x2018, 0xbeab, 0x4770]
+            .iter()
+            .enumerate()
+        {
+            data[0x40 + i * 2..0x42 + i * 2].copy_from_slice(&opcode.to_le_bytes());
+        }
+        data[0x4c..0x50].copy_from_slice(&0xe000_ed04_u32.to_le_bytes());
+        data[0x50..0x54].copy_from_slice(&0x1000_0000_u32.to_le_bytes());
+        let config = SimulationConfig {
+            image: vec![Segment { address: 0, data }],
+            ..SimulationConfig::default()
+        };
+
+        let semihost_func = Box::new(|_: &SemihostingCommand| SemihostingResponse::SysException {
+            success: true,
+            stop: true,
+        });
+        let Ok(statistics) = simulate(&config, semihost_func, None) else {
+            panic!("simulation failed");
+        };
+        assert_eq!(statistics.instruction_count, 6);
+        assert_eq!(statistics.semihost_call_count, 1);
+        assert_eq!(statistics.sleep_cycle_count, 0);
+        assert_eq!(
+            statistics.exceptions,
+            BTreeMap::from([(usize::from(Exception::PendSV), 1)])
+        );
+    }
+
+    #[test]
+    fn test_fault_trap() {
+        // reset handler at 0x8: "udf #0"
+        let data = [
+            0x00, 0x10, 0x00, 0x20, 0x09, 0x00, 0x00, 0x00, 0x00, 0xde, 0x00, 0x00,
+        ];
+        let config = SimulationConfig {
+            image: vec![Segment {
+                address: 0,
+                data: data.to_vec(),
+            }],
+            fault_trap: true,
+            ..SimulationConfig::default()
+        };
+        match simulate(&config, Box::new(|_| unreachable!()), None) {
+            Err(SimulationError::FaultTrap {
+                fault,
+                instruction,
+                state,
+            }) => {
+                assert_eq!(fault, Fault::UndefInstr);
+                assert!(matches!(instruction, Some(Instruction::UDF { .. })));
+                assert_eq!(state.pc(), 0x8);
+                assert_eq!(state.instruction_count, 1);
+            }
+            _ => panic!("fault not trapped"),
+        }
+    }
+
+    #[test]
+    fn test_lockup() {
+        // reset and HardFault handlers at 0x10: "udf #0"
+        let data = [
+            0x00, 0x10, 0x00, 0x20, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x00,
+            0x00, 0x00, 0x00, 0xde,
+        ];
+        let config = SimulationConfig {
+            image: vec![Segment {
+                address: 0,
+                data: data.to_vec(),
+            }],
+            ..SimulationConfig::default()
+        };
+        match simulate(&config, Box::new(|_| unreachable!()), None) {
+            Err(SimulationError::Lockup { pc, reason, state }) => {
+                assert_eq!(pc, 0x10);
+                assert_eq!(reason, LockupReason::Fault(Fault::UndefInstr));
+                assert_eq!(state.instruction_count, 2);
+            }
+            _ => panic!("processor not locked up"),
+        }
+    }
+
+    #[test]
+    fn test_reset_lockup() {
+        // vector table without the reset vector
+        let config = SimulationConfig {
+            image: vec![Segment {
+                address: 0,
+                data: vec![0x00, 0x10, 0x00, 0x20],
+            }],
+            ..SimulationConfig::default()
+        };
+        match simulate(&config, Box::new(|_| unreachable!()), None) {
+            Err(SimulationError::Lockup { pc, reason, state }) => {
+                assert_eq!(pc, 0);
+                assert!(matches!(
+                    reason,
+                    LockupReason::ExceptionEntry {
+                        exception: Exception::Reset,
+                        ..
+                    }
+                ));
+                assert_eq!(state.instruction_count, 0);
+            }
+            _ => panic!("processor not locked up"),
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut config = endless_loop_config();
+        config.max_instructions = Some(100);
+        match simulate(&config, Box::new(|_| unreachable!()), None) {
+            Err(SimulationError::LimitReached { limit, state }) => {
+                assert_eq!(limit, Limit::Instructions);
+                assert_eq!(state.instruction_count, 100);
+                assert_eq!(state.pc(), 0x8);
+                assert_eq!(state.registers[13], 0x2000_1000);
+                let statistics = state.statistics(Duration::ZERO, 124);
+                assert_eq!(statistics.instruction_count, 100);
+                assert_eq!(statistics.exit_code, 124);
+            }
+            _ => panic!("instruction limit not reached"),
+        }
+
+        let mut config = endless_loop_config();
+        config.max_cycles = Some(1000);
+        match simulate(&config, Box::new(|_| unreachable!()), None) {
+            Err(SimulationError::LimitReached { limit, state }) => {
+                assert_eq!(limit, Limit::Cycles);
+                assert!(state.cycle_count >= 1000);
+            }
+            _ => panic!("cycle limit not reached"),
+        }
+
+        let mut config = endless_loop_config();
+        config.timeout = Some(Duration::ZERO);
+        match simulate(&config, Box::new(|_| unreachable!()), None) {
+            Err(SimulationError::LimitReached { limit, .. }) => assert_eq!(limit, Limit::Timeout),
+            _ => panic!("timeout not reached"),
+        }
+    }
 }